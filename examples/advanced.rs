@@ -196,7 +196,7 @@ fn demo_derive_nonexistent_schema() {
     println!("   @derive advanced_base.aam::NonExistentSchema\n");
 
     match AAML::parse("@derive advanced_base.aam::NonExistentSchema\n") {
-        Err(AamlError::DirectiveError(cmd, msg)) =>
+        Err(AamlError::DirectiveError(cmd, msg, _)) =>
             println!("   ✔ @{cmd}: {msg}"),
         other =>
             eprintln!("   ✘ Unexpected result: {other:?}"),
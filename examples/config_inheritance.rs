@@ -158,7 +158,7 @@ fn section_5_error_cases() {
     // 5a. Derive non-existent schema
     println!("   5a. @derive config_base.aam::PhantomSchema → DirectiveError");
     match AAML::parse("@derive config_base.aam::PhantomSchema\n") {
-        Err(AamlError::DirectiveError(cmd, msg)) =>
+        Err(AamlError::DirectiveError(cmd, msg, _)) =>
             println!("       ✔ @{cmd}: {msg}"),
         other => eprintln!("       ✘ Unexpected: {other:?}"),
     }
@@ -167,7 +167,7 @@ fn section_5_error_cases() {
     println!("\n   5b. build_id = not-a-number  in @schema with i32 → SchemaValidationError");
     let src = "@schema Build { build_id: i32, env: string }\nbuild_id = not-a-number\nenv = prod\n";
     match AAML::parse(src) {
-        Err(AamlError::SchemaValidationError { schema, field, type_name, details }) =>
+        Err(AamlError::SchemaValidationError { schema, field, type_name, details, .. }) =>
             println!(
                 "       ✔ schema '{schema}', field '{field}' ({type_name}): {details}"
             ),
@@ -12,7 +12,7 @@
 //! ```
 
 use aam_rs::aaml::AAML;
-use aam_rs::builder::AAMBuilder;
+use aam_rs::builder::{AAMBuilder, SchemaField};
 use aam_rs::error::AamlError;
 use std::collections::HashMap;
 use std::path::Path;
@@ -65,7 +65,11 @@ fn main() {
         // Write a temporary base that defines the schema but does NOT supply 'active'
         let base_path = "tmp_base_missing_field.aam";
         let mut b = AAMBuilder::new();
-        b.add_raw("@schema Entity { id: i32, name: string, active: bool }");
+        b.schema("Entity", [
+            SchemaField::required("id", "i32"),
+            SchemaField::required("name", "string"),
+            SchemaField::required("active", "bool"),
+        ]);
         b.add_line("id", "10");
         b.add_line("name", "TestApp");
         // 'active' is intentionally omitted
@@ -76,7 +80,7 @@ fn main() {
         let _ = std::fs::remove_file(base_path);
 
         match result {
-            Err(AamlError::SchemaValidationError { schema, field, type_name, details }) => {
+            Err(AamlError::SchemaValidationError { schema, field, type_name, details, .. }) => {
                 println!(
                     "   ✔ Got expected error — schema: '{schema}', field: '{field}' \
                      (type: '{type_name}'), reason: {details}"
@@ -92,7 +96,11 @@ fn main() {
     {
         let base_path = "tmp_base_wrong_type.aam";
         let mut b = AAMBuilder::new();
-        b.add_raw("@schema Entity { id: i32, name: string, active: bool }");
+        b.schema("Entity", [
+            SchemaField::required("id", "i32"),
+            SchemaField::required("name", "string"),
+            SchemaField::required("active", "bool"),
+        ]);
         b.add_line("id", "not-a-number");   // ← wrong type
         b.add_line("name", "TestApp");
         b.add_line("active", "true");
@@ -103,7 +111,7 @@ fn main() {
         let _ = std::fs::remove_file(base_path);
 
         match result {
-            Err(AamlError::SchemaValidationError { schema, field, type_name, details }) => {
+            Err(AamlError::SchemaValidationError { schema, field, type_name, details, .. }) => {
                 println!(
                     "   ✔ Got expected error — schema: '{schema}', field: '{field}' \
                      (type: '{type_name}'), reason: {details}"
@@ -0,0 +1,250 @@
+//! Span-preserving syntax tree for tooling.
+//!
+//! [`parse_to_ast`] turns AAML source into a [`Document`] whose nodes keep the
+//! exact byte range and line/column span of every key, value, directive and
+//! argument, for formatters, linters and editor integrations. It is built on
+//! the [`tokenize`] lexer and records the source as
+//! written: values keep their quotes and escapes, and directives are not run.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::ast::{Node, parse_to_ast};
+//!
+//! let doc = parse_to_ast("@import base.aam\nport = 8080 # http").unwrap();
+//! let Node::Assignment { key, value, .. } = &doc.nodes[1] else { panic!() };
+//! assert_eq!(key.text, "port");
+//! assert_eq!((value.span.start.line, value.span.start.column), (2, 8));
+//! assert_eq!(doc.comments[0].text, "# http");
+//! ```
+
+use super::parsing::{Token, TokenKind, tokenize};
+use crate::error::AamlError;
+use std::iter::Peekable;
+use std::ops::Range;
+
+/// A 1-based line and column; columns count characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// 1-based line.
+    pub line: usize,
+    /// 1-based column.
+    pub column: usize,
+}
+
+/// Where a node or piece of text sits in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// Byte range in the source.
+    pub range: Range<usize>,
+    /// Position of the first character.
+    pub start: Position,
+    /// Position just past the last character.
+    pub end: Position,
+}
+
+/// A piece of source text with its span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned {
+    /// The text exactly as written.
+    pub text: String,
+    /// Where the text sits.
+    pub span: Span,
+}
+
+/// A statement of an AAML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// `key = value`. The value is the raw text after `=`, which may span
+    /// several lines for a multi-line literal, and is empty for `key =`.
+    /// A one-line `key { ... }` block is an assignment whose value is the
+    /// braced body.
+    Assignment { key: Spanned, value: Spanned, span: Span },
+    /// `@name args`. The arguments are whitespace-separated, with quoted
+    /// strings kept whole and every `{` and `}` of a body as its own argument.
+    Directive { name: Spanned, args: Vec<Spanned>, span: Span },
+    /// An INI-style `[name]` header; the name excludes the brackets.
+    Section { name: Spanned, span: Span },
+    /// A nested `key { ... }` block written over several lines.
+    Block { key: Spanned, nodes: Vec<Node>, span: Span },
+}
+
+impl Node {
+    /// The span of the whole statement.
+    pub fn span(&self) -> &Span {
+        match self {
+            Node::Assignment { span, .. }
+            | Node::Directive { span, .. }
+            | Node::Section { span, .. }
+            | Node::Block { span, .. } => span,
+        }
+    }
+}
+
+/// A parsed document: its statements and, separately, its `#` comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    /// Top-level statements, in source order.
+    pub nodes: Vec<Node>,
+    /// Every comment, including those inside directive bodies and blocks.
+    pub comments: Vec<Spanned>,
+}
+
+/// Parses `source` into a span-preserving [`Document`].
+///
+/// # Errors
+/// [`AamlError::ParseError`] for a line that is neither a directive, a
+/// section, a block nor a `key = value` assignment, and for a block that is
+/// never closed.
+pub fn parse_to_ast(source: &str) -> Result<Document, AamlError> {
+    let mut builder = AstBuilder {
+        source,
+        line_starts: std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect(),
+        comments: Vec::new(),
+    };
+    let mut tokens = tokenize(source).into_iter().peekable();
+    let (nodes, _) = builder.nodes(&mut tokens, None)?;
+    Ok(Document { nodes, comments: builder.comments })
+}
+
+struct AstBuilder<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    comments: Vec<Spanned>,
+}
+
+type Tokens = Peekable<std::vec::IntoIter<Token>>;
+
+impl AstBuilder<'_> {
+    fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let start = self.line_starts[line - 1];
+        Position { line, column: self.source[start..offset].chars().count() + 1 }
+    }
+
+    fn span(&self, range: Range<usize>) -> Span {
+        Span { start: self.position(range.start), end: self.position(range.end), range }
+    }
+
+    fn spanned(&self, range: Range<usize>) -> Spanned {
+        Spanned { text: self.source[range.clone()].to_string(), span: self.span(range) }
+    }
+
+    fn error(&self, token: &Token, details: &str) -> AamlError {
+        let start = self.line_starts[token.line - 1];
+        let end = self.source[start..].find('\n').map_or(self.source.len(), |i| start + i);
+        AamlError::ParseError {
+            line: token.line,
+            content: self.source[start..end].trim().to_string(),
+            details: details.to_string(),
+        }
+    }
+
+    /// Takes the next token, moving comments aside.
+    fn next(&mut self, tokens: &mut Tokens) -> Option<Token> {
+        while let Some(token) = tokens.next_if(|t| t.kind == TokenKind::Comment) {
+            self.comments.push(self.spanned(token.span));
+        }
+        tokens.next()
+    }
+
+    fn peek_kind(&mut self, tokens: &mut Tokens) -> Option<TokenKind> {
+        while let Some(token) = tokens.next_if(|t| t.kind == TokenKind::Comment) {
+            self.comments.push(self.spanned(token.span));
+        }
+        tokens.peek().map(|t| t.kind)
+    }
+
+    /// Parses statements until the end of input or, inside the block opened
+    /// by `open`, until its closing brace, which is returned with them.
+    fn nodes(&mut self, tokens: &mut Tokens, open: Option<&Token>) -> Result<(Vec<Node>, Option<Token>), AamlError> {
+        let mut nodes = Vec::new();
+        while let Some(token) = self.next(tokens) {
+            let node = match token.kind {
+                TokenKind::CloseBrace if open.is_some() => return Ok((nodes, Some(token))),
+                TokenKind::Directive => self.directive(token, tokens),
+                TokenKind::Section => {
+                    let inner = token.span.start + 1..token.span.end - 1;
+                    let name = self.spanned(inner);
+                    let name = Spanned { text: name.text.trim().to_string(), ..name };
+                    Node::Section { name, span: self.span(token.span) }
+                }
+                TokenKind::Key => self.keyed(token, tokens)?,
+                _ => return Err(self.error(&token, "Unexpected token")),
+            };
+            nodes.push(node);
+        }
+        match open {
+            Some(open) => Err(self.error(open, "Unclosed block: expected }")),
+            None => Ok((nodes, None)),
+        }
+    }
+
+    fn directive(&mut self, name: Token, tokens: &mut Tokens) -> Node {
+        let mut args = Vec::new();
+        let mut depth = 0isize;
+        let mut line = name.line;
+        let mut end = name.span.end;
+        while let Some(kind) = self.peek_kind(tokens) {
+            let Some(token) = tokens.peek() else { break };
+            let continued = self.source[..end].ends_with('\\');
+            let belongs = matches!(kind, TokenKind::Argument | TokenKind::OpenBrace | TokenKind::CloseBrace)
+                && (depth > 0 || token.line == line || continued);
+            if !belongs {
+                break;
+            }
+            let token = tokens.next().expect("peeked");
+            match token.kind {
+                TokenKind::OpenBrace => depth += 1,
+                TokenKind::CloseBrace => depth -= 1,
+                _ => {}
+            }
+            line = token.line;
+            end = token.span.end;
+            args.push(self.spanned(token.span));
+        }
+        Node::Directive { span: self.span(name.span.start..end), name: self.spanned(name.span), args }
+    }
+
+    fn keyed(&mut self, key: Token, tokens: &mut Tokens) -> Result<Node, AamlError> {
+        match self.peek_kind(tokens) {
+            Some(TokenKind::Assign) => {
+                let assign = tokens.next().expect("peeked");
+                let mut value = assign.span.end..assign.span.end;
+                while self.peek_kind(tokens) == Some(TokenKind::Value) {
+                    let token = tokens.next().expect("peeked");
+                    if value.is_empty() {
+                        value.start = token.span.start;
+                    }
+                    value.end = token.span.end;
+                }
+                Ok(Node::Assignment {
+                    span: self.span(key.span.start..value.end),
+                    key: self.spanned(key.span),
+                    value: self.spanned(value),
+                })
+            }
+            Some(TokenKind::OpenBrace) => {
+                let open = tokens.next().expect("peeked");
+                let kind = self.peek_kind(tokens);
+                let one_line = tokens.peek().is_some_and(|t| t.line == open.line)
+                    && matches!(kind, Some(TokenKind::Value | TokenKind::CloseBrace));
+                if one_line {
+                    tokens.next_if(|t| t.kind == TokenKind::Value);
+                    let close = self.next(tokens).filter(|t| t.kind == TokenKind::CloseBrace);
+                    let close = close.ok_or_else(|| self.error(&open, "Unclosed block: expected }"))?;
+                    return Ok(Node::Assignment {
+                        span: self.span(key.span.start..close.span.end),
+                        key: self.spanned(key.span),
+                        value: self.spanned(open.span.start..close.span.end),
+                    });
+                }
+                let (nodes, close) = self.nodes(tokens, Some(&open))?;
+                let end = close.map_or(open.span.end, |close| close.span.end);
+                Ok(Node::Block { span: self.span(key.span.start..end), key: self.spanned(key.span), nodes })
+            }
+            _ => Err(self.error(&key, "Missing assignment operator '='")),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Async loading for tokio services (`tokio` feature).
+//!
+//! Directives run synchronously while a document is parsed, so reading an
+//! imported file there would block the runtime. The async entry points avoid
+//! that by fetching first and parsing afterwards:
+//!
+//! 1. The top-level file is read with `tokio::fs`.
+//! 2. Every file reachable through `@import`, `@import_optional` and
+//!    `@derive` is read through the instance's
+//!    [`SourceLoader`](super::loader::SourceLoader) on the blocking pool, one
+//!    nesting level at a time, with all files of a level read concurrently.
+//! 3. The document is merged exactly like [`AAML::merge_file`], with
+//!    directives served from the prefetched files.
+//!
+//! Prefetching stops at the instance's [`ImportLimits`](super::limits::ImportLimits),
+//! which are then enforced as usual. Directives inside `@if` branches are
+//! prefetched regardless of the condition; a prefetched file that is not
+//! needed is simply dropped, and a read error only surfaces if the directive
+//! actually runs.
+//!
+//! # Example
+//! ```no_run
+//! use aam_rs::aaml::AAML;
+//!
+//! # async fn run() -> Result<(), aam_rs::error::AamlError> {
+//! let cfg = AAML::load_async("config/app.aam").await?;
+//! # Ok(()) }
+//! ```
+
+use super::loader::SourceLoader;
+use super::{AAML, dir_of, parsing, resolve_in};
+use crate::commands::derive::{parse_derive_arg, split_bases};
+use crate::commands::import::split_prefix;
+use crate::error::AamlError;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+impl AAML {
+    /// Async variant of [`load`](Self::load).
+    ///
+    /// Requires the `tokio` feature and must run inside a tokio runtime.
+    pub async fn load_async<P: AsRef<Path>>(file_path: P) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new();
+        aaml.merge_file_async(file_path).await?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Async variant of [`merge_file`](Self::merge_file).
+    ///
+    /// Requires the `tokio` feature and must run inside a tokio runtime.
+    pub async fn merge_file_async<P: AsRef<Path>>(&mut self, file_path: P) -> Result<(), AamlError> {
+        let path = file_path.as_ref();
+        let content = tokio::fs::read_to_string(path).await?;
+        let loader = self.prefetch_imports(&content, dir_of(path)).await;
+        let result = self.in_file_dir(path, |aaml| aaml.merge_content(&content));
+        self.loader = loader;
+        result
+    }
+
+    /// Async variant of [`merge_content`](Self::merge_content): files named by
+    /// directives are read without blocking the runtime. Relative paths
+    /// resolve against [`source_dir`](Self::source_dir).
+    ///
+    /// Requires the `tokio` feature and must run inside a tokio runtime.
+    pub async fn merge_content_async(&mut self, content: &str) -> Result<(), AamlError> {
+        let loader = self.prefetch_imports(content, self.source_dir.clone()).await;
+        let result = self.merge_content(content);
+        self.loader = loader;
+        result
+    }
+
+    /// Reads every file reachable from `content` through directives and
+    /// installs a loader serving them. Returns the loader it replaced.
+    async fn prefetch_imports(
+        &mut self,
+        content: &str,
+        dir: Option<PathBuf>,
+    ) -> Arc<dyn SourceLoader> {
+        let inner = self.loader.clone();
+        let mut files = HashMap::new();
+        let mut level: Vec<PathBuf> = directive_paths(content, dir.as_deref());
+        let mut depth = self.usage.depth;
+        let mut bytes = self.usage.bytes;
+
+        while !level.is_empty() && depth < self.options.import_limits.max_depth && bytes <= self.options.import_limits.max_bytes {
+            let mut reads = tokio::task::JoinSet::new();
+            let mut queued = HashSet::new();
+            for path in level.drain(..) {
+                if self.usage.files + files.len() + queued.len() >= self.options.import_limits.max_files {
+                    break;
+                }
+                if !files.contains_key(&path) && queued.insert(path.clone()) {
+                    let loader = inner.clone();
+                    let budget = self.options.import_limits.max_bytes.saturating_sub(bytes);
+                    reads.spawn_blocking(move || {
+                        let result = loader.load_limited(&path, budget);
+                        (path, result)
+                    });
+                }
+            }
+            while let Some(joined) = reads.join_next().await {
+                let Ok((path, result)) = joined else {
+                    continue;
+                };
+                if let Ok(content) = &result {
+                    bytes = bytes.saturating_add(content.len() as u64);
+                    level.extend(directive_paths(content, dir_of(&path).as_deref()));
+                }
+                files.insert(path, result.map_err(|e| (e.kind(), e.to_string())));
+            }
+            depth += 1;
+        }
+
+        std::mem::replace(&mut self.loader, Arc::new(Prefetched { files, inner }))
+    }
+}
+
+/// Serves prefetched files, and anything else through the original loader.
+struct Prefetched {
+    files: HashMap<PathBuf, Result<String, (io::ErrorKind, String)>>,
+    inner: Arc<dyn SourceLoader>,
+}
+
+impl SourceLoader for Prefetched {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        match self.files.get(path) {
+            Some(Ok(content)) => Ok(content.clone()),
+            Some(Err((kind, msg))) => Err(io::Error::new(*kind, msg.clone())),
+            None => self.inner.load(path),
+        }
+    }
+
+    fn load_limited(&self, path: &Path, max_bytes: u64) -> io::Result<String> {
+        match self.files.get(path) {
+            Some(Ok(content)) if content.len() as u64 > max_bytes => {
+                Err(super::loader::too_large(path, max_bytes))
+            }
+            Some(_) => self.load(path),
+            None => self.inner.load_limited(path, max_bytes),
+        }
+    }
+}
+
+/// Returns the resolved paths named by `@import`, `@import_optional` and
+/// `@derive` lines in `content`.
+fn directive_paths(content: &str, dir: Option<&Path>) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = parsing::strip_comment(line).trim().strip_prefix('@')?;
+            let (name, args) = line.split_once(char::is_whitespace)?;
+            let paths = match name {
+                "import" | "import_optional" => vec![parsing::unwrap_quotes(split_prefix(args).0)],
+                "derive" => split_bases(args.trim())
+                    .into_iter()
+                    .map(|base| parse_derive_arg(base).0)
+                    .collect(),
+                _ => return None,
+            };
+            Some(paths)
+        })
+        .flatten()
+        .filter(|path| !path.is_empty())
+        .map(|path| resolve_in(dir, path))
+        .collect()
+}
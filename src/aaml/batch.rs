@@ -0,0 +1,147 @@
+//! Validation of many records against one schema.
+//!
+//! Services that accept user-submitted objects can register their schemas in
+//! an [`AAML`] document once and check every incoming record with
+//! [`AAML::apply_schema_batch`]. Each record is validated exactly like
+//! [`AAML::apply_schema`]; failures are collected instead of stopping the run.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use std::collections::HashMap;
+//!
+//! let cfg = AAML::parse("@schema User { name: string, age: i32 }").unwrap();
+//! let records: Vec<HashMap<String, String>> = vec![
+//!     HashMap::from([("name".into(), "ann".into()), ("age".into(), "31".into())]),
+//!     HashMap::from([("name".into(), "bob".into()), ("age".into(), "old".into())]),
+//! ];
+//!
+//! let report = cfg.apply_schema_batch("User", &records).unwrap();
+//! assert_eq!(report.passed(), 1);
+//! assert_eq!(report.failures[0].0, 1);
+//! ```
+
+use super::AAML;
+use crate::error::AamlError;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Outcome of validating a batch of records against a schema.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// Number of records that were validated.
+    pub total: usize,
+    /// `(record index, error)` for every record that failed, in input order.
+    pub failures: Vec<(usize, AamlError)>,
+}
+
+impl BatchReport {
+    /// Returns `true` when every record passed validation.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Number of records that passed validation.
+    pub fn passed(&self) -> usize {
+        self.total - self.failures.len()
+    }
+
+    /// Number of records that failed validation.
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+}
+
+impl AAML {
+    /// Validates every record in `records` against the schema `schema_name`.
+    ///
+    /// The schema is looked up once; each record is then checked with the same
+    /// rules as [`apply_schema`](Self::apply_schema). A failing record does not
+    /// stop the batch.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if no schema named `schema_name` is registered.
+    pub fn apply_schema_batch(
+        &self,
+        schema_name: &str,
+        records: &[HashMap<String, String>],
+    ) -> Result<BatchReport, AamlError> {
+        let schema = self
+            .schemas
+            .get(schema_name)
+            .ok_or_else(|| AamlError::NotFound(format!("Schema '{}' not found", schema_name)))?;
+
+        let failures = records
+            .iter()
+            .enumerate()
+            .filter_map(|(i, data)| {
+                self.apply_schema_def(schema_name, schema, data)
+                    .err()
+                    .map(|e| (i, e))
+            })
+            .collect();
+        Ok(BatchReport {
+            total: records.len(),
+            failures,
+        })
+    }
+
+    /// Like [`apply_schema_batch`](Self::apply_schema_batch), but splits the
+    /// records across the available CPU cores using scoped threads.
+    ///
+    /// The report is identical to the sequential version, including the order
+    /// of `failures`.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if no schema named `schema_name` is registered.
+    pub fn apply_schema_batch_parallel(
+        &self,
+        schema_name: &str,
+        records: &[HashMap<String, String>],
+    ) -> Result<BatchReport, AamlError> {
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        if workers < 2 || records.len() < 2 {
+            return self.apply_schema_batch(schema_name, records);
+        }
+        if !self.schemas.contains_key(schema_name) {
+            return Err(AamlError::NotFound(format!(
+                "Schema '{}' not found",
+                schema_name
+            )));
+        }
+
+        let chunk_size = records.len().div_ceil(workers);
+        let mut failures = Vec::new();
+        thread::scope(|scope| {
+            let handles: Vec<_> = records
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(n, chunk)| {
+                    scope.spawn(move || {
+                        self.apply_schema_batch(schema_name, chunk).map(|report| {
+                            report
+                                .failures
+                                .into_iter()
+                                .map(|(i, e)| (n * chunk_size + i, e))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                })
+                .collect();
+            for handle in handles {
+                match handle.join() {
+                    Ok(Ok(chunk_failures)) => failures.extend(chunk_failures),
+                    Ok(Err(e)) => return Err(e),
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(BatchReport {
+            total: records.len(),
+            failures,
+        })
+    }
+}
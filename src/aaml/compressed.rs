@@ -0,0 +1,64 @@
+//! Loading of compressed `.aam` files (`gzip` and `zstd` features).
+//!
+//! The decompressor is wrapped in a [`BufReader`] and fed to the parser line by
+//! line, so the decompressed document is never materialised as a whole.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use crate::error::AamlError;
+use super::AAML;
+
+impl AAML {
+    /// Loads a gzip-compressed AAML file (e.g. `config.aam.gz`).
+    ///
+    /// Requires the `gzip` feature.
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if the file cannot be opened or is not valid gzip,
+    /// plus any parse error from the decompressed content.
+    #[cfg(feature = "gzip")]
+    pub fn load_gz<P: AsRef<Path>>(file_path: P) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new();
+        aaml.merge_gz(file_path)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Decompresses a gzip file and merges its content into this instance.
+    ///
+    /// Requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    pub fn merge_gz<P: AsRef<Path>>(&mut self, file_path: P) -> Result<(), AamlError> {
+        let path = file_path.as_ref();
+        let decoder = flate2::read::MultiGzDecoder::new(File::open(path)?);
+        self.in_file_dir(path, |aaml| aaml.merge_reader(BufReader::new(decoder)))
+    }
+
+    /// Loads a zstd-compressed AAML file (e.g. `config.aam.zst`).
+    ///
+    /// Requires the `zstd` feature.
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if the file cannot be opened or is not valid zstd,
+    /// plus any parse error from the decompressed content.
+    #[cfg(feature = "zstd")]
+    pub fn load_zstd<P: AsRef<Path>>(file_path: P) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new();
+        aaml.merge_zstd(file_path)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Decompresses a zstd file and merges its content into this instance.
+    ///
+    /// Requires the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    pub fn merge_zstd<P: AsRef<Path>>(&mut self, file_path: P) -> Result<(), AamlError> {
+        let path = file_path.as_ref();
+        let decoder = zstd::stream::read::Decoder::new(File::open(path)?)?;
+        self.in_file_dir(path, |aaml| aaml.merge_reader(BufReader::new(decoder)))
+    }
+}
@@ -0,0 +1,230 @@
+//! `@if` / `@elif` / `@else` / `@endif` blocks.
+//!
+//! # Syntax
+//! ```text
+//! @if env == prod
+//! log_level = warn
+//! @elif env == staging || debug
+//! log_level = info
+//! @else
+//! log_level = debug
+//! @endif
+//! ```
+//!
+//! # Semantics
+//! Lines inside a branch whose condition does not hold are skipped entirely,
+//! directives included. A condition is made of comparisons joined by `&&` and
+//! `||` (`&&` binds tighter; there are no parentheses):
+//! - `name == value` / `name != value` — compares the value of `name`;
+//!   quotes around `value` are optional.
+//! - `name` — holds when `name` has a value that is not empty, `false`, `no`,
+//!   `off` or `0`.
+//! - `!name` — the negation of `name`.
+//!
+//! `name` is looked up in the context supplied with [`AAML::with_context`] or
+//! [`AAML::parse_with_context`] first, then among the keys assigned so far
+//! (and schema defaults). Blocks nest, and must be closed within the same
+//! content: an `@if` in an imported file ends in that file.
+//!
+//! A multi-line `@profile` block (see [`crate::commands::profile`]) is handled
+//! here as well: `@profile dev {` opens a block that is active only when the
+//! `dev` profile is selected, and a line holding just `}` closes it.
+
+use super::{AAML, parsing};
+use crate::commands::namespace::is_key_path;
+use crate::error::AamlError;
+use crate::types::primitive_type::parse_bool_relaxed;
+
+/// One open `@if` or `@profile` block.
+struct Frame {
+    /// Whether the block was opened by `@profile` rather than `@if`.
+    profile: bool,
+    /// Line of the `@if`, for unclosed-block errors.
+    line: usize,
+    /// Whether the enclosing block is active.
+    parent_active: bool,
+    /// Whether the current branch is active.
+    active: bool,
+    /// Whether some branch of this block has already been taken.
+    taken: bool,
+    /// Whether `@else` has been seen.
+    in_else: bool,
+}
+
+/// The stack of open conditional blocks while merging one piece of content.
+#[derive(Default)]
+pub(super) struct Conditions {
+    frames: Vec<Frame>,
+}
+
+impl Conditions {
+    /// Returns `true` when lines at the current position should be processed.
+    pub(super) fn active(&self) -> bool {
+        self.frames.last().is_none_or(|f| f.active)
+    }
+
+    /// Returns the line and error of a block left open at the end of the content.
+    pub(super) fn unclosed(&self) -> Option<(usize, AamlError)> {
+        self.frames.last().map(|frame| {
+            let (content, details) = if frame.profile {
+                ("@profile", "Unclosed @profile: expected }")
+            } else {
+                ("@if", "Unclosed @if: expected @endif")
+            };
+            let error = AamlError::ParseError {
+                line: frame.line,
+                content: content.to_string(),
+                details: details.to_string(),
+            };
+            (frame.line, error)
+        })
+    }
+
+    /// Returns the innermost block if it is an `@if`.
+    fn open_if(&mut self) -> Option<&mut Frame> {
+        self.frames.last_mut().filter(|f| !f.profile)
+    }
+}
+
+impl AAML {
+    /// Handles `line` if it is a conditional directive, or reports whether it
+    /// lies in an inactive branch. Returns `true` when the caller must not
+    /// process the line any further.
+    pub(super) fn conditional_line(
+        &self,
+        line: &str,
+        line_num: usize,
+        conditions: &mut Conditions,
+    ) -> Result<bool, AamlError> {
+        let text = parsing::strip_comment(line).trim();
+        if text == "}" && conditions.frames.last().is_some_and(|f| f.profile) {
+            conditions.frames.pop();
+            return Ok(true);
+        }
+        let Some(rest) = text.strip_prefix('@') else {
+            return Ok(!conditions.active());
+        };
+        let (name, cond) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let error = |details: &str| AamlError::ParseError {
+            line: line_num,
+            content: text.to_string(),
+            details: details.to_string(),
+        };
+        let cond = cond.trim();
+
+        match name {
+            "if" => {
+                let parent_active = conditions.active();
+                let active = parent_active && self.eval_condition(cond).map_err(|d| error(&d))?;
+                conditions.frames.push(Frame {
+                    profile: false,
+                    line: line_num,
+                    parent_active,
+                    active,
+                    taken: active,
+                    in_else: false,
+                });
+            }
+            "elif" => {
+                let frame = conditions.open_if().ok_or_else(|| error("@elif without @if"))?;
+                if frame.in_else {
+                    return Err(error("@elif after @else"));
+                }
+                frame.active = frame.parent_active
+                    && !frame.taken
+                    && self.eval_condition(cond).map_err(|d| error(&d))?;
+                frame.taken |= frame.active;
+            }
+            "else" | "endif" if !cond.is_empty() => {
+                return Err(error(&format!("@{name} takes no condition")));
+            }
+            "else" => {
+                let frame = conditions.open_if().ok_or_else(|| error("@else without @if"))?;
+                if frame.in_else {
+                    return Err(error("Duplicate @else"));
+                }
+                frame.active = frame.parent_active && !frame.taken;
+                frame.taken = true;
+                frame.in_else = true;
+            }
+            "endif" => {
+                conditions.open_if().ok_or_else(|| error("@endif without @if"))?;
+                conditions.frames.pop();
+            }
+            "profile" if !cond.ends_with('{') && parsing::needs_accumulation(text) => {
+                return Err(error("A multi-line @profile must end its first line with '{'"));
+            }
+            // `@profile name { ... }` on one line is left to the command.
+            "profile" if !cond.ends_with('{') => return Ok(!conditions.active()),
+            "profile" => {
+                let names = cond.trim_end_matches('{').trim();
+                if names.is_empty() || !names.split(',').all(|n| is_key_path(n.trim())) {
+                    return Err(error(&format!("Invalid profile name '{names}'")));
+                }
+                let parent_active = conditions.active();
+                let active = parent_active && self.profile_selected(names);
+                conditions.frames.push(Frame {
+                    profile: true,
+                    line: line_num,
+                    parent_active,
+                    active,
+                    taken: active,
+                    in_else: false,
+                });
+            }
+            _ => return Ok(!conditions.active()),
+        }
+        Ok(true)
+    }
+
+    /// Evaluates an `@if` condition against the context and assigned keys.
+    fn eval_condition(&self, cond: &str) -> Result<bool, String> {
+        if cond.is_empty() {
+            return Err("Missing condition".to_string());
+        }
+        let mut any = false;
+        for alternative in cond.split("||") {
+            let mut all = true;
+            for term in alternative.split("&&") {
+                all &= self.eval_term(term.trim())?;
+            }
+            any |= all;
+        }
+        Ok(any)
+    }
+
+    /// Evaluates a single `name`, `!name`, `name == value` or `name != value`.
+    fn eval_term(&self, term: &str) -> Result<bool, String> {
+        let comparison = term
+            .split_once("==")
+            .map(|(name, value)| (name, value, true))
+            .or_else(|| term.split_once("!=").map(|(name, value)| (name, value, false)));
+        if let Some((name, value, equal)) = comparison {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("Missing name in condition '{term}'"));
+            }
+            let matches = self.condition_value(name) == Some(parsing::unwrap_quotes(value));
+            return Ok(matches == equal);
+        }
+        let (name, negate) = match term.strip_prefix('!') {
+            Some(name) => (name.trim(), true),
+            None => (term, false),
+        };
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(format!("Invalid condition '{term}'"));
+        }
+        let truthy = self
+            .condition_value(name)
+            .is_some_and(|v| !v.is_empty() && parse_bool_relaxed(v) != Some(false));
+        Ok(truthy != negate)
+    }
+
+    /// Looks `name` up in the context, then among assigned keys and defaults.
+    fn condition_value(&self, name: &str) -> Option<&str> {
+        self.context
+            .get(name)
+            .map(String::as_str)
+            .or_else(|| self.value_of(name))
+    }
+}
@@ -0,0 +1,230 @@
+//! Streaming, event-based parsing.
+//!
+//! [`EventReader`] reads AAML line by line and yields an [`Event`] for each
+//! statement without building a map, so huge documents can be filtered or
+//! transformed in constant memory (bounded by the longest statement).
+//!
+//! Events describe the source as written: directives are reported but not
+//! run, so `@import` is not followed, `@if` branches are not evaluated,
+//! `${name}` references are not expanded and keys are not prefixed with the
+//! open section or namespace.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::events::{Event, EventReader};
+//!
+//! let content = "# Server settings\n@schema Server { host: string, port*: i32 = 80 }\nhost = localhost";
+//! let events: Vec<Event> = EventReader::new(content.as_bytes())
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(events[0], Event::Comment { line: 1, text: "Server settings".into() });
+//! assert!(matches!(&events[1], Event::DirectiveStart { name, .. } if name == "schema"));
+//! assert!(matches!(&events[3], Event::SchemaField { name, optional: true, .. } if name == "port"));
+//! assert_eq!(events[5], Event::Assignment { line: 3, key: "host".into(), value: "localhost".into() });
+//! ```
+
+use super::parsing;
+use crate::commands::namespace::is_key_path;
+use crate::commands::schema::SchemaCommand;
+use crate::error::{AamlError, SourceLocation};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// One parsed statement, as yielded by [`EventReader`]. Every event carries
+/// the 1-based line the statement starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A whole-line `# comment`, without the `#`.
+    Comment { line: usize, text: String },
+    /// A `key = value` assignment or a nested `key { ... }` block. The value
+    /// is unquoted with escape sequences resolved, as it would be stored; a
+    /// block becomes an inline object literal.
+    Assignment { line: usize, key: String, value: String },
+    /// An INI-style `[name]` section header.
+    Section { line: usize, name: String },
+    /// A directive `@name args`. For `@schema` it is followed by one
+    /// [`SchemaField`](Event::SchemaField) per declared field. Always closed
+    /// by a matching [`DirectiveEnd`](Event::DirectiveEnd).
+    DirectiveStart { line: usize, name: String, args: String },
+    /// A field declared by the `@schema` directive being reported, in
+    /// declaration order. Fields spliced in from mixins are not listed.
+    SchemaField {
+        line: usize,
+        schema: String,
+        name: String,
+        type_name: String,
+        optional: bool,
+        default: Option<String>,
+    },
+    /// The end of the directive opened by the last
+    /// [`DirectiveStart`](Event::DirectiveStart).
+    DirectiveEnd { line: usize, name: String },
+}
+
+impl Event {
+    /// The 1-based line the statement starts on.
+    pub fn line(&self) -> usize {
+        match self {
+            Event::Comment { line, .. }
+            | Event::Assignment { line, .. }
+            | Event::Section { line, .. }
+            | Event::DirectiveStart { line, .. }
+            | Event::SchemaField { line, .. }
+            | Event::DirectiveEnd { line, .. } => *line,
+        }
+    }
+}
+
+/// Pull parser yielding the [`Event`]s of AAML read from a [`BufRead`].
+///
+/// Iteration stops after the first error.
+pub struct EventReader<R> {
+    reader: R,
+    line: String,
+    line_num: usize,
+    pending: Option<(String, usize)>,
+    queue: VecDeque<Event>,
+    finished: bool,
+}
+
+impl<R: BufRead> EventReader<R> {
+    /// Creates a reader over `reader`. For a string, pass `content.as_bytes()`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_num: 0,
+            pending: None,
+            queue: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Reads one source line, queueing the events of any statement it
+    /// completes. Returns `false` at the end of the input.
+    fn advance(&mut self) -> Result<bool, AamlError> {
+        let mut raw = std::mem::take(&mut self.line);
+        raw.clear();
+        if self.reader.read_line(&mut raw)? == 0 {
+            if let Some((text, start)) = self.pending.take() {
+                self.push_statement(&text, start)?;
+            }
+            return Ok(false);
+        }
+        self.line_num += 1;
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let result = self.accumulate(line);
+        self.line = raw;
+        result.map(|()| true)
+    }
+
+    fn accumulate(&mut self, line: &str) -> Result<(), AamlError> {
+        if let Some((buf, _)) = &mut self.pending {
+            if parsing::append_line(buf, line)
+                && let Some((text, start)) = self.pending.take()
+            {
+                self.push_statement(&text, start)?;
+            }
+            return Ok(());
+        }
+        if let Some(text) = parsing::doc_comment(line) {
+            self.queue.push_back(Event::Comment { line: self.line_num, text: text.to_string() });
+            return Ok(());
+        }
+        let stripped = parsing::strip_comment(line).trim();
+        if let Some(head) = parsing::strip_continuation(stripped) {
+            self.pending = Some((head.to_string(), self.line_num));
+        } else if parsing::needs_accumulation(stripped) {
+            self.pending = Some((stripped.to_string(), self.line_num));
+        } else {
+            self.push_statement(stripped, self.line_num)?;
+        }
+        Ok(())
+    }
+
+    /// Queues the events of one complete statement.
+    fn push_statement(&mut self, text: &str, line: usize) -> Result<(), AamlError> {
+        let text = parsing::strip_comment(text).trim();
+        let error = |details: String| AamlError::ParseError { line, content: text.to_string(), details };
+        if text.is_empty() {
+            return Ok(());
+        }
+        if let Some(rest) = text.strip_prefix('@') {
+            let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            return self.push_directive(name, args.trim(), line);
+        }
+        if let Some(name) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            let name = name.trim();
+            if !is_key_path(name) {
+                return Err(error(format!("Invalid section '{name}'")));
+            }
+            self.queue.push_back(Event::Section { line, name: name.to_string() });
+            return Ok(());
+        }
+        let (key, value) = match parsing::block_head(text) {
+            Some((key, block)) => (key, parsing::block_to_object(block).map_err(error)?),
+            None => {
+                let (key, value) = parsing::parse_assignment(text).map_err(|e| error(e.to_string()))?;
+                let raw_value = text[text.find('=').unwrap_or(0) + 1..].trim();
+                let value = if raw_value.len() >= 2 && raw_value.starts_with('"') && raw_value.ends_with('"') {
+                    parsing::unescape(value).map_err(error)?.into_owned()
+                } else {
+                    value.to_string()
+                };
+                (key, value)
+            }
+        };
+        self.queue.push_back(Event::Assignment { line, key: key.to_string(), value });
+        Ok(())
+    }
+
+    fn push_directive(&mut self, name: &str, args: &str, line: usize) -> Result<(), AamlError> {
+        self.queue.push_back(Event::DirectiveStart { line, name: name.to_string(), args: args.to_string() });
+        if name == "schema" {
+            let (schema_name, schema, _, order) = SchemaCommand::parse_with_order(args).map_err(|e| match e {
+                AamlError::DirectiveError(cmd, msg, None) => {
+                    let location = SourceLocation { line, ..SourceLocation::default() };
+                    AamlError::DirectiveError(cmd, msg, Some(Box::new(location)))
+                }
+                other => other,
+            })?;
+            for field in order {
+                self.queue.push_back(Event::SchemaField {
+                    line,
+                    schema: schema_name.clone(),
+                    type_name: schema.fields[&field].clone(),
+                    optional: schema.is_optional(&field),
+                    default: schema.defaults.get(&field).cloned(),
+                    name: field,
+                });
+            }
+        }
+        self.queue.push_back(Event::DirectiveEnd { line, name: name.to_string() });
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for EventReader<R> {
+    type Item = Result<Event, AamlError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.finished {
+                return None;
+            }
+            match self.advance() {
+                Ok(true) => {}
+                Ok(false) => self.finished = true,
+                Err(e) => {
+                    self.finished = true;
+                    self.queue.clear();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
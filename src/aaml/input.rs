@@ -0,0 +1,59 @@
+//! Loading from standard input and from memory-mapped files.
+//!
+//! [`AAML::from_stdin`] streams standard input through the line-by-line
+//! parser, so a config piped into a tool is never buffered as a whole.
+//! [`AAML::load_mmap`] (`mmap` feature) parses a file straight from a memory
+//! map instead of copying it into a `String` first, which matters for very
+//! large generated files.
+
+use crate::error::AamlError;
+use super::AAML;
+#[cfg(feature = "mmap")]
+use std::path::Path;
+
+impl AAML {
+    /// Parses AAML read line by line from standard input, like
+    /// [`parse_reader`](Self::parse_reader). Relative `@import` paths resolve
+    /// against the working directory.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// // generate-config | my-tool
+    /// let cfg = AAML::from_stdin().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if reading fails or the input is not valid
+    /// UTF-8, plus everything [`parse`](Self::parse) reports.
+    pub fn from_stdin() -> Result<Self, AamlError> {
+        Self::parse_reader(std::io::stdin().lock())
+    }
+
+    /// Loads a file like [`load`](Self::load), parsing it directly from a
+    /// read-only memory map rather than reading it into a `String`.
+    ///
+    /// The file must not be modified while it is being parsed. Requires the
+    /// `mmap` feature.
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if the file cannot be opened or mapped, or is
+    /// not valid UTF-8, plus everything [`load`](Self::load) reports.
+    #[cfg(feature = "mmap")]
+    pub fn load_mmap<P: AsRef<Path>>(file_path: P) -> Result<Self, AamlError> {
+        let path = file_path.as_ref();
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only and only lives for this call; callers
+        // are told not to modify the file while it is parsed.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        let content = std::str::from_utf8(&map)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let mut aaml = AAML::new();
+        aaml.in_file_dir(path, |aaml| aaml.merge_content(content))?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+}
@@ -0,0 +1,140 @@
+//! `${name}` references in assignment values.
+//!
+//! A value such as `log_dir = ${base_dir}/logs` is stored as written and
+//! resolved once the whole content has been merged, so it may refer to keys
+//! assigned further down. Each `${name}` is replaced by, in order:
+//! 1. the value of the key `name`, following alias chains like
+//!    [`AAML::find_deep`] and falling back to a schema default;
+//! 2. the environment variable `name`;
+//! 3. the `default` of `${name:-default}`.
+//!
+//! The default also replaces an empty value. A value that still contains
+//! references is validated against its schema field only after it is
+//! resolved. References between keys may not form a cycle.
+//!
+//! A value assigned inside a `@namespace` or an `@import ... as` prefix looks
+//! keys up inside that scope first: `${port}` in namespace `database` finds
+//! `database.port` before a top-level `port`.
+
+use super::{AAML, parsing};
+use crate::error::AamlError;
+use std::collections::HashMap;
+
+/// A value awaiting reference resolution.
+pub(super) struct Deferred {
+    key: String,
+    /// Line the value was assigned on.
+    line: usize,
+    /// Namespace the value was assigned in, for relative lookups.
+    scope: Option<String>,
+}
+
+impl AAML {
+    /// Stores `value` under `key` without validating it and queues it for
+    /// [`resolve_references`](Self::resolve_references). `scope` is the
+    /// namespace the assignment appeared in.
+    pub(super) fn defer_references(&mut self, key: &str, value: &str, line: usize, scope: Option<&str>) {
+        self.forget_deferred(key);
+        self.deferred.push(Deferred {
+            key: key.to_string(),
+            line,
+            scope: scope.map(str::to_string),
+        });
+        self.map.insert(Box::from(key), Box::from(value));
+    }
+
+    /// Drops `key` from the deferred values, once it has been assigned again.
+    pub(super) fn forget_deferred(&mut self, key: &str) {
+        if !self.deferred.is_empty() {
+            self.deferred.retain(|d| d.key != key);
+        }
+    }
+
+    /// Resolves the references of every deferred value, then validates and
+    /// stores the result. Each failure is handed to `on_error` with the line
+    /// the value was assigned on.
+    pub(crate) fn resolve_references(
+        &mut self,
+        mut on_error: impl FnMut(usize, AamlError) -> Result<(), AamlError>,
+    ) -> Result<(), AamlError> {
+        let deferred = std::mem::take(&mut self.deferred);
+        let mut resolved = HashMap::new();
+        for Deferred { key, line, .. } in &deferred {
+            let raw = self.map.get(key.as_str()).map(|v| v.to_string()).unwrap_or_default();
+            let result = self
+                .resolve_key(key, &deferred, &mut resolved, &mut Vec::new())
+                .map_err(|details| AamlError::ParseError {
+                    line: *line,
+                    content: format!("{key} = {raw}"),
+                    details,
+                })
+                .and_then(|value| self.insert_validated(key, &value))
+                .map_err(|e| self.locate(e, *line, 0));
+            if let Err(e) = result {
+                on_error(*line, e)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the fully expanded value of the deferred `key`. `stack` holds
+    /// the keys being expanded, to report cycles.
+    fn resolve_key(
+        &self,
+        key: &str,
+        deferred: &[Deferred],
+        resolved: &mut HashMap<String, String>,
+        stack: &mut Vec<String>,
+    ) -> Result<String, String> {
+        if let Some(value) = resolved.get(key) {
+            return Ok(value.clone());
+        }
+        if stack.iter().any(|k| k == key) {
+            stack.push(key.to_string());
+            return Err(format!("Circular reference: {}", stack.join(" -> ")));
+        }
+        let raw = self.map.get(key).map(|v| v.to_string()).unwrap_or_default();
+        let scope = deferred.iter().find(|d| d.key == key).and_then(|d| d.scope.as_deref());
+        stack.push(key.to_string());
+        let value = parsing::expand_with(&raw, |name, default| {
+            let mut found = None;
+            for candidate in scoped_names(scope, name) {
+                let candidate = self.canonical_key(&candidate).to_string();
+                found = if deferred.iter().any(|d| d.key == candidate) {
+                    Some(self.resolve_key(&candidate, deferred, resolved, stack)?)
+                } else {
+                    self.find_deep(&candidate).map(|v| v.as_str().to_string())
+                };
+                if found.is_some() {
+                    break;
+                }
+            }
+            let found = found.or_else(|| std::env::var(name).ok());
+            match (found, default) {
+                (Some(v), Some(default)) if v.is_empty() => Ok(default.to_string()),
+                (Some(v), _) => Ok(v),
+                (None, Some(default)) => Ok(default.to_string()),
+                (None, None) => Err(format!(
+                    "'{name}' is neither a key nor a set environment variable"
+                )),
+            }
+        })?
+        .into_owned();
+        stack.pop();
+        resolved.insert(key.to_string(), value.clone());
+        Ok(value)
+    }
+}
+
+/// Returns the keys `name` may refer to from inside `scope`, innermost
+/// first: `scoped_names(Some("a.b"), "x")` is `a.b.x`, `a.x`, `x`.
+fn scoped_names(scope: Option<&str>, name: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut scope = scope;
+    while let Some(s) = scope {
+        names.push(format!("{s}.{name}"));
+        scope = s.rsplit_once('.').map(|(outer, _)| outer);
+    }
+    names.push(name.to_string());
+    names
+}
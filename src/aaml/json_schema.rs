@@ -0,0 +1,205 @@
+//! JSON Schema export for [`SchemaDef`] and the schemas registered in [`AAML`](super::AAML).
+//!
+//! The generated documents follow JSON Schema draft 2020-12 and describe the
+//! *logical* shape of a configuration: `i32` becomes an integer, `list<T>` an
+//! array, `math::vector3` a three-element number array and nested schemas an
+//! object referenced through `$defs`.
+
+use std::collections::BTreeSet;
+use crate::commands::schema::SchemaDef;
+use crate::error::AamlError;
+use crate::types::list::ListType;
+use crate::types::map::MapType;
+use crate::types::optional::OptionalType;
+use crate::types::union::UnionType;
+use crate::types::resolve_builtin;
+use super::AAML;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl SchemaDef {
+    /// Renders this schema as a standalone JSON Schema object.
+    ///
+    /// Required fields are listed under `"required"`; optional (`*`) and
+    /// defaulted fields only appear in `"properties"`; `deny_extra_objects`
+    /// schemas set `"additionalProperties": false`; field doc comments become
+    /// `"description"`s. Type names that are
+    /// neither built-ins nor `list<T>` are assumed to be nested schemas and
+    /// emitted as `{"$ref": "#/$defs/<Name>"}`, with a plain `{"type":"object"}`
+    /// entry under `"$defs"` for each — use [`AAML::schema_to_json_schema`] to
+    /// get a document with the full definitions and resolved `@type` aliases.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let cfg = AAML::parse("@schema Server { host: string, port*: i32 }").unwrap();
+    /// let json = cfg.get_schema("Server").unwrap().to_json_schema();
+    /// assert!(json.contains(r#""required":["host"]"#));
+    /// ```
+    pub fn to_json_schema(&self) -> String {
+        let mut refs = BTreeSet::new();
+        let body = object_schema(self, None, &mut refs);
+        let defs = refs
+            .into_iter()
+            .map(|name| (name, r#"{"type":"object"}"#.to_string()))
+            .collect();
+        document(None, &body, defs)
+    }
+}
+
+impl AAML {
+    /// Renders the schema registered as `name` as a JSON Schema document.
+    ///
+    /// Unlike [`SchemaDef::to_json_schema`], custom `@type` aliases are resolved
+    /// against this instance's type registry and every nested schema reachable
+    /// from `name` is included under `"$defs"`.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if no schema named `name` is registered.
+    pub fn schema_to_json_schema(&self, name: &str) -> Result<String, AamlError> {
+        let schema = self
+            .get_schema(name)
+            .ok_or_else(|| AamlError::NotFound(format!("Schema '{name}' not found")))?;
+
+        let mut refs = BTreeSet::new();
+        let body = object_schema(schema, Some(self), &mut refs);
+
+        let mut defs = Vec::new();
+        let mut done = BTreeSet::new();
+        while let Some(next) = refs.iter().find(|r| !done.contains(*r)).cloned() {
+            done.insert(next.clone());
+            if let Some(nested) = self.get_schema(&next) {
+                let nested_body = object_schema(nested, Some(self), &mut refs);
+                defs.push((next, nested_body));
+            }
+        }
+        Ok(document(Some(name), &body, defs))
+    }
+}
+
+/// Wraps an object `body` into a top-level document with the draft URI, an
+/// optional title and the given `$defs` entries.
+fn document(title: Option<&str>, body: &str, defs: Vec<(String, String)>) -> String {
+    let mut out = format!(r#"{{"$schema":{}"#, json_string(DRAFT));
+    if let Some(title) = title {
+        out.push_str(&format!(r#","title":{}"#, json_string(title)));
+    }
+    out.push(',');
+    out.push_str(&body[1..body.len() - 1]);
+    if !defs.is_empty() {
+        let defs: Vec<String> = defs
+            .iter()
+            .map(|(name, def)| format!("{}:{def}", json_string(name)))
+            .collect();
+        out.push_str(&format!(r#","$defs":{{{}}}"#, defs.join(",")));
+    }
+    out.push('}');
+    out
+}
+
+/// Renders a `$ref` to the `$defs` entry for `name`, escaping it both as a
+/// JSON pointer token and as a JSON string.
+fn def_ref(name: &str) -> String {
+    let token = name.replace('~', "~0").replace('/', "~1");
+    format!(r#"{{"$ref":{}}}"#, json_string(&format!("#/$defs/{token}")))
+}
+
+/// Renders the `{"type":"object",...}` body for `schema`, collecting the names
+/// of referenced nested schemas into `refs`.
+fn object_schema(schema: &SchemaDef, ctx: Option<&AAML>, refs: &mut BTreeSet<String>) -> String {
+    let mut names: Vec<&String> = schema.fields.keys().collect();
+    names.sort();
+
+    let properties: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let mut ty = type_schema(&schema.fields[*name], ctx, refs);
+            if let Some(doc) = schema.field_doc(name) {
+                let sep = if ty == "{}" { "" } else { "," };
+                ty = format!(r#"{{"description":{}{sep}{}"#, json_string(doc), &ty[1..]);
+            }
+            format!("{}:{}", json_string(name), ty)
+        })
+        .collect();
+
+    let required: Vec<String> = names
+        .iter()
+        .filter(|name| schema.is_required(name))
+        .map(|name| json_string(name))
+        .collect();
+
+    let extra = if schema.deny_extra_objects {
+        r#","additionalProperties":false"#
+    } else {
+        ""
+    };
+    format!(
+        r#"{{"type":"object","properties":{{{}}},"required":[{}]{extra}}}"#,
+        properties.join(","),
+        required.join(",")
+    )
+}
+
+/// Maps a single AAML type name to its JSON Schema fragment, following the
+/// same resolution order as schema validation.
+fn type_schema(type_name: &str, ctx: Option<&AAML>, refs: &mut BTreeSet<String>) -> String {
+    let type_name = ctx.map_or(type_name, |aaml| aaml.resolve_alias(type_name));
+    if let Some(aaml) = ctx {
+        if let Some(type_def) = aaml.get_type(type_name) {
+            return type_def.json_schema();
+        }
+        if aaml.get_schema(type_name).is_some() {
+            refs.insert(type_name.to_string());
+            return def_ref(type_name);
+        }
+    }
+
+    if let Some(members) = UnionType::split(type_name) {
+        let members: Vec<String> = members.iter().map(|m| type_schema(m, ctx, refs)).collect();
+        return format!(r#"{{"anyOf":[{}]}}"#, members.join(","));
+    }
+
+    if let Ok(list) = ListType::parse(type_name) {
+        let items = type_schema(&list.inner_type, ctx, refs);
+        return format!(r#"{{"type":"array","items":{items}{}}}"#, list.len_keywords());
+    }
+
+    if let Some((_, value_type)) = MapType::split(type_name) {
+        let values = type_schema(value_type, ctx, refs);
+        return format!(r#"{{"type":"object","additionalProperties":{values}}}"#);
+    }
+
+    if let Some(inner) = OptionalType::parse_inner(type_name) {
+        let inner = type_schema(inner, ctx, refs);
+        return format!(r#"{{"anyOf":[{inner},{{"type":"null"}}]}}"#);
+    }
+
+    match resolve_builtin(type_name) {
+        Ok(type_def) => type_def.json_schema(),
+        Err(_) if ctx.is_some() => "{}".to_string(),
+        Err(_) => {
+            refs.insert(type_name.to_string());
+            def_ref(type_name)
+        }
+    }
+}
+
+/// Encodes `s` as a JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
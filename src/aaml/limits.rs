@@ -0,0 +1,169 @@
+//! Resource limits for files pulled in by `@import`, `@import_optional` and
+//! `@derive`.
+//!
+//! Without limits, a file that imports itself recurses until the stack
+//! overflows, and a config that fans out to many or huge files can exhaust
+//! memory. Every instance therefore enforces [`ImportLimits`]:
+//!
+//! - **depth** — how deeply directives may nest; a file loaded directly with
+//!   [`AAML::load`] or [`AAML::merge_file`] is at depth 0.
+//! - **files** — how many files directives may read in total.
+//! - **bytes** — how many bytes those files may add up to.
+//!
+//! Usage is counted over the lifetime of the instance, across every merge.
+//! Exceeding a limit fails the directive with [`AamlError::DirectiveError`].
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::aaml::limits::ImportLimits;
+//!
+//! let limits = ImportLimits { max_files: 0, ..ImportLimits::default() };
+//! let mut cfg = AAML::new().with_import_limits(limits);
+//! assert!(cfg.merge_content("@import other.aam").is_err());
+//! ```
+
+use super::AAML;
+use super::loader::{MEMORY_SCHEME, too_large};
+use super::options::ParseOptions;
+use crate::error::AamlError;
+use std::path::Path;
+
+/// Limits on the files read by directives. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportLimits {
+    /// Maximum nesting of imports; `1` allows imports but not nested ones.
+    pub max_depth: usize,
+    /// Maximum number of files read by directives.
+    pub max_files: usize,
+    /// Maximum cumulative size, in bytes, of the files read by directives.
+    pub max_bytes: u64,
+}
+
+impl ImportLimits {
+    /// Limits that never trigger, for trusted configs.
+    pub const UNLIMITED: ImportLimits = ImportLimits {
+        max_depth: usize::MAX,
+        max_files: usize::MAX,
+        max_bytes: u64::MAX,
+    };
+}
+
+impl Default for ImportLimits {
+    /// Depth 32, 256 files and 64 MiB — far above what hand-written configs need.
+    fn default() -> Self {
+        ImportLimits {
+            max_depth: 32,
+            max_files: 256,
+            max_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// What directives of one instance have consumed so far.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ImportUsage {
+    pub(super) depth: usize,
+    pub(super) files: usize,
+    pub(super) bytes: u64,
+}
+
+impl AAML {
+    /// Replaces the default [`ImportLimits`].
+    pub fn with_import_limits(mut self, limits: ImportLimits) -> Self {
+        self.options.import_limits = limits;
+        self
+    }
+
+    /// Returns the limits enforced on `@import` and `@derive`.
+    pub fn import_limits(&self) -> &ImportLimits {
+        &self.options.import_limits
+    }
+
+    /// Reads the file at `path` for `directive` from the registered sources or
+    /// the [`SourceLoader`](super::loader::SourceLoader), charging it against the
+    /// limits. The file count is checked before anything is read, and the
+    /// loader is given the remaining byte budget so it can stop early. A
+    /// missing file is reported as [`AamlError::IoError`], and charges nothing.
+    pub(crate) fn read_import(&mut self, directive: &str, path: &Path) -> Result<String, AamlError> {
+        let error = |msg: String| Err(AamlError::DirectiveError(directive.to_string(), msg, None));
+        let shown = path.display();
+        if self.usage.depth >= self.options.import_limits.max_depth {
+            return error(format!(
+                "Cannot read '{shown}': imports nest deeper than {} levels (is there a cycle?)",
+                self.options.import_limits.max_depth
+            ));
+        }
+        if self.usage.files >= self.options.import_limits.max_files {
+            return error(format!(
+                "Cannot read '{shown}': more than {} files imported",
+                self.options.import_limits.max_files
+            ));
+        }
+        let budget = self.options.import_limits.max_bytes.saturating_sub(self.usage.bytes);
+        let loaded = match path.to_str().and_then(|p| p.strip_prefix(MEMORY_SCHEME)) {
+            Some(name) => match self.sources.get(name) {
+                Some(source) if source.len() as u64 > budget => Err(too_large(path, budget)),
+                Some(source) => Ok(source.clone()),
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("No source registered as '{name}'"),
+                )),
+            },
+            None => self.loader.load_limited(path, budget),
+        };
+        let content = match loaded {
+            Err(e) if e.kind() == std::io::ErrorKind::FileTooLarge => {
+                return error(format!(
+                    "Cannot read '{shown}': imported files exceed {} bytes",
+                    self.options.import_limits.max_bytes
+                ));
+            }
+            other => other?,
+        };
+        let size = content.len() as u64;
+        self.usage.files += 1;
+        self.usage.bytes = self.usage.bytes.saturating_add(size);
+        Ok(content)
+    }
+
+    /// Runs `merge` one import level deeper.
+    pub(crate) fn nested<T>(&mut self, merge: impl FnOnce(&mut Self) -> T) -> T {
+        self.usage.depth += 1;
+        let result = merge(self);
+        self.usage.depth -= 1;
+        result
+    }
+
+    /// Creates an empty instance for a file loaded by a directive, which
+    /// shares this instance's limits, loader and sources and sits one level
+    /// deeper.
+    pub(crate) fn nested_instance(&self) -> AAML {
+        let mut aaml = AAML::new();
+        aaml.loader = self.loader.clone();
+        aaml.sources = self.sources.clone();
+        aaml.profile = self.profile.clone();
+        // A base file shares the syntax and key handling of the file deriving
+        // from it; strictness and coercion stay with the deriving instance.
+        aaml.options = ParseOptions {
+            strict_overrides: false,
+            strict_deprecations: false,
+            coercion: false,
+            ..self.options
+        };
+        aaml.import_stack = self.import_stack.clone();
+        aaml.unknown_directive = self.unknown_directive.clone();
+        aaml.usage = ImportUsage {
+            depth: self.usage.depth + 1,
+            ..self.usage
+        };
+        aaml
+    }
+
+    /// Takes over the files and bytes consumed by an instance created with
+    /// [`nested_instance`](Self::nested_instance).
+    pub(crate) fn absorb_usage(&mut self, nested: &AAML) {
+        self.usage.files = nested.usage.files;
+        self.usage.bytes = nested.usage.bytes;
+    }
+}
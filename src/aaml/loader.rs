@@ -0,0 +1,293 @@
+//! Where `@import`, `@import_optional` and `@derive` read their files from.
+//!
+//! Directive paths go through a [`SourceLoader`]. The default, [`FsLoader`],
+//! reads from disk; [`MemoryLoader`] serves a fixed set of files, and any
+//! other source (embedded assets, archives, a database) can implement the
+//! trait and be installed with [`AAML::with_loader`].
+//!
+//! With the `http-import` feature, [`HttpLoader`] additionally serves
+//! `http://` and `https://` URLs through a fetcher supplied by the
+//! application, so the crate does not pick an HTTP client.
+//!
+//! Sources registered with [`AAML::register_source`] are named
+//! `mem://name` and are served by the instance itself, whatever the loader.
+//!
+//! Paths handed to the loader are already resolved against the directory of
+//! the importing file (see [`AAML::with_source_dir`]). Only directives use the
+//! loader: [`AAML::load`] and [`AAML::merge_file`] always read from disk.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::aaml::loader::MemoryLoader;
+//!
+//! let loader = MemoryLoader::new()
+//!     .with_file("config/db.aam", "host = db.local")
+//!     .with_file("config/pool.aam", "size = 8");
+//! let mut cfg = AAML::new().with_loader(loader).with_source_dir("config");
+//! cfg.merge_content("@import db.aam\n@import pool.aam").unwrap();
+//! assert_eq!(cfg.find_obj("host").unwrap().as_str(), "db.local");
+//! ```
+//!
+//! [`AAML::with_loader`]: super::AAML::with_loader
+//! [`AAML::register_source`]: super::AAML::register_source
+//! [`AAML::with_source_dir`]: super::AAML::with_source_dir
+//! [`AAML::load`]: super::AAML::load
+//! [`AAML::merge_file`]: super::AAML::merge_file
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// A source of files for directives.
+pub trait SourceLoader: Send + Sync {
+    /// Returns the content of the file at `path`.
+    ///
+    /// A missing file must be reported with [`io::ErrorKind::NotFound`], which
+    /// `@import_optional` treats as "skip".
+    fn load(&self, path: &Path) -> io::Result<String>;
+
+    /// Like [`load`](Self::load), but fails with
+    /// [`io::ErrorKind::FileTooLarge`] when the file is longer than
+    /// `max_bytes`. Directives pass what is left of their byte budget.
+    ///
+    /// The default loads the whole file before checking its size; loaders
+    /// that can tell the size sooner should override it and stop early.
+    fn load_limited(&self, path: &Path, max_bytes: u64) -> io::Result<String> {
+        let content = self.load(path)?;
+        if content.len() as u64 > max_bytes {
+            return Err(too_large(path, max_bytes));
+        }
+        Ok(content)
+    }
+}
+
+/// The error for a file longer than the `max_bytes` given to
+/// [`SourceLoader::load_limited`].
+pub(crate) fn too_large(path: &Path, max_bytes: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::FileTooLarge,
+        format!("'{}' is larger than {max_bytes} bytes", path.display()),
+    )
+}
+
+/// Reads files from the filesystem. This is the default loader.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsLoader;
+
+impl SourceLoader for FsLoader {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    /// Checks the file's length first, and never reads more than one byte
+    /// past `max_bytes` in case it grows meanwhile.
+    fn load_limited(&self, path: &Path, max_bytes: u64) -> io::Result<String> {
+        let file = std::fs::File::open(path)?;
+        if file.metadata()?.len() > max_bytes {
+            return Err(too_large(path, max_bytes));
+        }
+        let mut content = String::new();
+        file.take(max_bytes.saturating_add(1)).read_to_string(&mut content)?;
+        if content.len() as u64 > max_bytes {
+            return Err(too_large(path, max_bytes));
+        }
+        Ok(content)
+    }
+}
+
+/// Serves files from memory, keyed by path. `.` and `..` components are
+/// resolved lexically, so `net/../base.aam` finds `base.aam`.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLoader {
+    files: HashMap<PathBuf, String>,
+}
+
+impl MemoryLoader {
+    /// Creates a loader without any files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file and returns the loader, for chaining.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    /// Adds or replaces a file.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.files.insert(normalize(&path.into()), content.into());
+    }
+}
+
+impl SourceLoader for MemoryLoader {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        self.load_limited(path, u64::MAX)
+    }
+
+    fn load_limited(&self, path: &Path, max_bytes: u64) -> io::Result<String> {
+        let content = self.files.get(&normalize(path)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("'{}' is not among the in-memory files", path.display()),
+            )
+        })?;
+        if content.len() as u64 > max_bytes {
+            return Err(too_large(path, max_bytes));
+        }
+        Ok(content.clone())
+    }
+}
+
+/// A request passed to the fetcher of an [`HttpLoader`] (`http-import` feature).
+#[cfg(feature = "http-import")]
+#[derive(Debug, Clone, Copy)]
+pub struct FetchRequest<'a> {
+    /// The `http://` or `https://` URL to fetch.
+    pub url: &'a str,
+    /// How long the whole request may take.
+    pub timeout: std::time::Duration,
+    /// The largest body to accept; the fetcher should stop reading beyond it.
+    pub max_bytes: u64,
+}
+
+/// The fetcher of an [`HttpLoader`].
+#[cfg(feature = "http-import")]
+type Fetcher = Box<dyn Fn(&FetchRequest<'_>) -> io::Result<String> + Send + Sync>;
+
+/// Serves `http://` and `https://` URLs with an application-supplied fetcher,
+/// and every other path with a fallback loader ([`FsLoader`] by default).
+///
+/// Requires the `http-import` feature. The fetcher receives the timeout and
+/// size limit to apply; a body over the limit is rejected even if the
+/// fetcher returns it. Relative paths inside a fetched file resolve against
+/// its URL, so `@import db.aam` in `https://cfg.example.com/base.aam` fetches
+/// `https://cfg.example.com/db.aam`.
+///
+/// # Example
+/// ```
+/// use aam_rs::aaml::AAML;
+/// use aam_rs::aaml::loader::HttpLoader;
+/// use std::time::Duration;
+///
+/// let loader = HttpLoader::new(|request| {
+///     // e.g. ureq or reqwest::blocking, honouring request.timeout
+///     assert_eq!(request.url, "https://config.example.com/base.aam");
+///     Ok("region = eu-west".to_string())
+/// })
+/// .with_timeout(Duration::from_secs(5));
+///
+/// let mut cfg = AAML::new().with_loader(loader);
+/// cfg.merge_content("@import https://config.example.com/base.aam").unwrap();
+/// assert_eq!(cfg.find_obj("region").unwrap().as_str(), "eu-west");
+/// ```
+#[cfg(feature = "http-import")]
+pub struct HttpLoader {
+    fetch: Fetcher,
+    fallback: Box<dyn SourceLoader>,
+    timeout: std::time::Duration,
+    max_bytes: u64,
+}
+
+#[cfg(feature = "http-import")]
+impl HttpLoader {
+    /// Creates a loader that fetches URLs with `fetch`, with a 10 s timeout
+    /// and a 1 MiB size limit.
+    pub fn new(
+        fetch: impl Fn(&FetchRequest<'_>) -> io::Result<String> + Send + Sync + 'static,
+    ) -> Self {
+        HttpLoader {
+            fetch: Box::new(fetch),
+            fallback: Box::new(FsLoader),
+            timeout: std::time::Duration::from_secs(10),
+            max_bytes: 1024 * 1024,
+        }
+    }
+
+    /// Sets the timeout passed to the fetcher.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the largest body accepted from a single URL.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets the loader used for paths that are not URLs.
+    pub fn with_fallback(mut self, loader: impl SourceLoader + 'static) -> Self {
+        self.fallback = Box::new(loader);
+        self
+    }
+}
+
+#[cfg(feature = "http-import")]
+impl SourceLoader for HttpLoader {
+    fn load(&self, path: &Path) -> io::Result<String> {
+        self.load_limited(path, u64::MAX)
+    }
+
+    /// Asks the fetcher for at most the smaller of `max_bytes` and the
+    /// loader's own limit.
+    fn load_limited(&self, path: &Path, max_bytes: u64) -> io::Result<String> {
+        let Some(url) = path.to_str().filter(|p| is_url(p)) else {
+            return self.fallback.load_limited(path, max_bytes);
+        };
+        let request = FetchRequest {
+            url,
+            timeout: self.timeout,
+            max_bytes: self.max_bytes.min(max_bytes),
+        };
+        let body = (self.fetch)(&request)?;
+        if body.len() as u64 > self.max_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("'{url}' is larger than {} bytes", self.max_bytes),
+            ));
+        }
+        if body.len() as u64 > max_bytes {
+            return Err(too_large(path, max_bytes));
+        }
+        Ok(body)
+    }
+}
+
+/// Returns `true` for `http://` and `https://` URLs.
+#[cfg(feature = "http-import")]
+fn is_url(path: &str) -> bool {
+    path.starts_with("https://") || path.starts_with("http://")
+}
+
+/// Prefix of paths naming sources registered with
+/// [`AAML::register_source`](super::AAML::register_source).
+pub const MEMORY_SCHEME: &str = "mem://";
+
+/// Returns the scheme `path` starts with, for paths that are not resolved
+/// against a directory: [`MEMORY_SCHEME`], and URLs with `http-import`.
+pub(crate) fn scheme_of(path: &str) -> Option<&'static str> {
+    #[cfg(feature = "http-import")]
+    for scheme in ["https://", "http://"] {
+        if path.starts_with(scheme) {
+            return Some(scheme);
+        }
+    }
+    path.starts_with(MEMORY_SCHEME).then_some(MEMORY_SCHEME)
+}
+
+/// Drops `.` components and cancels `..` against the preceding component.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(out.components().next_back(), Some(Component::Normal(_))) => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
@@ -5,15 +5,41 @@ use crate::found_value::FoundValue;
 use super::{AAML, Hasher};
 
 impl AAML {
-    /// Looks up `key` in the map. If not found as a key, performs a reverse
-    /// lookup — searching for an entry whose *value* matches `key`.
+    /// Looks up `key` in the map. If the key is not assigned, falls back to a
+    /// schema default declared for it (`field: type = value`), and finally
+    /// performs a reverse lookup — searching for an entry whose *value* matches `key`.
+    /// A key declared with `@alias` is looked up as the key it stands for.
     pub fn find_obj(&self, key: &str) -> Option<FoundValue> {
-        self.map
-            .get(key)
-            .map(|v| FoundValue::new(v))
+        self.value_of(key)
+            .map(FoundValue::new)
             .or_else(|| self.find_key(key))
     }
 
+    /// Returns the default value a schema declares for `key`.
+    ///
+    /// The default comes from the `@bind` schema of the key's prefix, else
+    /// from the `@conform` schema. Without either, every registered schema is
+    /// consulted and the default is only returned when all schemas declaring
+    /// one for `key` agree on it; conflicting defaults yield `None`.
+    pub fn schema_default(&self, key: &str) -> Option<&str> {
+        if let Some((schema, rest)) = self.binding_for(key) {
+            return self.schemas.get(schema)?.default_for(rest?);
+        }
+        if let Some(name) = &self.conform {
+            return self.schemas.get(name)?.default_for(key);
+        }
+        let mut defaults = self.schemas.values().filter_map(|s| s.default_for(key));
+        let first = defaults.next()?;
+        defaults.all(|d| d == first).then_some(first)
+    }
+
+    /// Returns the assigned value of `key`, else its schema default, without
+    /// the reverse lookup done by [`find_obj`](Self::find_obj).
+    pub(crate) fn value_of(&self, key: &str) -> Option<&str> {
+        let key = &*self.canonical_key(key);
+        self.map.get(key).map(|v| &**v).or_else(|| self.schema_default(key))
+    }
+
     /// Reverse lookup: finds the key whose value equals `value`.
     pub fn find_key(&self, value: &str) -> Option<FoundValue> {
         self.map
@@ -22,8 +48,12 @@ impl AAML {
     }
 
     /// Follows a chain of key -> value -> key lookups until a terminal value
-    /// is reached or a cycle is detected.
+    /// is reached or a cycle is detected. A key that is not assigned but has a
+    /// schema default resolves to that default. An `@alias` is followed to
+    /// the key it stands for before the chain starts.
     pub fn find_deep(&self, key: &str) -> Option<FoundValue> {
+        let canonical = self.canonical_key(key);
+        let key = canonical.as_ref();
         let mut current_key = key;
         let mut last_found = None;
         let mut visited: HashSet<&str, Hasher> = HashSet::with_hasher(Hasher::default());
@@ -42,6 +72,8 @@ impl AAML {
             current_key = next_val;
         }
 
-        last_found.map(|v| FoundValue::new(v))
+        last_found
+            .map(|v| FoundValue::new(v))
+            .or_else(|| self.schema_default(key).map(FoundValue::new))
     }
 }
\ No newline at end of file
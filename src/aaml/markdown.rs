@@ -0,0 +1,78 @@
+//! Markdown reference documentation for the schemas registered in [`AAML`](super::AAML).
+
+use crate::commands::schema::SchemaDef;
+use super::AAML;
+
+impl AAML {
+    /// Renders every registered schema as a Markdown section, ordered by name.
+    ///
+    /// Each section is a `##` heading followed by a table of the schema's
+    /// fields (sorted by name) with their type, whether they are required,
+    /// their default and the `# comment` documentation written above them.
+    /// The heading shows a declared `vN` version; `strict` and
+    /// `deny_extra_objects` schemas say so under it.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let cfg = AAML::parse("@schema Server {\n# Listen port.\nport: i32 = 80\n}").unwrap();
+    /// let md = cfg.schemas_to_markdown();
+    /// assert!(md.starts_with("## Server\n"));
+    /// assert!(md.contains("| `port` | `i32` | no | `80` | Listen port. |"));
+    /// ```
+    pub fn schemas_to_markdown(&self) -> String {
+        let mut names: Vec<&String> = self.schemas.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| schema_section(name, &self.schemas[name]))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Renders the heading, flags and field table for one schema.
+fn schema_section(name: &str, schema: &SchemaDef) -> String {
+    let mut out = match schema.version {
+        Some(version) => format!("## {name} (v{version})\n\n"),
+        None => format!("## {name}\n\n"),
+    };
+
+    let mut notes = Vec::new();
+    if schema.strict {
+        notes.push("Strict: keys it does not declare are rejected.");
+    }
+    if schema.deny_extra_objects {
+        notes.push("Inline objects may not contain undeclared fields.");
+    }
+    if !notes.is_empty() {
+        out.push_str(&notes.join(" "));
+        out.push_str("\n\n");
+    }
+
+    out.push_str("| Field | Type | Required | Default | Description |\n");
+    out.push_str("|-------|------|----------|---------|-------------|\n");
+    let mut fields: Vec<&String> = schema.fields.keys().collect();
+    fields.sort();
+    for field in fields {
+        let default = schema
+            .default_for(field)
+            .map(|d| format!("`{}`", cell(d)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "| `{}` | `{}` | {} | {} | {} |\n",
+            cell(field),
+            cell(&schema.fields[field]),
+            if schema.is_required(field) { "yes" } else { "no" },
+            default,
+            schema.field_doc(field).map(cell).unwrap_or_default(),
+        ));
+    }
+    out
+}
+
+/// Escapes text for a table cell: `|` would end the cell and a newline the row.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
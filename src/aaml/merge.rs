@@ -0,0 +1,172 @@
+//! Three-way merge of concurrently edited documents.
+//!
+//! [`AAML::merge3`] combines two documents (`ours`, `theirs`) that were both
+//! derived from a common `base`, the way version control merges text files:
+//!
+//! - A key changed (or added, or removed) on one side only takes that side's value.
+//! - A key changed identically on both sides takes the common value.
+//! - A key whose value is an inline object on every side that has it is merged
+//!   field by field, recursively, so edits to different fields of `server`
+//!   do not conflict.
+//! - Anything else changed on both sides is a [`MergeConflict`]; the merged
+//!   document keeps `ours` for it.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let base = AAML::parse("server = { host = a, port = 80 }\nworkers = 4").unwrap();
+//! let ours = AAML::parse("server = { host = b, port = 80 }\nworkers = 8").unwrap();
+//! let theirs = AAML::parse("server = { host = a, port = 81 }\nworkers = 16").unwrap();
+//!
+//! let result = AAML::merge3(&base, &ours, &theirs);
+//! assert_eq!(result.merged.find_obj("server").unwrap().as_str(), "{ host = b, port = 81 }");
+//! assert_eq!(result.conflicts.len(), 1);
+//! assert_eq!(result.conflicts[0].path, "workers");
+//! ```
+
+use super::{AAML, parsing};
+use std::collections::BTreeSet;
+
+/// A key or inline-object field changed differently on both sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MergeConflict {
+    /// The key, or `key.field` for a field of an inline object.
+    pub path: String,
+    /// Value in the common ancestor, `None` if absent.
+    pub base: Option<String>,
+    /// Value on our side, `None` if removed.
+    pub ours: Option<String>,
+    /// Value on their side, `None` if removed.
+    pub theirs: Option<String>,
+}
+
+/// Outcome of [`AAML::merge3`].
+#[derive(Debug)]
+pub struct MergeResult {
+    /// The merged document. Conflicting paths hold `ours`.
+    pub merged: AAML,
+    /// Every conflicting path, ordered by key.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Returns `true` when the merge needed no manual resolution.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl AAML {
+    /// Merges `ours` and `theirs`, both derived from `base`.
+    ///
+    /// The merged document holds the merged key-value pairs and the schemas
+    /// of `ours`, plus schemas only `theirs` declares. Values are not
+    /// re-validated, and registered `@type`s are not carried over.
+    pub fn merge3(base: &AAML, ours: &AAML, theirs: &AAML) -> MergeResult {
+        let keys: BTreeSet<&str> = [base, ours, theirs]
+            .iter()
+            .flat_map(|doc| doc.map.keys().map(|k| &**k))
+            .collect();
+
+        let mut merged = AAML::new();
+        let mut conflicts = Vec::new();
+        for key in keys {
+            let value = merge_value(
+                key,
+                base.map.get(key).map(|v| &**v),
+                ours.map.get(key).map(|v| &**v),
+                theirs.map.get(key).map(|v| &**v),
+                &mut conflicts,
+            );
+            if let Some(value) = value {
+                merged.map.insert(Box::from(key), value.into_boxed_str());
+            }
+        }
+
+        merged.schemas = ours.schemas.clone();
+        for (name, schema) in &theirs.schemas {
+            merged.schemas.entry(name.clone()).or_insert_with(|| schema.clone());
+        }
+        MergeResult { merged, conflicts }
+    }
+}
+
+/// Merges one value; `None` means absent. Records a conflict and keeps
+/// `ours` when both sides changed it differently.
+fn merge_value(
+    path: &str,
+    base: Option<&str>,
+    ours: Option<&str>,
+    theirs: Option<&str>,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<String> {
+    if ours == theirs || theirs == base {
+        return ours.map(str::to_string);
+    }
+    if ours == base {
+        return theirs.map(str::to_string);
+    }
+    if let (Some(o), Some(t)) = (ours, theirs)
+        && let Some(merged) = merge_objects(path, base, o, t, conflicts)
+    {
+        return Some(merged);
+    }
+    conflicts.push(MergeConflict {
+        path: path.to_string(),
+        base: base.map(str::to_string),
+        ours: ours.map(str::to_string),
+        theirs: theirs.map(str::to_string),
+    });
+    ours.map(str::to_string)
+}
+
+/// Merges two inline objects field by field. Returns `None` when either side
+/// (or an existing base) is not a parseable inline object.
+fn merge_objects(
+    path: &str,
+    base: Option<&str>,
+    ours: &str,
+    theirs: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<String> {
+    let parse = |v: &str| {
+        parsing::is_inline_object(v)
+            .then(|| parsing::parse_inline_object(v).ok())
+            .flatten()
+    };
+    let base = match base {
+        Some(b) => parse(b)?,
+        None => Vec::new(),
+    };
+    let (ours, theirs) = (parse(ours)?, parse(theirs)?);
+
+    // Ours' order first, then fields only theirs or base mention.
+    let mut fields: Vec<&str> = Vec::new();
+    for (k, _) in ours.iter().chain(&theirs).chain(&base) {
+        if !fields.contains(&k.as_str()) {
+            fields.push(k);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for field in fields {
+        let value = merge_value(
+            &format!("{path}.{field}"),
+            get(&base, field),
+            get(&ours, field),
+            get(&theirs, field),
+            conflicts,
+        );
+        if let Some(value) = value {
+            pairs.push((field.to_string(), value));
+        }
+    }
+    Some(parsing::format_inline_object(&pairs))
+}
+
+/// Returns the value of `field` in parsed inline-object pairs.
+fn get<'a>(pairs: &'a [(String, String)], field: &str) -> Option<&'a str> {
+    pairs.iter().find(|(k, _)| k == field).map(|(_, v)| v.as_str())
+}
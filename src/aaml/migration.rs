@@ -0,0 +1,123 @@
+//! Schema versioning: migrations that upgrade documents written for an older
+//! version of a schema.
+//!
+//! A schema declares its version with a `vN` header flag. An application
+//! registers one migration per version step with
+//! [`AAML::register_migration`]; when content declaring an older version of
+//! the schema is merged, the steps are chained from that version to the newest
+//! one reachable and each rewrites the key map in turn.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let mut cfg = AAML::new();
+//! cfg.register_migration("Config", 1, 2, |map| {
+//!     if let Some(secs) = map.remove("timeout") {
+//!         let secs: i64 = secs.parse().map_err(|_| format!("bad timeout '{secs}'"))?;
+//!         map.insert("timeout_ms".into(), (secs * 1000).to_string());
+//!     }
+//!     Ok(())
+//! })
+//! .unwrap();
+//!
+//! cfg.merge_content("@schema Config v1 { timeout: i32 }\ntimeout = 5").unwrap();
+//! assert_eq!(cfg.find_obj("timeout_ms").unwrap().as_str(), "5000");
+//! assert_eq!(cfg.get_schema("Config").unwrap().version(), Some(2));
+//! ```
+
+use super::AAML;
+use crate::error::AamlError;
+use std::collections::HashMap;
+
+impl AAML {
+    /// Registers a migration that rewrites a document from version `from` of
+    /// the schema `schema` to version `to`.
+    ///
+    /// Migrations run at the end of [`merge_content`](Self::merge_content)
+    /// (and the file and compressed variants) for every schema whose declared
+    /// version has a registered step, chaining steps until none starts at the
+    /// reached version. Each step receives every assigned key; an `Err`
+    /// message aborts the whole upgrade and leaves the document unchanged.
+    /// Afterwards the schema's [`version`](crate::commands::schema::SchemaDef::version)
+    /// is the version reached. Rewritten values are not re-validated; check
+    /// them against the current definition with
+    /// [`apply_schema`](Self::apply_schema) if needed.
+    ///
+    /// A later migration from the same `from` replaces the earlier one.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] if `to` is not greater than `from`.
+    pub fn register_migration<F>(
+        &mut self,
+        schema: &str,
+        from: u32,
+        to: u32,
+        migration: F,
+    ) -> Result<(), AamlError>
+    where
+        F: Fn(&mut HashMap<String, String>) -> Result<(), String> + Send + Sync + 'static,
+    {
+        if to <= from {
+            return Err(AamlError::InvalidValue(format!(
+                "Migration of schema '{schema}' must go to a newer version, got v{from} -> v{to}"
+            )));
+        }
+        self.migrations
+            .entry(schema.to_string())
+            .or_default()
+            .insert(from, (to, Box::new(migration)));
+        Ok(())
+    }
+
+    /// Upgrades the key map of every schema with pending migration steps.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] for `schema` naming the failed step.
+    pub(crate) fn run_migrations(&mut self) -> Result<(), AamlError> {
+        if self.migrations.is_empty() {
+            return Ok(());
+        }
+        let mut names: Vec<&String> = self.schemas.keys().collect();
+        names.sort();
+
+        let mut map: Option<HashMap<String, String>> = None;
+        let mut reached = Vec::new();
+        for name in names {
+            let (Some(steps), Some(start)) = (self.migrations.get(name), self.schemas[name].version)
+            else {
+                continue;
+            };
+            let mut version = start;
+            while let Some((to, migrate)) = steps.get(&version) {
+                let map = map.get_or_insert_with(|| {
+                    self.map.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+                });
+                migrate(map).map_err(|msg| {
+                    AamlError::DirectiveError(
+                        "schema".into(),
+                        format!("Migrating schema '{name}' from v{version} to v{to} failed: {msg}"),
+                        None,
+                    )
+                })?;
+                version = *to;
+            }
+            if version != start {
+                reached.push((name.clone(), version));
+            }
+        }
+
+        if let Some(map) = map {
+            self.map = map
+                .into_iter()
+                .map(|(k, v)| (k.into_boxed_str(), v.into_boxed_str()))
+                .collect();
+        }
+        for (name, version) in reached {
+            if let Some(schema) = self.schemas.get_mut(&name) {
+                schema.version = Some(version);
+            }
+        }
+        Ok(())
+    }
+}
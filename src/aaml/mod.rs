@@ -2,28 +2,77 @@
 //!
 //! [`AAML`] is the main entry point for parsing `.aam` configuration files.
 //! It supports:
-//! - Key-value assignments (`key = value`)
-//! - Directives: `@import`, `@derive`, `@schema`, `@type`
+//! - Key-value assignments (`key = value`), with `${name}` / `${name:-default}`
+//!   references to other keys or environment variables in values
+//! - Directives: `@import`, `@import_optional`, `@derive`, `@schema`, `@mixin`, `@type`, `@conform`, `@bind`,
+//!   `@require`, `@rule`, `@assert_os`, `@assert_min_version`, `@env`, `@define`,
+//!   `@namespace`, `@endnamespace`, `@override`, `@sealed`, `@deprecated`, `@alias`, `@profile`
+//! - Nested blocks (`server { host = localhost  port = 8080 }`), stored as
+//!   inline objects
+//! - INI-style section headers (`[database]`), shorthand for `@namespace database`
+//! - Conditional blocks (`@if` / `@elif` / `@else` / `@endif`) evaluated against
+//!   a context supplied with [`AAML::with_context`]
+//! - Profile blocks (`@profile dev { ... }`) selected with [`AAML::with_profile`]
+//!   or [`AAML::load_with_profile`]
 //! - Runtime type validation via registered or built-in types
-//! - Schema-based struct validation with [`AAML::apply_schema`]
+//! - Schema-based struct validation with [`AAML::apply_schema`] and, for many
+//!   records at once, [`AAML::apply_schema_batch`]
+//! - Reports listing every violation with [`AAML::apply_schema_report`] and
+//!   [`AAML::parse_with_options`]
+//! - Atomic delta files applied with [`AAML::apply_patch`]
+//! - Three-way merges of concurrent edits with [`AAML::merge3`]
+//! - Reference documentation generated with [`AAML::schemas_to_markdown`]
+//! - Versioned schemas upgraded on load with [`AAML::register_migration`]
+//! - Depth, file-count and size limits on imports with [`AAML::with_import_limits`]
+//! - Imports served from memory or any other source with [`AAML::with_loader`]
+//! - A span-preserving syntax tree for tooling with [`AAML::parse_to_ast`]
+//! - Constant-memory streaming of parse events with [`events::EventReader`]
+//! - Reading from standard input with [`AAML::from_stdin`] and from memory-mapped
+//!   files with `AAML::load_mmap` (`mmap` feature)
+//! - Non-blocking loading for tokio services with `AAML::load_async` (`tokio` feature)
 
-use crate::commands::{self, Command};
-use crate::error::AamlError;
-use crate::commands::schema::SchemaDef;
-use crate::types::Type;
-use std::collections::HashMap;
+use crate::commands::{self, Command, ExecutionContext};
+use crate::error::{AamlError, SourceLocation};
+use crate::commands::rule::Rule;
+use crate::commands::schema::{self, SchemaDef};
+use crate::types::{Type, resolve_builtin};
+use crate::units::Dimension;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::ops::{Add, AddAssign};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 mod lookup;
+mod interpolate;
+mod conditional;
 mod validation;
+mod tree;
+pub mod batch;
+pub mod patch;
+pub mod merge;
+pub mod report;
+pub mod options;
+pub mod events;
+pub mod ast;
+pub mod limits;
+pub mod loader;
+pub(crate) mod json_schema;
+mod markdown;
+mod migration;
+mod input;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compressed;
+#[cfg(feature = "tokio")]
+mod async_load;
 pub mod parsing;
 pub mod types_registry;
 #[cfg(feature = "serde")]
 pub mod serialize;
 
+pub use options::{DuplicateKeys, ParseOptions};
+
 #[cfg(feature = "perf-hash")]
 type Hasher = ahash::RandomState;
 
@@ -32,6 +81,16 @@ type Hasher = std::collections::hash_map::RandomState;
 
 type AamlString = Box<str>;
 
+/// A domain check registered with [`AAML::register_field_validator`].
+type FieldValidator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// A key-map rewrite registered with [`AAML::register_migration`].
+type Migration = Box<dyn Fn(&mut HashMap<String, String>) -> Result<(), String> + Send + Sync>;
+
+/// A fallback set with [`AAML::set_unknown_directive_handler`].
+type UnknownDirectiveHandler =
+    Arc<dyn Fn(&mut AAML, &str, &str, &ExecutionContext) -> Result<(), AamlError> + Send + Sync>;
+
 /// The main AAML parser and configuration store.
 ///
 /// Holds a flat key-value map, registered type definitions, command handlers,
@@ -48,8 +107,58 @@ type AamlString = Box<str>;
 pub struct AAML {
     map: HashMap<AamlString, AamlString, Hasher>,
     commands: HashMap<String, Arc<dyn Command>>,
+    /// Runs directives that no registered command handles.
+    unknown_directive: Option<UnknownDirectiveHandler>,
     types: HashMap<String, Box<dyn Type>>,
     schemas: HashMap<String, SchemaDef>,
+    mixins: HashMap<String, SchemaDef>,
+    /// Parsing switches, set with [`with_parse_options`](Self::with_parse_options)
+    /// or the individual `with_*` builders.
+    options: ParseOptions,
+    /// Keys marked with `@override`.
+    overrides: HashSet<String>,
+    /// Keys taken from `@derive` bases, tracked in strict mode.
+    inherited: HashSet<String>,
+    /// Keys marked with `@deprecated`, with their optional hint.
+    deprecated: HashMap<String, Option<String>>,
+    /// Line of the first assignment of each key, tracked unless later
+    /// assignments simply win.
+    assigned: HashMap<String, usize>,
+    /// Non-fatal problems found while parsing, such as deprecated keys.
+    warnings: Vec<report::Warning>,
+    conform: Option<String>,
+    bindings: HashMap<String, String>,
+    /// Old key names declared with `@alias`, mapped to the key they stand for.
+    aliases: HashMap<String, String>,
+    required: Vec<String>,
+    required_types: HashMap<String, String>,
+    rules: Vec<Rule>,
+    field_validators: HashMap<String, HashMap<String, Vec<FieldValidator>>>,
+    migrations: HashMap<String, HashMap<u32, (u32, Migration)>>,
+    /// Keys whose values contain `${...}` references, with the line they were
+    /// assigned on, awaiting resolution at the end of the merge.
+    deferred: Vec<interpolate::Deferred>,
+    /// Names available to `@if` conditions, set with [`AAML::with_context`].
+    context: HashMap<String, String>,
+    /// Profile whose `@profile` blocks apply, set with [`AAML::with_profile`].
+    profile: Option<String>,
+    /// Constants declared with `@define`, substituted into later values.
+    constants: HashMap<String, String>,
+    /// Directory of the file being merged, against which relative `@import`
+    /// and `@derive` paths are resolved.
+    source_dir: Option<PathBuf>,
+    /// Files being merged, outermost first, for directive locations.
+    import_stack: Vec<PathBuf>,
+    /// Prefix opened by `@namespace` for the keys assigned after it.
+    namespace: Option<String>,
+    /// Prefix of the `@import ... as` being merged, applied before `namespace`.
+    key_prefix: Option<String>,
+    /// What files read by `@import` and `@derive` have used so far.
+    usage: limits::ImportUsage,
+    /// Reads the files named by `@import` and `@derive`.
+    loader: Arc<dyn loader::SourceLoader>,
+    /// Named in-memory sources, addressed as `mem://name`.
+    sources: Arc<HashMap<String, String>>,
 }
 
 impl std::fmt::Debug for AAML {
@@ -67,8 +176,35 @@ impl AAML {
         let mut instance = AAML {
             map: HashMap::with_hasher(Hasher::new()),
             commands: HashMap::new(),
+            unknown_directive: None,
             types: HashMap::new(),
             schemas: HashMap::new(),
+            mixins: HashMap::new(),
+            options: ParseOptions::default(),
+            overrides: HashSet::new(),
+            inherited: HashSet::new(),
+            deprecated: HashMap::new(),
+            assigned: HashMap::new(),
+            warnings: Vec::new(),
+            conform: None,
+            bindings: HashMap::new(),
+            aliases: HashMap::new(),
+            required: Vec::new(),
+            required_types: HashMap::new(),
+            rules: Vec::new(),
+            field_validators: HashMap::new(),
+            migrations: HashMap::new(),
+            deferred: Vec::new(),
+            context: HashMap::new(),
+            profile: None,
+            constants: HashMap::new(),
+            source_dir: None,
+            import_stack: Vec::new(),
+            namespace: None,
+            key_prefix: None,
+            usage: limits::ImportUsage::default(),
+            loader: Arc::new(loader::FsLoader),
+            sources: Arc::default(),
         };
         instance.register_default_commands();
         instance
@@ -76,13 +212,8 @@ impl AAML {
 
     /// Creates a new [`AAML`] instance pre-allocated for `capacity` key-value entries.
     pub fn with_capacity(capacity: usize) -> AAML {
-        let mut instance = AAML {
-            map: HashMap::with_capacity_and_hasher(capacity, Hasher::default()),
-            commands: HashMap::new(),
-            types: HashMap::new(),
-            schemas: HashMap::new(),
-        };
-        instance.register_default_commands();
+        let mut instance = AAML::new();
+        instance.map.reserve(capacity);
         instance
     }
 
@@ -96,10 +227,596 @@ impl AAML {
         self.schemas.get(name)
     }
 
+    /// Registers `schema` under `name`, exactly as if it had been declared
+    /// with `@schema`. A schema with the same name is replaced unless it is
+    /// sealed.
+    ///
+    /// # Errors
+    /// - [`AamlError::InvalidValue`] — the name is empty, an optional field
+    ///   or default refers to a field the schema does not declare, or the
+    ///   existing schema is sealed.
+    /// - [`AamlError::SchemaValidationError`] — a field type is malformed or a
+    ///   default does not satisfy its field type.
+    ///
+    /// On error the previously registered schema, if any, is kept.
+    pub fn register_schema(&mut self, name: &str, schema: SchemaDef) -> Result<(), AamlError> {
+        schema::ensure_unsealed(self, name).map_err(AamlError::InvalidValue)?;
+        schema::check_structure(name, &schema)?;
+        schema::validate_defaults(self, name, &schema)?;
+        self.schemas.insert(name.to_string(), schema);
+        Ok(())
+    }
+
+    /// Returns the field bundle registered with `@mixin` under `name`, if any.
+    pub fn get_mixin(&self, name: &str) -> Option<&SchemaDef> {
+        self.mixins.get(name)
+    }
+
+    pub(crate) fn get_mixins_mut(&mut self) -> &mut HashMap<String, SchemaDef> {
+        &mut self.mixins
+    }
+
+    /// Returns the schema the document is bound to via `@conform`, if any.
+    pub fn conformed_schema(&self) -> Option<&str> {
+        self.conform.as_deref()
+    }
+
+    pub(crate) fn set_conformed_schema(&mut self, name: String) {
+        self.conform = Some(name);
+    }
+
+    /// Returns the schema `key` is bound to via `@bind`, if any.
+    pub fn bound_schema(&self, key: &str) -> Option<&str> {
+        self.bindings.get(key).map(String::as_str)
+    }
+
+    pub(crate) fn bind_key(&mut self, key: String, schema: String) {
+        self.bindings.insert(key, schema);
+    }
+
+    /// Returns the key `key` stands for when it is an `@alias`, else `key`.
+    /// With [`with_case_insensitive_keys`](Self::with_case_insensitive_keys)
+    /// the key is lowercased first.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let cfg = AAML::parse("@alias hostname = host\nhostname = example.com").unwrap();
+    /// assert_eq!(cfg.canonical_key("hostname"), "host");
+    /// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "example.com");
+    /// assert_eq!(cfg.find_obj("hostname").unwrap().as_str(), "example.com");
+    /// ```
+    pub fn canonical_key<'a>(&'a self, key: &'a str) -> std::borrow::Cow<'a, str> {
+        let key = self.fold_case(key);
+        match self.aliases.get(key.as_ref()) {
+            Some(target) => std::borrow::Cow::Borrowed(target),
+            None => key,
+        }
+    }
+
+    /// Lowercases `key` when keys are case-insensitive, else returns it as is.
+    pub(crate) fn fold_case<'a>(&self, key: &'a str) -> std::borrow::Cow<'a, str> {
+        if self.options.case_insensitive_keys && key.chars().any(char::is_uppercase) {
+            std::borrow::Cow::Owned(key.to_lowercase())
+        } else {
+            std::borrow::Cow::Borrowed(key)
+        }
+    }
+
+    /// Treats keys that differ only in case as the same key, for configs
+    /// where `Host` and `host` are used interchangeably. Assigned keys and
+    /// `@alias` names are stored lowercased, and lookups such as
+    /// [`find_obj`](Self::find_obj) lowercase the key they are given. Keys
+    /// named by other directives and schema fields are matched as written,
+    /// so write those in lowercase. Off by default.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_case_insensitive_keys(true);
+    /// cfg.merge_content("Host = example.com\nPORT = 80").unwrap();
+    /// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "example.com");
+    /// assert_eq!(cfg.find_obj("Port").unwrap().as_str(), "80");
+    /// ```
+    pub fn with_case_insensitive_keys(mut self, enabled: bool) -> Self {
+        self.options.case_insensitive_keys = enabled;
+        self
+    }
+
+    /// Returns `true` when keys are matched case-insensitively.
+    pub fn case_insensitive_keys_enabled(&self) -> bool {
+        self.options.case_insensitive_keys
+    }
+
+    /// Makes `alias` stand for `target`, which must already be canonical.
+    /// Aliases of `alias` are redirected to `target`, and a value already
+    /// assigned to `alias` moves to `target` unless `target` has one.
+    pub(crate) fn add_alias(&mut self, alias: &str, target: &str) -> Result<(), AamlError> {
+        let alias = self.fold_case(alias).into_owned();
+        let alias = alias.as_str();
+        for existing in self.aliases.values_mut().filter(|t| *t == alias) {
+            *existing = target.to_string();
+        }
+        self.aliases.insert(alias.to_string(), target.to_string());
+        match self.map.remove(alias) {
+            Some(value) if !self.map.contains_key(target) => self.insert_validated(target, &value),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the keys marked mandatory with `@require`, in declaration order.
+    pub fn required_keys(&self) -> &[String] {
+        &self.required
+    }
+
+    /// Returns the type a `@require key: type` declared for `key`, if any.
+    pub fn required_type(&self, key: &str) -> Option<&str> {
+        self.required_types.get(key).map(String::as_str)
+    }
+
+    pub(crate) fn require_key(&mut self, key: &str, type_name: Option<&str>) {
+        if !self.required.iter().any(|k| k == key) {
+            self.required.push(key.to_string());
+        }
+        if let Some(type_name) = type_name {
+            self.required_types.insert(key.to_string(), type_name.to_string());
+        }
+    }
+
+    /// Returns the cross-field rules declared with `@rule`, in declaration order.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub(crate) fn add_rule(&mut self, rule: Rule) {
+        self.rules.push(rule);
+    }
+
+    /// Returns the prefix opened by `@namespace`, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    pub(crate) fn set_namespace(&mut self, namespace: Option<String>) {
+        self.namespace = namespace;
+    }
+
+    /// Runs `merge` with every assigned key prefixed by `prefix`, nested in
+    /// any enclosing prefix, restoring the previous prefix afterwards.
+    pub(crate) fn with_key_prefix<T>(&mut self, prefix: &str, merge: impl FnOnce(&mut Self) -> T) -> T {
+        let prefix = parsing::qualify(self.key_prefix.as_deref(), prefix).into_owned();
+        let outer = self.key_prefix.replace(prefix);
+        let result = merge(self);
+        self.key_prefix = outer;
+        result
+    }
+
+    /// Returns the prefix for keys assigned at this point: the import prefix
+    /// followed by the open namespace.
+    fn key_scope(&self) -> Option<String> {
+        match (&self.key_prefix, &self.namespace) {
+            (Some(prefix), Some(namespace)) => Some(format!("{prefix}.{namespace}")),
+            (prefix, namespace) => prefix.clone().or_else(|| namespace.clone()),
+        }
+    }
+
+    /// Returns the value of the constant `name` declared with `@define`.
+    pub fn constant(&self, name: &str) -> Option<&str> {
+        self.constants.get(name).map(String::as_str)
+    }
+
+    /// Declares a constant, substituting the constants already declared into
+    /// its value.
+    pub(crate) fn define_constant(&mut self, name: &str, value: &str) {
+        let value = parsing::substitute_constants(value, &self.constants).0.into_owned();
+        self.constants.insert(name.to_string(), value);
+    }
+
     pub(crate) fn get_map_mut(&mut self) -> &mut HashMap<AamlString, AamlString, Hasher> {
         &mut self.map
     }
 
+    /// Validates `value` against any schema declaring `key`, then stores it —
+    /// in canonical form when coercion is enabled.
+    pub(crate) fn insert_validated(&mut self, key: &str, value: &str) -> Result<(), AamlError> {
+        self.validate_against_schemas(key, value)?;
+        self.forget_deferred(key);
+        let stored = match self.schema_field_type(key) {
+            Some(type_name) if self.options.coercion => self.normalize_value(type_name, value),
+            _ => value.to_string(),
+        };
+        self.map.insert(Box::from(key), stored.into_boxed_str());
+        Ok(())
+    }
+
+    /// Enables or disables coercion of schema-validated values.
+    ///
+    /// With coercion on, every assignment whose key is declared in a schema is
+    /// stored in the canonical form of its type: trimmed, booleans lowercased,
+    /// numbers without padding (`007` → `7`) and colors as `#rrggbbaa`. Values
+    /// of undeclared keys are stored as written. Off by default.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_coercion(true);
+    /// cfg.merge_content("@schema S { debug: bool, tint: color }\ndebug = TRUE\ntint = #FF8800").unwrap();
+    /// assert_eq!(cfg.find_obj("debug").unwrap().as_str(), "true");
+    /// assert_eq!(cfg.find_obj("tint").unwrap().as_str(), "#ff8800ff");
+    /// ```
+    pub fn with_coercion(mut self, enabled: bool) -> Self {
+        self.options.coercion = enabled;
+        self
+    }
+
+    /// Returns `true` when coercion of validated values is enabled.
+    pub fn coercion_enabled(&self) -> bool {
+        self.options.coercion
+    }
+
+    /// When enabled, a key that collides with one inherited through `@derive`
+    /// is an error unless it is marked with `@override`, so that shadowing a
+    /// base value is always intentional. Off by default, where the child
+    /// silently wins.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_strict_overrides(true);
+    /// cfg.register_source("base", "port = 80\nhost = localhost");
+    /// assert!(cfg.merge_content("@derive mem://base\n@override port = 8080").is_ok());
+    /// assert!(cfg.merge_content("host = example.com").is_err());
+    /// ```
+    pub fn with_strict_overrides(mut self, enabled: bool) -> Self {
+        self.options.strict_overrides = enabled;
+        self
+    }
+
+    /// Returns `true` when overriding inherited keys requires `@override`.
+    pub fn strict_overrides_enabled(&self) -> bool {
+        self.options.strict_overrides
+    }
+
+    /// Returns `true` when `key` is marked with `@override`.
+    pub fn is_override(&self, key: &str) -> bool {
+        self.overrides.contains(key)
+    }
+
+    /// Marks `key`, relative to the open namespace, with `@override` and
+    /// returns the full key.
+    pub(crate) fn mark_override(&mut self, key: &str) -> String {
+        let key = parsing::qualify(self.key_scope().as_deref(), key).into_owned();
+        self.overrides.insert(key.clone());
+        key
+    }
+
+    /// Records that `key` was inherited from a `@derive` base.
+    pub(crate) fn mark_inherited(&mut self, key: &str) {
+        if self.options.strict_overrides {
+            self.inherited.insert(key.to_string());
+        }
+    }
+
+    /// In strict mode, fails when `key` shadows an inherited value without
+    /// an `@override` marker.
+    pub(crate) fn check_override(&self, key: &str, inherited: bool) -> Result<(), String> {
+        if self.options.strict_overrides && inherited && !self.overrides.contains(key) {
+            return Err(format!(
+                "Key '{key}' overrides an inherited value; mark it with '@override {key}' if this is intended"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Makes assigning a key marked with `@deprecated` an error instead of a
+    /// warning. Off by default, where the assignment is recorded in
+    /// [`warnings`](Self::warnings) and otherwise takes effect normally.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let content = "@deprecated old_timeout \"use timeout_ms instead\"\nold_timeout = 30";
+    /// let mut cfg = AAML::new();
+    /// cfg.merge_content(content).unwrap();
+    /// assert_eq!(cfg.warnings().len(), 1);
+    ///
+    /// let mut cfg = AAML::new().with_strict_deprecations(true);
+    /// assert!(cfg.merge_content(content).is_err());
+    /// ```
+    pub fn with_strict_deprecations(mut self, enabled: bool) -> Self {
+        self.options.strict_deprecations = enabled;
+        self
+    }
+
+    /// Returns `true` when assigning a deprecated key is an error.
+    pub fn strict_deprecations_enabled(&self) -> bool {
+        self.options.strict_deprecations
+    }
+
+    /// Accepts `key: value` as well as `key = value` for top-level
+    /// assignments, matching the inline-object grammar. Eases migration from
+    /// YAML-like files; off by default. Imported and derived files inherit
+    /// the setting.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_colon_assignments(true);
+    /// cfg.merge_content("host: localhost\nport = 8080").unwrap();
+    /// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "localhost");
+    /// assert!(AAML::parse("host: localhost").is_err());
+    /// ```
+    pub fn with_colon_assignments(mut self, enabled: bool) -> Self {
+        self.options.colon_assignments = enabled;
+        self
+    }
+
+    /// Returns `true` when `key: value` assignments are accepted.
+    pub fn colon_assignments_enabled(&self) -> bool {
+        self.options.colon_assignments
+    }
+
+    /// Accepts `//` line comments and `/* ... */` block comments alongside
+    /// `#`. Off by default, since `//` is otherwise ordinary value text.
+    /// Imported and derived files inherit the setting.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_slash_comments(true);
+    /// cfg.merge_content("/* server */\nhost = localhost // dev\nurl = http://host").unwrap();
+    /// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "localhost");
+    /// assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://host");
+    /// ```
+    pub fn with_slash_comments(mut self, enabled: bool) -> Self {
+        self.options.slash_comments = enabled;
+        self
+    }
+
+    /// Returns `true` when `//` and `/* */` comments are stripped.
+    pub fn slash_comments_enabled(&self) -> bool {
+        self.options.slash_comments
+    }
+
+    /// Sets what happens when a key is assigned more than once, which by
+    /// default silently replaces the earlier value. Imported and derived files
+    /// inherit the policy; each file is checked on its own.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    /// use aam_rs::aaml::DuplicateKeys;
+    ///
+    /// let content = "port = 80\nport = 8080";
+    /// let mut cfg = AAML::new().with_duplicate_keys(DuplicateKeys::FirstWins);
+    /// cfg.merge_content(content).unwrap();
+    /// assert_eq!(cfg.find_obj("port").unwrap().as_str(), "80");
+    ///
+    /// let mut cfg = AAML::new().with_duplicate_keys(DuplicateKeys::Error);
+    /// assert!(cfg.merge_content(content).is_err());
+    /// ```
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.options.duplicate_keys = policy;
+        self
+    }
+
+    /// Returns the policy for keys assigned more than once.
+    pub fn duplicate_keys(&self) -> DuplicateKeys {
+        self.options.duplicate_keys
+    }
+
+    /// Applies the duplicate-key policy to an assignment of `key` on `line`.
+    /// Returns whether the assignment should take effect, or the message when
+    /// a duplicate is an error.
+    fn check_duplicate(&mut self, key: &str, line: usize) -> Result<bool, String> {
+        if self.options.duplicate_keys == DuplicateKeys::LastWins {
+            return Ok(true);
+        }
+        let Some(&first) = self.assigned.get(key) else {
+            self.assigned.insert(key.to_string(), line);
+            return Ok(true);
+        };
+        let message = format!("Key '{key}' is already assigned on line {first}");
+        match self.options.duplicate_keys {
+            DuplicateKeys::Error => Err(message),
+            DuplicateKeys::Warn => {
+                self.warnings.push(report::Warning { line: (line > 0).then_some(line), message });
+                Ok(true)
+            }
+            DuplicateKeys::FirstWins => Ok(false),
+            DuplicateKeys::LastWins => Ok(true),
+        }
+    }
+
+    /// Returns `true` when `key` is marked with `@deprecated`.
+    pub fn is_deprecated(&self, key: &str) -> bool {
+        self.deprecated.contains_key(key)
+    }
+
+    /// Returns the warnings recorded so far, in the order found.
+    pub fn warnings(&self) -> &[report::Warning] {
+        &self.warnings
+    }
+
+    /// Marks `key` as deprecated, with an optional hint for the replacement.
+    pub(crate) fn deprecate_key(&mut self, key: &str, hint: Option<&str>) {
+        self.deprecated.insert(key.to_string(), hint.map(str::to_string));
+    }
+
+    /// Reports an assignment to `key` if it is deprecated: a warning
+    /// recorded against `line` (0 when unknown), or in strict mode an error.
+    pub(crate) fn check_deprecated(&mut self, key: &str, line: usize) -> Result<(), String> {
+        let Some(hint) = self.deprecated.get(key) else {
+            return Ok(());
+        };
+        let message = match hint {
+            Some(hint) => format!("Key '{key}' is deprecated: {hint}"),
+            None => format!("Key '{key}' is deprecated"),
+        };
+        if self.options.strict_deprecations {
+            return Err(message);
+        }
+        self.warnings.push(report::Warning { line: (line > 0).then_some(line), message });
+        Ok(())
+    }
+
+    /// Processes `line` as a `key = value` assignment found on line `line_num`.
+    pub(crate) fn assign(&mut self, line: &str, line_num: usize) -> Result<(), AamlError> {
+        self.process_assignment(line, line_num)
+    }
+
+    /// Processes `line` as an assignment or directive found on line `line_num`.
+    pub(crate) fn apply_line(&mut self, line: &str, line_num: usize) -> Result<(), AamlError> {
+        self.process_line(line, line_num)
+    }
+
+    /// Sets the names `@if` conditions can test, such as the target platform
+    /// or deployment environment. Context entries take precedence over keys
+    /// of the same name.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut cfg = AAML::new().with_context(HashMap::from([("env".to_string(), "prod".to_string())]));
+    /// cfg.merge_content("@if env == prod\nreplicas = 3\n@else\nreplicas = 1\n@endif").unwrap();
+    /// assert_eq!(cfg.find_obj("replicas").unwrap().as_str(), "3");
+    /// ```
+    pub fn with_context(mut self, context: HashMap<String, String>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Selects the profile whose `@profile` blocks apply; blocks for every
+    /// other profile are skipped. Without a profile, no block applies.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let content = "log_level = info\n@profile dev {\nlog_level = debug\n}";
+    /// let mut cfg = AAML::new().with_profile("dev");
+    /// cfg.merge_content(content).unwrap();
+    /// assert_eq!(cfg.find_obj("log_level").unwrap().as_str(), "debug");
+    /// ```
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Returns the profile selected with [`with_profile`](Self::with_profile).
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns `true` when `names`, a comma-separated list of profiles,
+    /// includes the selected one.
+    pub(crate) fn profile_selected(&self, names: &str) -> bool {
+        self.profile
+            .as_deref()
+            .is_some_and(|profile| names.split(',').any(|name| name.trim() == profile))
+    }
+
+    /// Resolves relative `@import` and `@derive` paths in content parsed from
+    /// a string against `dir` instead of the working directory.
+    ///
+    /// Files merged with [`merge_file`](Self::merge_file) or
+    /// [`load`](Self::load) always resolve against their own directory.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new().with_source_dir("config");
+    /// cfg.merge_content("@import database.aam").unwrap(); // reads config/database.aam
+    /// ```
+    pub fn with_source_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.source_dir = Some(dir.into());
+        self
+    }
+
+    /// Reads the files named by `@import`, `@import_optional` and `@derive`
+    /// through `loader` instead of the filesystem (see the [`loader`] module).
+    pub fn with_loader(mut self, loader: impl loader::SourceLoader + 'static) -> Self {
+        self.loader = Arc::new(loader);
+        self
+    }
+
+    /// Registers `content` under `name`, so that `@derive mem://name` and
+    /// `@import mem://name` read it without touching disk. Registering a
+    /// name again replaces its content.
+    ///
+    /// Relative paths inside a source resolve to other sources:
+    /// `@import db.aam` in `mem://app/main.aam` reads `mem://app/db.aam`.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new();
+    /// cfg.register_source("base", "host = localhost\nport = 8080");
+    /// cfg.merge_content("@derive mem://base\nport = 9000").unwrap();
+    /// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "localhost");
+    /// assert_eq!(cfg.find_obj("port").unwrap().as_str(), "9000");
+    /// ```
+    pub fn register_source(&mut self, name: &str, content: impl Into<String>) {
+        Arc::make_mut(&mut self.sources).insert(name.to_string(), content.into());
+    }
+
+    /// Returns the directory relative directive paths are currently resolved
+    /// against, or `None` for the working directory.
+    pub fn source_dir(&self) -> Option<&Path> {
+        self.source_dir.as_deref()
+    }
+
+    /// Resolves a path written in a directive against [`source_dir`](Self::source_dir).
+    pub(crate) fn resolve_path(&self, path: &str) -> PathBuf {
+        resolve_in(self.source_dir.as_deref(), path)
+    }
+
+    /// Runs `merge` with relative directive paths resolved against the
+    /// directory of `file`, restoring the previous directory afterwards.
+    /// The file is also on the import stack while `merge` runs.
+    pub(crate) fn in_file_dir<T>(&mut self, file: &Path, merge: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = std::mem::replace(&mut self.source_dir, dir_of(file));
+        self.import_stack.push(file.to_path_buf());
+        let result = merge(self);
+        self.import_stack.pop();
+        self.source_dir = previous;
+        result
+    }
+
+    /// Returns the location of line `line` of the content being merged.
+    fn location(&self, line: usize, column: usize) -> SourceLocation {
+        SourceLocation {
+            file: self.import_stack.last().cloned(),
+            line,
+            column,
+            import_stack: self.import_stack.clone(),
+        }
+    }
+
+    /// Fills in the location of the statement on `line` for errors that carry
+    /// one, unless it is already set by a nested file or statement. Other
+    /// errors are returned unchanged.
+    fn locate(&self, mut error: AamlError, line: usize, column: usize) -> AamlError {
+        if let AamlError::DirectiveError(_, _, location @ None)
+        | AamlError::InvalidType { location: location @ None, .. }
+        | AamlError::SchemaValidationError { location: location @ None, .. } = &mut error
+        {
+            *location = Some(Box::new(self.location(line, column)));
+        }
+        error
+    }
+
     // ── Type registry ────────────────────────────────────────────────────────
 
     /// Registers a custom command handler.
@@ -107,6 +824,33 @@ impl AAML {
         self.commands.insert(command.name().to_string(), Arc::new(command));
     }
 
+    /// Sets a handler for directives that no registered command handles,
+    /// instead of failing with "Unknown directive". It receives the instance,
+    /// the directive name without `@`, its arguments and where it appeared;
+    /// returning `Ok` accepts the directive.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let log = seen.clone();
+    /// let mut cfg = AAML::new();
+    /// cfg.set_unknown_directive_handler(move |_, name, args, ctx| {
+    ///     log.lock().unwrap().push(format!("{}: @{name} {}", ctx.line(), args.trim()));
+    ///     Ok(())
+    /// });
+    /// cfg.merge_content("name = player\n@unity_guid 6f1c2a").unwrap();
+    /// assert_eq!(seen.lock().unwrap()[0], "2: @unity_guid 6f1c2a");
+    /// ```
+    pub fn set_unknown_directive_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&mut AAML, &str, &str, &ExecutionContext) -> Result<(), AamlError> + Send + Sync + 'static,
+    {
+        self.unknown_directive = Some(Arc::new(handler));
+    }
+
     /// Registers a named type definition for use in schema field validation.
     pub fn register_type<T: Type + 'static>(&mut self, name: String, type_def: T) {
         self.types.insert(name, Box::new(type_def));
@@ -122,30 +866,99 @@ impl AAML {
         self.types.remove(name);
     }
 
+    /// Registers a domain check for `field` of the schema `schema`.
+    ///
+    /// The validator runs after the value has passed type validation, wherever
+    /// the field is checked: assignments governed by the schema, inline
+    /// objects of the schema's type, and [`apply_schema`](Self::apply_schema).
+    /// An `Err` message becomes the `details` of an
+    /// [`AamlError::SchemaValidationError`]. Several validators on one field
+    /// run in registration order.
+    ///
+    /// The schema does not need to exist yet; values already assigned are not
+    /// re-checked, so register validators before merging content.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new();
+    /// cfg.register_field_validator("Server", "host", |host| {
+    ///     if host.ends_with(".internal") { Ok(()) } else { Err(format!("'{host}' is not internal")) }
+    /// });
+    /// cfg.merge_content("@schema Server { host: string }").unwrap();
+    /// assert!(cfg.merge_content("host = db.internal").is_ok());
+    /// assert!(cfg.merge_content("host = example.com").is_err());
+    /// ```
+    pub fn register_field_validator<F>(&mut self, schema: &str, field: &str, validator: F)
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.field_validators
+            .entry(schema.to_string())
+            .or_default()
+            .entry(field.to_string())
+            .or_default()
+            .push(Box::new(validator));
+    }
+
+    /// Follows `@type` alias references starting at `name` and returns the
+    /// name of the first type that is not an alias (a concrete type, a schema,
+    /// or an unregistered name). Stops early if a cycle is encountered.
+    pub(crate) fn resolve_alias<'a>(&'a self, name: &'a str) -> &'a str {
+        let mut current = name;
+        let mut steps = 0;
+        while let Some(next) = self.types.get(current).and_then(|t| t.alias_target()) {
+            steps += 1;
+            if steps > self.types.len() {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
     /// Validates `value` against a type registered under `type_name`.
     pub fn check_type(&self, type_name: &str, value: &str) -> Result<(), AamlError> {
-        self.types
+        let type_def = self
+            .types
             .get(type_name)
-            .ok_or_else(|| AamlError::NotFound(type_name.to_string()))?
-            .validate(value)
+            .ok_or_else(|| AamlError::NotFound(type_name.to_string()))?;
+        if type_def.alias_target().is_some() {
+            return self.validate_value(type_name, value);
+        }
+        type_def.validate(value)
     }
 
-    /// Validates `value` against the type registered as `type_name`, also
-    /// resolving built-in primitive types and module paths.
-    pub fn validate_value(&self, type_name: &str, value: &str) -> Result<(), AamlError> {
-        let make_err = |e: AamlError| AamlError::InvalidType {
-            type_name: type_name.to_string(),
-            details: e.to_string(),
-        };
-
-        if let Some(type_def) = self.types.get(type_name) {
-            return type_def.validate(value).map_err(make_err);
+    /// Returns the physical dimension of the type `type_name` (a registered
+    /// type, an alias of one, or a built-in path such as `physics::newton`).
+    ///
+    /// `None` for types that do not measure a physical quantity and for names
+    /// that cannot be resolved.
+    pub fn type_dimension(&self, type_name: &str) -> Option<Dimension> {
+        let type_name = self.resolve_alias(type_name);
+        match self.types.get(type_name) {
+            Some(type_def) => type_def.dimension(),
+            None => resolve_builtin(type_name).ok()?.dimension(),
         }
+    }
 
-        crate::types::resolve_builtin(type_name)
-            .map_err(|_| AamlError::NotFound(type_name.to_string()))?
-            .validate(value)
-            .map_err(make_err)
+    /// Validates `value` against the type registered as `type_name`, also
+    /// resolving built-in primitive types, module paths, alias chains,
+    /// `list<T>` and schemas (which expect an inline object).
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if the type cannot be resolved, otherwise
+    /// [`AamlError::InvalidType`] describing why the value was rejected.
+    pub fn validate_value(&self, type_name: &str, value: &str) -> Result<(), AamlError> {
+        self.check_value(type_name, value).map_err(|e| match e {
+            AamlError::NotFound(_) => AamlError::NotFound(type_name.to_string()),
+            other => AamlError::InvalidType {
+                type_name: type_name.to_string(),
+                details: other.to_string(),
+                location: None,
+            },
+        })
     }
 
     // ── Parsing ──────────────────────────────────────────────────────────────
@@ -153,46 +966,101 @@ impl AAML {
     /// Parses AAML content from a string, merging it into this instance.
     ///
     /// Multi-line directives (e.g. a `@schema` body spread across several lines)
-    /// are accumulated until the opening `{` is matched by a closing `}`.
+    /// are accumulated until the opening `{` is matched by a closing `}`, and
+    /// an assignment whose `[…]` or `{…}` value spans several lines until the
+    /// literal is closed; its entries may be written one per line without
+    /// commas. A line ending in whitespace and `\` continues on the next
+    /// line, so `tags = [a, b, \` followed by `c, d]` assigns `[a, b, c, d]`.
+    /// `${name}` references in values are resolved once every line is read,
+    /// then registered schema migrations run.
     pub fn merge_content(&mut self, content: &str) -> Result<(), AamlError> {
+        self.merge_content_with(content, |_, e| Err(e))?;
+        self.resolve_references(|_, e| Err(e))?;
+        self.run_migrations()
+    }
+
+    /// Like [`merge_content`](Self::merge_content), but hands each failing
+    /// line (with its 1-based number) to `on_error`. Parsing continues with
+    /// the next line when `on_error` returns `Ok`. `${name}` references are
+    /// left for [`resolve_references`](Self::resolve_references).
+    pub(crate) fn merge_content_with(
+        &mut self,
+        content: &str,
+        on_error: impl FnMut(usize, AamlError) -> Result<(), AamlError>,
+    ) -> Result<(), AamlError> {
+        self.in_own_namespace(|aaml| aaml.merge_lines_with(content, on_error))
+    }
+
+    fn merge_lines_with(
+        &mut self,
+        content: &str,
+        mut on_error: impl FnMut(usize, AamlError) -> Result<(), AamlError>,
+    ) -> Result<(), AamlError> {
         self.map.reserve(content.len() / 40);
         let mut pending: Option<(String, usize)> = None;
+        let mut conditions = conditional::Conditions::default();
+        let mut comments = parsing::SlashComments::default();
 
         for (i, line) in content.lines().enumerate() {
             let line_num = i + 1;
-            if let Some(result) = self.accumulate_or_process(line, line_num, &mut pending)? {
-                self.process_line(&result.0, result.1)?;
+            match self.accumulate_or_process(line, line_num, &mut pending, &mut conditions, &mut comments) {
+                Ok(Some((text, start))) => {
+                    if let Err(e) = self.process_line(&text, start) {
+                        on_error(start, e)?;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => on_error(line_num, e)?,
             }
         }
 
-        if let Some((buf, start)) = pending {
-            self.process_line(&buf, start)?;
+        if let Some((buf, start)) = pending
+            && let Err(e) = self.process_pending(&buf, start)
+        {
+            on_error(start, e)?;
+        }
+        if let Some((line, e)) = comments.unclosed() {
+            on_error(line, e)?;
+        }
+        if let Some((line, e)) = conditions.unclosed() {
+            on_error(line, e)?;
         }
         Ok(())
     }
 
     /// Handles one source line: either appends it to a pending multi-line block
-    /// or processes it immediately. Returns `Some((text, line_num))` when a
-    /// complete directive has been accumulated and is ready to process.
+    /// or continued line, or processes it immediately. Returns
+    /// `Some((text, line_num))` when a complete statement has been
+    /// accumulated and is ready to process.
+    /// Conditional directives update `conditions`, and lines in an inactive
+    /// branch are skipped. With slash comments on, `comments` strips them
+    /// first and tracks a `/*` left open across lines.
     fn accumulate_or_process(
         &mut self,
         line: &str,
         line_num: usize,
         pending: &mut Option<(String, usize)>,
+        conditions: &mut conditional::Conditions,
+        comments: &mut parsing::SlashComments,
     ) -> Result<Option<(String, usize)>, AamlError> {
-        if let Some((buf, start)) = pending {
-            buf.push(' ');
-            buf.push_str(parsing::strip_comment(line).trim());
-            if parsing::block_is_complete(buf) {
-                let complete = buf.clone();
-                let start_line = *start;
-                *pending = None;
-                return Ok(Some((complete, start_line)));
-            }
+        let line = if self.options.slash_comments {
+            comments.strip(line, line_num)
+        } else {
+            Cow::Borrowed(line)
+        };
+        let line = line.as_ref();
+        if pending.is_none() && self.conditional_line(line, line_num, conditions)? {
             return Ok(None);
         }
+        if let Some((buf, _)) = pending {
+            return Ok(if parsing::append_line(buf, line) { pending.take() } else { None });
+        }
 
         let stripped = parsing::strip_comment(line).trim();
+        if let Some(head) = parsing::strip_continuation(stripped) {
+            *pending = Some((head.to_string(), line_num));
+            return Ok(None);
+        }
         if parsing::needs_accumulation(stripped) {
             *pending = Some((stripped.to_string(), line_num));
             return Ok(None);
@@ -202,23 +1070,231 @@ impl AAML {
         Ok(None)
     }
 
+    /// Processes the statement still accumulating when the content ends. An
+    /// assignment whose `[…]` or `{…}` literal was never closed is an error.
+    fn process_pending(&mut self, buf: &str, start: usize) -> Result<(), AamlError> {
+        if let Some(open) = parsing::unclosed_literal(buf) {
+            return Err(AamlError::ParseError {
+                line: start,
+                content: buf.to_string(),
+                details: format!("Unclosed '{open}' opened on line {start}"),
+            });
+        }
+        self.process_line(buf, start)
+    }
+
+    /// Parses AAML content line by line from `reader`, merging it into this instance.
+    ///
+    /// Behaves exactly like [`merge_content`](Self::merge_content) but never holds
+    /// more than the current line (or pending multi-line block) in memory.
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if reading fails or the input is not valid
+    /// UTF-8, plus any parse error.
+    pub fn merge_reader<R: std::io::BufRead>(&mut self, reader: R) -> Result<(), AamlError> {
+        self.in_own_namespace(|aaml| aaml.merge_lines_from(reader))?;
+        self.resolve_references(|_, e| Err(e))?;
+        self.run_migrations()
+    }
+
+    fn merge_lines_from<R: std::io::BufRead>(&mut self, mut reader: R) -> Result<(), AamlError> {
+        let mut pending: Option<(String, usize)> = None;
+        let mut conditions = conditional::Conditions::default();
+        let mut comments = parsing::SlashComments::default();
+        let mut line = String::new();
+        let mut line_num = 0;
+
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            line_num += 1;
+            let text = line.trim_end_matches(['\n', '\r']);
+            if let Some(result) = self.accumulate_or_process(text, line_num, &mut pending, &mut conditions, &mut comments)? {
+                self.process_line(&result.0, result.1)?;
+            }
+        }
+
+        if let Some((buf, start)) = pending {
+            self.process_pending(&buf, start)?;
+        }
+        if let Some((_, e)) = comments.unclosed() {
+            return Err(e);
+        }
+        match conditions.unclosed() {
+            Some((_, e)) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs `merge` with no namespace open, restoring the enclosing one
+    /// afterwards, so a `@namespace` never outlives the content it appears in.
+    fn in_own_namespace<T>(&mut self, merge: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = self.namespace.take();
+        let result = merge(self);
+        self.namespace = outer;
+        result
+    }
+
     /// Reads a file from disk and merges its content into this instance.
+    ///
+    /// Relative `@import` and `@derive` paths in the file are resolved against
+    /// the file's own directory.
     pub fn merge_file<P: AsRef<Path>>(&mut self, file_path: P) -> Result<(), AamlError> {
-        let content = fs::read_to_string(file_path)?;
-        self.merge_content(&content)
+        let path = file_path.as_ref();
+        let content = fs::read_to_string(path)?;
+        self.in_file_dir(path, |aaml| aaml.merge_content(&content))
     }
 
     /// Parses an AAML string and returns a new [`AAML`] instance.
+    ///
+    /// Keys marked with `@require` and `@rule` invariants are checked once the
+    /// whole document has been read.
     pub fn parse(content: &str) -> Result<Self, AamlError> {
         let mut aaml = AAML::new();
         aaml.merge_content(content)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Parses AAML from `reader` line by line, like [`parse`](Self::parse)
+    /// but without reading the whole input into memory first. Relative
+    /// `@import` paths resolve against the working directory, as for `parse`.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let input = std::io::Cursor::new("host = localhost\nport = 8080\n");
+    /// let cfg = AAML::parse_reader(input).unwrap();
+    /// assert_eq!(cfg.find_obj("port").unwrap().as_str(), "8080");
+    /// ```
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if reading fails or the input is not valid
+    /// UTF-8, plus everything [`parse`](Self::parse) reports.
+    pub fn parse_reader<R: std::io::BufRead>(reader: R) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new();
+        aaml.merge_reader(reader)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Parses `content` into a span-preserving syntax tree without running
+    /// it; see [`ast::parse_to_ast`].
+    ///
+    /// # Errors
+    /// [`AamlError::ParseError`] for a malformed statement or unclosed block.
+    pub fn parse_to_ast(content: &str) -> Result<ast::Document, AamlError> {
+        ast::parse_to_ast(content)
+    }
+
+    /// Like [`parse`](Self::parse), with `context` available to `@if`
+    /// conditions (see [`with_context`](Self::with_context)).
+    pub fn parse_with_context(
+        content: &str,
+        context: HashMap<String, String>,
+    ) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new().with_context(context);
+        aaml.merge_content(content)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
         Ok(aaml)
     }
 
     /// Loads an AAML file from disk and returns a new [`AAML`] instance.
+    ///
+    /// Relative `@import` and `@derive` paths in the file are resolved against
+    /// the file's own directory, as with [`merge_file`](Self::merge_file).
     pub fn load<P: AsRef<Path>>(file_path: P) -> Result<Self, AamlError> {
-        let content = fs::read_to_string(file_path)?;
-        Self::parse(&content)
+        let mut aaml = AAML::new();
+        aaml.merge_file(file_path)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Like [`load`](Self::load), applying only the `@profile` blocks for
+    /// `profile` (see [`with_profile`](Self::with_profile)).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let cfg = AAML::load_with_profile("config/app.aam", "prod").unwrap();
+    /// ```
+    pub fn load_with_profile<P: AsRef<Path>>(file_path: P, profile: &str) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new().with_profile(profile);
+        aaml.merge_file(file_path)?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
+    }
+
+    /// Creates an instance from defaults baked into the binary, typically via
+    /// `include_str!`.
+    ///
+    /// The defaults are parsed immediately, so a broken embedded file fails on
+    /// the first call rather than after a user file has been layered on top.
+    /// Anything merged afterwards (e.g. with [`merge_file`](Self::merge_file) or
+    /// [`merge_file_if_exists`](Self::merge_file_if_exists)) **overrides** the
+    /// defaults, and schemas declared in the defaults validate those later
+    /// assignments as they are parsed. Keys marked with `@require` and `@rule`
+    /// invariants are not checked yet, since a later layer may supply or change
+    /// their values; call [`validate_required_keys`](Self::validate_required_keys)
+    /// and [`validate_rules`](Self::validate_rules) once every layer is merged,
+    /// or use [`load_with_defaults`](Self::load_with_defaults).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::with_embedded_defaults("host = localhost\nport = 8080").unwrap();
+    /// cfg.merge_file_if_exists("user.aam").unwrap();
+    /// ```
+    pub fn with_embedded_defaults(defaults: &str) -> Result<Self, AamlError> {
+        let mut aaml = AAML::new();
+        aaml.merge_content(defaults)?;
+        Ok(aaml)
+    }
+
+    /// Merges the file at `file_path` if it exists, returning whether it was found.
+    ///
+    /// A missing file is not an error; any other I/O or parse error is.
+    pub fn merge_file_if_exists<P: AsRef<Path>>(&mut self, file_path: P) -> Result<bool, AamlError> {
+        let path = file_path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(content) => self.in_file_dir(path, |aaml| aaml.merge_content(&content)).map(|_| true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Loads embedded `defaults`, layers an optional user file on top, then
+    /// checks that every required schema field has a value.
+    ///
+    /// Precedence is *user file over defaults*. Validation happens in three steps:
+    /// 1. The defaults are parsed (and their schemas registered) first.
+    /// 2. Each user assignment is type-checked against those schemas while parsing.
+    /// 3. Schema completeness, `@require` keys and `@rule` invariants are verified once, after both
+    ///    sources are merged, so a required field may come from either one.
+    ///
+    /// Pass `None` as `user_path` to use the defaults alone.
+    pub fn load_with_defaults<P: AsRef<Path>>(
+        defaults: &str,
+        user_path: Option<P>,
+    ) -> Result<Self, AamlError> {
+        let mut aaml = Self::with_embedded_defaults(defaults)?;
+        if let Some(path) = user_path {
+            aaml.merge_file_if_exists(path)?;
+        }
+        aaml.validate_schemas_completeness()?;
+        aaml.validate_required_keys()?;
+        aaml.validate_rules()?;
+        Ok(aaml)
     }
 
     /// Strips surrounding `"…"` or `'…'` quotes. Returns the trimmed string unchanged
@@ -231,9 +1307,26 @@ impl AAML {
 
     fn register_default_commands(&mut self) {
         self.register_command(commands::import::ImportCommand);
+        self.register_command(commands::import::ImportOptionalCommand);
         self.register_command(commands::typecm::TypeCommand);
         self.register_command(commands::schema::SchemaCommand);
         self.register_command(commands::derive::DeriveCommand);
+        self.register_command(commands::conform::ConformCommand);
+        self.register_command(commands::bind::BindCommand);
+        self.register_command(commands::require::RequireCommand);
+        self.register_command(commands::rule::RuleCommand);
+        self.register_command(commands::assert::AssertOsCommand);
+        self.register_command(commands::assert::AssertMinVersionCommand);
+        self.register_command(commands::mixin::MixinCommand);
+        self.register_command(commands::env::EnvCommand);
+        self.register_command(commands::define::DefineCommand);
+        self.register_command(commands::namespace::NamespaceCommand);
+        self.register_command(commands::namespace::EndNamespaceCommand);
+        self.register_command(commands::overrides::OverrideCommand);
+        self.register_command(commands::sealed::SealedCommand);
+        self.register_command(commands::deprecated::DeprecatedCommand);
+        self.register_command(commands::alias::AliasCommand);
+        self.register_command(commands::profile::ProfileCommand);
     }
 
     fn process_line(&mut self, raw_line: &str, line_num: usize) -> Result<(), AamlError> {
@@ -241,18 +1334,98 @@ impl AAML {
         if line.is_empty() {
             return Ok(());
         }
+        let indent = raw_line.len() - raw_line.trim_start().len();
+        let column = raw_line[..indent].chars().count() + 1;
         if let Some(rest) = line.strip_prefix('@') {
-            return self.process_directive(rest, line_num);
+            return self.process_directive(rest, line_num, column);
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            return self.open_section(name.trim(), line, line_num);
         }
+        if let Some((key, block)) = parsing::block_head(line) {
+            let object = parsing::block_to_object(block).map_err(|details| AamlError::ParseError {
+                line: line_num,
+                content: line.to_string(),
+                details,
+            })?;
+            return self
+                .process_assignment(&format!("{key} = {object}"), line_num)
+                .map_err(|e| self.locate(e, line_num, column));
+        }
+        let value_column = parsing::separator_pos(line, self.options.colon_assignments).map_or(column, |pos| {
+            let value = &line[pos + 1..];
+            column + line[..=pos].chars().count() + value.len() - value.trim_start().len()
+        });
         self.process_assignment(line, line_num)
+            .map_err(|e| self.locate(e, line_num, value_column))
+    }
+
+    /// Handles a `[section]` header, which opens a namespace exactly like
+    /// `@namespace section`.
+    fn open_section(&mut self, name: &str, line: &str, line_num: usize) -> Result<(), AamlError> {
+        if !commands::namespace::is_key_path(name) {
+            return Err(AamlError::ParseError {
+                line: line_num,
+                content: line.to_string(),
+                details: format!("Invalid section '{name}': expected a name such as 'database' or 'net.proxy'"),
+            });
+        }
+        self.set_namespace(Some(name.to_string()));
+        Ok(())
     }
 
     fn process_assignment(&mut self, line: &str, line_num: usize) -> Result<(), AamlError> {
-        match parsing::parse_assignment(line) {
+        let scope = self.key_scope();
+        let qualified = parsing::split_assignment(line, self.options.colon_assignments).map(|(key, value)| (parsing::qualify(scope.as_deref(), key), value));
+        let qualified = qualified.map(|(key, value)| match self.canonical_key(&key) {
+            std::borrow::Cow::Borrowed(canonical) if canonical == key.as_ref() => (key, value),
+            canonical => (std::borrow::Cow::Owned(canonical.into_owned()), value),
+        });
+        match qualified {
+            // `key =` with nothing after it — not the same as `key = ""` or `key = null`.
+            Ok((key, "")) if line.ends_with('=') && self.schema_field_type(&key).is_some() => {
+                Err(AamlError::ParseError {
+                    line: line_num,
+                    content: line.to_string(),
+                    details: format!(
+                        "Missing value for schema field '{key}': write \"\" for an empty string, or null if the field is nullable"
+                    ),
+                })
+            }
             Ok((key, value)) => {
-                self.validate_against_schemas(key, value)?;
-                self.map.insert(Box::from(key), Box::from(value));
-                Ok(())
+                let error = |details| AamlError::ParseError {
+                    line: line_num,
+                    content: line.to_string(),
+                    details,
+                };
+                if !self.check_duplicate(&key, line_num).map_err(error)? {
+                    return Ok(());
+                }
+                self.check_override(&key, self.inherited.contains(key.as_ref()))
+                    .map_err(error)?;
+                self.check_deprecated(&key, line_num).map_err(error)?;
+                let raw_value = line[line.find('=').unwrap_or(0) + 1..].trim();
+                let quoted = raw_value.starts_with(['"', '\'']);
+                // `"…"` values resolve escape sequences; `'…'` values are raw.
+                let value = if raw_value.len() >= 2 && raw_value.starts_with('"') && raw_value.ends_with('"') {
+                    parsing::unescape(value).map_err(error)?
+                } else {
+                    std::borrow::Cow::Borrowed(value)
+                };
+                let (value, used) = if quoted || self.constants.is_empty() {
+                    (value, Vec::new())
+                } else {
+                    parsing::substitute_constants(&value, &self.constants)
+                };
+                let value = value.as_ref();
+                if !parsing::references(value).map_err(error)?.is_empty() {
+                    self.defer_references(&key, value, line_num, scope.as_deref());
+                    return Ok(());
+                }
+                // No references, but `$${` escapes still need unescaping.
+                let value = parsing::expand_with(value, |_, _| Ok(String::new())).map_err(error)?;
+                self.insert_validated(&key, &value)
+                    .map_err(|e| parsing::blame_constants(e, &used, &self.constants))
             }
             Err(details) => Err(AamlError::ParseError {
                 line: line_num,
@@ -262,7 +1435,7 @@ impl AAML {
         }
     }
 
-    fn process_directive(&mut self, content: &str, line_num: usize) -> Result<(), AamlError> {
+    fn process_directive(&mut self, content: &str, line_num: usize, column: usize) -> Result<(), AamlError> {
         let mut parts = content.splitn(2, char::is_whitespace);
         let command_name = parts.next().unwrap_or("").trim();
         let args = parts.next().unwrap_or("");
@@ -275,15 +1448,27 @@ impl AAML {
             });
         }
 
-        let command = self.commands.get(command_name).cloned();
-        match command {
-            Some(cmd) => cmd.execute(self, args),
-            None => Err(AamlError::ParseError {
+        let ctx = ExecutionContext::new(self.location(line_num, column));
+        let result = match (self.commands.get(command_name).cloned(), self.unknown_directive.clone()) {
+            (Some(cmd), _) => cmd.execute(self, args, &ctx),
+            (None, Some(handler)) => handler(self, command_name, args, &ctx),
+            (None, None) => {
+                return Err(AamlError::ParseError {
+                    line: line_num,
+                    content: content.to_string(),
+                    details: format!("Unknown directive: @{}", command_name),
+                });
+            }
+        };
+        result.map_err(|e| match e {
+            // Attach the directive's line to parse errors that lack one.
+            AamlError::ParseError { line: 0, content, details } => AamlError::ParseError {
                 line: line_num,
-                content: content.to_string(),
-                details: format!("Unknown directive: @{}", command_name),
-            }),
-        }
+                content,
+                details,
+            },
+            other => self.locate(other, line_num, column),
+        })
     }
 }
 
@@ -306,6 +1491,35 @@ impl AddAssign for AAML {
     }
 }
 
+/// Resolves a directive path against `dir`, the directory of the file it
+/// appears in.
+pub(crate) fn resolve_in(dir: Option<&Path>, path: &str) -> PathBuf {
+    if loader::scheme_of(path).is_some() {
+        return PathBuf::from(path);
+    } else if let Some(dir) = dir.and_then(Path::to_str).filter(|d| loader::scheme_of(d).is_some()) {
+        return match dir.ends_with("://") {
+            true => PathBuf::from(format!("{dir}{path}")),
+            false => PathBuf::from(format!("{dir}/{path}")),
+        };
+    }
+    match dir {
+        Some(dir) if Path::new(path).is_relative() => dir.join(path),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Returns the directory relative paths in `file` resolve against.
+pub(crate) fn dir_of(file: &Path) -> Option<PathBuf> {
+    if let Some(file) = file.to_str()
+        && let Some(scheme) = loader::scheme_of(file)
+    {
+        let rest = &file[scheme.len()..];
+        let dir = rest.rsplit_once('/').map_or("", |(dir, _)| dir);
+        return Some(PathBuf::from(format!("{scheme}{dir}")));
+    }
+    file.parent().map(Path::to_path_buf)
+}
+
 impl Default for AAML {
     fn default() -> Self {
         Self::new()
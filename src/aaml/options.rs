@@ -0,0 +1,153 @@
+//! Parsing switches for an [`AAML`] instance.
+//!
+//! Every instance keeps its switches in one [`ParseOptions`]: set them all at
+//! once with [`AAML::with_parse_options`], or one at a time with builders
+//! such as [`AAML::with_coercion`] and [`AAML::with_colon_assignments`],
+//! which update the same value. [`AAML::parse_options`] returns it.
+
+use super::AAML;
+use super::limits::ImportLimits;
+
+/// Behavioural switches for parsing, gathered in one place. Build one with
+/// the `with_*` methods or a struct literal over [`ParseOptions::default`],
+/// then pass it to [`AAML::parse_with_options`],
+/// [`AAML::load_with_options`] or [`AAML::with_parse_options`].
+///
+/// # Example
+/// ```
+/// use aam_rs::aaml::AAML;
+/// use aam_rs::aaml::{DuplicateKeys, ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .with_slash_comments(true)
+///     .with_duplicate_keys(DuplicateKeys::Error)
+///     .with_case_insensitive_keys(true);
+/// let (cfg, _) = AAML::parse_with_options("Host = a // primary", &options).unwrap();
+/// assert_eq!(cfg.find_obj("host").unwrap().as_str(), "a");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Keep parsing after an assigned value fails validation and report every
+    /// failing field, instead of returning the first error. Failing
+    /// assignments are skipped; other errors, such as malformed lines or
+    /// failing directives, still stop parsing — use
+    /// [`AAML::merge_content_report`] to collect those too.
+    pub collect_errors: bool,
+    /// Also accept `// line` and `/* block */` comments alongside `#`; see
+    /// [`AAML::with_slash_comments`].
+    pub slash_comments: bool,
+    /// Accept `key: value` assignments alongside `key = value`; see
+    /// [`AAML::with_colon_assignments`].
+    pub colon_assignments: bool,
+    /// What happens when a key is assigned more than once; see
+    /// [`AAML::with_duplicate_keys`].
+    pub duplicate_keys: DuplicateKeys,
+    /// Treat keys that differ only in case as the same key; see
+    /// [`AAML::with_case_insensitive_keys`].
+    pub case_insensitive_keys: bool,
+    /// Require `@override` on keys that shadow inherited ones; see
+    /// [`AAML::with_strict_overrides`].
+    pub strict_overrides: bool,
+    /// Make assigning a `@deprecated` key an error; see
+    /// [`AAML::with_strict_deprecations`].
+    pub strict_deprecations: bool,
+    /// Store schema-typed values in canonical form; see
+    /// [`AAML::with_coercion`].
+    pub coercion: bool,
+    /// Limits on imported files, [`ImportLimits::default`] unless set; see
+    /// [`AAML::with_import_limits`].
+    pub import_limits: ImportLimits,
+}
+
+impl ParseOptions {
+    /// Options with every switch off and the default import limits, same as
+    /// [`ParseOptions::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`collect_errors`](Self::collect_errors).
+    pub fn with_collect_errors(mut self, enabled: bool) -> Self {
+        self.collect_errors = enabled;
+        self
+    }
+
+    /// Sets [`slash_comments`](Self::slash_comments).
+    pub fn with_slash_comments(mut self, enabled: bool) -> Self {
+        self.slash_comments = enabled;
+        self
+    }
+
+    /// Sets [`colon_assignments`](Self::colon_assignments).
+    pub fn with_colon_assignments(mut self, enabled: bool) -> Self {
+        self.colon_assignments = enabled;
+        self
+    }
+
+    /// Sets [`duplicate_keys`](Self::duplicate_keys).
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Sets [`case_insensitive_keys`](Self::case_insensitive_keys).
+    pub fn with_case_insensitive_keys(mut self, enabled: bool) -> Self {
+        self.case_insensitive_keys = enabled;
+        self
+    }
+
+    /// Sets [`strict_overrides`](Self::strict_overrides).
+    pub fn with_strict_overrides(mut self, enabled: bool) -> Self {
+        self.strict_overrides = enabled;
+        self
+    }
+
+    /// Sets [`strict_deprecations`](Self::strict_deprecations).
+    pub fn with_strict_deprecations(mut self, enabled: bool) -> Self {
+        self.strict_deprecations = enabled;
+        self
+    }
+
+    /// Sets [`coercion`](Self::coercion).
+    pub fn with_coercion(mut self, enabled: bool) -> Self {
+        self.coercion = enabled;
+        self
+    }
+
+    /// Sets [`import_limits`](Self::import_limits).
+    pub fn with_import_limits(mut self, limits: ImportLimits) -> Self {
+        self.import_limits = limits;
+        self
+    }
+}
+
+/// Policy for a key assigned more than once, set with
+/// [`ParseOptions::duplicate_keys`] or [`AAML::with_duplicate_keys`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// The later assignment replaces the earlier one.
+    #[default]
+    LastWins,
+    /// The later assignment is ignored.
+    FirstWins,
+    /// The later assignment is a parse error.
+    Error,
+    /// The later assignment wins and is recorded in [`AAML::warnings`].
+    Warn,
+}
+
+impl AAML {
+    /// Replaces the parsing switches, import limits included, with `options`.
+    /// `collect_errors` only matters to
+    /// [`parse_with_options`](Self::parse_with_options) and
+    /// [`load_with_options`](Self::load_with_options).
+    pub fn with_parse_options(mut self, options: &ParseOptions) -> Self {
+        self.options = *options;
+        self
+    }
+
+    /// Returns the parsing switches of this instance.
+    pub fn parse_options(&self) -> &ParseOptions {
+        &self.options
+    }
+}
@@ -1,15 +1,28 @@
-//! Parsing helpers: comment stripping, assignment parsing, multi-line block accumulation.
+//! Parsing helpers: comment stripping, assignment parsing, `${name}` expansion,
+//! multi-line block accumulation, and a public token-level lexer
+//! ([`tokenize`]) for syntax highlighters and other tools.
+
+use crate::commands::namespace::is_key_path;
+use crate::error::AamlError;
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Strips an inline `#` comment from a raw source line, respecting quoted strings.
 ///
 /// A `#` is a comment start only when it is preceded by whitespace (or at line start),
-/// so unquoted color values like `tint = #ff6600` are stored correctly.
+/// so unquoted color values like `tint = #ff6600` are stored correctly. Inside
+/// `"…"` a backslash escapes the next character, so `\"` does not end the string.
 pub fn strip_comment(line: &str) -> &str {
     let mut quote_state: Option<char> = None;
+    let mut escaped = false;
     let bytes = line.as_bytes();
 
     for (idx, c) in line.char_indices() {
+        if std::mem::take(&mut escaped) {
+            continue;
+        }
         match (quote_state, c) {
+            (Some('"'), '\\') => escaped = true,
             (None, '#') => {
                 let preceded_by_space = idx == 0
                     || bytes.get(idx - 1).is_some_and(|b| b.is_ascii_whitespace());
@@ -28,30 +41,112 @@ pub fn strip_comment(line: &str) -> &str {
     line
 }
 
-/// Parses a `key = value` assignment and returns trimmed (key, value) slices.
-///
-/// The split point is the **first `=`** that appears outside of any
-/// `{ ... }` or `[ ... ]` nesting.  This allows values like
-/// `pos = { x = 1.0, y = 2.0 }` or `tags = [a, b, c]` to be parsed
-/// correctly.  Surrounding quotes are stripped from the value via
-/// [`unwrap_quotes`], but `{...}` and `[...]` literals are returned as-is.
-pub(super) fn parse_assignment(line: &str) -> Result<(&str, &str), &'static str> {
-    // Find the first '=' outside of nesting
+/// Returns the byte index of the `=` (or, with `colon`, `:`) that separates
+/// the key from the value: the first one outside `{}` / `[]` nesting.
+pub(super) fn separator_pos(line: &str, colon: bool) -> Option<usize> {
     let mut depth: i32 = 0;
-    let mut eq_pos: Option<usize> = None;
     for (i, ch) in line.char_indices() {
         match ch {
             '{' | '[' => depth += 1,
             '}' | ']' => depth -= 1,
-            '=' if depth == 0 => {
-                eq_pos = Some(i);
-                break;
-            }
+            '=' if depth == 0 => return Some(i),
+            ':' if colon && depth == 0 => return Some(i),
             _ => {}
         }
     }
+    None
+}
 
-    let pos = eq_pos.ok_or("Missing assignment operator '='")?;
+/// Blanks out `//` line comments and `/* ... */` block comments line by
+/// line, for [`AAML::with_slash_comments`](super::AAML::with_slash_comments).
+///
+/// As with `#`, a marker starts a comment only outside quotes and at the start
+/// of a line or after whitespace, so `url = http://host` is kept. A block
+/// comment may span lines; the lines it covers come out empty, so line
+/// numbers stay correct.
+#[derive(Default)]
+pub(super) struct SlashComments {
+    /// Line number and text of the line that opened an unfinished `/*`.
+    open: Option<(usize, String)>,
+}
+
+impl SlashComments {
+    /// Returns `line` with its comments removed, carrying an open block
+    /// comment over to the next call.
+    pub(super) fn strip<'a>(&mut self, line: &'a str, line_num: usize) -> Cow<'a, str> {
+        if self.open.is_none() && !line.contains("//") && !line.contains("/*") {
+            return Cow::Borrowed(line);
+        }
+        let mut out = String::with_capacity(line.len());
+        let mut quote_state: Option<char> = None;
+        let mut escaped = false;
+        let mut i = 0;
+        while let Some(c) = line[i..].chars().next() {
+            let tail = &line[i..];
+            if self.open.is_some() {
+                let Some(end) = tail.find("*/") else { break };
+                self.open = None;
+                out.push(' ');
+                i += end + 2;
+                continue;
+            }
+            let at_boundary = i == 0 || line.as_bytes()[i - 1].is_ascii_whitespace();
+            if quote_state.is_none() && at_boundary {
+                if tail.starts_with("//") {
+                    break;
+                }
+                if tail.starts_with("/*") {
+                    self.open = Some((line_num, line.trim().to_string()));
+                    i += 2;
+                    continue;
+                }
+            }
+            if !std::mem::take(&mut escaped) {
+                match (quote_state, c) {
+                    (Some('"'), '\\') => escaped = true,
+                    (None, '"' | '\'') => quote_state = Some(c),
+                    (Some(q), c) if c == q => quote_state = None,
+                    _ => {}
+                }
+            }
+            out.push(c);
+            i += c.len_utf8();
+        }
+        Cow::Owned(out)
+    }
+
+    /// Returns the line and error of a `/*` left open at the end of the content.
+    pub(super) fn unclosed(&self) -> Option<(usize, AamlError)> {
+        self.open.as_ref().map(|(line, content)| {
+            let error = AamlError::ParseError {
+                line: *line,
+                content: content.clone(),
+                details: "Unclosed block comment: expected */".into(),
+            };
+            (*line, error)
+        })
+    }
+}
+
+/// Parses a `key = value` assignment and returns trimmed (key, value) slices.
+///
+/// The split point is the **first `=`** that appears outside of any
+/// `{ ... }` or `[ ... ]` nesting.  This allows values like
+/// `pos = { x = 1.0, y = 2.0 }` or `tags = [a, b, c]` to be parsed
+/// correctly.  Surrounding quotes are stripped from the value via
+/// [`unwrap_quotes`], but `{...}` and `[...]` literals are returned as-is.
+pub(super) fn parse_assignment(line: &str) -> Result<(&str, &str), &'static str> {
+    split_assignment(line, false)
+}
+
+/// Like [`parse_assignment`], but with `colon` set a `:` also separates the
+/// key from the value (`key: value`), whichever of `=` and `:` comes first.
+pub(super) fn split_assignment(line: &str, colon: bool) -> Result<(&str, &str), &'static str> {
+    let pos = separator_pos(line, colon).ok_or(if colon {
+        "Missing assignment operator '=' or ':'"
+    } else {
+        "Missing assignment operator '='"
+    })?;
     let key = line[..pos].trim();
     let raw_val = line[pos + 1..].trim();
 
@@ -69,6 +164,61 @@ pub(super) fn parse_assignment(line: &str) -> Result<(&str, &str), &'static str>
     Ok((key, val))
 }
 
+/// Prefixes `key` with `namespace`, if any (`host` in `database` becomes
+/// `database.host`).
+pub(super) fn qualify<'a>(namespace: Option<&str>, key: &'a str) -> Cow<'a, str> {
+    match namespace {
+        Some(namespace) => Cow::Owned(format!("{namespace}.{key}")),
+        None => Cow::Borrowed(key),
+    }
+}
+
+/// Resolves the escape sequences of a `"…"` value with its quotes removed:
+/// `\n`, `\t`, `\r`, `\0`, `\"`, `\'`, `\\` and `\u{XXXX}` (1–6 hex
+/// digits). A backslash before any other character is kept as written, so
+/// paths such as `"C:\Games"` keep working. `'…'` values are raw and are not
+/// passed through here.
+///
+/// # Errors
+/// A message when a `\u{…}` escape is malformed or not a valid character.
+pub(super) fn unescape(s: &str) -> Result<Cow<'_, str>, String> {
+    if !s.contains('\\') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('0') => out.push('\0'),
+            Some(c @ ('"' | '\'' | '\\')) => out.push(c),
+            Some('u') if chars.peek() == Some(&'{') => {
+                chars.next();
+                let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let code = (1..=6)
+                    .contains(&hex.len())
+                    .then(|| u32::from_str_radix(&hex, 16).ok())
+                    .flatten()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("Invalid unicode escape '\\u{{{hex}}}'"))?;
+                out.push(code);
+            }
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    Ok(Cow::Owned(out))
+}
+
 /// Strips a matching pair of surrounding `"…"` or `'…'` quotes from `s`.
 ///
 /// Returns `s` unchanged (trimmed) if it is not quoted.
@@ -85,22 +235,361 @@ pub fn unwrap_quotes(s: &str) -> &str {
     s
 }
 
-/// Returns `true` when `text` is a directive that opens a `{` block that is
-/// not yet closed on the same line — i.e. it needs multi-line accumulation.
+/// Returns the names referenced by `${name}` / `${name:-default}` in `value`,
+/// in order. `$${` is an escaped literal `${` and references nothing.
+///
+/// # Errors
+/// A message when a reference is unterminated or names an invalid variable.
+pub(super) fn references(value: &str) -> Result<Vec<&str>, String> {
+    let mut names = Vec::new();
+    expand_with(value, |name, _| {
+        names.push(name);
+        Ok(String::new())
+    })?;
+    Ok(names)
+}
+
+/// Replaces every `${name}` / `${name:-default}` in `value` with the text
+/// returned by `resolve(name, default)`, and every `$${` with a literal `${`.
+///
+/// # Errors
+/// A message when a reference is malformed, or the error of `resolve`.
+pub(super) fn expand_with<'a>(
+    value: &'a str,
+    mut resolve: impl FnMut(&'a str, Option<&'a str>) -> Result<String, String>,
+) -> Result<Cow<'a, str>, String> {
+    if !value.contains("${") {
+        return Ok(Cow::Borrowed(value));
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        if rest[..start].ends_with('$') {
+            // `$${` — the first `$` is already written; keep the brace literal.
+            out.push('{');
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let body_len = rest[start + 2..]
+            .find('}')
+            .ok_or_else(|| format!("Unterminated '${{' in '{value}'"))?;
+        let body = &rest[start + 2..start + 2 + body_len];
+        let (name, default) = match body.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (body, None),
+        };
+        if name.is_empty()
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        {
+            return Err(format!("Invalid reference name '{name}' in '{value}'"));
+        }
+        out.push_str(&resolve(name, default)?);
+        rest = &rest[start + 2 + body_len + 1..];
+    }
+    out.push_str(rest);
+    Ok(Cow::Owned(out))
+}
+
+/// Replaces every whole word of `value` that names a `@define` constant with
+/// the constant's value, leaving quoted strings and `${...}` references
+/// untouched. Returns the new value and the names of the constants used.
+pub(super) fn substitute_constants<'a>(
+    value: &'a str,
+    constants: &HashMap<String, String>,
+) -> (Cow<'a, str>, Vec<String>) {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+    let mut out = String::with_capacity(value.len());
+    let mut used: Vec<String> = Vec::new();
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '"' || c == '\'' {
+            rest[1..].find(c).map_or(rest.len(), |end| end + 2)
+        } else if rest.starts_with("${") {
+            rest.find('}').map_or(rest.len(), |end| end + 1)
+        } else if is_word(c) {
+            let len = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+            if let Some(constant) = constants.get(&rest[..len]) {
+                if !used.iter().any(|n| *n == rest[..len]) {
+                    used.push(rest[..len].to_string());
+                }
+                out.push_str(constant);
+                rest = &rest[len..];
+                continue;
+            }
+            len
+        } else {
+            c.len_utf8()
+        };
+        out.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+    if used.is_empty() {
+        (Cow::Borrowed(value), used)
+    } else {
+        (Cow::Owned(out), used)
+    }
+}
+
+/// Adds the constants substituted into a rejected value to the error, so it
+/// names what the author wrote rather than only the expanded text.
+pub(super) fn blame_constants(
+    error: AamlError,
+    used: &[String],
+    constants: &HashMap<String, String>,
+) -> AamlError {
+    if used.is_empty() {
+        return error;
+    }
+    let names: Vec<String> = used.iter().map(|n| format!("{n} = {}", constants[n])).collect();
+    let note = format!(" (after substituting {})", names.join(", "));
+    match error {
+        AamlError::SchemaValidationError { schema, field, type_name, details, location } => {
+            AamlError::SchemaValidationError { schema, field, type_name, details: details + &note, location }
+        }
+        AamlError::InvalidType { type_name, details, location } => {
+            AamlError::InvalidType { type_name, details: details + &note, location }
+        }
+        AamlError::InvalidValue(details) => AamlError::InvalidValue(details + &note),
+        other => other,
+    }
+}
+
+/// Returns `text` without its line-continuation marker when it ends with a
+/// `\` preceded by whitespace (`tags = [a, b, \`), else `None`. The
+/// whitespace keeps values such as `dir = C:\Games\` from continuing.
+pub(super) fn strip_continuation(text: &str) -> Option<&str> {
+    let rest = text.strip_suffix('\\')?;
+    (rest.is_empty() || rest.ends_with(char::is_whitespace)).then(|| rest.trim_end())
+}
+
+/// Returns `true` when `text` needs multi-line accumulation: a directive that
+/// opens a `{` block not yet closed on the same line, or an assignment whose
+/// value opens a `[` or `{` literal not yet closed (`allowed_ips = [`, or
+/// `allowed_ips: [` in `key: value` mode).
 pub(super) fn needs_accumulation(text: &str) -> bool {
-    if !text.starts_with('@') {
-        return false;
+    if text.starts_with('@') {
+        return nesting_balance(text, false) > 0;
+    }
+    let opens_literal = split_assignment(text, true)
+        .is_ok_and(|(_, value)| value.starts_with(['[', '{']));
+    (opens_literal || block_head(text).is_some()) && nesting_balance(text, true) > 0
+}
+
+/// Splits a nested block `server { host = localhost  port = 8080 }` into the
+/// key and the braced body. Returns `None` unless the text before the first
+/// `{` is a bare key path.
+pub(super) fn block_head(text: &str) -> Option<(&str, &str)> {
+    let open = text.find('{')?;
+    let key = text[..open].trim();
+    is_key_path(key).then(|| (key, &text[open..]))
+}
+
+/// Converts the body of a nested block into an inline object literal
+/// `{ key = val, ... }`. Fields are `key = value` (or `key: value`) or nested
+/// `key { ... }` blocks, separated by commas or just whitespace; an unquoted
+/// value runs until a comma or the next `name =` / `name {`.
+pub(super) fn block_to_object(block: &str) -> Result<String, String> {
+    let inner = block
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| format!("Block must be wrapped in '{{}}', got: '{}'", block.trim()))?;
+    let skip = |s: &str| s.trim_start_matches(|c: char| c.is_whitespace() || c == ',').to_string();
+
+    let mut fields = Vec::new();
+    let mut rest = skip(inner);
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | ':' | '{'))
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        if !is_key_path(key) {
+            return Err(format!("Expected a field name in block, found '{rest}'"));
+        }
+        let after = rest[key_end..].trim_start();
+        let (value, tail) = if after.starts_with('{') {
+            let end = closing_brace(after).ok_or_else(|| format!("Unclosed block '{key}'"))?;
+            (block_to_object(&after[..end])?, &after[end..])
+        } else if let Some(value) = after.strip_prefix(['=', ':']) {
+            let value = value.trim_start();
+            let end = block_value_end(value);
+            if value[..end].trim().is_empty() {
+                return Err(format!("Missing value for field '{key}' in block"));
+            }
+            (value[..end].trim().to_string(), &value[end..])
+        } else {
+            return Err(format!("Expected '=' or '{{' after field '{key}' in block"));
+        };
+        fields.push(format!("{key} = {value}"));
+        rest = skip(tail);
+    }
+    Ok(if fields.is_empty() { "{}".to_string() } else { format!("{{ {} }}", fields.join(", ")) })
+}
+
+/// Returns the index just past the `}` that closes the `{` starting `text`.
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        if std::mem::take(&mut escaped) {
+            continue;
+        }
+        match (quote, c) {
+            (Some('"'), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns where a block field value starting `text` ends: at a top-level
+/// comma, or at whitespace followed by the next `name =` or `name {`.
+fn block_value_end(text: &str) -> usize {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        if std::mem::take(&mut escaped) {
+            continue;
+        }
+        match (quote, c) {
+            (Some('"'), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')') => depth -= 1,
+            (None, ',') if depth == 0 => return i,
+            (None, c) if c.is_whitespace() && depth == 0 && starts_field(text[i..].trim_start()) => {
+                return i;
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Returns `true` when `text` begins with `name =` or `name {`.
+fn starts_field(text: &str) -> bool {
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '=' | '{'))
+        .unwrap_or(text.len());
+    is_key_path(&text[..end]) && text[end..].trim_start().starts_with(['=', '{'])
+}
+
+/// Appends source line `line` to the pending multi-line statement `buf`,
+/// returning `true` once the statement is complete.
+pub(super) fn append_line(buf: &mut String, line: &str) -> bool {
+    let text = strip_comment(line).trim();
+    let continued = strip_continuation(text);
+    match doc_comment(line) {
+        Some(doc) if takes_doc_comments(buf) => {
+            buf.push(' ');
+            buf.push_str(&doc_token(doc));
+        }
+        _ => {
+            let text = continued.unwrap_or(text);
+            buf.push_str(entry_separator(buf, text));
+            buf.push_str(text);
+        }
     }
-    let opens = text.chars().filter(|&c| c == '{').count();
-    let closes = text.chars().filter(|&c| c == '}').count();
-    opens > closes
+    continued.is_none() && block_is_complete(buf)
 }
 
-/// Returns `true` when the accumulated buffer has at least as many `}` as `{`.
+/// Returns `true` when the accumulated buffer closes every block it opened:
+/// `{` for directives, and also `[` for assignments.
 pub(super) fn block_is_complete(buf: &str) -> bool {
-    let opens = buf.chars().filter(|&c| c == '{').count();
-    let closes = buf.chars().filter(|&c| c == '}').count();
-    closes >= opens
+    nesting_balance(buf, !buf.starts_with('@')) <= 0
+}
+
+/// Returns the `[` or `{` opening the value of an accumulated assignment that
+/// is still open, or `None` when `buf` is complete or a directive.
+pub(super) fn unclosed_literal(buf: &str) -> Option<char> {
+    if buf.starts_with('@') || block_is_complete(buf) {
+        return None;
+    }
+    buf.chars().find(|c| matches!(c, '[' | '{'))
+}
+
+/// Returns the text that joins the next line `next` onto the accumulated
+/// assignment `buf`: a comma when both sit between entries of an open `[…]`
+/// or `{…}` literal and none was written, so entries may be listed one per
+/// line; a space otherwise.
+pub(super) fn entry_separator(buf: &str, next: &str) -> &'static str {
+    let (balance, quote) = scan_nesting(buf, true);
+    let between_entries = balance > 0
+        && quote.is_none()
+        && !buf.ends_with([',', '{', '['])
+        && !next.is_empty()
+        && !next.starts_with([',', '}', ']']);
+    if between_entries && !buf.starts_with('@') { ", " } else { " " }
+}
+
+/// Counts `{` minus `}` outside quoted strings, and `[` minus `]` as well
+/// with `brackets`.
+fn nesting_balance(text: &str, brackets: bool) -> isize {
+    scan_nesting(text, brackets).0
+}
+
+/// Returns the nesting balance of `text` (see [`nesting_balance`]) and the
+/// quote still open at its end, if any.
+fn scan_nesting(text: &str, brackets: bool) -> (isize, Option<char>) {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut balance = 0;
+    for c in text.chars() {
+        if std::mem::take(&mut escaped) {
+            continue;
+        }
+        match (quote, c) {
+            (Some('"'), '\\') => escaped = true,
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '{') => balance += 1,
+            (None, '}') => balance -= 1,
+            (None, '[') if brackets => balance += 1,
+            (None, ']') if brackets => balance -= 1,
+            _ => {}
+        }
+    }
+    (balance, quote)
+}
+
+/// Prefix of the token that carries a `# comment` line of a multi-line
+/// `@schema` / `@mixin` block to the directive, as `#"text"`.
+pub(crate) const DOC_MARKER: char = '#';
+
+/// Returns the text of a whole-line `# comment`, or `None` for any other line.
+pub(super) fn doc_comment(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix('#')?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+}
+
+/// Encodes a doc comment as a single quoted body token (`#"text"`) that
+/// survives [`strip_comment`] and the schema tokenizer.
+pub(super) fn doc_token(text: &str) -> String {
+    if text.contains('"') {
+        format!("{DOC_MARKER}'{}'", text.replace('\'', "\""))
+    } else {
+        format!("{DOC_MARKER}\"{text}\"")
+    }
+}
+
+/// Returns `true` for a block that may carry field doc comments.
+pub(super) fn takes_doc_comments(buf: &str) -> bool {
+    ["@schema ", "@mixin "].iter().any(|d| buf.starts_with(d))
 }
 
 /// Returns `true` when `value` is an inline object literal `{ ... }`.
@@ -111,7 +600,7 @@ pub fn is_inline_object(value: &str) -> bool {
 
 /// Parses an inline object `{ key = val, key2 = val2, ... }` into `(key, value)` pairs.
 ///
-/// Field separators are commas respecting `{}` / `[]` nesting, so values like
+/// Field separators are commas respecting `{}` / `[]` / `()` nesting, so values like
 /// `{ base = { x = 1, y = 2 }, z = 3 }` are parsed correctly.
 // Medium Complexity 
 pub fn parse_inline_object(value: &str) -> Result<Vec<(String, String)>, String> {
@@ -141,15 +630,25 @@ pub fn parse_inline_object(value: &str) -> Result<Vec<(String, String)>, String>
     Ok(fields)
 }
 
-/// Splits `s` on commas that are not inside `{}` or `[]` nesting.
+/// Renders `(key, value)` pairs as `{ key = val, ... }`, the inverse of
+/// [`parse_inline_object`].
+pub(crate) fn format_inline_object(pairs: &[(String, String)]) -> String {
+    if pairs.is_empty() {
+        return "{}".to_string();
+    }
+    let fields: Vec<String> = pairs.iter().map(|(k, v)| format!("{k} = {v}")).collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Splits `s` on commas that are not inside `{}`, `[]` or `()` nesting.
 fn split_top_level_fields(s: &str) -> Vec<String> {
     let mut items = Vec::new();
     let mut depth: i32 = 0;
     let mut cur = String::new();
     for ch in s.chars() {
         match ch {
-            '{' | '[' => { depth += 1; cur.push(ch); }
-            '}' | ']' => { depth -= 1; cur.push(ch); }
+            '{' | '[' | '(' => { depth += 1; cur.push(ch); }
+            '}' | ']' | ')' => { depth -= 1; cur.push(ch); }
             ',' if depth == 0 => {
                 items.push(cur.clone());
                 cur.clear();
@@ -173,4 +672,228 @@ fn split_field_pair(entry: &str) -> Result<(&str, &str), String> {
         }
     }
     Err(format!("Inline object field '{entry}' has no '=' or ':' separator"))
-}
\ No newline at end of file
+}
+/// Kind of a [`Token`] produced by [`tokenize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// The key of an assignment or nested block.
+    Key,
+    /// The `=` of an assignment.
+    Assign,
+    /// An assignment value, including quotes and any `{...}` / `[...]` literal.
+    Value,
+    /// A `#` comment, from the `#` to the end of the line.
+    Comment,
+    /// A directive name including its `@`, such as `@schema`.
+    Directive,
+    /// A whitespace-separated directive argument, quoted strings kept whole.
+    Argument,
+    /// A `{` opening a directive body or nested block.
+    OpenBrace,
+    /// A `}` closing a directive body or nested block.
+    CloseBrace,
+    /// An INI-style `[section]` header.
+    Section,
+}
+
+/// A lexical token of AAML source: its kind, byte range in the source and
+/// 1-based line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// What the token is.
+    pub kind: TokenKind,
+    /// Byte range of the token in the source.
+    pub span: std::ops::Range<usize>,
+    /// 1-based line the token is on.
+    pub line: usize,
+}
+
+impl Token {
+    /// Returns the token's text within `source`, the string passed to
+    /// [`tokenize`].
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.span.clone()]
+    }
+}
+
+/// Splits AAML source into spanned [`Token`]s with the parser's quoting and
+/// comment rules, for syntax highlighters and other tools.
+///
+/// Lexing never fails: text that does not form a valid statement is still
+/// covered by tokens, so a highlighter can colour a file while it is being
+/// edited. Whitespace and line breaks are not tokens.
+///
+/// # Example
+/// ```
+/// use aam_rs::aaml::parsing::{TokenKind, tokenize};
+///
+/// let source = "@schema S { port: i32 }\nport = 8080 # http";
+/// let tokens = tokenize(source);
+/// let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind).collect();
+/// assert_eq!(kinds, [
+///     TokenKind::Directive, TokenKind::Argument, TokenKind::OpenBrace, TokenKind::Argument,
+///     TokenKind::Argument, TokenKind::CloseBrace,
+///     TokenKind::Key, TokenKind::Assign, TokenKind::Value, TokenKind::Comment,
+/// ]);
+/// assert_eq!(tokens[8].text(source), "8080");
+/// ```
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let mut lexer = Lexer::default();
+    let mut offset = 0;
+    for (idx, raw) in source.split_inclusive('\n').enumerate() {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        lexer.line(line, offset, idx + 1);
+        offset += raw.len();
+    }
+    lexer.tokens
+}
+
+/// Line-by-line state of [`tokenize`].
+#[derive(Default)]
+struct Lexer {
+    tokens: Vec<Token>,
+    /// Open `{` of a directive body spanning lines.
+    directive_depth: isize,
+    /// Open `[` / `{` of an assignment value spanning lines.
+    value_depth: isize,
+    /// Nested `key {` blocks spanning lines.
+    block_depth: usize,
+    /// Kind of the statement continued with a trailing `\`.
+    continued: Option<TokenKind>,
+}
+
+impl Lexer {
+    fn push(&mut self, kind: TokenKind, start: usize, end: usize, line: usize) {
+        if start < end {
+            self.tokens.push(Token { kind, span: start..end, line });
+        }
+    }
+
+    /// Pushes a token for `text`, a slice of the line starting at byte
+    /// `offset` of the source, with surrounding whitespace excluded.
+    fn push_trimmed(&mut self, kind: TokenKind, line_text: &str, text: &str, offset: usize, line: usize) {
+        let start = offset + (text.as_ptr() as usize - line_text.as_ptr() as usize);
+        let trimmed = text.trim_start();
+        let start = start + (text.len() - trimmed.len());
+        self.push(kind, start, start + trimmed.trim_end().len(), line);
+    }
+
+    fn line(&mut self, text: &str, offset: usize, line: usize) {
+        let code = strip_comment(text);
+        let comment = &text[code.len()..];
+        let trimmed = code.trim();
+        self.statement(text, trimmed, offset, line);
+        self.push_trimmed(TokenKind::Comment, text, comment, offset, line);
+    }
+
+    fn statement(&mut self, text: &str, code: &str, offset: usize, line: usize) {
+        if code.is_empty() {
+            return;
+        }
+        let continues = strip_continuation(code).is_some();
+        if let Some(kind) = self.continued.take() {
+            match kind {
+                TokenKind::Argument => self.arguments(text, code, offset, line),
+                _ => self.push_trimmed(kind, text, code, offset, line),
+            }
+            self.continued = continues.then_some(kind);
+            return;
+        }
+        if self.directive_depth > 0 {
+            self.arguments(text, code, offset, line);
+            self.directive_depth += nesting_balance(code, false);
+            return;
+        }
+        if self.value_depth > 0 {
+            self.push_trimmed(TokenKind::Value, text, code, offset, line);
+            self.value_depth += nesting_balance(code, true);
+            self.continued = continues.then_some(TokenKind::Value);
+            return;
+        }
+        if let Some(rest) = code.strip_prefix('@') {
+            let name_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            self.push_trimmed(TokenKind::Directive, text, &code[..name_len + 1], offset, line);
+            self.arguments(text, &rest[name_len..], offset, line);
+            self.directive_depth = nesting_balance(code, false).max(0);
+            self.continued = continues.then_some(TokenKind::Argument);
+            return;
+        }
+        if code.starts_with('[') && code.ends_with(']') {
+            self.push_trimmed(TokenKind::Section, text, code, offset, line);
+            return;
+        }
+        if code == "}" && self.block_depth > 0 {
+            self.block_depth -= 1;
+            self.push_trimmed(TokenKind::CloseBrace, text, code, offset, line);
+            return;
+        }
+        if let Some((key, body)) = block_head(code) {
+            self.push_trimmed(TokenKind::Key, text, key, offset, line);
+            self.push_trimmed(TokenKind::OpenBrace, text, &body[..1], offset, line);
+            match body.strip_suffix('}') {
+                Some(inner) if nesting_balance(body, true) <= 0 => {
+                    self.push_trimmed(TokenKind::Value, text, &inner[1..], offset, line);
+                    self.push_trimmed(TokenKind::CloseBrace, text, &body[body.len() - 1..], offset, line);
+                }
+                _ => {
+                    self.block_depth += 1;
+                    self.statement(text, body[1..].trim(), offset, line);
+                }
+            }
+            return;
+        }
+        match separator_pos(code, false) {
+            Some(eq) => {
+                let value = &code[eq + 1..];
+                self.push_trimmed(TokenKind::Key, text, &code[..eq], offset, line);
+                self.push_trimmed(TokenKind::Assign, text, &code[eq..eq + 1], offset, line);
+                self.push_trimmed(TokenKind::Value, text, value, offset, line);
+                if value.trim_start().starts_with(['[', '{']) {
+                    self.value_depth = nesting_balance(value, true).max(0);
+                }
+                self.continued = continues.then_some(TokenKind::Value);
+            }
+            None => self.push_trimmed(TokenKind::Key, text, code, offset, line),
+        }
+    }
+
+    /// Pushes the directive arguments in `args`: whitespace-separated runs
+    /// with quoted strings kept whole, and each `{` / `}` on its own.
+    fn arguments(&mut self, text: &str, args: &str, offset: usize, line: usize) {
+        let base = offset + (args.as_ptr() as usize - text.as_ptr() as usize);
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut start: Option<usize> = None;
+        for (i, c) in args.char_indices() {
+            if std::mem::take(&mut escaped) {
+                continue;
+            }
+            match (quote, c) {
+                (Some('"'), '\\') => escaped = true,
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '{' | '}') => {
+                    if let Some(s) = start.take() {
+                        self.push(TokenKind::Argument, base + s, base + i, line);
+                    }
+                    let kind = if c == '{' { TokenKind::OpenBrace } else { TokenKind::CloseBrace };
+                    self.push(kind, base + i, base + i + 1, line);
+                }
+                (None, c) if c.is_whitespace() => {
+                    if let Some(s) = start.take() {
+                        self.push(TokenKind::Argument, base + s, base + i, line);
+                    }
+                }
+                (None, c) => {
+                    if matches!(c, '"' | '\'') {
+                        quote = Some(c);
+                    }
+                    start.get_or_insert(i);
+                }
+            }
+        }
+        if let Some(s) = start {
+            self.push(TokenKind::Argument, base + s, base + args.len(), line);
+        }
+    }
+}
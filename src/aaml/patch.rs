@@ -0,0 +1,259 @@
+//! Patch files — small, auditable deltas applied to a loaded document.
+//!
+//! A patch lists one operation per line, in the spirit of JSON Patch:
+//! ```text
+//! # rollout 2024-06: move to the new region
+//! replace region = eu-west-2
+//! add server.timeout = 30      # field of the inline object `server`
+//! remove debug
+//! ```
+//!
+//! | Operation | Effect | Fails when |
+//! |-----------|--------|------------|
+//! | `add path = value` | Creates the key or object field | The target already exists |
+//! | `replace path = value` | Overwrites the key or object field | The target does not exist |
+//! | `remove path` | Deletes the key or object field | The target does not exist |
+//!
+//! A path is a key of the document. When no key matches the whole path, the
+//! longest dotted prefix that holds an inline object is used and the rest of
+//! the path names a field inside it (`server.tls.port` edits `port` in the
+//! nested object `tls` of `server`). Scalar values written into an inline
+//! object cannot contain commas. Comments and blank lines are ignored.
+//!
+//! New values are validated against the document's schemas exactly like
+//! parsed assignments. [`AAML::apply_patch`] is atomic: if any operation
+//! fails, the document is left as it was.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::aaml::patch::Patch;
+//!
+//! let mut cfg = AAML::parse("server = { host = a, port = 80 }\ndebug = true").unwrap();
+//! let patch = Patch::parse("replace server.port = 8080\nremove debug").unwrap();
+//! cfg.apply_patch(&patch).unwrap();
+//! assert_eq!(cfg.find_obj("server").unwrap().as_str(), "{ host = a, port = 8080 }");
+//! assert!(cfg.find_obj("debug").is_none());
+//! ```
+
+use super::{AAML, parsing};
+use crate::error::AamlError;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single patch operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PatchOp {
+    /// `add path = value`
+    Add { path: String, value: String },
+    /// `replace path = value`
+    Replace { path: String, value: String },
+    /// `remove path`
+    Remove { path: String },
+}
+
+impl PatchOp {
+    /// Returns the key or field path the operation targets.
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOp::Add { path, .. } | PatchOp::Replace { path, .. } | PatchOp::Remove { path } => {
+                path
+            }
+        }
+    }
+}
+
+impl fmt::Display for PatchOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchOp::Add { path, value } => write!(f, "add {path} = {value}"),
+            PatchOp::Replace { path, value } => write!(f, "replace {path} = {value}"),
+            PatchOp::Remove { path } => write!(f, "remove {path}"),
+        }
+    }
+}
+
+/// An ordered list of [`PatchOp`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Patch {
+    ops: Vec<PatchOp>,
+}
+
+impl Patch {
+    /// Creates a patch from already-built operations.
+    pub fn new(ops: Vec<PatchOp>) -> Self {
+        Self { ops }
+    }
+
+    /// Returns the operations in the order they are applied.
+    pub fn ops(&self) -> &[PatchOp] {
+        &self.ops
+    }
+
+    /// Parses patch text, one operation per line.
+    ///
+    /// # Errors
+    /// [`AamlError::ParseError`] naming the line of the first malformed operation.
+    pub fn parse(text: &str) -> Result<Self, AamlError> {
+        let mut ops = Vec::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line = parsing::strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |details: &str| AamlError::ParseError {
+                line: i + 1,
+                content: line.to_string(),
+                details: details.to_string(),
+            };
+            let (verb, rest) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| error("Expected 'add', 'replace' or 'remove' followed by a path"))?;
+            let op = match verb {
+                "add" | "replace" => {
+                    let (path, value) = parsing::parse_assignment(rest.trim()).map_err(error)?;
+                    let (path, value) = (path.to_string(), value.to_string());
+                    if verb == "add" {
+                        PatchOp::Add { path, value }
+                    } else {
+                        PatchOp::Replace { path, value }
+                    }
+                }
+                "remove" => {
+                    let path = rest.trim();
+                    if path.contains(char::is_whitespace) {
+                        return Err(error("'remove' takes a single path"));
+                    }
+                    PatchOp::Remove { path: path.to_string() }
+                }
+                _ => return Err(error("Unknown patch operation, expected 'add', 'replace' or 'remove'")),
+            };
+            ops.push(op);
+        }
+        Ok(Self { ops })
+    }
+}
+
+impl FromStr for Patch {
+    type Err = AamlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in &self.ops {
+            writeln!(f, "{op}")?;
+        }
+        Ok(())
+    }
+}
+
+impl AAML {
+    /// Applies every operation of `patch` in order.
+    ///
+    /// Added and replaced values are validated against the schemas governing
+    /// their keys; editing a field of an inline object re-validates the whole
+    /// object. If any operation fails, every change made by the patch is
+    /// rolled back.
+    ///
+    /// # Errors
+    /// - [`AamlError::NotFound`] — `replace` / `remove` of an unassigned key.
+    /// - [`AamlError::InvalidValue`] — `add` of an existing key, any failed
+    ///   inline-object field edit (missing or existing field, a path crossing a
+    ///   value that is not an inline object, a scalar containing `,`).
+    /// - [`AamlError::SchemaValidationError`] — a new value violates a schema.
+    pub fn apply_patch(&mut self, patch: &Patch) -> Result<(), AamlError> {
+        let snapshot = self.map.clone();
+        for op in &patch.ops {
+            if let Err(e) = self.apply_op(op) {
+                self.map = snapshot;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_op(&mut self, op: &PatchOp) -> Result<(), AamlError> {
+        let path = op.path();
+        if let Some((key, field)) = self.object_target(path) {
+            let object = self.map[key].to_string();
+            let edited = edit_object(&object, field, op)
+                .map_err(|details| AamlError::InvalidValue(format!("'{op}': {details}")))?;
+            let key = key.to_string();
+            return self.insert_validated(&key, &edited);
+        }
+
+        let exists = self.map.contains_key(path);
+        match op {
+            PatchOp::Add { .. } if exists => Err(AamlError::InvalidValue(format!(
+                "'{op}': key '{path}' already exists"
+            ))),
+            PatchOp::Replace { .. } | PatchOp::Remove { .. } if !exists => {
+                Err(AamlError::NotFound(format!("'{op}': key '{path}' is not assigned")))
+            }
+            PatchOp::Add { value, .. } | PatchOp::Replace { value, .. } => {
+                self.insert_validated(path, value)
+            }
+            PatchOp::Remove { .. } => {
+                self.map.remove(path);
+                Ok(())
+            }
+        }
+    }
+
+    /// Splits `path` into `(key, field path)` when `path` is not itself a key
+    /// but its longest dotted prefix holds an inline object.
+    fn object_target<'p>(&self, path: &'p str) -> Option<(&'p str, &'p str)> {
+        if self.map.contains_key(path) {
+            return None;
+        }
+        path.rmatch_indices('.').find_map(|(i, _)| {
+            let key = &path[..i];
+            self.map
+                .get(key)
+                .filter(|v| parsing::is_inline_object(v))
+                .map(|_| (key, &path[i + 1..]))
+        })
+    }
+}
+
+/// Applies `op` to the field `field` (possibly dotted) of the inline object
+/// `object` and returns the rewritten object.
+fn edit_object(object: &str, field: &str, op: &PatchOp) -> Result<String, String> {
+    let mut pairs = parsing::parse_inline_object(object)?;
+    let position = pairs.iter().position(|(k, _)| k == field);
+
+    if position.is_none()
+        && let Some((head, rest)) = field.split_once('.')
+        && let Some(nested) = pairs.iter_mut().find(|(k, _)| k == head)
+    {
+        if !parsing::is_inline_object(&nested.1) {
+            return Err(format!("field '{head}' is not an inline object"));
+        }
+        nested.1 = edit_object(&nested.1, rest, op)?;
+        return Ok(parsing::format_inline_object(&pairs));
+    }
+
+    if let PatchOp::Add { value, .. } | PatchOp::Replace { value, .. } = op
+        && !value.starts_with(['{', '['])
+        && value.contains(',')
+    {
+        return Err(format!("value '{value}' of field '{field}' cannot contain ','"));
+    }
+
+    match (op, position) {
+        (PatchOp::Add { .. }, Some(_)) => return Err(format!("field '{field}' already exists")),
+        (PatchOp::Add { value, .. }, None) => pairs.push((field.to_string(), value.clone())),
+        (PatchOp::Replace { value, .. }, Some(i)) => pairs[i].1 = value.clone(),
+        (PatchOp::Remove { .. }, Some(i)) => {
+            pairs.remove(i);
+        }
+        (_, None) => return Err(format!("field '{field}' does not exist")),
+    }
+    Ok(parsing::format_inline_object(&pairs))
+}
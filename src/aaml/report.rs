@@ -0,0 +1,291 @@
+//! Validation reports that collect every violation instead of the first.
+//!
+//! [`AAML::parse`] and [`AAML::apply_schema`] stop at the first error. When a
+//! user has to fix a config by hand it is friendlier to list everything that
+//! is wrong at once:
+//!
+//! - [`AAML::apply_schema_report`] checks every field of a record.
+//! - [`AAML::merge_content_report`] merges content into an instance, reporting
+//!   every failing line.
+//! - [`AAML::parse_with_options`] and [`AAML::load_with_options`] with
+//!   [`ParseOptions::collect_errors`] keep parsing after a value fails its
+//!   schema, skipping that assignment, and report each failing field.
+//!
+//! [`ParseOptions`], from the [`options`](super::options) module, also
+//! carries the other parsing switches, such as
+//! [`ParseOptions::slash_comments`] for `//` and `/* */` comments and
+//! [`ParseOptions::colon_assignments`] for `key: value`, which are handy for
+//! files migrated from other formats.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::aaml::ParseOptions;
+//!
+//! let content = "@schema Server { host: string, port: i32, debug: bool }\n\
+//!                port = http\ndebug = maybe\nhost = localhost";
+//! let options = ParseOptions::new().with_collect_errors(true);
+//! let (cfg, report) = AAML::parse_with_options(content, &options).unwrap();
+//!
+//! assert_eq!(report.len(), 2);
+//! assert_eq!(report.violations[0].line, Some(2));
+//! assert_eq!(report.violations[0].field(), Some("port"));
+//! assert_eq!(cfg.find_obj("host").unwrap().as_str(), "localhost");
+//! ```
+
+use super::AAML;
+use super::options::ParseOptions;
+use crate::error::AamlError;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A single failure recorded in a [`ValidationReport`].
+#[derive(Debug)]
+pub struct Violation {
+    /// 1-based source line, when the violation comes from parsing.
+    pub line: Option<usize>,
+    /// The underlying error.
+    pub error: AamlError,
+}
+
+impl Violation {
+    /// The failing field, for schema violations.
+    pub fn field(&self) -> Option<&str> {
+        match &self.error {
+            AamlError::SchemaValidationError { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// The type the value was checked against, when known.
+    pub fn type_name(&self) -> Option<&str> {
+        match &self.error {
+            AamlError::SchemaValidationError { type_name, .. }
+            | AamlError::InvalidType { type_name, .. } => Some(type_name),
+            _ => None,
+        }
+    }
+
+    /// Why the value was rejected, without the field and type prefix.
+    pub fn reason(&self) -> String {
+        match &self.error {
+            AamlError::SchemaValidationError { details, .. }
+            | AamlError::InvalidType { details, .. }
+            | AamlError::ParseError { details, .. } => details.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+/// A non-fatal problem found while parsing, such as an assignment to a key
+/// marked with `@deprecated`. See [`AAML::warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// 1-based source line, when known.
+    pub line: Option<usize>,
+    /// What was found.
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Every violation found by a collecting validation run, in the order found.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    /// The recorded failures.
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    /// Number of violations.
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Consumes the report, returning just the errors in the order found.
+    pub fn into_errors(self) -> Vec<AamlError> {
+        self.violations.into_iter().map(|v| v.error).collect()
+    }
+
+    /// Returns `true` when nothing failed.
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn push(&mut self, line: Option<usize>, error: AamlError) {
+        self.violations.push(Violation { line, error });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for violation in &self.violations {
+            writeln!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl AAML {
+    /// Like [`apply_schema`](Self::apply_schema), but checks every field and
+    /// reports all failures, ordered by field name.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if the schema is not registered.
+    pub fn apply_schema_report(
+        &self,
+        schema_name: &str,
+        data: &HashMap<String, String>,
+    ) -> Result<ValidationReport, AamlError> {
+        let schema = self.schemas.get(schema_name).ok_or_else(|| {
+            AamlError::NotFound(format!("Schema '{}' not found", schema_name))
+        })?;
+        let mut report = ValidationReport::default();
+        for error in self.schema_violations(schema_name, schema, data) {
+            report.push(None, error);
+        }
+        Ok(report)
+    }
+
+    /// Parses `content` like [`parse`](Self::parse) with the switches in
+    /// `options`, optionally collecting every error into a
+    /// [`ValidationReport`] instead of stopping.
+    ///
+    /// With `collect_errors` off the report is always empty and the first
+    /// error is returned. With it on, assignments whose value fails its type
+    /// or schema are skipped and reported together with any missing
+    /// `@require` keys and every violated `@rule`.
+    ///
+    /// # Errors
+    /// The first error that is not a failing value: a malformed line, a
+    /// failing directive, or with `slash_comments` on a `/*` that is never
+    /// closed.
+    pub fn parse_with_options(
+        content: &str,
+        options: &ParseOptions,
+    ) -> Result<(Self, ValidationReport), AamlError> {
+        let mut aaml = AAML::new().with_parse_options(options);
+        let report = aaml.merge_with_options(content, options)?;
+        Ok((aaml, report))
+    }
+
+    /// Loads a file like [`load`](Self::load) with the switches in `options`;
+    /// see [`parse_with_options`](Self::parse_with_options).
+    ///
+    /// # Errors
+    /// [`AamlError::IoError`] if the file cannot be read, plus everything
+    /// [`parse_with_options`](Self::parse_with_options) reports.
+    pub fn load_with_options<P: AsRef<Path>>(
+        file_path: P,
+        options: &ParseOptions,
+    ) -> Result<(Self, ValidationReport), AamlError> {
+        let path = file_path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let mut aaml = AAML::new().with_parse_options(options);
+        let report = aaml.in_file_dir(path, |aaml| aaml.merge_with_options(&content, options))?;
+        Ok((aaml, report))
+    }
+
+    /// Like [`merge_content`](Self::merge_content), but keeps going after a
+    /// failing line and returns every failure in one [`ValidationReport`],
+    /// so a hand-edited file shows all of its problems in a single run.
+    ///
+    /// Every error is collected: malformed lines, failing directives and
+    /// values rejected by their type or schema are reported with their line
+    /// and skipped, and the rest of the content is merged. `${name}`
+    /// references are still resolved once every line is read; one that
+    /// cannot be resolved, or whose expanded value is rejected, is reported
+    /// against the line of its assignment. Registered migrations still run
+    /// afterwards, and a failing one is reported without a line. `@require`
+    /// and `@rule` checks are not run, as with `merge_content`.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let mut cfg = AAML::new();
+    /// let report = cfg.merge_content_report("@schema S { port: i32 }\nport = x\nbroken\nname = ok");
+    /// assert_eq!(report.violations.iter().map(|v| v.line).collect::<Vec<_>>(), [Some(2), Some(3)]);
+    /// assert_eq!(cfg.find_obj("name").unwrap().as_str(), "ok");
+    /// ```
+    pub fn merge_content_report(&mut self, content: &str) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let merged = self.merge_content_with(content, |line, error| {
+            report.push(Some(line), error);
+            Ok(())
+        });
+        let resolved = merged.and_then(|()| {
+            self.resolve_references(|line, error| {
+                report.push(Some(line), error);
+                Ok(())
+            })
+        });
+        if let Err(error) = resolved.and_then(|()| self.run_migrations()) {
+            report.push(None, error);
+        }
+        report
+    }
+
+    /// Merges and validates `content`, stopping at the first error or, with
+    /// `collect_errors`, reporting every value that fails validation.
+    fn merge_with_options(
+        &mut self,
+        content: &str,
+        options: &ParseOptions,
+    ) -> Result<ValidationReport, AamlError> {
+        if !options.collect_errors {
+            self.merge_content(content)?;
+            self.validate_required_keys()?;
+            self.validate_rules()?;
+            return Ok(ValidationReport::default());
+        }
+        let mut report = ValidationReport::default();
+        let mut collect = |line: usize, error: AamlError| {
+            if is_violation(&error) {
+                report.push(Some(line), error);
+                Ok(())
+            } else {
+                Err(error)
+            }
+        };
+        self.merge_content_with(content, &mut collect)?;
+        self.resolve_references(&mut collect)?;
+        self.run_migrations()?;
+        if let Err(error) = self.validate_required_keys() {
+            report.push(None, error);
+        }
+        for rule in &self.rules {
+            if let Err(error) = rule.check(self) {
+                report.push(None, error);
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Returns `true` for errors raised by a value that fails its type or schema,
+/// which [`ParseOptions::collect_errors`] reports instead of stopping.
+fn is_violation(error: &AamlError) -> bool {
+    matches!(
+        error,
+        AamlError::SchemaValidationError { .. } | AamlError::InvalidType { .. }
+    )
+}
@@ -0,0 +1,100 @@
+//! Conversion of the flat key-value map into a nested [`AamlValue`] tree.
+
+use super::AAML;
+use crate::error::AamlError;
+use crate::value::AamlValue;
+use std::collections::BTreeMap;
+
+impl AAML {
+    /// Builds a nested [`AamlValue::Object`] from the assigned keys.
+    ///
+    /// Dotted keys are grouped (`server.port = 80` becomes
+    /// `{ server: { port: "80" } }`), inline objects become objects and list
+    /// literals become lists. An inline object and dotted keys under the same
+    /// prefix are merged. Schema defaults for unassigned keys are not included.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] when a key is both a scalar and a prefix of
+    /// other keys (`a = 1` together with `a.b = 2`), or a key has an empty
+    /// segment (`a..b`).
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    ///
+    /// let cfg = AAML::parse("server.host = localhost\nserver.tls = { enabled = true }").unwrap();
+    /// let tree = cfg.to_tree().unwrap();
+    /// assert_eq!(tree.get("server.tls.enabled").unwrap().as_str(), Some("true"));
+    /// ```
+    pub fn to_tree(&self) -> Result<AamlValue, AamlError> {
+        let mut keys: Vec<&str> = self.map.keys().map(|k| &**k).collect();
+        keys.sort_unstable();
+
+        let mut root = BTreeMap::new();
+        for key in keys {
+            let segments: Vec<&str> = key.split('.').collect();
+            if segments.iter().any(|s| s.is_empty()) {
+                return Err(AamlError::InvalidValue(format!(
+                    "Key '{key}' has an empty path segment"
+                )));
+            }
+            insert_path(&mut root, &segments, AamlValue::parse(&self.map[key]), key)?;
+        }
+        Ok(AamlValue::Object(root))
+    }
+}
+
+/// Inserts `value` at `path` below `node`, creating intermediate objects.
+fn insert_path(
+    node: &mut BTreeMap<String, AamlValue>,
+    path: &[&str],
+    value: AamlValue,
+    key: &str,
+) -> Result<(), AamlError> {
+    let Some((first, rest)) = path.split_first() else {
+        return Ok(());
+    };
+    if rest.is_empty() {
+        return match node.remove(*first) {
+            None => {
+                node.insert(first.to_string(), value);
+                Ok(())
+            }
+            Some(existing) => {
+                let merged = merge(existing, value, key)?;
+                node.insert(first.to_string(), merged);
+                Ok(())
+            }
+        };
+    }
+    let child = node
+        .entry(first.to_string())
+        .or_insert_with(|| AamlValue::Object(BTreeMap::new()));
+    match child {
+        AamlValue::Object(fields) => insert_path(fields, rest, value, key),
+        _ => Err(conflict(key)),
+    }
+}
+
+/// Merges two values placed at the same path; only objects can be combined.
+fn merge(existing: AamlValue, value: AamlValue, key: &str) -> Result<AamlValue, AamlError> {
+    match (existing, value) {
+        (AamlValue::Object(mut a), AamlValue::Object(b)) => {
+            for (k, v) in b {
+                let merged = match a.remove(&k) {
+                    Some(old) => merge(old, v, key)?,
+                    None => v,
+                };
+                a.insert(k, merged);
+            }
+            Ok(AamlValue::Object(a))
+        }
+        _ => Err(conflict(key)),
+    }
+}
+
+fn conflict(key: &str) -> AamlError {
+    AamlError::InvalidValue(format!(
+        "Key '{key}' conflicts with another key that uses it as both a value and an object"
+    ))
+}
@@ -2,35 +2,250 @@
 
 use std::collections::HashMap;
 use crate::error::AamlError;
-use crate::types::{resolve_builtin};
-use crate::types::list::ListType;
+use crate::types::resolve_builtin;
+use crate::types::constraint::{ConstrainedType, Constraint};
+use crate::types::enumeration::EnumType;
+use crate::types::list::{ListType, describe_element};
+use crate::types::map::MapType;
+use crate::types::optional::OptionalType;
+use crate::types::range::RangeType;
+use crate::types::union::UnionType;
 use crate::aaml::parsing;
+use crate::commands::schema::SchemaDef;
 use super::AAML;
 
 impl AAML {
-    /// Validates a single field value against any schema that declares it.
+    /// Validates a single field value against the schema that governs it.
     ///
-    /// If the field is not declared in any schema the function succeeds silently.
+    /// A key bound with `@bind` is validated as an instance of its schema, and
+    /// its dotted sub-keys against that schema's fields. Other keys are checked
+    /// against the `@conform` schema when there is one, otherwise against every
+    /// registered schema. If the field is not declared the function succeeds
+    /// silently, unless the governing schema is `strict`.
     pub(super) fn validate_against_schemas(
         &self,
         field: &str,
         value: &str,
     ) -> Result<(), AamlError> {
-        for (schema_name, schema_def) in &self.schemas {
-            if let Some(type_name) = schema_def.fields.get(field) {
-                return self.validate_typed_field(type_name, value, schema_name, field);
+        match self.governing_field(field) {
+            Some((schema_name, type_name)) => match self.binding_for(field) {
+                Some((_, None)) => self.validate_typed_field(type_name, value, schema_name, field),
+                Some((_, Some(rest))) => {
+                    self.check_schema_field(type_name, value, schema_name, rest, field)
+                }
+                None => self.check_schema_field(type_name, value, schema_name, field, field),
+            },
+            None => self.check_declared(field),
+        }
+    }
+
+    /// Runs [`check_declared`](Self::check_declared) for every assigned key.
+    pub(crate) fn check_all_declared(&self) -> Result<(), AamlError> {
+        let mut keys: Vec<&str> = self.map.keys().map(|k| &**k).collect();
+        keys.sort_unstable();
+        keys.into_iter().try_for_each(|key| self.check_declared(key))
+    }
+
+    /// Re-validates the value of a key bound with `@bind`, and of its dotted
+    /// sub-keys, if they are already assigned.
+    pub(crate) fn check_bound_key(&self, key: &str) -> Result<(), AamlError> {
+        let prefix = format!("{key}.");
+        let mut assigned: Vec<(&str, &str)> = self
+            .map
+            .iter()
+            .filter(|(k, _)| &***k == key || k.starts_with(&prefix))
+            .map(|(k, v)| (&**k, &**v))
+            .collect();
+        assigned.sort_unstable();
+        assigned
+            .into_iter()
+            .try_for_each(|(k, v)| self.validate_against_schemas(k, v))
+    }
+
+    /// Fails when `field` falls under a `strict` schema — the `@bind` schema
+    /// of its prefix, or else the `@conform` schema — that does not declare it.
+    pub(crate) fn check_declared(&self, field: &str) -> Result<(), AamlError> {
+        let (name, field) = match self.binding_for(field) {
+            Some((_, None)) => return Ok(()),
+            Some((schema, Some(rest))) => (schema, rest),
+            None => match &self.conform {
+                Some(name) => (name.as_str(), field),
+                None => return Ok(()),
+            },
+        };
+        match self.schemas.get(name) {
+            Some(schema) if schema.strict && !schema.fields.contains_key(field) => {
+                Err(AamlError::SchemaValidationError {
+                    schema: name.to_string(),
+                    field: field.to_string(),
+                    type_name: name.to_string(),
+                    details: format!("Key '{field}' is not declared in strict schema '{name}'"),
+                    location: None,
+                })
             }
+            _ => Ok(()),
         }
-        Ok(())
     }
 
-    /// Validates `value` against `type_name`, checking:
-    /// 1. Registered custom types.
-    /// 2. Nested schema types (type_name matches a registered schema name).
-    /// 3. `list<T>` — validates every element of a `[...]` literal against `T`.
-    /// 4. Built-in module types (`math::`, `time::`, `physics::`, primitives).
+    /// Returns the type declared for `field` by the schema that governs it.
+    pub(super) fn schema_field_type(&self, field: &str) -> Option<&str> {
+        self.governing_field(field).map(|(_, type_name)| type_name)
+    }
+
+    /// Finds the `@bind` binding covering `field`: `(schema, None)` when the
+    /// key itself is bound, `(schema, Some(rest))` for a sub-key `key.rest`.
+    pub(super) fn binding_for<'f>(&self, field: &'f str) -> Option<(&str, Option<&'f str>)> {
+        if self.bindings.is_empty() {
+            return None;
+        }
+        if let Some(schema) = self.bindings.get(field) {
+            return Some((schema, None));
+        }
+        field.match_indices('.').find_map(|(i, _)| {
+            self.bindings
+                .get(&field[..i])
+                .map(|schema| (schema.as_str(), Some(&field[i + 1..])))
+        })
+    }
+
+    /// Finds `(schema name, declared type)` for `field`, honouring `@bind`
+    /// and `@conform`. A bound key's declared type is its schema.
+    fn governing_field(&self, field: &str) -> Option<(&str, &str)> {
+        if let Some((schema_name, rest)) = self.binding_for(field) {
+            return match rest {
+                None => Some((schema_name, schema_name)),
+                Some(rest) => self
+                    .schemas
+                    .get(schema_name)?
+                    .fields
+                    .get(rest)
+                    .map(|type_name| (schema_name, type_name.as_str())),
+            };
+        }
+        if let Some(name) = &self.conform {
+            let schema_def = self.schemas.get(name)?;
+            return schema_def
+                .fields
+                .get(field)
+                .map(|type_name| (name.as_str(), type_name.as_str()));
+        }
+        self.schemas.iter().find_map(|(schema_name, schema_def)| {
+            schema_def
+                .fields
+                .get(field)
+                .map(|type_name| (schema_name.as_str(), type_name.as_str()))
+        })
+    }
+
+    /// Returns the canonical form of a value already validated against
+    /// `type_name`, resolving aliases and registered types like
+    /// [`check_value`](Self::check_value). Schemas and types that cannot be
+    /// resolved without the registry keep the value as written, trimmed.
+    pub(super) fn normalize_value(&self, type_name: &str, value: &str) -> String {
+        let type_name = self.resolve_alias(type_name);
+        if let Some(type_def) = self.types.get(type_name) {
+            return type_def.normalize(value);
+        }
+        resolve_builtin(type_name).map_or_else(|_| value.trim().to_string(), |t| t.normalize(value))
+    }
+
+    /// Validates the value of the schema field `schema_name.field` against its
+    /// type, then runs the field validators registered for it.
+    pub(crate) fn validate_schema_field(
+        &self,
+        type_name: &str,
+        value: &str,
+        schema_name: &str,
+        field: &str,
+    ) -> Result<(), AamlError> {
+        self.check_schema_field(type_name, value, schema_name, field, field)
+    }
+
+    /// [`validate_schema_field`](Self::validate_schema_field) for a value
+    /// assigned under the key `reported`, e.g. the sub-key `player1.hp` of a
+    /// key bound to the schema. A type failure carries the field's custom
+    /// message, if it declares one.
+    fn check_schema_field(
+        &self,
+        type_name: &str,
+        value: &str,
+        schema_name: &str,
+        field: &str,
+        reported: &str,
+    ) -> Result<(), AamlError> {
+        let message = self.field_message(schema_name, field);
+        self.check_field_value(type_name, value, schema_name, field, reported, message)
+    }
+
+    /// Checks the default `value` of `schema.field` as if `schema` were
+    /// registered as `schema_name`, so a schema can be validated before it
+    /// is registered.
+    pub(crate) fn validate_default_value(
+        &self,
+        schema: &SchemaDef,
+        schema_name: &str,
+        field: &str,
+        value: &str,
+    ) -> Result<(), AamlError> {
+        let type_name = &schema.fields[field];
+        self.check_field_value(type_name, value, schema_name, field, field, schema.message_for(field))
+    }
+
+    /// Shared body of [`check_schema_field`](Self::check_schema_field): a
+    /// type failure is reported with `message` in place of its details.
+    fn check_field_value(
+        &self,
+        type_name: &str,
+        value: &str,
+        schema_name: &str,
+        field: &str,
+        reported: &str,
+        message: Option<&str>,
+    ) -> Result<(), AamlError> {
+        self.validate_typed_field(type_name, value, schema_name, reported)
+            .map_err(|e| match (e, message) {
+                (AamlError::SchemaValidationError { schema, field, type_name, location, .. }, Some(msg)) => {
+                    AamlError::SchemaValidationError { schema, field, type_name, details: msg.to_string(), location }
+                }
+                (e, _) => e,
+            })?;
+        self.run_field_validators(schema_name, field, type_name, value, reported)
+    }
+
+    /// Returns the custom message `schema_name` declares for `field`, if any.
+    fn field_message(&self, schema_name: &str, field: &str) -> Option<&str> {
+        self.schemas.get(schema_name)?.message_for(field)
+    }
+
+    /// Runs the validators registered for `schema_name.field`, reporting a
+    /// rejection under the key `reported`.
+    fn run_field_validators(
+        &self,
+        schema_name: &str,
+        field: &str,
+        type_name: &str,
+        value: &str,
+        reported: &str,
+    ) -> Result<(), AamlError> {
+        let Some(validators) = self.field_validators.get(schema_name).and_then(|f| f.get(field))
+        else {
+            return Ok(());
+        };
+        validators.iter().try_for_each(|validator| {
+            validator(value).map_err(|details| AamlError::SchemaValidationError {
+                schema: schema_name.to_string(),
+                field: reported.to_string(),
+                type_name: type_name.to_string(),
+                details,
+                location: None,
+            })
+        })
+    }
+
+    /// Validates `value` against `type_name` and reports failures as a
+    /// [`AamlError::SchemaValidationError`] for `schema_name.field`.
     ///
-    /// Returns a [`AamlError::SchemaValidationError`] on failure.
+    /// See [`check_value`](Self::check_value) for the resolution order.
     pub(crate) fn validate_typed_field(
         &self,
         type_name: &str,
@@ -38,72 +253,182 @@ impl AAML {
         schema_name: &str,
         field: &str,
     ) -> Result<(), AamlError> {
-        let make_err = |details: String| AamlError::SchemaValidationError {
-            schema: schema_name.to_string(),
-            field: field.to_string(),
-            type_name: type_name.to_string(),
-            details,
-        };
+        self.check_value(type_name, value).map_err(|e| {
+            let details = match e {
+                AamlError::NotFound(msg) => msg,
+                other => other.to_string(),
+            };
+            AamlError::SchemaValidationError {
+                schema: schema_name.to_string(),
+                field: field.to_string(),
+                type_name: type_name.to_string(),
+                details,
+                location: None,
+            }
+        })
+    }
 
-        // 1. Registered custom type alias
+    /// Validates `value` against the type expression `type_name`, checking:
+    /// 1. `@type` alias chains — followed to the type they name.
+    /// 2. Registered custom types.
+    /// 3. Nested schema types (type_name matches a registered schema name).
+    /// 4. `A | B` / `union<A, B>` — succeeds when any member accepts the value.
+    /// 5. `list<T>` / `list<T, a..b>` / `set<T>` — checks the item count, then
+    ///    validates every element of a `[...]` literal against `T`; sets also
+    ///    reject duplicate elements.
+    /// 6. `map<K, V>` — validates every key of an inline object against `K` and
+    ///    every value against `V`.
+    /// 7. `optional<T>` / `nullable<T>` — accepts `none` / `null`, otherwise validates against `T`.
+    /// 8. `range<T>` — validates both endpoints of a `start..end` literal against `T`.
+    /// 9. Constraints on registered types — `port_t(1..1024)` / `port_t[1..1024]`.
+    /// 10. Built-in module types (`math::`, `time::`, `physics::`, `data::`, `net::`, `id::`, `fs::`, `geo::`, primitives).
+    ///
+    /// Unknown types produce [`AamlError::NotFound`]; every other failure is
+    /// returned as produced by the type that rejected the value.
+    pub(crate) fn check_value(&self, type_name: &str, value: &str) -> Result<(), AamlError> {
+        // 1. Alias chains
+        let type_name = self.resolve_alias(type_name);
+
+        // 2. Registered custom type
         if let Some(type_def) = self.types.get(type_name) {
-            return type_def.validate(value).map_err(|e| make_err(e.to_string()));
+            return type_def.validate(value);
         }
 
-        // 2. Nested schema — type_name matches a registered schema name
+        // 3. Nested schema — type_name matches a registered schema name
         if let Some(nested_schema) = self.schemas.get(type_name) {
-            return self
-                .validate_inline_object_against_schema(value, type_name, nested_schema.fields.clone())
-                .map_err(|e| make_err(e.to_string()));
+            return self.validate_inline_object_against_schema(
+                value,
+                type_name,
+                nested_schema.fields.clone(),
+            );
+        }
+
+        // 4. A | B
+        if UnionType::split(type_name).is_some() {
+            return UnionType::parse(type_name)?
+                .check_members(value, |member| self.check_value(member, value));
+        }
+
+        // 5. list<T> / set<T>
+        if ListType::split(type_name).is_some() {
+            return self.validate_list_value(value, &ListType::parse(type_name)?);
+        }
+
+        // 6. map<K, V>
+        if let Some((key_type, value_type)) = MapType::split(type_name) {
+            return self.validate_map_value(value, key_type, value_type);
         }
 
-        // 3. list<T>
-        if let Some(inner_type) = ListType::parse_inner(type_name) {
-            return self
-                .validate_list_value(value, &inner_type)
-                .map_err(|e| make_err(e.to_string()));
+        // 7. optional<T> / nullable<T>
+        if let Some(inner) = OptionalType::parse_inner(type_name) {
+            if OptionalType::is_unset(value) {
+                return Ok(());
+            }
+            return self.check_value(inner, value);
         }
 
-        // 4. Built-in types
-        match resolve_builtin(type_name) {
-            Ok(type_def) => type_def.validate(value).map_err(|e| make_err(e.to_string())),
-            Err(_) => Err(make_err(format!("Unknown type '{}'", type_name))),
+        // 8. range<T>
+        if let Some(inner) = RangeType::parse_inner(type_name) {
+            return RangeType::check_literal(value, |endpoint| self.check_value(inner, endpoint));
         }
+
+        // 9. Constraint on a registered base type
+        if let Some((base, args)) = self.registry_constraint(type_name) {
+            self.check_value(base, value)?;
+            return Constraint::parse(args)?.check(value);
+        }
+
+        // 10. Built-in types
+        resolve_builtin(type_name)
+            .map_err(|_| AamlError::NotFound(format!("Unknown type '{}'", type_name)))?
+            .validate(value)
     }
 
-    /// Validates a `[item, item, ...]` literal where each item is validated
-    /// against `inner_type`.
-    ///
-    /// If `inner_type` names a registered schema the items are treated as
-    /// inline objects `{ k = v, ... }` and validated against that schema.
-    /// Validates a `[item, …]` literal where each item is checked against `inner_type`.
+    /// Splits `base(args)` / `base[args]` when `base` is a registered custom
+    /// type. Constraints on built-in bases are handled by `resolve_builtin`.
+    fn registry_constraint<'a>(&self, type_name: &'a str) -> Option<(&'a str, &'a str)> {
+        if EnumType::is_enum(type_name) {
+            return None;
+        }
+        ConstrainedType::split(type_name).filter(|(base, _)| self.types.contains_key(*base))
+    }
+
+    /// Returns `true` when `type_name` can be resolved, either through the
+    /// registry (custom types, schemas, aliases) or as a built-in.
+    pub(crate) fn type_is_known(&self, type_name: &str) -> bool {
+        let type_name = self.resolve_alias(type_name);
+        if self.types.contains_key(type_name) || self.schemas.contains_key(type_name) {
+            return true;
+        }
+        if let Some(members) = UnionType::split(type_name) {
+            return members.iter().all(|m| self.type_is_known(m));
+        }
+        if let Some(inner) = ListType::parse_inner(type_name) {
+            return self.type_is_known(&inner);
+        }
+        if let Some((key, value)) = MapType::split(type_name) {
+            return self.type_is_known(key) && self.type_is_known(value);
+        }
+        if let Some(inner) = OptionalType::parse_inner(type_name) {
+            return self.type_is_known(inner);
+        }
+        if let Some(inner) = RangeType::parse_inner(type_name) {
+            return self.type_is_known(inner);
+        }
+        self.registry_constraint(type_name).is_some() || resolve_builtin(type_name).is_ok()
+    }
+
+    /// Validates a `[item, …]` literal against `list`: the item count must fit
+    /// its cardinality and each item is checked against the element type.
     /// Items are split respecting nested `{}` / `[]`, so `list<Schema>` works correctly.
-    fn validate_list_value(&self, value: &str, inner_type: &str) -> Result<(), AamlError> {
-        let items = ListType::parse_items(value).ok_or_else(|| {
+    /// Element errors name the index and byte offset of the failing item.
+    fn validate_list_value(&self, value: &str, list: &ListType) -> Result<(), AamlError> {
+        let items = ListType::parse_items_with_offsets(value).ok_or_else(|| {
             AamlError::InvalidValue(format!("Expected a list literal '[…]', got '{value}'"))
         })?;
+        list.check_len(items.len())?;
 
-        for item in &items {
-            if let Some(nested_schema) = self.schemas.get(inner_type) {
-                let fields = nested_schema.fields.clone();
-                self.validate_inline_object_against_schema(item, inner_type, fields)?;
-            } else if let Ok(builtin) = resolve_builtin(inner_type) {
-                builtin.validate(item).map_err(|e| {
-                    AamlError::InvalidValue(format!(
-                        "List item '{item}' failed for type '{inner_type}': {e}"
-                    ))
-                })?;
-            } else if let Some(type_def) = self.types.get(inner_type) {
-                type_def.validate(item).map_err(|e| {
-                    AamlError::InvalidValue(format!(
-                        "List item '{item}' failed for type '{inner_type}': {e}"
-                    ))
-                })?;
-            } else {
-                return Err(AamlError::NotFound(format!(
-                    "Unknown list element type '{inner_type}'"
-                )));
-            }
+        let inner_type = list.inner_type.as_str();
+        for (index, (offset, item)) in items.iter().enumerate() {
+            self.check_value(inner_type, item).map_err(|e| match e {
+                AamlError::NotFound(msg) => AamlError::NotFound(format!(
+                    "Unknown list element type '{inner_type}': {msg}"
+                )),
+                other => AamlError::InvalidValue(format!(
+                    "{} failed for type '{inner_type}': {other}",
+                    describe_element(index, *offset, item)
+                )),
+            })?;
+        }
+        list.check_unique(&items, |item| self.normalize_value(inner_type, item))
+    }
+
+    /// Validates an inline object literal `{ key = val, ... }` as a `map<K, V>`:
+    /// keys are checked against `key_type` and values against `value_type`,
+    /// both resolved through the registry.
+    fn validate_map_value(
+        &self,
+        value: &str,
+        key_type: &str,
+        value_type: &str,
+    ) -> Result<(), AamlError> {
+        for (k, v) in MapType::parse_entries(value)? {
+            self.check_value(key_type, &k).map_err(|e| match e {
+                AamlError::NotFound(msg) => AamlError::NotFound(format!(
+                    "Unknown map key type '{key_type}': {msg}"
+                )),
+                other => AamlError::InvalidValue(format!(
+                    "Map key '{k}' failed for type '{key_type}': {other}"
+                )),
+            })?;
+            self.check_value(value_type, &v).map_err(|e| match e {
+                AamlError::NotFound(msg) => AamlError::NotFound(format!(
+                    "Unknown map value type '{value_type}': {msg}"
+                )),
+                other => AamlError::InvalidValue(format!(
+                    "Map value for key '{k}' failed for type '{value_type}': {other}"
+                )),
+            })?;
         }
         Ok(())
     }
@@ -114,6 +439,8 @@ impl AAML {
     /// - Required fields (not marked `*`) declared in the schema must be present.
     /// - Optional fields (marked `*`) may be absent; if present they are validated.
     /// - Each value is validated against its declared type (recursively).
+    /// - Keys the schema does not declare are ignored unless the schema sets
+    ///   `deny_extra_objects`.
     fn validate_inline_object_against_schema(
         &self,
         value: &str,
@@ -139,18 +466,15 @@ impl AAML {
             .map(|(k, v)| (k.as_str(), v.as_str()))
             .collect();
 
-        // Fetch optional set from the registered schema (if still available).
-        let optional_fields = self
-            .schemas
-            .get(schema_name)
-            .map(|s| s.optional_fields.clone())
-            .unwrap_or_default();
+        // Fetch the registered schema (if still available) to learn which fields are required.
+        let registered = self.schemas.get(schema_name);
+        let is_required = |field: &str| registered.is_none_or(|s| s.is_required(field));
 
         for (field, type_name) in &schema_fields {
             match pair_map.get(field.as_str()) {
                 None => {
                     // Missing field — only an error for required fields
-                    if !optional_fields.contains(field.as_str()) {
+                    if is_required(field) {
                         return Err(AamlError::SchemaValidationError {
                             schema: schema_name.to_string(),
                             field: field.clone(),
@@ -159,20 +483,36 @@ impl AAML {
                                 "Missing field '{}' in inline object for schema '{}'",
                                 field, schema_name
                             ),
+                            location: None,
                         });
                     }
                 }
                 Some(field_value) => {
-                    self.validate_typed_field(type_name, field_value, schema_name, field)?;
+                    self.validate_schema_field(type_name, field_value, schema_name, field)?;
                 }
             }
         }
 
+        if registered.is_some_and(|s| s.deny_extra_objects)
+            && let Some((extra, _)) = pairs.iter().find(|(k, _)| !schema_fields.contains_key(k))
+        {
+            return Err(AamlError::SchemaValidationError {
+                schema: schema_name.to_string(),
+                field: extra.clone(),
+                type_name: schema_name.to_string(),
+                details: format!(
+                    "Unknown field '{}' in inline object for schema '{}'",
+                    extra, schema_name
+                ),
+                location: None,
+            });
+        }
+
         Ok(())
     }
 
     /// Checks every **required** field in every registered schema against the current map.
-    /// Optional fields (declared with `*`) are skipped.
+    /// Optional fields (declared with `*`) and fields with a default value are skipped.
     pub fn validate_schemas_completeness(&self) -> Result<(), AamlError> {
         let names: Vec<&str> = self.schemas.keys().map(|s| s.as_str()).collect();
         self.validate_schemas_completeness_for(&names)
@@ -184,13 +524,14 @@ impl AAML {
         for name in schema_names {
             let Some(schema_def) = self.schemas.get(*name) else { continue };
             for (field, type_name) in &schema_def.fields {
-                if schema_def.is_optional(field) { continue; }
+                if !schema_def.is_required(field) { continue; }
                 if !self.map.contains_key(field.as_str()) {
                     return Err(AamlError::SchemaValidationError {
                         schema: name.to_string(),
                         field: field.clone(),
                         type_name: type_name.clone(),
                         details: format!("Missing required field '{field}'"),
+                        location: None,
                     });
                 }
             }
@@ -198,14 +539,58 @@ impl AAML {
         Ok(())
     }
 
+    /// Checks that every key marked with `@require` has a value, either
+    /// assigned or as a schema default, and that keys required with a type
+    /// hold a value of that type.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] for `@require` listing every missing key,
+    /// or naming the first key whose value does not match its type.
+    pub fn validate_required_keys(&self) -> Result<(), AamlError> {
+        let missing: Vec<&str> = self
+            .required
+            .iter()
+            .map(String::as_str)
+            .filter(|key| !self.map.contains_key(*key) && self.schema_default(key).is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AamlError::DirectiveError(
+                "require".into(),
+                format!("Missing required key(s): {}", missing.join(", ")),
+                None,
+            ));
+        }
+        for key in &self.required {
+            let (Some(type_name), Some(value)) = (self.required_type(key), self.value_of(key)) else {
+                continue;
+            };
+            self.check_value(type_name, value).map_err(|e| {
+                AamlError::DirectiveError(
+                    "require".into(),
+                    format!("Required key '{key}' must be of type '{type_name}': {e}"),
+                    None,
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Checks every `@rule` against the current values, in declaration order.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] for `@rule` describing the first violated rule.
+    pub fn validate_rules(&self) -> Result<(), AamlError> {
+        self.rules.iter().try_for_each(|rule| rule.check(self))
+    }
+
     /// Validates a complete `data` map against the named schema.
     ///
     /// For every **required** field declared in the schema the method checks:
     /// 1. The key is present in `data`.
     /// 2. The value satisfies the declared type (including nested schemas and lists).
     ///
-    /// Optional fields (declared with `*`) are only validated when they are
-    /// present in `data`; their absence is not an error.
+    /// Optional fields (declared with `*`) and fields with a default value are
+    /// only validated when they are present in `data`; their absence is not an error.
     pub fn apply_schema(
         &self,
         schema_name: &str,
@@ -214,34 +599,56 @@ impl AAML {
         let schema = self.schemas.get(schema_name).ok_or_else(|| {
             AamlError::NotFound(format!("Schema '{}' not found", schema_name))
         })?;
+        self.apply_schema_def(schema_name, schema, data)
+    }
 
-        let fields: Vec<(String, String)> = schema
-            .fields
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
-
-        let optional = schema.optional_fields.clone();
-
-        for (field, type_name) in &fields {
-            match data.get(field) {
-                None => {
-                    if !optional.contains(field.as_str()) {
-                        return Err(AamlError::SchemaValidationError {
-                            schema: schema_name.to_string(),
-                            field: field.clone(),
-                            type_name: type_name.clone(),
-                            details: format!("Missing required field '{}'", field),
-                        });
-                    }
-                }
-                Some(value) => {
-                    self.validate_typed_field(type_name, value, schema_name, field)?;
-                }
-            }
+    /// Validates `data` against an already looked-up schema definition.
+    pub(crate) fn apply_schema_def(
+        &self,
+        schema_name: &str,
+        schema: &SchemaDef,
+        data: &HashMap<String, String>,
+    ) -> Result<(), AamlError> {
+        match self.schema_violations(schema_name, schema, data).next() {
+            Some(e) => Err(e),
+            None => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Yields one error per field of `schema` that `data` is missing or holds
+    /// an invalid value for, in field-name order. Lazy, so callers that only
+    /// need the first failure stop there.
+    pub(crate) fn schema_violations<'a>(
+        &'a self,
+        schema_name: &'a str,
+        schema: &'a SchemaDef,
+        data: &'a HashMap<String, String>,
+    ) -> impl Iterator<Item = AamlError> + 'a {
+        let mut fields: Vec<(&String, &String)> = schema.fields.iter().collect();
+        fields.sort_unstable();
+        fields.into_iter().filter_map(move |(field, type_name)| match self.record_value(data, field) {
+            None if schema.is_required(field) => Some(AamlError::SchemaValidationError {
+                schema: schema_name.to_string(),
+                field: field.clone(),
+                type_name: type_name.clone(),
+                details: format!("Missing required field '{}'", field),
+                location: None,
+            }),
+            None => None,
+            Some(value) => self
+                .validate_schema_field(type_name, value, schema_name, field)
+                .err(),
+        })
     }
-}
 
+    /// Returns the value `data` holds for `field`, under its own name or
+    /// under an `@alias` of it.
+    fn record_value<'d>(&self, data: &'d HashMap<String, String>, field: &str) -> Option<&'d String> {
+        data.get(field).or_else(|| {
+            self.aliases
+                .iter()
+                .filter(|(_, target)| *target == field)
+                .find_map(|(alias, _)| data.get(alias))
+        })
+    }
+}
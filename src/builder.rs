@@ -15,6 +15,9 @@
 //! | [`AAMBuilder::type_alias`] | `@type alias = type` |
 //! | [`AAMBuilder::comment`] | `# ...` |
 //!
+//! To define a schema in memory rather than as text, use [`SchemaDefBuilder`]
+//! and register the result with [`AAML::register_schema`](crate::aaml::AAML::register_schema).
+//!
 //! # Example
 //! ```
 //! use aam_rs::builder::{AAMBuilder, SchemaField};
@@ -35,6 +38,9 @@
 //! assert!(content.contains("host = localhost"));
 //! ```
 
+use crate::aaml::AAML;
+use crate::commands::schema::SchemaDef;
+use crate::error::AamlError;
 use std::fmt::Display;
 use std::io;
 use std::ops::Deref;
@@ -44,7 +50,8 @@ use std::path::Path;
 ///
 /// Fields declared with [`SchemaField::optional`] are emitted with a `*` suffix,
 /// meaning the key does not have to be present in the data map, but its value is
-/// still type-checked when it *is* present.
+/// still type-checked when it *is* present. [`SchemaField::with_default`] appends
+/// a `= value` clause used when the key is absent.
 ///
 /// # Example
 /// ```
@@ -55,6 +62,12 @@ use std::path::Path;
 ///
 /// let g = SchemaField::optional("debug", "bool");
 /// assert_eq!(g.to_aaml(), "debug*: bool");
+///
+/// let h = SchemaField::required("retries", "i32").with_default("3");
+/// assert_eq!(h.to_aaml(), "retries: i32 = 3");
+///
+/// let p = SchemaField::required("port", "i32(1..65535)").with_message("pick a TCP port");
+/// assert_eq!(p.to_aaml(), r#"port: i32(1..65535) "pick a TCP port""#);
 /// ```
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -62,6 +75,8 @@ pub struct SchemaField {
     name: String,
     type_name: String,
     optional: bool,
+    default: Option<String>,
+    message: Option<String>,
 }
 
 impl SchemaField {
@@ -71,6 +86,8 @@ impl SchemaField {
             name: name.into(),
             type_name: type_name.into(),
             optional: false,
+            default: None,
+            message: None,
         }
     }
 
@@ -80,19 +97,149 @@ impl SchemaField {
             name: name.into(),
             type_name: type_name.into(),
             optional: true,
+            default: None,
+            message: None,
         }
     }
 
+    /// Sets a default value (rendered as `name: type = value`).
+    ///
+    /// The value is emitted verbatim, so quote it yourself if it contains
+    /// separators such as spaces or commas.
+    pub fn with_default(mut self, value: impl Into<String>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Sets the message reported when a value of the field is rejected
+    /// (rendered as `name: type "message"`).
+    ///
+    /// The message is wrapped in double quotes, so it must not contain any.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
     /// Renders the field as an AAML field declaration string.
     pub fn to_aaml(&self) -> String {
-        if self.optional {
+        let mut decl = if self.optional {
             format!("{}*: {}", self.name, self.type_name)
         } else {
             format!("{}: {}", self.name, self.type_name)
+        };
+        if let Some(message) = &self.message {
+            decl = format!("{decl} \"{message}\"");
+        }
+        match &self.default {
+            Some(value) => format!("{decl} = {value}"),
+            None => decl,
         }
     }
 }
 
+/// Fluent builder for an in-memory [`SchemaDef`], taking the same field
+/// declarations as [`AAMBuilder::schema`].
+///
+/// # Example
+/// ```
+/// use aam_rs::aaml::AAML;
+/// use aam_rs::builder::{SchemaDefBuilder, SchemaField};
+///
+/// let mut cfg = AAML::new();
+/// SchemaDefBuilder::new("Server")
+///     .required("host", "string")
+///     .optional("debug", "bool")
+///     .field(SchemaField::required("retries", "i32").with_default("3"))
+///     .register(&mut cfg)
+///     .unwrap();
+///
+/// assert!(cfg.get_schema("Server").unwrap().is_optional("debug"));
+/// assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "3");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaDefBuilder {
+    name: String,
+    schema: SchemaDef,
+}
+
+impl SchemaDefBuilder {
+    /// Starts an empty schema that will be registered as `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            schema: SchemaDef::default(),
+        }
+    }
+
+    /// Adds a **required** field.
+    pub fn required(self, name: impl Into<String>, type_name: impl Into<String>) -> Self {
+        self.field(SchemaField::required(name, type_name))
+    }
+
+    /// Adds an **optional** field.
+    pub fn optional(self, name: impl Into<String>, type_name: impl Into<String>) -> Self {
+        self.field(SchemaField::optional(name, type_name))
+    }
+
+    /// Adds a field declared with [`SchemaField`]. A later field with the same
+    /// name replaces the earlier one.
+    pub fn field(mut self, field: SchemaField) -> Self {
+        let SchemaField { name, type_name, optional, default, message } = field;
+        self.schema.optional_fields.remove(&name);
+        self.schema.defaults.remove(&name);
+        self.schema.messages.remove(&name);
+        if optional {
+            self.schema.optional_fields.insert(name.clone());
+        }
+        if let Some(value) = default {
+            self.schema.defaults.insert(name.clone(), value);
+        }
+        if let Some(message) = message {
+            self.schema.messages.insert(name.clone(), message);
+        }
+        self.schema.fields.insert(name, type_name);
+        self
+    }
+
+    /// Makes inline objects of this schema reject undeclared keys
+    /// (the `deny_extra_objects` flag).
+    pub fn deny_extra_objects(mut self) -> Self {
+        self.schema.deny_extra_objects = true;
+        self
+    }
+
+    /// Closes the schema (the `strict` flag / `Name!` suffix).
+    pub fn strict(mut self) -> Self {
+        self.schema.strict = true;
+        self
+    }
+
+    /// Sets the format version (the `vN` flag).
+    pub fn version(mut self, version: u32) -> Self {
+        self.schema.version = Some(version);
+        self
+    }
+
+    /// Returns the name the schema will be registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the finished definition, ready for
+    /// [`AAML::register_schema`](crate::aaml::AAML::register_schema).
+    pub fn build(self) -> SchemaDef {
+        self.schema
+    }
+
+    /// Registers the schema in `aaml` under its name.
+    ///
+    /// # Errors
+    /// Same as [`AAML::register_schema`](crate::aaml::AAML::register_schema).
+    pub fn register(self, aaml: &mut AAML) -> Result<(), AamlError> {
+        aaml.register_schema(&self.name, self.schema)
+    }
+}
+
 /// Accumulates AAML source lines and can flush them to a file or a `String`.
 ///
 /// # Example
@@ -147,6 +294,43 @@ impl AAMBuilder {
         self
     }
 
+    /// Appends a `key = { ... }` inline object with one `field = value` entry
+    /// per indented line, which reads better than a long single-line object.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    /// use aam_rs::builder::AAMBuilder;
+    ///
+    /// let mut b = AAMBuilder::new();
+    /// b.add_object_multiline("server", [("host", "localhost"), ("port", "8080")]);
+    /// let out = b.build();
+    /// assert!(out.contains("    host = localhost"));
+    /// let cfg = AAML::parse(&out).unwrap();
+    /// assert_eq!(cfg.find_obj("server").unwrap().as_str(), "{ host = localhost, port = 8080 }");
+    /// ```
+    pub fn add_object_multiline<K, V>(
+        &mut self,
+        key: &str,
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.push_sep();
+        self.buffer.push_str(key);
+        self.buffer.push_str(" = {");
+        for (field, value) in entries {
+            self.buffer.push_str("\n    ");
+            self.buffer.push_str(field.as_ref());
+            self.buffer.push_str(" = ");
+            self.buffer.push_str(value.as_ref());
+        }
+        self.buffer.push_str("\n}");
+        self
+    }
+
     // ── Comments ──────────────────────────────────────────────────────────────
 
     /// Appends a `# text` comment line.
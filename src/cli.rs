@@ -0,0 +1,149 @@
+//! Command-line flags generated from registered schemas (`cli` feature).
+//!
+//! [`AAML::schema_to_command`] turns a `@schema` into a [`clap::Command`] with one
+//! `--flag` per field, so an application can expose its configuration surface on
+//! the command line without declaring every option twice.
+//! [`AAML::merge_arg_matches`] writes the parsed flags back into the document.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let mut cfg = AAML::parse("@schema Server { host: string, max_conn*: i32 }").unwrap();
+//! let cmd = cfg.schema_to_command("Server").unwrap();
+//! let matches = cmd.get_matches_from(["app", "--host", "example.org", "--max-conn", "64"]);
+//!
+//! cfg.merge_arg_matches("Server", &matches).unwrap();
+//! assert_eq!(cfg.find_obj("max_conn").unwrap().as_str(), "64");
+//! ```
+
+use crate::aaml::AAML;
+use crate::error::AamlError;
+use crate::types::primitive_type::PrimitiveType;
+use crate::types::{Type, resolve_builtin};
+use clap::{Arg, ArgMatches, Command};
+
+/// How a flag value is checked by clap before it reaches the document.
+///
+/// Built-in types are validated exactly. Custom `@type` aliases are checked
+/// against their base primitive; nested schemas are passed through and only
+/// validated when the matches are merged back via [`AAML::merge_arg_matches`].
+#[derive(Clone)]
+enum FlagCheck {
+    Builtin(String),
+    Primitive(PrimitiveType),
+    Any,
+}
+
+impl FlagCheck {
+    fn for_type(aaml: &AAML, type_name: &str) -> FlagCheck {
+        let type_name = aaml.resolve_alias(type_name);
+        if let Some(type_def) = aaml.get_type(type_name) {
+            return FlagCheck::Primitive(type_def.base_type());
+        }
+        if aaml.get_schema(type_name).is_some() {
+            return FlagCheck::Any;
+        }
+        match resolve_builtin(type_name) {
+            Ok(_) => FlagCheck::Builtin(type_name.to_string()),
+            Err(_) => FlagCheck::Any,
+        }
+    }
+
+    /// `true` when the flag takes a boolean, so it may be passed without a value.
+    fn is_bool(&self) -> bool {
+        match self {
+            FlagCheck::Builtin(path) => path == "bool" || path == "bool_relaxed",
+            FlagCheck::Primitive(primitive) => {
+                matches!(primitive, PrimitiveType::Bool | PrimitiveType::BoolRelaxed)
+            }
+            FlagCheck::Any => false,
+        }
+    }
+
+    fn check(&self, value: &str) -> Result<String, String> {
+        let result = match self {
+            FlagCheck::Builtin(path) => resolve_builtin(path).and_then(|t| t.validate(value)),
+            FlagCheck::Primitive(primitive) => primitive.validate(value),
+            FlagCheck::Any => Ok(()),
+        };
+        result.map(|_| value.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+/// Converts a schema field name into a conventional long flag (`max_conn` → `max-conn`).
+fn flag_name(field: &str) -> String {
+    field.replace('_', "-")
+}
+
+impl AAML {
+    /// Builds a [`clap::Command`] named after the schema with one flag per field.
+    ///
+    /// - Each field `name` becomes `--name` (underscores turn into dashes).
+    /// - Values are checked by the field's declared type before clap accepts them.
+    /// - Required fields are required flags; optional (`*`) and defaulted fields
+    ///   are optional.
+    /// - `bool` fields may be passed bare (`--debug` means `--debug true`).
+    ///
+    /// Flags are sorted by field name so `--help` output is stable.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if no schema named `name` is registered.
+    pub fn schema_to_command(&self, name: &str) -> Result<Command, AamlError> {
+        let schema = self
+            .get_schema(name)
+            .ok_or_else(|| AamlError::NotFound(format!("Schema '{name}' not found")))?;
+
+        let mut fields: Vec<(&String, &String)> = schema.fields.iter().collect();
+        fields.sort();
+
+        let mut command = Command::new(name.to_string());
+        for (field, type_name) in fields {
+            let check = FlagCheck::for_type(self, type_name);
+            let bare_bool = check.is_bool();
+            let mut arg = Arg::new(field.clone())
+                .long(flag_name(field))
+                .value_name(type_name.clone())
+                .required(schema.is_required(field))
+                .value_parser(move |v: &str| check.check(v));
+
+            if bare_bool {
+                arg = arg.num_args(0..=1).default_missing_value("true");
+            }
+            command = command.arg(arg);
+        }
+        Ok(command)
+    }
+
+    /// Writes every flag of schema `name` present in `matches` into the
+    /// document as `field = value`.
+    ///
+    /// Arguments that are not fields of the schema, or that hold something
+    /// other than a string (e.g. flags the application added itself), are
+    /// skipped. Values go through the same schema validation as parsed
+    /// assignments, so nested-schema fields and custom types are fully
+    /// checked here.
+    ///
+    /// # Errors
+    /// - [`AamlError::NotFound`] if no schema named `name` is registered.
+    /// - Any validation error for a field value.
+    pub fn merge_arg_matches(&mut self, name: &str, matches: &ArgMatches) -> Result<(), AamlError> {
+        let schema = self
+            .get_schema(name)
+            .ok_or_else(|| AamlError::NotFound(format!("Schema '{name}' not found")))?;
+
+        let values: Vec<(String, String)> = matches
+            .ids()
+            .filter(|id| schema.fields.contains_key(id.as_str()))
+            .filter_map(|id| {
+                let value = matches.try_get_one::<String>(id.as_str()).ok()??;
+                Some((id.as_str().to_string(), value.clone()))
+            })
+            .collect();
+
+        for (key, value) in values {
+            self.insert_validated(&key, &value)?;
+        }
+        Ok(())
+    }
+}
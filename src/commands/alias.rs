@@ -0,0 +1,53 @@
+//! `@alias` directive — keeps an old key name working after a rename.
+//!
+//! # Syntax
+//! ```text
+//! @alias hostname = host
+//! ```
+//!
+//! # Semantics
+//! After the directive, `hostname` stands for `host`: an assignment to
+//! `hostname` stores `host` (and is validated against any schema declaring
+//! `host`), while [`AAML::find_obj`], [`AAML::find_deep`] and `${hostname}`
+//! references read the value of `host`. A record passed to
+//! [`AAML::apply_schema`] may also provide a field under an alias.
+//!
+//! A value already assigned to the alias moves to the new key unless that key
+//! has its own value. Aliases may chain (`@alias host = server` later
+//! redirects `hostname` too), but may not form a cycle. Like `@require`, both
+//! names are written in full regardless of any open `@namespace`.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::commands::namespace::is_key_path;
+use crate::error::AamlError;
+
+/// Command handler for the `@alias` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AliasCommand;
+
+impl Command for AliasCommand {
+    fn name(&self) -> &str {
+        "alias"
+    }
+
+    /// Makes the key left of `=` in `args` stand for the key on its right.
+    ///
+    /// # Errors
+    /// - [`AamlError::DirectiveError`] if `args` is not `old = new`, or the
+    ///   alias would refer to itself.
+    /// - Any schema error from moving an already assigned value to the new key.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let error = |details: String| AamlError::DirectiveError("alias".into(), details, None);
+        let (alias, target) = args
+            .split_once('=')
+            .map(|(alias, target)| (alias.trim(), target.trim()))
+            .filter(|(alias, target)| is_key_path(alias) && is_key_path(target))
+            .ok_or_else(|| error(format!("Expected '@alias old_key = new_key', got '@alias {}'", args.trim())))?;
+        let target = aaml.canonical_key(target).to_string();
+        if target == aaml.fold_case(alias) {
+            return Err(error(format!("Alias '{alias}' would refer to itself")));
+        }
+        aaml.add_alias(alias, &target)
+    }
+}
@@ -0,0 +1,114 @@
+//! `@assert_os` and `@assert_min_version` — host-environment assertions.
+//!
+//! # Syntax
+//! ```text
+//! @assert_os linux | macos
+//! @assert_min_version crate=1.2
+//! ```
+//!
+//! # Semantics
+//! `@assert_os` fails unless the running operating system (as reported by
+//! [`std::env::consts::OS`], e.g. `linux`, `windows`, `macos`) is one of the
+//! `|`-separated names. `@assert_min_version crate=X.Y.Z` fails unless the
+//! `aam-rs` library parsing the document is at least version `X.Y.Z`; missing
+//! components count as `0`. Both stop parsing at the directive, so a config
+//! written for one platform is never partially applied on another.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@assert_os` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssertOsCommand;
+
+impl Command for AssertOsCommand {
+    fn name(&self) -> &str {
+        "assert_os"
+    }
+
+    /// Checks the current OS against the allowed names in `args`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the list is empty or the current OS is
+    /// not in it.
+    fn execute(&self, _aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        check_os(args, std::env::consts::OS)
+    }
+}
+
+/// Command handler for the `@assert_min_version` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssertMinVersionCommand;
+
+impl Command for AssertMinVersionCommand {
+    fn name(&self) -> &str {
+        "assert_min_version"
+    }
+
+    /// Checks the library version against the `crate=X.Y.Z` requirement in `args`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the requirement is malformed, names a
+    /// component other than `crate`, or is newer than the running version.
+    fn execute(&self, _aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        check_min_version(args, env!("CARGO_PKG_VERSION"))
+    }
+}
+
+/// Fails unless `current` is one of the `|`-separated names in `args`.
+fn check_os(args: &str, current: &str) -> Result<(), AamlError> {
+    let allowed: Vec<&str> = args.split('|').map(str::trim).collect();
+    if allowed.iter().any(|os| os.is_empty()) {
+        return Err(AamlError::DirectiveError(
+            "assert_os".into(),
+            format!("Expected '@assert_os name | name ...', got '@assert_os {}'", args.trim()),
+            None,
+        ));
+    }
+    if allowed.iter().any(|os| os.eq_ignore_ascii_case(current)) {
+        return Ok(());
+    }
+    Err(AamlError::DirectiveError(
+        "assert_os".into(),
+        format!("Config requires OS {}, running on {current}", allowed.join(" | ")),
+        None,
+    ))
+}
+
+/// Fails unless `current` is at least the version required by `crate=X.Y.Z`.
+fn check_min_version(args: &str, current: &str) -> Result<(), AamlError> {
+    let err = |msg: String| AamlError::DirectiveError("assert_min_version".into(), msg, None);
+    let (component, required) = args
+        .split_once('=')
+        .map(|(c, v)| (c.trim(), v.trim()))
+        .ok_or_else(|| err(format!("Expected 'crate=X.Y.Z', got '{}'", args.trim())))?;
+    if component != "crate" {
+        return Err(err(format!("Unknown component '{component}', expected 'crate'")));
+    }
+    let wanted = parse_version(required)
+        .ok_or_else(|| err(format!("Invalid version '{required}'")))?;
+    let running = parse_version(current)
+        .ok_or_else(|| err(format!("Invalid running version '{current}'")))?;
+    if running < wanted {
+        return Err(err(format!(
+            "Config requires aam-rs {required} or newer, running {current}"
+        )));
+    }
+    Ok(())
+}
+
+/// Parses `X`, `X.Y` or `X.Y.Z` (ignoring any `-pre` / `+build` suffix) into
+/// a comparable triple.
+fn parse_version(s: &str) -> Option<[u64; 3]> {
+    let core = s.split(['-', '+']).next()?;
+    let mut out = [0; 3];
+    let mut parts = core.split('.');
+    for slot in &mut out {
+        match parts.next() {
+            Some(p) => *slot = p.parse().ok()?,
+            None => break,
+        }
+    }
+    parts.next().is_none().then_some(out)
+}
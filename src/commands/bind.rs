@@ -0,0 +1,69 @@
+//! `@bind` directive — binds a single key to a schema.
+//!
+//! # Syntax
+//! ```text
+//! @bind key SchemaName
+//! ```
+//!
+//! # Semantics
+//! The value of `key` must be an inline object that is a valid instance of
+//! `SchemaName`, and each dotted sub-key `key.field` is validated against the
+//! type `SchemaName` declares for `field`. Bound keys are not matched against
+//! other schemas, so two keys can hold instances of different schemas even
+//! when those schemas share field names:
+//!
+//! ```text
+//! @schema Player { name: string, hp: i32 }
+//! @schema Enemy { name: string, damage: f64 }
+//! @bind player1 Player
+//! @bind boss Enemy
+//! player1 = { name = Ann, hp = 100 }
+//! boss.name = Dragon
+//! boss.damage = 12.5
+//! ```
+//!
+//! The schema must be registered before the directive. Values already assigned
+//! to the key are validated when the binding is made, and a later `@bind` of
+//! the same key replaces the earlier one. Sub-keys a `strict` schema does not
+//! declare are rejected.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@bind` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindCommand;
+
+impl Command for BindCommand {
+    fn name(&self) -> &str {
+        "bind"
+    }
+
+    /// Binds the key named in `args` to the schema that follows it.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if `args` is not exactly `key SchemaName`
+    /// or the schema is not registered, and
+    /// [`AamlError::SchemaValidationError`] if an already assigned value does
+    /// not satisfy the schema.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let parts: Vec<&str> = args.split_whitespace().collect();
+        let [key, schema] = parts[..] else {
+            return Err(AamlError::DirectiveError(
+                "bind".into(),
+                format!("Expected '@bind key SchemaName', got '@bind {}'", args.trim()),
+                None,
+            ));
+        };
+        if aaml.get_schema(schema).is_none() {
+            return Err(AamlError::DirectiveError(
+                "bind".into(),
+                format!("Unknown schema '{schema}'"),
+                None,
+            ));
+        }
+        aaml.bind_key(key.to_string(), schema.to_string());
+        aaml.check_bound_key(key)
+    }
+}
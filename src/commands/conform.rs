@@ -0,0 +1,58 @@
+//! `@conform` directive — binds the document to a single schema.
+//!
+//! # Syntax
+//! ```text
+//! @conform SchemaName
+//! ```
+//!
+//! # Semantics
+//! Without `@conform`, every assignment is checked against whichever registered
+//! schema declares its key. After `@conform Server`, assignments are checked
+//! only against the fields of `Server`; keys declared solely by other schemas
+//! (for example schemas used as nested object types) are no longer validated
+//! as top-level fields. The schema must be registered before the directive.
+//! A later `@conform` replaces the earlier binding.
+//!
+//! Binding to a `strict` schema (`@schema Name! { ... }`) also rejects every
+//! assignment of an undeclared key, including keys assigned before the
+//! directive.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@conform` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConformCommand;
+
+impl Command for ConformCommand {
+    fn name(&self) -> &str {
+        "conform"
+    }
+
+    /// Binds `aaml` to the schema named in `args`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the name is empty or no schema with
+    /// that name is registered, and [`AamlError::SchemaValidationError`] if the
+    /// schema is strict and an undeclared key is already assigned.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let name = args.trim();
+        if name.is_empty() {
+            return Err(AamlError::DirectiveError(
+                "conform".into(),
+                "Schema name is empty".into(),
+                None,
+            ));
+        }
+        if aaml.get_schema(name).is_none() {
+            return Err(AamlError::DirectiveError(
+                "conform".into(),
+                format!("Unknown schema '{name}'"),
+                None,
+            ));
+        }
+        aaml.set_conformed_schema(name.to_string());
+        aaml.check_all_declared()
+    }
+}
@@ -0,0 +1,54 @@
+//! `@define` directive — declares a named constant.
+//!
+//! # Syntax
+//! ```text
+//! @define MAX_PLAYERS 64
+//! @define GREETING "Hello, world"
+//! ```
+//!
+//! # Semantics
+//! Every later occurrence of the constant's name as a whole word in an
+//! unquoted value is replaced by its value while the document is parsed, so
+//! `lobby_size = MAX_PLAYERS` stores `64`. Words inside quoted strings and
+//! `${...}` references are left alone. The value of a constant may use
+//! constants defined before it. A value that fails validation after
+//! substitution is reported together with the constants it came from.
+//! Redefining a constant is an error.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@define` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefineCommand;
+
+impl Command for DefineCommand {
+    fn name(&self) -> &str {
+        "define"
+    }
+
+    /// Declares the constant named by the first word of `args`, with the rest
+    /// of `args` as its value.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the name is missing or not an
+    /// identifier, the value is missing, or the constant is already defined.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let error = |details: String| AamlError::DirectiveError("define".into(), details, None);
+        let (name, value) = args
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error(format!("Expected '@define NAME value', got '@define {}'", args.trim())))?;
+        let is_identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(error(format!("Invalid constant name '{name}'")));
+        }
+        if aaml.constant(name).is_some() {
+            return Err(error(format!("Constant '{name}' is already defined")));
+        }
+        aaml.define_constant(name, AAML::unwrap_quotes(value));
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+//! `@deprecated` directive — flags keys that should no longer be assigned.
+//!
+//! # Syntax
+//! ```text
+//! @deprecated old_timeout "use timeout_ms instead"
+//! @deprecated legacy_mode
+//! ```
+//!
+//! # Semantics
+//! Every later assignment to the key, including one in an `@import`ed file,
+//! records a [`Warning`](crate::aaml::report::Warning) carrying the optional
+//! hint, available from [`AAML::warnings`]. The value is still stored, so
+//! files can move to the new key gradually. A key already assigned when the
+//! directive runs is reported at that point.
+//!
+//! With [`AAML::with_strict_deprecations`], assigning a deprecated key is an
+//! error instead. Like `@require`, the key is written in full regardless of
+//! any open `@namespace`.
+
+use crate::aaml::AAML;
+use crate::aaml::parsing::unwrap_quotes;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@deprecated` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeprecatedCommand;
+
+impl Command for DeprecatedCommand {
+    fn name(&self) -> &str {
+        "deprecated"
+    }
+
+    /// Marks the key that starts `args` as deprecated, with the rest of
+    /// `args` (quotes removed) as its hint.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if no key is given, or in strict mode
+    /// if the key is already assigned.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let args = args.trim();
+        let (key, hint) = match args.split_once(char::is_whitespace) {
+            Some((key, hint)) => (key, Some(unwrap_quotes(hint.trim()))),
+            None => (args, None),
+        };
+        if key.is_empty() {
+            return Err(AamlError::DirectiveError(
+                "deprecated".into(),
+                "Expected '@deprecated key' or '@deprecated key \"hint\"'".into(),
+                None,
+            ));
+        }
+        aaml.deprecate_key(key, hint.filter(|hint| !hint.is_empty()));
+        if aaml.get_map_mut().contains_key(key) {
+            aaml.check_deprecated(key, 0)
+                .map_err(|msg| AamlError::DirectiveError("deprecated".into(), msg, None))?;
+        }
+        Ok(())
+    }
+}
@@ -6,6 +6,8 @@
 //! @derive "path/to/base.aam"
 //! @derive path/to/base.aam::Schema1
 //! @derive path/to/base.aam::Schema1::Schema2
+//! @derive mem://base
+//! @derive common.aam, staging.aam::Server
 //! ```
 //!
 //! # Semantics
@@ -13,27 +15,63 @@
 //! - Child values take precedence: existing keys are **never** overwritten.
 //! - Schema definitions follow the same rule: a child schema beats a base schema
 //!   with the same name.
+//! - Several comma-separated bases are layered left to right: a later base
+//!   overrides keys and schemas of an earlier one, and the child still
+//!   overrides them all. Each base may carry its own `::Schema` selectors.
+//! - A schema sealed with `@sealed` in a base cannot be shadowed by the
+//!   child or by a later base.
+//! - With [`AAML::with_strict_overrides`], a child key that shadows a base key
+//!   must be marked with `@override`.
 //! - After the merge, all schemas that are now in scope are checked for
 //!   completeness — every declared field must have a value assigned somewhere
 //!   in the resulting document. Missing fields produce a
 //!   [`AamlError::SchemaValidationError`].
 //!   Optional fields (declared with `*`) are ignored during completeness check.
+//! - A relative path is resolved against the directory of the file containing
+//!   the directive (see [`AAML::with_source_dir`] for content parsed from a string).
+//! - `mem://name` reads a source registered with [`AAML::register_source`]
+//!   instead of a file.
+//! - The base file counts against the
+//!   [`ImportLimits`](crate::aaml::limits::ImportLimits) of the deriving document.
 
 use crate::aaml::AAML;
-use crate::commands::Command;
+use crate::commands::{Command, ExecutionContext};
+use crate::commands::schema::SchemaDef;
 use crate::error::AamlError;
+use std::collections::hash_map::Entry;
 
 /// Command handler for the `@derive` directive.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeriveCommand;
 
-/// Splits a raw `@derive` argument into `(file_path, schema_selectors)`.
+/// Splits a raw `@derive` argument into its comma-separated bases, ignoring
+/// commas inside quoted paths.
+pub(crate) fn split_bases(raw: &str) -> Vec<&str> {
+    let mut bases = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in raw.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, ',') => {
+                bases.push(raw[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    bases.push(raw[start..].trim());
+    bases
+}
+
+/// Splits one `@derive` base into `(file_path, schema_selectors)`.
 ///
 /// Supported forms:
 /// - `base.aam` → `("base.aam", [])`
 /// - `base.aam::Foo::Bar` → `("base.aam", ["Foo", "Bar"])`
 /// - `"base.aam"::Foo` → `("base.aam", ["Foo"])`
-fn parse_derive_arg(raw: &str) -> (&str, Vec<&str>) {
+pub(crate) fn parse_derive_arg(raw: &str) -> (&str, Vec<&str>) {
     let (path_raw, rest) = if raw.starts_with('"') || raw.starts_with('\'') {
         let q = raw.chars().next().unwrap();
         match raw[1..].find(q) {
@@ -65,56 +103,89 @@ impl Command for DeriveCommand {
         "derive"
     }
 
-    /// Loads the base file, merges its schemas and key-value pairs into `aaml`
-    /// (child entries win on conflict), then verifies that every required field
-    /// declared in any active schema is present in the final map.
+    /// Loads each base file, merges their schemas and key-value pairs into
+    /// `aaml` (later bases beat earlier ones, and child entries beat both),
+    /// then verifies that every required field declared in any active schema
+    /// is present in the final map.
     ///
-    /// If schema selectors (`::SchemaName`) are provided, only the named
-    /// schemas are imported from the base file.
+    /// If schema selectors (`::SchemaName`) are provided for a base, only the
+    /// named schemas are imported from it.
     ///
     /// # Errors
-    /// - [`AamlError::DirectiveError`] — path argument is missing or a
-    ///   requested schema does not exist in the base file.
-    /// - [`AamlError::IoError`] — base file cannot be read.
-    /// - Any parse error from the base file.
+    /// - [`AamlError::DirectiveError`] — a path is missing, a requested
+    ///   schema does not exist in its base file, an import limit is
+    ///   exceeded, a sealed base schema would be shadowed, or (in strict
+    ///   mode) a child key shadows a base key without `@override`.
+    /// - [`AamlError::IoError`] — a base file cannot be read.
+    /// - Any parse error from a base file.
     /// - [`AamlError::SchemaValidationError`] — after the merge a required
     ///   schema field has no value assigned.
-    fn execute(&self, aaml: &mut AAML, args: &str) -> Result<(), AamlError> {
-        let raw = args.trim();
-        if raw.is_empty() {
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let bases = split_bases(args.trim());
+        if bases.iter().any(|base| base.is_empty()) {
             return Err(AamlError::DirectiveError(
                 "derive".into(),
                 "Missing file path".into(),
+                None,
             ));
         }
 
         // Snapshot child-owned schema names BEFORE merging base schemas.
         let child_schema_names: Vec<String> = aaml.get_schemas_mut().keys().cloned().collect();
 
-        let (path, selectors) = parse_derive_arg(raw);
-        let mut base = AAML::load(path)?;
+        let mut loaded = Vec::with_capacity(bases.len());
+        for raw in bases {
+            let (path, selectors) = parse_derive_arg(raw);
+            let resolved = aaml.resolve_path(path);
+            let content = aaml.read_import("derive", &resolved)?;
+            let mut base = aaml.nested_instance();
+            base.in_file_dir(&resolved, |base| base.merge_content(&content))?;
+            base.validate_required_keys()?;
+            base.validate_rules()?;
+            aaml.absorb_usage(&base);
+            loaded.push((path, selectors, base));
+        }
 
-        if selectors.is_empty() {
-            for (name, schema) in base.get_schemas_mut().drain() {
-                aaml.get_schemas_mut().entry(name).or_insert(schema);
-            }
-        } else {
-            for selector in &selectors {
-                let schema = base.get_schemas_mut().remove(*selector).ok_or_else(|| {
-                    AamlError::DirectiveError(
-                        "derive".into(),
-                        format!("Schema '{selector}' not found in '{path}'"),
-                    )
-                })?;
-                aaml.get_schemas_mut()
-                    .entry(selector.to_string())
-                    .or_insert(schema);
+        // In strict mode, keys the child already assigned must be marked.
+        for (_, _, base) in &mut loaded {
+            for key in base.get_map_mut().keys() {
+                let key = aaml.canonical_key(key).into_owned();
+                if aaml.get_map_mut().contains_key(key.as_str()) {
+                    aaml.check_override(&key, true)
+                        .map_err(|msg| AamlError::DirectiveError("derive".into(), msg, None))?;
+                }
             }
         }
 
-        // Merge key-value pairs — child wins on conflict.
-        for (k, v) in base.get_map_mut().drain() {
-            aaml.get_map_mut().entry(k).or_insert(v);
+        // Existing entries are never overwritten, so merging the rightmost
+        // base first gives it precedence over the bases to its left.
+        for (path, selectors, mut base) in loaded.into_iter().rev() {
+            if selectors.is_empty() {
+                for (name, schema) in base.get_schemas_mut().drain() {
+                    merge_schema(aaml, name, schema, path)?;
+                }
+            } else {
+                for selector in &selectors {
+                    let schema = base.get_schemas_mut().remove(*selector).ok_or_else(|| {
+                        AamlError::DirectiveError(
+                            "derive".into(),
+                            format!("Schema '{selector}' not found in '{path}'"),
+                            None,
+                        )
+                    })?;
+                    merge_schema(aaml, selector.to_string(), schema, path)?;
+                }
+            }
+
+            // Merge key-value pairs under their canonical names — child wins
+            // on conflict.
+            for (k, v) in base.get_map_mut().drain() {
+                let k = aaml.canonical_key(&k).into_owned();
+                if !aaml.get_map_mut().contains_key(k.as_str()) {
+                    aaml.mark_inherited(&k);
+                    aaml.get_map_mut().insert(k.into(), v);
+                }
+            }
         }
 
         // Validate completeness only for child-owned schemas.
@@ -124,3 +195,22 @@ impl Command for DeriveCommand {
         Ok(())
     }
 }
+
+/// Adds a base schema unless one with the same name is already in scope.
+/// A sealed base schema may not be shadowed.
+fn merge_schema(aaml: &mut AAML, name: String, schema: SchemaDef, path: &str) -> Result<(), AamlError> {
+    match aaml.get_schemas_mut().entry(name) {
+        Entry::Vacant(slot) => {
+            slot.insert(schema);
+        }
+        Entry::Occupied(existing) if schema.sealed => {
+            return Err(AamlError::DirectiveError(
+                "derive".into(),
+                format!("Schema '{}' is sealed in '{path}' and cannot be redefined", existing.key()),
+                None,
+            ));
+        }
+        Entry::Occupied(_) => {}
+    }
+    Ok(())
+}
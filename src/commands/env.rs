@@ -0,0 +1,60 @@
+//! `@env` directive — imports environment variables as keys.
+//!
+//! # Syntax
+//! ```text
+//! @env APP_
+//! ```
+//!
+//! # Semantics
+//! Every environment variable whose name starts with the prefix is assigned
+//! under the rest of its name, lowercased: `APP_DB_URL=postgres://db` becomes
+//! `db_url = postgres://db`. Variables are imported in name order and
+//! validated like ordinary assignments, so schema fields still apply.
+//! Assignments after the directive override imported values; the directive
+//! overrides assignments before it. Variables that are not valid UTF-8, or
+//! whose name is exactly the prefix, are skipped.
+//!
+//! To read a single variable, use `${VAR}` in a value instead:
+//! ```text
+//! db_url = ${DATABASE_URL:-postgres://localhost/dev}
+//! ```
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@env` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvCommand;
+
+impl Command for EnvCommand {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    /// Assigns every environment variable starting with the prefix in `args`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the prefix is empty or contains
+    /// whitespace, or the error of the first imported value a schema rejects.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let prefix = args.trim();
+        if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+            return Err(AamlError::DirectiveError(
+                "env".into(),
+                format!("Expected a single variable prefix, got '{prefix}'"),
+                None,
+            ));
+        }
+        let mut vars: Vec<(String, String)> = std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .filter_map(|(name, value)| {
+                let key = name.strip_prefix(prefix).filter(|k| !k.is_empty())?;
+                Some((key.to_lowercase(), value))
+            })
+            .collect();
+        vars.sort();
+        vars.iter()
+            .try_for_each(|(key, value)| aaml.insert_validated(key, value))
+    }
+}
@@ -1,18 +1,43 @@
-//! `@import` directive — merges another `.aam` file into the current document.
+//! `@import` and `@import_optional` directives — merge another `.aam` file
+//! into the current document.
 //!
 //! # Syntax
 //! ```text
 //! @import path/to/file.aam
 //! @import "path/to/file.aam"
+//! @import_optional local_overrides.aam
+//! @import db.aam as database
 //! ```
 //!
 //! # Semantics
 //! Unlike `@derive`, `@import` uses `merge_content` which means **later** values
 //! overwrite earlier ones. If the same key appears in both the current document
 //! and the imported file, the imported value **wins** (last-write semantics).
+//!
+//! A relative path is resolved against the directory of the file containing
+//! the directive, so nested imports work wherever the program is started from.
+//! Content parsed from a string resolves against the working directory, or the
+//! directory given to [`AAML::with_source_dir`].
+//!
+//! `${name}` references in the imported file are resolved together with those
+//! of the importing document, so either may refer to keys of the other.
+//!
+//! With `as prefix`, every key assigned by the imported file (and the files it
+//! imports) is stored under `prefix.`, so two files that both define
+//! `host`/`port` can be composed side by side. Inside the file, `${port}`
+//! still finds the file's own `port` first.
+//!
+//! Imports count against the instance's
+//! [`ImportLimits`](crate::aaml::limits::ImportLimits), which stop runaway
+//! nesting (including a file that imports itself) and oversized inputs.
+//!
+//! `@import_optional` behaves like `@import` but silently does nothing when
+//! the file does not exist, for uncommitted per-developer overrides. Any other
+//! error, including a parse error inside an existing file, is still reported.
 
 use crate::aaml::AAML;
-use crate::commands::Command;
+use crate::commands::{Command, ExecutionContext};
+use crate::commands::namespace::is_key_path;
 use crate::error::AamlError;
 
 /// Command handler for the `@import` directive.
@@ -29,18 +54,79 @@ impl Command for ImportCommand {
     /// # Errors
     /// - [`AamlError::ParseError`] — path argument is empty.
     /// - [`AamlError::IoError`] — file cannot be read.
+    /// - [`AamlError::DirectiveError`] — an import limit is exceeded.
     /// - Any parse error from the imported file.
-    fn execute(&self, aaml: &mut AAML, args: &str) -> Result<(), AamlError> {
-        let raw_path = args.trim();
-        if raw_path.is_empty() {
-            return Err(AamlError::ParseError {
-                line: 0,
-                content: args.to_string(),
-                details: "Import path cannot be empty".to_string(),
-            });
+    fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+        import(aaml, args, ctx, false)
+    }
+}
+
+/// Command handler for the `@import_optional` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportOptionalCommand;
+
+impl Command for ImportOptionalCommand {
+    fn name(&self) -> &str {
+        "import_optional"
+    }
+
+    /// Like `@import`, but succeeds without merging anything when the file
+    /// does not exist.
+    ///
+    /// # Errors
+    /// The same as `@import`, except for a missing file.
+    fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+        import(aaml, args, ctx, true)
+    }
+}
+
+/// Merges the file named by `args` into `aaml`. With `optional`, a missing
+/// file is skipped.
+fn import(aaml: &mut AAML, args: &str, ctx: &ExecutionContext, optional: bool) -> Result<(), AamlError> {
+    let directive = if optional { "import_optional" } else { "import" };
+    let (raw_path, prefix) = split_prefix(args);
+    if raw_path.is_empty() {
+        return Err(AamlError::ParseError {
+            line: ctx.line(),
+            content: args.to_string(),
+            details: "Import path cannot be empty".to_string(),
+        });
+    }
+
+    if let Some(prefix) = prefix.filter(|p| !is_key_path(p)) {
+        return Err(AamlError::DirectiveError(
+            directive.into(),
+            format!("Invalid prefix '{prefix}': expected a name such as 'database' or 'net.proxy'"),
+            None,
+        ));
+    }
+
+    let path = aaml.resolve_path(AAML::unwrap_quotes(raw_path));
+    let content = match aaml.read_import(directive, &path) {
+        Ok(content) => content,
+        Err(AamlError::IoError(e)) if optional && e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(());
         }
+        Err(e) => return Err(e),
+    };
+    let merge = |aaml: &mut AAML| aaml.merge_content_with(&content, |_, e| Err(e));
+    aaml.nested(|aaml| {
+        aaml.in_file_dir(&path, |aaml| match prefix {
+            Some(prefix) => aaml.with_key_prefix(prefix, merge),
+            None => merge(aaml),
+        })
+    })?;
+    aaml.run_migrations()
+}
 
-        let path = AAML::unwrap_quotes(raw_path);
-        aaml.merge_file(path)
+/// Splits `path as prefix` into the path and the prefix.
+pub(crate) fn split_prefix(args: &str) -> (&str, Option<&str>) {
+    let args = args.trim();
+    if let Some((head, prefix)) = args.rsplit_once(char::is_whitespace)
+        && let Some(path) = head.trim_end().strip_suffix("as")
+        && path.ends_with(char::is_whitespace)
+    {
+        return (path.trim_end(), Some(prefix));
     }
+    (args, None)
 }
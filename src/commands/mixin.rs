@@ -0,0 +1,56 @@
+//! `@mixin` directive — declares a reusable bundle of schema fields.
+//!
+//! # Syntax
+//! ```text
+//! @mixin Name { field1: type1, field2*: type2, field3: type3 = default, ... }
+//! ```
+//!
+//! The body uses the same field syntax as `@schema`. A mixin is not a schema:
+//! it validates nothing on its own and cannot be used as a field type. Its
+//! fields are copied into any schema that splices it with `..Name`:
+//! ```text
+//! @mixin Timestamps { created_at: time::datetime, updated_at*: time::datetime }
+//! @schema Asset { ..Timestamps, name: string }
+//! ```
+//!
+//! A mixin may itself splice earlier mixins. Fields are copied when the
+//! `@schema` directive runs, so redefining a mixin later does not change
+//! schemas that were already declared.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::commands::schema::{SchemaCommand, splice_mixins, validate_defaults};
+use crate::error::AamlError;
+
+/// Command handler for the `@mixin` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixinCommand;
+
+impl Command for MixinCommand {
+    fn name(&self) -> &str {
+        "mixin"
+    }
+
+    /// Parses the field bundle and registers it under its name, replacing any
+    /// mixin with the same name.
+    ///
+    /// # Errors
+    /// - [`AamlError::DirectiveError`] if the body is malformed, the header has
+    ///   flags, or a spliced mixin is not registered.
+    /// - [`AamlError::SchemaValidationError`] if a default value does not
+    ///   satisfy its field type.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let (name, mut fields, mixins) = SchemaCommand::parse(args)?;
+        if fields.deny_extra_objects || fields.strict || fields.version.is_some() {
+            return Err(AamlError::DirectiveError(
+                "mixin".into(),
+                format!("Mixin '{name}' cannot take schema flags"),
+                None,
+            ));
+        }
+        splice_mixins(aaml, &mut fields, &mixins)?;
+        validate_defaults(aaml, &name, &fields)?;
+        aaml.get_mixins_mut().insert(name, fields);
+        Ok(())
+    }
+}
@@ -1,16 +1,31 @@
 //! Command infrastructure for AAML directives.
 //!
-//! Each directive (`@import`, `@derive`, `@schema`, `@type`) is implemented as
-//! a struct that implements the [`Command`] trait and is registered in
+//! Each directive (`@import`, `@import_optional`, `@derive`, `@schema`, `@mixin`, `@type`, `@conform`,
+//! `@bind`, `@require`, `@rule`, `@assert_os`, `@assert_min_version`, `@env`, `@define`, `@namespace`, `@endnamespace`, `@override`, `@sealed`, `@deprecated`, `@alias`, `@profile`) is implemented as a struct that implements the [`Command`] trait and is registered in
 //! [`AAML::register_default_commands`](crate::aaml::AAML).
 
 use crate::aaml::AAML;
-use crate::error::AamlError;
+use crate::error::{AamlError, SourceLocation};
+use std::path::{Path, PathBuf};
 
 pub mod import;
 pub mod schema;
 pub mod typecm;
 pub mod derive;
+pub mod conform;
+pub mod mixin;
+pub mod bind;
+pub mod require;
+pub mod rule;
+pub mod assert;
+pub mod env;
+pub mod define;
+pub mod namespace;
+pub mod overrides;
+pub mod sealed;
+pub mod deprecated;
+pub mod alias;
+pub mod profile;
 
 /// Trait implemented by every AAML directive handler.
 ///
@@ -23,6 +38,46 @@ pub trait Command: Send + Sync {
     /// Executes the directive with the given argument string.
     ///
     /// `args` contains everything after the directive name on the same line,
-    /// with leading whitespace preserved.
-    fn execute(&self, aaml: &mut AAML, args: &str) -> Result<(), AamlError>;
+    /// with leading whitespace preserved. `ctx` tells where the directive
+    /// appeared; a returned [`AamlError::DirectiveError`] is annotated with
+    /// that location automatically.
+    fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError>;
+}
+
+/// Where a directive is being executed, passed to [`Command::execute`].
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionContext {
+    location: SourceLocation,
+}
+
+impl ExecutionContext {
+    /// Creates a context for a directive at `location`.
+    pub fn new(location: SourceLocation) -> Self {
+        Self { location }
+    }
+
+    /// The file containing the directive, or `None` for content parsed from a string.
+    pub fn file(&self) -> Option<&Path> {
+        self.location.file.as_deref()
+    }
+
+    /// The 1-based line of the directive, or 0 when unknown.
+    pub fn line(&self) -> usize {
+        self.location.line
+    }
+
+    /// The 1-based column of the directive's `@`, or 0 when unknown.
+    pub fn column(&self) -> usize {
+        self.location.column
+    }
+
+    /// The files being merged, outermost first; the last one is [`file`](Self::file).
+    pub fn import_stack(&self) -> &[PathBuf] {
+        &self.location.import_stack
+    }
+
+    /// The complete location, as attached to errors.
+    pub fn location(&self) -> &SourceLocation {
+        &self.location
+    }
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+//! `@namespace` and `@endnamespace` — prefix the keys assigned in between.
+//!
+//! # Syntax
+//! ```text
+//! @namespace database
+//! host = db.local
+//! port = 5432
+//! @endnamespace
+//! ```
+//!
+//! # Semantics
+//! While a namespace is open, every assignment `key = value` stores
+//! `namespace.key`, so the example above sets `database.host` and
+//! `database.port`. A namespace may itself be dotted (`@namespace net.proxy`).
+//! It stays open until `@endnamespace`, the next `@namespace`, or the end of
+//! the content: namespaces do not nest and never leak into or out of an
+//! `@import`ed file. Only assignments are prefixed; keys named by directives
+//! such as `@require` or `@bind` are written in full, while a `${name}`
+//! reference looks inside the namespace before falling back to `name`.
+//!
+//! An INI-style header line `[database]` is shorthand for `@namespace database`
+//! and follows the same rules, so it is closed by `@endnamespace` or the next
+//! header.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@namespace` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceCommand;
+
+impl Command for NamespaceCommand {
+    fn name(&self) -> &str {
+        "namespace"
+    }
+
+    /// Opens the namespace named by `args`, closing any open one.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the name is missing or is not a
+    /// dot-separated list of identifiers.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let name = args.trim();
+        if !is_key_path(name) {
+            return Err(AamlError::DirectiveError(
+                "namespace".into(),
+                format!("Invalid namespace '{name}': expected a name such as 'database' or 'net.proxy'"),
+                None,
+            ));
+        }
+        aaml.set_namespace(Some(name.to_string()));
+        Ok(())
+    }
+}
+
+/// Command handler for the `@endnamespace` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EndNamespaceCommand;
+
+impl Command for EndNamespaceCommand {
+    fn name(&self) -> &str {
+        "endnamespace"
+    }
+
+    /// Closes the open namespace.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if arguments are given or no namespace
+    /// is open.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let error = |details: &str| AamlError::DirectiveError("endnamespace".into(), details.into(), None);
+        if !args.trim().is_empty() {
+            return Err(error("@endnamespace takes no arguments"));
+        }
+        if aaml.namespace().is_none() {
+            return Err(error("@endnamespace without @namespace"));
+        }
+        aaml.set_namespace(None);
+        Ok(())
+    }
+}
+
+/// Returns `true` for a dot-separated list of names usable as a key prefix.
+pub(crate) fn is_key_path(name: &str) -> bool {
+    name.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
@@ -0,0 +1,58 @@
+//! `@override` directive — marks keys that intentionally shadow inherited ones.
+//!
+//! # Syntax
+//! ```text
+//! @override port, host
+//! @override port = 8080
+//! ```
+//!
+//! # Semantics
+//! The first form marks the listed keys; the second marks the key and assigns
+//! it in one line. Keys are relative to the open `@namespace`, like
+//! assignments.
+//!
+//! Markers only matter with [`AAML::with_strict_overrides`]: a key assigned
+//! both by the document and by a `@derive` base is then an error unless it is
+//! marked, whichever of the two comes first. A marker must appear before the
+//! collision it allows — before the `@derive` when the key is assigned
+//! earlier, or before the assignment when the `@derive` comes first.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@override` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverrideCommand;
+
+impl Command for OverrideCommand {
+    fn name(&self) -> &str {
+        "override"
+    }
+
+    /// Marks the comma-separated keys in `args`, or marks and assigns the key
+    /// of a `key = value` argument.
+    ///
+    /// # Errors
+    /// - [`AamlError::DirectiveError`] if no key is given.
+    /// - Any error from the assignment in the `key = value` form.
+    fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let args = args.trim();
+        if let Some((key, _)) = args.split_once('=') {
+            aaml.mark_override(key.trim());
+            return aaml.assign(args, ctx.line());
+        }
+        let keys: Vec<&str> = args.split(',').map(str::trim).collect();
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(AamlError::DirectiveError(
+                "override".into(),
+                format!("Expected '@override key' or '@override key = value', got '@override {args}'"),
+                None,
+            ));
+        }
+        for key in keys {
+            aaml.mark_override(key);
+        }
+        Ok(())
+    }
+}
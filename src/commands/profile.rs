@@ -0,0 +1,63 @@
+//! `@profile` directive — settings that apply only to one deployment profile.
+//!
+//! # Syntax
+//! ```text
+//! log_level = info
+//!
+//! @profile dev {
+//! log_level = debug
+//! @import dev-overrides.aam
+//! }
+//!
+//! @profile staging, prod { replicas = 3 }
+//! ```
+//!
+//! # Semantics
+//! A block applies only when one of the comma-separated profiles it names is
+//! the one selected with [`AAML::with_profile`] or [`AAML::load_with_profile`];
+//! otherwise its lines are skipped entirely, directives included. Without a
+//! selected profile no block applies, so the lines outside blocks act as the
+//! defaults.
+//!
+//! In the multi-line form the first line must end with `{` and the block is
+//! closed by a line holding just `}`; blocks nest with `@if` blocks and must
+//! be closed within the same content. The one-line form holds a single
+//! assignment or directive between the braces.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::commands::namespace::is_key_path;
+use crate::error::AamlError;
+
+/// Command handler for the one-line form of the `@profile` directive. The
+/// multi-line form is handled while lines are read, like `@if`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileCommand;
+
+impl Command for ProfileCommand {
+    fn name(&self) -> &str {
+        "profile"
+    }
+
+    /// Applies the statement between the braces of `args` when one of the
+    /// profiles named before them is selected.
+    ///
+    /// # Errors
+    /// - [`AamlError::DirectiveError`] if `args` is not `names { statement }`
+    ///   or a profile name is invalid.
+    /// - Any error from the statement.
+    fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let error = |details: String| AamlError::DirectiveError("profile".into(), details, None);
+        let (names, body) = args
+            .split_once('{')
+            .and_then(|(names, rest)| Some((names.trim(), rest.trim_end().strip_suffix('}')?)))
+            .ok_or_else(|| error(format!("Expected '@profile name {{ ... }}', got '@profile {}'", args.trim())))?;
+        if names.is_empty() || !names.split(',').all(|name| is_key_path(name.trim())) {
+            return Err(error(format!("Invalid profile name '{names}'")));
+        }
+        if aaml.profile_selected(names) {
+            aaml.apply_line(body, ctx.line())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! `@require` directive — marks keys as mandatory without declaring a schema.
+//!
+//! # Syntax
+//! ```text
+//! @require api_key, region
+//! @require api_key: string, retries: i32
+//! ```
+//!
+//! # Semantics
+//! The listed keys must have a value once the document is fully parsed; they
+//! may be assigned before or after the directive. The check runs at the end of
+//! [`AAML::parse`], [`AAML::load`] and [`AAML::load_with_defaults`] (see
+//! [`AAML::validate_required_keys`]), and reports every missing key at once.
+//! A schema default for the key also counts as a value. Repeating a key is
+//! harmless.
+//!
+//! A key followed by `: type` must also hold a value of that type, checked at
+//! the same point. The type must be known when the directive runs; a later
+//! `@require` of the same key with a type replaces the earlier one.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@require` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequireCommand;
+
+impl Command for RequireCommand {
+    fn name(&self) -> &str {
+        "require"
+    }
+
+    /// Marks each comma-separated key in `args` as required, with the type
+    /// that follows a `:` if there is one.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the list is empty, contains an empty
+    /// or whitespace-separated key, or names an unknown type.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let mut entries = Vec::new();
+        for entry in split_entries(args) {
+            let (key, type_name) = match entry.split_once(':') {
+                Some((key, type_name)) => (key.trim(), Some(type_name.trim())),
+                None => (entry.trim(), None),
+            };
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(AamlError::DirectiveError(
+                    "require".into(),
+                    format!("Invalid key '{key}' in '@require {}'", args.trim()),
+                    None,
+                ));
+            }
+            if let Some(type_name) = type_name.filter(|t| !aaml.type_is_known(t)) {
+                return Err(AamlError::DirectiveError(
+                    "require".into(),
+                    format!("Unknown type '{type_name}' for required key '{key}'"),
+                    None,
+                ));
+            }
+            entries.push((key, type_name));
+        }
+        entries.into_iter().for_each(|(key, type_name)| aaml.require_key(key, type_name));
+        Ok(())
+    }
+}
+
+/// Splits `args` on commas outside `<>`, `()`, `[]` and `{}`, so types such
+/// as `map<string, i32>` stay whole.
+fn split_entries(args: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                entries.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&args[start..]);
+    entries
+}
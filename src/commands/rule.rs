@@ -0,0 +1,276 @@
+//! `@rule` directive — cross-field invariants.
+//!
+//! # Syntax
+//! ```text
+//! @rule pool_min <= pool_max
+//! @rule tls == true requires port == 443
+//! ```
+//!
+//! # Semantics
+//! A rule is a comparison (`==`, `!=`, `<`, `<=`, `>`, `>=`), optionally
+//! followed by `requires` and a second comparison that must hold whenever the
+//! first one does. Each operand is either a literal — a number, `true` /
+//! `false`, or a quoted string — or the name of a key, whose assigned value
+//! (or schema default) is used.
+//!
+//! Two numbers compare numerically, two booleans as booleans, anything else
+//! as text; `<` and friends require numbers. Rules are checked once the
+//! document is fully parsed, like `@require` (see
+//! [`AAML::validate_rules`]). A rule that mentions a key without a value is
+//! skipped — use `@require` to make the key mandatory.
+
+use crate::aaml::{AAML, parsing};
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+use std::fmt;
+
+/// A comparison operator usable in a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    /// Operators in matching order: two-character forms before their prefixes.
+    const ALL: [(&'static str, CompareOp); 6] = [
+        ("<=", CompareOp::Le),
+        (">=", CompareOp::Ge),
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+    ];
+
+    fn symbol(self) -> &'static str {
+        Self::ALL.iter().find(|(_, op)| *op == self).map_or("?", |(s, _)| s)
+    }
+}
+
+/// One side of a comparison.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operand {
+    /// The value of a key.
+    Key(String),
+    /// A literal written in the rule, quotes removed.
+    Literal(String),
+}
+
+impl Operand {
+    fn parse(raw: &str) -> Result<Self, AamlError> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Err(AamlError::DirectiveError("rule".into(), "Missing operand".into(), None));
+        }
+        let quoted = raw.len() >= 2 && (raw.starts_with('"') || raw.starts_with('\''));
+        let literal = quoted || raw.parse::<f64>().is_ok() || parse_bool(raw).is_some();
+        if literal {
+            return Ok(Operand::Literal(parsing::unwrap_quotes(raw).to_string()));
+        }
+        if raw.contains(char::is_whitespace) {
+            return Err(AamlError::DirectiveError(
+                "rule".into(),
+                format!("Invalid operand '{raw}'; quote string literals"),
+                None,
+            ));
+        }
+        Ok(Operand::Key(raw.to_string()))
+    }
+
+    /// Resolves the operand, or `None` when it names a key without a value.
+    fn resolve(&self, aaml: &AAML) -> Option<String> {
+        match self {
+            Operand::Key(key) => aaml.value_of(key).map(str::to_string),
+            Operand::Literal(value) => Some(value.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Key(key) => write!(f, "{key}"),
+            Operand::Literal(value) if value.parse::<f64>().is_ok() || parse_bool(value).is_some() => {
+                write!(f, "{value}")
+            }
+            Operand::Literal(value) => write!(f, "\"{value}\""),
+        }
+    }
+}
+
+/// `lhs op rhs`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comparison {
+    pub lhs: Operand,
+    pub op: CompareOp,
+    pub rhs: Operand,
+}
+
+impl Comparison {
+    /// Parses `lhs op rhs`, splitting on the first operator outside quotes.
+    pub fn parse(s: &str) -> Result<Self, AamlError> {
+        let mut quote = None;
+        for (i, ch) in s.char_indices() {
+            match (quote, ch) {
+                (None, '"' | '\'') => quote = Some(ch),
+                (Some(q), c) if c == q => quote = None,
+                (None, _) => {
+                    if let Some((sym, op)) = CompareOp::ALL.iter().find(|(sym, _)| s[i..].starts_with(sym)) {
+                        return Ok(Self {
+                            lhs: Operand::parse(&s[..i])?,
+                            op: *op,
+                            rhs: Operand::parse(&s[i + sym.len()..])?,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err(AamlError::DirectiveError(
+            "rule".into(),
+            format!("Expected a comparison like 'a <= b', got '{}'", s.trim()),
+            None,
+        ))
+    }
+
+    /// Evaluates the comparison; `Ok(None)` when an operand has no value.
+    fn eval(&self, aaml: &AAML) -> Result<Option<(bool, String)>, AamlError> {
+        let (Some(l), Some(r)) = (self.lhs.resolve(aaml), self.rhs.resolve(aaml)) else {
+            return Ok(None);
+        };
+        let ordering = match (l.parse::<f64>(), r.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => match (parse_bool(&l), parse_bool(&r)) {
+                (Some(a), Some(b)) => Some(a.cmp(&b)),
+                _ if matches!(self.op, CompareOp::Eq | CompareOp::Ne) => Some(l.cmp(&r)),
+                _ => {
+                    return Err(AamlError::DirectiveError(
+                        "rule".into(),
+                        format!("'{self}' compares non-numeric values '{l}' and '{r}'"),
+                        None,
+                    ));
+                }
+            },
+        };
+        let holds = ordering.is_some_and(|o| match self.op {
+            CompareOp::Eq => o.is_eq(),
+            CompareOp::Ne => o.is_ne(),
+            CompareOp::Lt => o.is_lt(),
+            CompareOp::Le => o.is_le(),
+            CompareOp::Gt => o.is_gt(),
+            CompareOp::Ge => o.is_ge(),
+        });
+        let shown = format!("{} {} {}", show(&self.lhs, &l), self.op.symbol(), show(&self.rhs, &r));
+        Ok(Some((holds, shown)))
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.lhs, self.op.symbol(), self.rhs)
+    }
+}
+
+/// A cross-field invariant declared with `@rule`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule {
+    /// When set, the rule only applies if this comparison holds.
+    pub condition: Option<Comparison>,
+    /// The comparison that must hold.
+    pub check: Comparison,
+}
+
+impl Rule {
+    /// Parses `a op b` or `a op b requires c op d`.
+    pub fn parse(s: &str) -> Result<Self, AamlError> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.iter().position(|w| *w == REQUIRES) {
+            Some(i) => Ok(Self {
+                condition: Some(Comparison::parse(&words[..i].join(" "))?),
+                check: Comparison::parse(&words[i + 1..].join(" "))?,
+            }),
+            None => Ok(Self { condition: None, check: Comparison::parse(s)? }),
+        }
+    }
+
+    /// Checks the rule against the values in `aaml`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] naming the rule and the values involved
+    /// when it is violated or orders non-numeric values.
+    pub fn check(&self, aaml: &AAML) -> Result<(), AamlError> {
+        let context = match &self.condition {
+            Some(condition) => match condition.eval(aaml)? {
+                Some((true, shown)) => Some(shown),
+                _ => return Ok(()),
+            },
+            None => None,
+        };
+        match self.check.eval(aaml)? {
+            Some((false, shown)) => {
+                let because = context.map(|c| format!(" (since {c})")).unwrap_or_default();
+                Err(AamlError::DirectiveError(
+                    "rule".into(),
+                    format!("Rule '{self}' violated: {shown}{because}"),
+                    None,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(condition) = &self.condition {
+            write!(f, "{condition} {REQUIRES} ")?;
+        }
+        write!(f, "{}", self.check)
+    }
+}
+
+/// Keyword separating a rule's condition from its check.
+const REQUIRES: &str = "requires";
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.to_ascii_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Renders an operand with its resolved value, e.g. `pool_min (10)`.
+fn show(operand: &Operand, value: &str) -> String {
+    match operand {
+        Operand::Key(key) => format!("{key} ({value})"),
+        Operand::Literal(_) => operand.to_string(),
+    }
+}
+
+/// Command handler for the `@rule` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleCommand;
+
+impl Command for RuleCommand {
+    fn name(&self) -> &str {
+        "rule"
+    }
+
+    /// Parses the rule in `args` and adds it to `aaml`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if the rule is malformed.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        aaml.add_rule(Rule::parse(args)?);
+        Ok(())
+    }
+}
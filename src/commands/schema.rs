@@ -8,39 +8,212 @@
 //! A field name ending with `*` is **optional** — it is not required to be present
 //! in the data map, but if it *is* present the value must satisfy the declared type.
 //!
+//! A field name ending with `?` is **nullable** — it must be present, but may be
+//! set to `null` / `none`. `field?: T` is stored as `field: optional<T>`; combine
+//! both markers (`field*?: T`) for a field that may be absent or null:
+//! ```text
+//! @schema Net { proxy?: string, timeout*?: i32 }
+//! ```
+//!
+//! A field may declare a default value with `= value`:
+//! ```text
+//! @schema Config { retries: i32 = 3, host: string = "localhost" }
+//! ```
+//! Defaults are validated against the field type when the directive runs, and
+//! lookups return them when the key is not assigned in the document.
+//!
+//! A quoted message after the type (or after the default) replaces the
+//! technical details of a failed value check, for people editing the file:
+//! ```text
+//! @schema Server { port: i32(1..65535) "port must be between 1 and 65535" }
+//! ```
+//!
+//! In a multi-line block, `# comment` lines directly above a field become its
+//! documentation, available through [`SchemaDef::field_doc`]:
+//! ```text
+//! @schema Server {
+//!     # Port the HTTP listener binds to.
+//!     port: i32
+//! }
+//! ```
+//!
+//! Field bundles declared with `@mixin` are spliced in with `..Name`; fields
+//! declared directly in the schema take precedence over spliced ones:
+//! ```text
+//! @mixin Timestamps { created_at: time::datetime, updated_at*: time::datetime }
+//! @schema Asset { ..Timestamps, name: string }
+//! ```
+//!
+//! The `deny_extra_objects` flag after the name makes inline objects typed as
+//! this schema reject keys it does not declare:
+//! ```text
+//! @schema Endpoint deny_extra_objects { host: string, port: i32 }
+//! ```
+//!
+//! The `strict` flag — or a `!` after the name — closes the schema: once a
+//! document is bound to it with `@conform`, assigning any key the schema does
+//! not declare is an error, so typos such as `retires = 3` are caught:
+//! ```text
+//! @schema Config! { retries: i32, timeout: f64 }
+//! @conform Config
+//! ```
+//!
+//! A `vN` flag records which version of the format the schema describes.
+//! Files written for an older version can be upgraded on load with migrations
+//! registered through [`AAML::register_migration`]:
+//! ```text
+//! @schema Config v2 { timeout_ms: i32 }
+//! ```
+//!
+//! `@sealed Name` (see [`sealed`](crate::commands::sealed)) forbids redefining
+//! a schema, in the same document or in files deriving from it.
+//!
 //! # Semantics
 //! After a schema is registered any `key = value` assignment whose key matches
 //! a schema field is automatically validated against the declared type.
-//! Use [`AAML::apply_schema`] to validate a complete data map programmatically.
+//! Use [`AAML::apply_schema`] to validate a complete data map programmatically,
+//! and [`SchemaDef::new`] with [`AAML::register_schema`] to define schemas in Rust.
 
-use crate::aaml::AAML;
-use crate::commands::Command;
+use crate::aaml::{AAML, parsing};
+use crate::commands::{Command, ExecutionContext};
 use crate::error::AamlError;
+use crate::types::check_declaration;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
 /// A parsed schema definition: maps field names to their declared type strings.
 ///
-/// Type strings can be primitives (`i32`, `f64`, `string`, `bool`, `color`),
+/// Type strings can be primitives (`i32`, `u64`, `f64`, `string`, `bool`, `color`, ...),
 /// built-in module paths (`math::vector3`, `physics::kilogram`, `time::datetime`),
-/// or custom aliases registered via `@type`.
+/// enumerations (`enum(debug, info)`), unions (`i32 | string`), or custom
+/// aliases registered via `@type`.
 ///
 /// Fields listed in `optional_fields` do not have to be present in the data map,
 /// but if they *are* present their values are still validated.
-#[derive(Clone, Debug)]
+///
+/// Fields declared as `field: type = value` carry a default in `defaults`. A
+/// defaulted field never counts as missing: lookups such as
+/// [`AAML::find_obj`] fall back to the default when the key is absent.
+///
+/// Fields declared with a quoted message carry it in `messages`; it is
+/// reported instead of the type's own error when a value is rejected.
+///
+/// `docs` holds the `# comment` lines written above fields in a multi-line
+/// block, one line per `\n`.
+///
+/// When `deny_extra_objects` is set, inline objects validated against this
+/// schema must not contain keys outside `fields`. When `strict` is set, a
+/// document bound to this schema with `@conform` may only assign keys in `fields`.
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaDef {
     /// Map of `field_name → type_name`.
     pub fields: HashMap<String, String>,
     /// Set of field names that are optional (declared with `*` suffix).
     pub optional_fields: HashSet<String>,
+    /// Map of `field_name → default value` for fields declared with `= value`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) defaults: HashMap<String, String>,
+    /// Map of `field_name → message` shown when a value of the field is rejected.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) messages: HashMap<String, String>,
+    /// Map of `field_name → documentation` from comment lines above the field.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) docs: HashMap<String, String>,
+    /// Whether inline objects of this schema reject undeclared keys.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) deny_extra_objects: bool,
+    /// Whether documents bound to this schema reject undeclared keys.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) strict: bool,
+    /// Format version declared with a `vN` flag, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) version: Option<u32>,
+    /// Whether the schema was sealed with `@sealed` and may not be redefined.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) sealed: bool,
 }
 
 impl SchemaDef {
+    /// Creates a schema from `field_name → type_name` pairs and the names of
+    /// the fields that are optional.
+    ///
+    /// Register it with [`AAML::register_schema`].
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::aaml::AAML;
+    /// use aam_rs::commands::schema::SchemaDef;
+    /// use std::collections::{HashMap, HashSet};
+    ///
+    /// let fields = HashMap::from([
+    ///     ("host".to_string(), "string".to_string()),
+    ///     ("port".to_string(), "i32(1..65536)".to_string()),
+    /// ]);
+    /// let mut cfg = AAML::new();
+    /// cfg.register_schema("Server", SchemaDef::new(fields, HashSet::from(["port".to_string()]))).unwrap();
+    /// assert!(cfg.merge_content("port = 99999").is_err());
+    /// ```
+    pub fn new(fields: HashMap<String, String>, optional_fields: HashSet<String>) -> Self {
+        Self {
+            fields,
+            optional_fields,
+            ..Self::default()
+        }
+    }
+
     /// Returns `true` when `field` was declared with `*` (optional).
     pub fn is_optional(&self, field: &str) -> bool {
         self.optional_fields.contains(field)
     }
+
+    /// Returns `true` when `field` must be present: it is neither optional nor
+    /// has a default value.
+    pub fn is_required(&self, field: &str) -> bool {
+        !self.is_optional(field) && !self.defaults.contains_key(field)
+    }
+
+    /// Returns the default values declared in this schema, keyed by field name.
+    pub fn defaults(&self) -> &HashMap<String, String> {
+        &self.defaults
+    }
+
+    /// Returns the default value declared for `field`, if any.
+    pub fn default_for(&self, field: &str) -> Option<&str> {
+        self.defaults.get(field).map(String::as_str)
+    }
+
+    /// Returns the documentation comment written above `field`, if any.
+    pub fn field_doc(&self, field: &str) -> Option<&str> {
+        self.docs.get(field).map(String::as_str)
+    }
+
+    /// Returns the custom error message declared for `field`, if any.
+    pub fn message_for(&self, field: &str) -> Option<&str> {
+        self.messages.get(field).map(String::as_str)
+    }
+
+    /// Returns `true` when inline objects of this schema reject undeclared keys
+    /// (the `deny_extra_objects` flag).
+    pub fn denies_extra_objects(&self) -> bool {
+        self.deny_extra_objects
+    }
+
+    /// Returns `true` when documents bound to this schema reject undeclared
+    /// keys (the `strict` flag or a `!` name suffix).
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the format version declared with a `vN` flag, if any.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// Returns `true` when the schema was sealed with `@sealed`.
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
 }
 
 /// Command handler for the `@schema` directive.
@@ -48,98 +221,399 @@ impl SchemaDef {
 pub struct SchemaCommand;
 
 impl SchemaCommand {
-    /// Splits `args` into the schema name and the raw body between `{` and `}`.
-    fn parse_header(args: &str) -> Result<(&str, &str), AamlError> {
+    /// Splits `args` into the schema name, the flags written after it, and the
+    /// raw body between `{` and `}`.
+    fn parse_header(args: &str) -> Result<(&str, Vec<&str>, &str), AamlError> {
         let (name_part, body_part) = args
             .split_once('{')
-            .ok_or_else(|| AamlError::DirectiveError("schema".into(), "Expected '{'".into()))?;
+            .ok_or_else(|| AamlError::DirectiveError("schema".into(), "Expected '{'".into(), None))?;
 
-        let name = name_part.trim();
-        if name.is_empty() {
-            return Err(AamlError::DirectiveError(
-                "schema".into(),
-                "Schema name is empty".into(),
-            ));
-        }
+        let mut words = name_part.split_whitespace();
+        let name = words.next().ok_or_else(|| {
+            AamlError::DirectiveError("schema".into(), "Schema name is empty".into(), None)
+        })?;
+        let flags = words.collect();
 
         let body = body_part
             .rsplit_once('}')
-            .ok_or_else(|| AamlError::DirectiveError("schema".into(), "Expected '}'".into()))?
+            .ok_or_else(|| AamlError::DirectiveError("schema".into(), "Expected '}'".into(), None))?
             .0;
 
-        Ok((name, body))
+        Ok((name, flags, body))
     }
 
-    /// Parses a single `field:type` or `field*:type` token pair.
+    /// Parses a single `field:type` or `field*:type` token pair, followed by an
+    /// optional `= default` clause and a quoted message before or after it.
     ///
-    /// Returns `(field_name, type_name, is_optional)`.
+    /// Returns the parsed [`FieldDecl`].
     /// A field name ending with `*` is optional — the `*` is stripped from
-    /// the stored name and `is_optional` is set to `true`.
-    fn parse_field<'a>(
-        token: &'a str,
-        tokens: &mut impl Iterator<Item = &'a str>,
-    ) -> Result<(String, String, bool), AamlError> {
-        let (field_raw, ty) = token.split_once(':').ok_or_else(|| {
-            AamlError::DirectiveError("schema".into(), format!("Bad field: '{token}'"))
+    /// the stored name and `is_optional` is set to `true`. A `?` marker is
+    /// stripped the same way and wraps the type in `optional<T>`.
+    fn parse_field(
+        token: &str,
+        tokens: &mut std::iter::Peekable<impl Iterator<Item = String>>,
+    ) -> Result<FieldDecl, AamlError> {
+        let (field_raw, ty) = split_field_colon(token).ok_or_else(|| {
+            AamlError::DirectiveError("schema".into(), format!("Bad field: '{token}'"), None)
         })?;
 
         // "field:type" or "field:" — type may follow as the next token.
-        let ty = if ty.is_empty() {
+        let mut ty = if ty.is_empty() {
             tokens.next().ok_or_else(|| {
                 AamlError::DirectiveError(
                     "schema".into(),
                     format!("Bad field: '{field_raw}:' has no type"),
+                    None,
                 )
             })?
         } else {
-            ty
+            ty.to_string()
         };
 
-        let is_optional = field_raw.ends_with('*');
-        let field = if is_optional {
-            field_raw.trim_end_matches('*')
-        } else {
-            field_raw
-        };
+        // "a | b" — union members may be separated by whitespace.
+        while ty.ends_with('|') || tokens.peek().is_some_and(|t| t.starts_with('|')) {
+            let member = tokens.next().ok_or_else(|| {
+                AamlError::DirectiveError(
+                    "schema".into(),
+                    format!("Field '{field_raw}': union type '{ty}' has no last member"),
+                    None,
+                )
+            })?;
+            ty.push_str(&member);
+        }
+
+        let markers = &field_raw[field_raw.trim_end_matches(['*', '?']).len()..];
+        let field = &field_raw[..field_raw.len() - markers.len()];
+        let is_optional = markers.contains('*');
+        if markers.contains('?') && !ty.is_empty() {
+            ty = format!("optional<{ty}>");
+        }
 
         if field.is_empty() || ty.is_empty() {
             return Err(AamlError::DirectiveError(
                 "schema".into(),
                 format!("Bad field: '{field}: {ty}'"),
+                None,
             ));
         }
 
-        Ok((field.to_string(), ty.to_string(), is_optional))
+        check_declaration(&ty).map_err(|e| {
+            AamlError::DirectiveError("schema".into(), format!("Field '{field}': {e}"), None)
+        })?;
+
+        // "message" — may precede or follow the default.
+        let mut message = take_message(tokens);
+
+        // "= value" — default used when the key is absent.
+        let default = if tokens.peek().is_some_and(|t| t == "=") {
+            tokens.next();
+            let raw = tokens.next().ok_or_else(|| {
+                AamlError::DirectiveError(
+                    "schema".into(),
+                    format!("Field '{field}' has '=' but no default value"),
+                    None,
+                )
+            })?;
+            Some(default_value(&raw).to_string())
+        } else {
+            None
+        };
+
+        if message.is_none() {
+            message = take_message(tokens);
+        }
+
+        Ok(FieldDecl {
+            name: field.to_string(),
+            type_name: ty,
+            is_optional,
+            default,
+            message,
+        })
     }
 
-    /// Parses the raw argument string into a `(name, SchemaDef)` pair.
+    /// Parses the raw argument string into the schema name, its own fields and
+    /// the names of the mixins spliced in with `..Name`, in order.
     ///
-    /// Expected format: `Name { field: type, field*: type, ... }`
-    fn parse(args: &str) -> Result<(String, SchemaDef), AamlError> {
-        let (name, body) = Self::parse_header(args.trim())?;
+    /// Expected format: `Name[!] [flags] { field: type, field*: type, field: type = default, ..Mixin, ... }`
+    pub(crate) fn parse(args: &str) -> Result<(String, SchemaDef, Vec<String>), AamlError> {
+        Self::parse_with_order(args).map(|(name, schema, mixins, _)| (name, schema, mixins))
+    }
+
+    /// Like [`parse`](Self::parse), also returning the schema's own field
+    /// names in declaration order.
+    pub(crate) fn parse_with_order(args: &str) -> Result<(String, SchemaDef, Vec<String>, Vec<String>), AamlError> {
+        let (name, flags, body) = Self::parse_header(args.trim())?;
+
+        // Commas and whitespace are both valid field separators; `=` is kept as
+        // its own token so defaults can be written with or without spaces.
+        let mut tokens = tokenize_body(body).into_iter().peekable();
+        let mut schema = SchemaDef::default();
+
+        let name = match name.strip_suffix('!') {
+            Some(base) => {
+                schema.strict = true;
+                base
+            }
+            None => name,
+        };
+        if name.is_empty() {
+            return Err(AamlError::DirectiveError(
+                "schema".into(),
+                "Schema name is empty".into(),
+                None,
+            ));
+        }
 
-        // Normalize: commas and whitespace are both valid field separators.
-        // Replace commas with spaces so we can use split_whitespace uniformly.
-        let normalized = body.replace(',', " ");
-        let mut tokens = normalized.split_whitespace();
-        let mut fields = HashMap::new();
-        let mut optional_fields = HashSet::new();
+        for flag in flags {
+            match flag {
+                DENY_EXTRA_OBJECTS => schema.deny_extra_objects = true,
+                STRICT => schema.strict = true,
+                other if version_flag(other).is_some() => schema.version = version_flag(other),
+                other => {
+                    return Err(AamlError::DirectiveError(
+                        "schema".into(),
+                        format!("Unknown schema flag '{other}'"),
+                        None,
+                    ));
+                }
+            }
+        }
 
+        let mut mixins = Vec::new();
+        let mut order = Vec::new();
+        let mut doc: Vec<String> = Vec::new();
         while let Some(token) = tokens.next() {
-            let (field, ty, is_optional) = Self::parse_field(token, &mut tokens)?;
-            if is_optional {
-                optional_fields.insert(field.clone());
+            if let Some(line) = doc_line(&token) {
+                doc.push(line.to_string());
+                continue;
+            }
+            if let Some(mixin) = token.strip_prefix("..") {
+                mixins.push(mixin.to_string());
+                doc.clear();
+                continue;
+            }
+            let decl = Self::parse_field(&token, &mut tokens)?;
+            if !doc.is_empty() {
+                schema.docs.insert(decl.name.clone(), std::mem::take(&mut doc).join("\n"));
+            }
+            if decl.is_optional {
+                schema.optional_fields.insert(decl.name.clone());
+            }
+            if let Some(default) = decl.default {
+                schema.defaults.insert(decl.name.clone(), default);
+            }
+            if let Some(message) = decl.message {
+                schema.messages.insert(decl.name.clone(), message);
             }
-            fields.insert(field, ty);
+            order.push(decl.name.clone());
+            schema.fields.insert(decl.name, decl.type_name);
         }
 
-        Ok((
-            name.to_string(),
-            SchemaDef {
-                fields,
-                optional_fields,
-            },
-        ))
+        Ok((name.to_string(), schema, mixins, order))
+    }
+}
+
+/// Copies the fields of each named mixin into `schema`. Fields the schema
+/// already declares are kept; otherwise earlier mixins win over later ones.
+///
+/// # Errors
+/// [`AamlError::DirectiveError`] if a mixin is not registered.
+pub(crate) fn splice_mixins(
+    aaml: &AAML,
+    schema: &mut SchemaDef,
+    mixins: &[String],
+) -> Result<(), AamlError> {
+    for name in mixins {
+        let mixin = aaml.get_mixin(name).ok_or_else(|| {
+            AamlError::DirectiveError("schema".into(), format!("Unknown mixin '{name}'"), None)
+        })?;
+        for (field, type_name) in &mixin.fields {
+            if schema.fields.contains_key(field) {
+                continue;
+            }
+            schema.fields.insert(field.clone(), type_name.clone());
+            if mixin.is_optional(field) {
+                schema.optional_fields.insert(field.clone());
+            }
+            if let Some(default) = mixin.default_for(field) {
+                schema.defaults.insert(field.clone(), default.to_string());
+            }
+            if let Some(message) = mixin.message_for(field) {
+                schema.messages.insert(field.clone(), message.to_string());
+            }
+            if let Some(doc) = mixin.field_doc(field) {
+                schema.docs.insert(field.clone(), doc.to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks a schema built outside the `@schema` parser: every field type must
+/// be a well-formed declaration, and optional fields, defaults, messages and docs
+/// must refer to declared fields.
+pub(crate) fn check_structure(name: &str, schema: &SchemaDef) -> Result<(), AamlError> {
+    if name.trim().is_empty() {
+        return Err(AamlError::InvalidValue("Schema name is empty".into()));
+    }
+    for (field, type_name) in &schema.fields {
+        check_declaration(type_name).map_err(|e| AamlError::SchemaValidationError {
+            schema: name.to_string(),
+            field: field.clone(),
+            type_name: type_name.clone(),
+            details: e.to_string(),
+            location: None,
+        })?;
+    }
+    let undeclared = schema
+        .optional_fields
+        .iter()
+        .chain(schema.defaults.keys())
+        .chain(schema.messages.keys())
+        .chain(schema.docs.keys())
+        .find(|field| !schema.fields.contains_key(*field));
+    match undeclared {
+        Some(field) => Err(AamlError::InvalidValue(format!(
+            "Schema '{name}' configures undeclared field '{field}'"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Fails when the schema registered under `name` is sealed, so it may not be
+/// replaced.
+pub(crate) fn ensure_unsealed(aaml: &AAML, name: &str) -> Result<(), String> {
+    match aaml.get_schema(name) {
+        Some(existing) if existing.sealed => Err(format!("Schema '{name}' is sealed and cannot be redefined")),
+        _ => Ok(()),
+    }
+}
+
+/// Checks every default declared in `schema` against its field type,
+/// reporting failures under `name`.
+pub(crate) fn validate_defaults(aaml: &AAML, name: &str, schema: &SchemaDef) -> Result<(), AamlError> {
+    for (field, value) in &schema.defaults {
+        aaml.validate_default_value(schema, name, field, value)?;
+    }
+    Ok(())
+}
+
+/// Header flag that makes inline objects reject undeclared keys.
+const DENY_EXTRA_OBJECTS: &str = "deny_extra_objects";
+
+/// Header flag (also written as a `!` name suffix) that closes the schema.
+const STRICT: &str = "strict";
+
+/// Parses a `vN` header flag into `N`.
+fn version_flag(flag: &str) -> Option<u32> {
+    flag.strip_prefix('v')
+        .filter(|n| n.bytes().all(|b| b.is_ascii_digit()))?
+        .parse()
+        .ok()
+}
+
+/// A single parsed field declaration from a `@schema` body.
+struct FieldDecl {
+    name: String,
+    type_name: String,
+    is_optional: bool,
+    default: Option<String>,
+    message: Option<String>,
+}
+
+/// Returns the text of a doc-comment token produced by the block accumulator.
+fn doc_line(token: &str) -> Option<&str> {
+    let quoted = token.strip_prefix(parsing::DOC_MARKER)?;
+    (quoted.len() >= 2 && quoted.starts_with(['"', '\''])).then(|| parsing::unwrap_quotes(quoted))
+}
+
+/// Consumes the next token if it is a quoted field message.
+fn take_message(tokens: &mut std::iter::Peekable<impl Iterator<Item = String>>) -> Option<String> {
+    tokens
+        .next_if(|t| t.len() >= 2 && (t.starts_with('"') || t.starts_with('\'')))
+        .map(|t| parsing::unwrap_quotes(&t).to_string())
+}
+
+/// Splits `field:type` on the first single `:` (a `::` path separator is not a
+/// field separator). Returns `None` when the token has no such colon.
+fn split_field_colon(token: &str) -> Option<(&str, &str)> {
+    let bytes = token.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b':' {
+            if bytes.get(i + 1) == Some(&b':') {
+                i += 2;
+                continue;
+            }
+            return Some((&token[..i], &token[i + 1..]));
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Strips quotes from a default value unless it is a list or object literal,
+/// mirroring how assignment values are stored.
+fn default_value(raw: &str) -> &str {
+    let raw = raw.trim();
+    if raw.starts_with('[') || raw.starts_with('{') {
+        raw
+    } else {
+        parsing::unwrap_quotes(raw)
+    }
+}
+
+/// Splits a schema body into tokens on whitespace and commas that are outside
+/// quotes and `()`, `<>`, `[]`, `{}` nesting. A top-level `=` is always emitted
+/// as a separate token. A `/pattern/` directly after `(` is read verbatim so
+/// regular expressions may contain unbalanced brackets.
+fn tokenize_body(body: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut cur = String::new();
+    let mut depth: i32 = 0;
+    let mut quote: Option<char> = None;
+
+    for ch in body.chars() {
+        if let Some(q) = quote {
+            cur.push(ch);
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => {
+                quote = Some(ch);
+                cur.push(ch);
+            }
+            '/' if cur.ends_with('(') => {
+                quote = Some('/');
+                cur.push(ch);
+            }
+            '(' | '<' | '[' | '{' => {
+                depth += 1;
+                cur.push(ch);
+            }
+            ')' | '>' | ']' | '}' => {
+                depth -= 1;
+                cur.push(ch);
+            }
+            '=' if depth == 0 => {
+                flush(&mut cur, &mut tokens);
+                tokens.push("=".to_string());
+            }
+            c if depth == 0 && (c == ',' || c.is_whitespace()) => flush(&mut cur, &mut tokens),
+            _ => cur.push(ch),
+        }
+    }
+    flush(&mut cur, &mut tokens);
+    tokens
+}
+
+fn flush(cur: &mut String, tokens: &mut Vec<String>) {
+    if !cur.is_empty() {
+        tokens.push(std::mem::take(cur));
     }
 }
 
@@ -150,9 +624,18 @@ impl Command for SchemaCommand {
 
     /// Parses the schema definition and registers it in the current [`AAML`] instance.
     ///
-    /// If a schema with the same name already exists it is **replaced**.
-    fn execute(&self, aaml: &mut AAML, args: &str) -> Result<(), AamlError> {
-        let (name, schema) = Self::parse(args)?;
+    /// If a schema with the same name already exists it is **replaced**,
+    /// unless it is sealed.
+    ///
+    /// # Errors
+    /// [`AamlError::SchemaValidationError`] if a default value does not satisfy
+    /// its field type, or [`AamlError::DirectiveError`] if a spliced mixin is
+    /// not registered or the existing schema is sealed.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let (name, mut schema, mixins) = Self::parse(args)?;
+        ensure_unsealed(aaml, &name).map_err(|msg| AamlError::DirectiveError("schema".into(), msg, None))?;
+        splice_mixins(aaml, &mut schema, &mixins)?;
+        validate_defaults(aaml, &name, &schema)?;
         aaml.get_schemas_mut().insert(name, schema);
         Ok(())
     }
@@ -0,0 +1,51 @@
+//! `@sealed` directive — protects schemas from redefinition.
+//!
+//! # Syntax
+//! ```text
+//! @schema Config { port: i32(1..65535), tls: bool }
+//! @sealed Config
+//! @sealed Config, Limits
+//! ```
+//!
+//! # Semantics
+//! A sealed schema can no longer be replaced: a later `@schema` with the same
+//! name, in this document or in a file that `@import`s it, fails with
+//! [`AamlError::DirectiveError`]. The seal travels with the schema through
+//! `@derive`, so a child file that declares a schema of the same name — before
+//! or after the `@derive` — is rejected instead of silently swapping out the
+//! base's validation rules. Sealing cannot be undone.
+
+use crate::aaml::AAML;
+use crate::commands::{Command, ExecutionContext};
+use crate::error::AamlError;
+
+/// Command handler for the `@sealed` directive.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SealedCommand;
+
+impl Command for SealedCommand {
+    fn name(&self) -> &str {
+        "sealed"
+    }
+
+    /// Seals each comma-separated schema named in `args`.
+    ///
+    /// # Errors
+    /// [`AamlError::DirectiveError`] if no name is given or a named schema is
+    /// not defined.
+    fn execute(&self, aaml: &mut AAML, args: &str, _ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let error = |details: String| AamlError::DirectiveError("sealed".into(), details, None);
+        let names: Vec<&str> = args.split(',').map(str::trim).collect();
+        if names.iter().any(|name| name.is_empty()) {
+            return Err(error(format!("Expected '@sealed SchemaName', got '@sealed {}'", args.trim())));
+        }
+        for name in names {
+            let schema = aaml
+                .get_schemas_mut()
+                .get_mut(name)
+                .ok_or_else(|| error(format!("Unknown schema '{name}'")))?;
+            schema.sealed = true;
+        }
+        Ok(())
+    }
+}
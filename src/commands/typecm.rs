@@ -13,31 +13,50 @@
 //! @type pos     = math::vector3
 //! @type mass    = physics::kilogram
 //! @type created = time::datetime
+//! @type level   = enum(debug, info, warn, error)
+//! @type percent = f64(0.0..=100.0)
+//! @type port    = i32[1..65536]
+//! @type ports   = list<port>
+//! @type token   = opaque
 //! ```
 //!
+//! Aliases may refer to other aliases and to schemas; they are resolved
+//! transitively during validation. A definition must name a known type at the
+//! point where the directive appears, and alias cycles are rejected.
+//!
+//! `opaque` declares a type whose values are intentionally left unchecked;
+//! it is the only way to opt a field out of validation.
+//!
 //! After registration the alias can be used as a field type in `@schema`
 //! definitions and validated via [`AAML::validate_value`].
 //!
 
-use crate::commands::Command;
+use crate::commands::{Command, ExecutionContext};
 use crate::error::AamlError;
 use crate::types::primitive_type::PrimitiveType;
-use crate::types::{Type, resolve_builtin};
+use crate::types::{Type, check_declaration, is_builtin, resolve_builtin};
+use crate::units::Dimension;
 
 /// A resolved type definition stored in the [`AAML`](crate::aaml::AAML) type registry.
 ///
-/// Variants correspond to the three ways a type can be declared:
+/// Variants correspond to the four ways a type can be declared:
 /// - [`TypeDefinition::Primitive`] — a primitive name such as `i32` or `bool`.
 /// - [`TypeDefinition::Builtin`] — a module-qualified path such as `math::vector3`.
-/// - [`TypeDefinition::Alias`] — an opaque alias (currently always passes validation).
+/// - [`TypeDefinition::Alias`] — a reference to another registered type or schema.
+/// - [`TypeDefinition::Opaque`] — an intentionally unchecked type (`opaque`).
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeDefinition {
     /// A primitive type identified by name (e.g. `"i32"`, `"f64"`).
     Primitive(String),
-    /// An alias that doesn't map to a concrete type — always valid.
+    /// A reference to another type registered via `@type` (or to a schema).
+    ///
+    /// Validated by following the target through the registry; on its own
+    /// (without a registry) it cannot be resolved and rejects every value.
     Alias(String),
     /// A built-in module path (e.g. `"math::vector3"`).
     Builtin(String),
+    /// A type declared as `opaque` — every value is accepted as-is.
+    Opaque,
 }
 
 impl Type for TypeDefinition {
@@ -59,7 +78,7 @@ impl Type for TypeDefinition {
             TypeDefinition::Primitive(name) => PrimitiveType::from_name(name)
                 .unwrap_or(PrimitiveType::String)
                 .base_type(),
-            TypeDefinition::Alias(_) => PrimitiveType::String,
+            TypeDefinition::Alias(_) | TypeDefinition::Opaque => PrimitiveType::String,
         }
     }
 
@@ -67,16 +86,57 @@ impl Type for TypeDefinition {
     ///
     /// - `Builtin` — delegates to the corresponding module type.
     /// - `Primitive` — delegates to [`PrimitiveType`].
-    /// - `Alias` — always returns `Ok(())`.
+    /// - `Alias` — fails with [`AamlError::NotFound`]; aliases are resolved
+    ///   by the [`AAML`](crate::aaml::AAML) registry before validation.
+    /// - `Opaque` — always returns `Ok(())`.
     fn validate(&self, value: &str) -> Result<(), AamlError> {
         match self {
             TypeDefinition::Builtin(path) => resolve_builtin(path)?.validate(value),
             TypeDefinition::Primitive(name) => PrimitiveType::from_name(name)?.validate(value),
-            TypeDefinition::Alias(_) => Ok(()),
+            TypeDefinition::Alias(target) => Err(AamlError::NotFound(format!(
+                "Alias target '{target}' must be resolved through the type registry"
+            ))),
+            TypeDefinition::Opaque => Ok(()),
+        }
+    }
+
+    fn normalize(&self, value: &str) -> String {
+        match self {
+            TypeDefinition::Builtin(path) | TypeDefinition::Primitive(path) => resolve_builtin(path)
+                .map_or_else(|_| value.trim().to_string(), |t| t.normalize(value)),
+            TypeDefinition::Alias(_) | TypeDefinition::Opaque => value.trim().to_string(),
+        }
+    }
+
+    fn alias_target(&self) -> Option<&str> {
+        match self {
+            TypeDefinition::Alias(target) => Some(target),
+            _ => None,
+        }
+    }
+
+    fn dimension(&self) -> Option<Dimension> {
+        match self {
+            TypeDefinition::Builtin(path) => resolve_builtin(path).ok()?.dimension(),
+            _ => None,
+        }
+    }
+
+    /// Delegates to the resolved type; aliases (resolved by the registry) and
+    /// opaque types accept any JSON value on their own.
+    fn json_schema(&self) -> String {
+        match self {
+            TypeDefinition::Builtin(path) | TypeDefinition::Primitive(path) => resolve_builtin(path)
+                .map(|t| t.json_schema())
+                .unwrap_or_else(|_| "{}".to_string()),
+            TypeDefinition::Alias(_) | TypeDefinition::Opaque => "{}".to_string(),
         }
     }
 }
 
+/// Keyword declaring a type whose values are not validated.
+const OPAQUE: &str = "opaque";
+
 /// Command handler for the `@type` directive.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeCommand;
@@ -88,40 +148,55 @@ impl Command for TypeCommand {
 
     /// Parses `name = definition` and registers the resulting [`TypeDefinition`].
     ///
-    /// Built-in paths (containing `::`) become [`TypeDefinition::Builtin`];
-    /// all other definitions become [`TypeDefinition::Primitive`].
+    /// The definition is resolved immediately:
+    /// - Primitive names (`i32`, `bool`, ...) become [`TypeDefinition::Primitive`].
+    /// - Module paths, `list<T>`, `enum(...)`, ranges and other built-ins become
+    ///   [`TypeDefinition::Builtin`].
+    /// - Names of already registered types or schemas — and expressions built
+    ///   from them, such as `list<port>` or `port(1..1024)` — become
+    ///   [`TypeDefinition::Alias`].
+    /// - The keyword `opaque` becomes [`TypeDefinition::Opaque`].
     ///
     /// # Errors
-    /// [`AamlError::ParseError`] if the format is invalid or name/definition is empty.
-    fn execute(&self, aaml: &mut crate::aaml::AAML, args: &str) -> Result<(), AamlError> {
-        let (name, definition) = args.split_once('=').ok_or_else(|| AamlError::ParseError {
-            line: 0,
+    /// [`AamlError::ParseError`] if the format is invalid, name/definition is
+    /// empty, the definition names an unknown type, or the alias would form a
+    /// cycle (`@type a = b` while `b` already resolves to `a`).
+    fn execute(&self, aaml: &mut crate::aaml::AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+        let make_err = |details: String| AamlError::ParseError {
+            line: ctx.line(),
             content: args.to_string(),
-            details: "Type definition must be in the format 'name = definition'".to_string(),
+            details,
+        };
+
+        let (name, definition) = args.split_once('=').ok_or_else(|| {
+            make_err("Type definition must be in the format 'name = definition'".to_string())
         })?;
 
         let name = name.trim();
         let definition = definition.trim();
 
         if name.is_empty() {
-            return Err(AamlError::ParseError {
-                line: 0,
-                content: args.to_string(),
-                details: "Type name cannot be empty".to_string(),
-            });
+            return Err(make_err("Type name cannot be empty".to_string()));
         }
         if definition.is_empty() {
-            return Err(AamlError::ParseError {
-                line: 0,
-                content: args.to_string(),
-                details: "Type definition cannot be empty".to_string(),
-            });
+            return Err(make_err("Type definition cannot be empty".to_string()));
         }
 
-        let type_def = if definition.contains("::") {
+        check_declaration(definition).map_err(|e| make_err(e.to_string()))?;
+
+        let type_def = if definition == OPAQUE {
+            TypeDefinition::Opaque
+        } else if PrimitiveType::from_name(definition).is_ok() {
+            TypeDefinition::Primitive(definition.to_string())
+        } else if is_builtin(definition) {
             TypeDefinition::Builtin(definition.to_string())
+        } else if aaml.type_is_known(definition) {
+            check_alias_cycle(aaml, name, definition).map_err(make_err)?;
+            TypeDefinition::Alias(definition.to_string())
         } else {
-            TypeDefinition::Primitive(definition.to_string())
+            return Err(make_err(format!(
+                "Unknown type '{definition}' in definition of '{name}'"
+            )));
         };
 
         aaml.register_type(name.to_string(), type_def);
@@ -129,3 +204,17 @@ impl Command for TypeCommand {
         Ok(())
     }
 }
+
+/// Follows the alias chain starting at `target` and fails if it leads back to `name`.
+fn check_alias_cycle(aaml: &crate::aaml::AAML, name: &str, target: &str) -> Result<(), String> {
+    let mut chain = vec![name, target];
+    let mut current = target;
+    while let Some(next) = aaml.get_type(current).and_then(|t| t.alias_target()) {
+        chain.push(next);
+        if next == name {
+            return Err(format!("Type alias cycle: {}", chain.join(" -> ")));
+        }
+        current = next;
+    }
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
 
 /// All errors that can be produced while parsing or validating an AAML document.
 #[derive(Debug)]
@@ -31,10 +32,15 @@ pub enum AamlError {
         type_name: String,
         /// Details from the type validator.
         details: String,
+        /// Where the failing assignment appeared, filled in by parsing.
+        location: Option<Box<SourceLocation>>,
     },
 
     /// A directive (`@import`, `@derive`, …) encountered an error in its arguments.
-    DirectiveError(String, String),
+    ///
+    /// Holds the directive name, the message, and where the directive
+    /// appeared. Parsing fills in the location; commands return `None`.
+    DirectiveError(String, String, Option<Box<SourceLocation>>),
 
     /// A schema constraint was violated during parsing or explicit validation.
     ///
@@ -53,6 +59,9 @@ pub enum AamlError {
         type_name: String,
         /// Human-readable description of the failure.
         details: String,
+        /// Where the failing assignment or directive appeared, filled in by
+        /// parsing; `None` for explicit validation.
+        location: Option<Box<SourceLocation>>,
     },
 }
 
@@ -65,13 +74,22 @@ impl fmt::Display for AamlError {
             }
             AamlError::NotFound(key) => write!(f, "Key not found: '{}'", key),
             AamlError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
-            AamlError::InvalidType { type_name, details } => {
+            AamlError::InvalidType { type_name, details, location } => {
+                if let Some(location) = location {
+                    write!(f, "{location}: ")?;
+                }
                 write!(f, "Invalid type '{}': {}", type_name, details)
             }
-            AamlError::DirectiveError(cmd, msg) => {
+            AamlError::DirectiveError(cmd, msg, location) => {
+                if let Some(location) = location {
+                    write!(f, "{location}: ")?;
+                }
                 write!(f, "Directive '@{}' error: {}", cmd, msg)
             }
-            AamlError::SchemaValidationError { schema, field, type_name, details } => {
+            AamlError::SchemaValidationError { schema, field, type_name, details, location } => {
+                if let Some(location) = location {
+                    write!(f, "{location}: ")?;
+                }
                 write!(
                     f,
                     "Schema '{}' validation error: field '{}' (type '{}') — {}",
@@ -82,7 +100,62 @@ impl fmt::Display for AamlError {
     }
 }
 
-impl std::error::Error for AamlError {}
+impl AamlError {
+    /// Returns where the failing statement appeared, when known.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            AamlError::DirectiveError(_, _, location)
+            | AamlError::InvalidType { location, .. }
+            | AamlError::SchemaValidationError { location, .. } => location.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for AamlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AamlError::IoError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Where a directive or assignment appeared in the sources being parsed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// File containing the statement, or `None` for content parsed from a string.
+    pub file: Option<PathBuf>,
+    /// 1-based line of the statement, or 0 when unknown.
+    pub line: usize,
+    /// 1-based column within the line, or 0 when unknown: the `@` of a
+    /// directive, or the start of an assignment's value.
+    pub column: usize,
+    /// Files being merged when the directive ran, outermost first; the last
+    /// one is `file`. Empty for content parsed from a string.
+    pub import_stack: Vec<PathBuf>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.column) {
+            (Some(file), 0) => write!(f, "{}:{}", file.display(), self.line)?,
+            (Some(file), column) => write!(f, "{}:{}:{column}", file.display(), self.line)?,
+            (None, 0) => write!(f, "line {}", self.line)?,
+            (None, column) => write!(f, "line {}, column {column}", self.line)?,
+        }
+        let outer = self.import_stack.len().saturating_sub(1);
+        if outer > 0 {
+            let files: Vec<String> = self.import_stack[..outer]
+                .iter()
+                .rev()
+                .map(|file| file.display().to_string())
+                .collect();
+            write!(f, " (imported from {})", files.join(", "))?;
+        }
+        Ok(())
+    }
+}
 
 impl From<io::Error> for AamlError {
     fn from(err: io::Error) -> Self {
@@ -2,9 +2,16 @@
 
 use crate::aaml::parsing;
 use crate::types::list::ListType;
+use crate::types::optional::OptionalType;
+use crate::types::data::parse_bytes;
+use crate::types::primitive_type::{parse_bool_relaxed, parse_float, parse_integer};
+use crate::types::time::parse_human_duration;
+use crate::values::{Kilogram, Quantity, Quat, Rgba, Span, Vec2, Vec3, Vec4};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::ops::Deref;
+use std::str::FromStr;
+use std::time::Duration;
 
 /// The result of a successful key lookup in an [`AAML`](crate::aaml::AAML) map.
 ///
@@ -53,6 +60,12 @@ impl FoundValue {
             .map(|items| items.iter().map(|s| s.to_string()).collect())
     }
 
+    /// Parses the value as a `range<T>` literal (`1..10`, `0.5..=2.5`) and
+    /// returns its endpoints, or `None` if either endpoint does not parse as `T`.
+    pub fn as_range<T: FromStr>(&self) -> Option<Span<T>> {
+        self.inner.parse().ok()
+    }
+
     /// Parses the value as an inline object `{ k = v, ... }` and returns a
     /// `HashMap<String, String>` of its fields.
     ///
@@ -74,6 +87,19 @@ impl FoundValue {
             .map(|pairs| pairs.into_iter().collect())
     }
 
+    /// Returns `true` when this value is an explicit unset marker (`null` or
+    /// `none`), as accepted by `optional<T>` fields. An empty string is not null.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// assert!(FoundValue::new("null").is_null());
+    /// assert!(!FoundValue::new("").is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        OptionalType::is_unset(&self.inner)
+    }
+
     /// Returns `true` when this value is a list literal `[...]`.
     pub fn is_list(&self) -> bool {
         let s = self.inner.trim();
@@ -84,6 +110,165 @@ impl FoundValue {
     pub fn is_object(&self) -> bool {
         parsing::is_inline_object(&self.inner)
     }
+
+    /// Parses the value as a `color` in any supported notation (`#RRGGBB`,
+    /// `rgba(255, 0, 0, 0.5)`, `hsl(0, 100%, 50%)`, `red`, ...).
+    ///
+    /// Returns `None` if the value is not a valid color. Use
+    /// `value.parse::<Rgba>()` to get the validation error instead.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// let c = FoundValue::new("#ff000080").as_color().unwrap();
+    /// assert_eq!((c.r, c.a), (255, 128));
+    /// ```
+    pub fn as_color(&self) -> Option<Rgba> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses the value as a `math::vector2` (`x, y`).
+    pub fn as_vec2(&self) -> Option<Vec2> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses the value as a `math::vector3` (`x, y, z`).
+    pub fn as_vec3(&self) -> Option<Vec3> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses the value as a `math::vector4` (`x, y, z, w`).
+    pub fn as_vec4(&self) -> Option<Vec4> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses the value as a `math::quaternion` (`x, y, z, w`).
+    pub fn as_quat(&self) -> Option<Quat> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses the value as a boolean, accepting every `bool_relaxed` spelling:
+    /// `true`/`false`, `1`/`0`, `yes`/`no`, `on`/`off` and `y`/`n`, in any case.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// assert_eq!(FoundValue::new("Yes").as_bool(), Some(true));
+    /// assert_eq!(FoundValue::new("off").as_bool(), Some(false));
+    /// assert_eq!(FoundValue::new("maybe").as_bool(), None);
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        parse_bool_relaxed(&self.inner)
+    }
+
+    /// Parses the value as an integer that fits in an `i32`.
+    ///
+    /// Accepts the same literals as the integer types: decimal, `0x` hex,
+    /// `0o` octal and `0b` binary, with `_` separators, plus whole numbers in
+    /// scientific notation such as `1e6`.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// assert_eq!(FoundValue::new("0xFF").as_i32(), Some(255));
+    /// assert_eq!(FoundValue::new("-0b101").as_i32(), Some(-5));
+    /// ```
+    pub fn as_i32(&self) -> Option<i32> {
+        parse_integer(&self.inner)?.try_into().ok()
+    }
+
+    /// Parses the value as an integer that fits in an `i64`
+    /// (see [`as_i32`](Self::as_i32) for the accepted literals).
+    pub fn as_i64(&self) -> Option<i64> {
+        parse_integer(&self.inner)?.try_into().ok()
+    }
+
+    /// Parses the value as a non-negative integer that fits in a `u64`
+    /// (see [`as_i32`](Self::as_i32) for the accepted literals).
+    pub fn as_u64(&self) -> Option<u64> {
+        parse_integer(&self.inner)?.try_into().ok()
+    }
+
+    /// Parses the value as an `f64`, accepting scientific notation (`6.02e23`)
+    /// and `_` digit separators (`1_000.5`).
+    pub fn as_f64(&self) -> Option<f64> {
+        parse_float(&self.inner)
+    }
+
+    /// Parses the value as a `data::bytes` size and returns the number of
+    /// bytes (`10MB` is `10_000_000`, `512KiB` is `524_288`).
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// assert_eq!(FoundValue::new("1.5 GiB").as_byte_size(), Some(1_610_612_736));
+    /// assert_eq!(FoundValue::new("4096").as_byte_size(), Some(4096));
+    /// ```
+    pub fn as_byte_size(&self) -> Option<u64> {
+        parse_bytes(&self.inner)
+    }
+
+    /// Parses the value as a `time::human_duration` (`5s`, `2h30m`, `250ms`).
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// use std::time::Duration;
+    /// assert_eq!(FoundValue::new("2h30m").as_duration(), Some(Duration::from_secs(9000)));
+    /// assert_eq!(FoundValue::new("1.5s").as_duration(), Some(Duration::from_millis(1500)));
+    /// ```
+    pub fn as_duration(&self) -> Option<Duration> {
+        parse_human_duration(&self.inner)
+    }
+
+    /// Parses the value as an `id::uuid` (`uuid` feature).
+    #[cfg(feature = "uuid")]
+    pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+        crate::types::id::is_uuid(&self.inner)
+            .then(|| uuid::Uuid::parse_str(&self.inner).ok())
+            .flatten()
+    }
+
+    /// Parses a quantity written with a unit symbol (`3.5km`, `20°C`).
+    ///
+    /// Returns `None` for bare numbers and unknown symbols; see
+    /// [`Quantity::to`] for conversions.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// let q = FoundValue::new("3.5km").as_quantity().unwrap();
+    /// assert_eq!((q.value, q.unit()), (3.5, "km"));
+    /// assert_eq!(q.value_in("m"), Some(3500.0));
+    /// ```
+    pub fn as_quantity(&self) -> Option<Quantity> {
+        self.inner.parse().ok()
+    }
+
+    /// Parses a quantity written with a unit symbol (`3.5km`, `20°C`) and
+    /// converts it into the unit `symbol` (see [`units::SYMBOLS`](crate::units::SYMBOLS)).
+    ///
+    /// A bare number is returned unchanged, as it is already in the unit of
+    /// its field. Returns `None` if the value is not a quantity, either symbol
+    /// is unknown, or the units measure different dimensions.
+    ///
+    /// # Example
+    /// ```
+    /// use aam_rs::found_value::FoundValue;
+    /// assert_eq!(FoundValue::new("3.5km").as_quantity_in("m"), Some(3500.0));
+    /// assert_eq!(FoundValue::new("75kg").as_quantity_in("m"), None);
+    /// ```
+    pub fn as_quantity_in(&self, symbol: &str) -> Option<f64> {
+        match parse_float(self.inner.trim()) {
+            Some(n) => Some(n),
+            None => self.as_quantity()?.value_in(symbol),
+        }
+    }
+
+    /// Parses the value as a `physics::kilogram` mass.
+    pub fn as_kilogram(&self) -> Option<Kilogram> {
+        self.inner.parse().ok()
+    }
 }
 
 impl From<String> for FoundValue {
@@ -6,8 +6,10 @@
 //! - Simple `key = value` configuration syntax with comment support (`#`)
 //! - Directive system: `@import`, `@derive`, `@schema`, `@type`
 //! - Schema-based type validation — fields are checked automatically during parsing
-//! - Built-in types: `i32`, `f64`, `string`, `bool`, `color`,
-//!   `math::vector2/3/4`, `physics::kilogram`, `time::datetime`, and more
+//! - Built-in types: `i32`, `i64`, `u8`–`u64`, `usize`, `f32`, `f64`, `decimal`, `string`, `bool`, `color`,
+//!   `math::vector2/3/4`, `physics::kilogram`, `time::datetime`, `data::bytes`, `net::socket_addr`, `id::uuid`, and more
+//! - Typed accessors for colors, vectors and masses (see [`values`]), byte sizes and durations
+//! - Dimension metadata for the `physics::` units (see [`units`])
 //! - Custom type aliases via `@type`
 //! - Inheritance via `@derive` with child-wins-on-conflict semantics
 //!
@@ -21,7 +23,12 @@
 
 pub mod aaml;
 pub mod found_value;
+pub mod value;
+pub mod values;
+pub mod units;
 pub mod error;
 pub mod builder;
 pub mod commands;
+#[cfg(feature = "cli")]
+pub mod cli;
 mod types;
\ No newline at end of file
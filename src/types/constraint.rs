@@ -0,0 +1,343 @@
+//! Constrained types — a base type with a parameter list in parentheses.
+//!
+//! ## Numeric ranges
+//! ```text
+//! @schema Server { port: i32(1..65536) }
+//! @type percent = f64(0.0..=100.0)
+//! ```
+//!
+//! Ranges follow Rust syntax: `a..b` excludes `b`, `a..=b` includes it, and
+//! either bound may be omitted (`1..`, `..=10`). Square brackets are accepted
+//! as an alternative to parentheses: `i32[1..65536]` is the same type as
+//! `i32(1..65536)`. The value must first satisfy
+//! the base type; it is then parsed as a number and checked against the range.
+//!
+//! ## Patterns (`regex` feature)
+//! ```text
+//! @schema Order { id: string(/^[A-Z]{3}-\d+$/) }
+//! ```
+//!
+//! A constraint written between slashes is a regular expression the value must
+//! match. Use `^`/`$` anchors to match the whole value, and `\/` for a literal
+//! slash. Each distinct pattern is compiled once and reused for every value.
+//! Without the `regex` feature such declarations are rejected.
+
+use crate::error::AamlError;
+use crate::types::primitive_type::{PrimitiveType, parse_number};
+use crate::types::{Type, resolve_builtin};
+use crate::units::Dimension;
+use std::fmt;
+
+/// A numeric interval with optional bounds, written `start..end` or `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumericRange {
+    /// Inclusive lower bound, if any.
+    pub start: Option<f64>,
+    /// Upper bound, if any.
+    pub end: Option<f64>,
+    /// Whether `end` itself is part of the range (`..=`).
+    pub inclusive_end: bool,
+}
+
+impl NumericRange {
+    /// Parses `a..b`, `a..=b`, `a..`, `..b` or `..=b`.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] if there is no `..`, a bound is not a number,
+    /// or the start is greater than the end.
+    pub fn parse(s: &str) -> Result<Self, AamlError> {
+        let s = s.trim();
+        let (start_raw, rest) = s
+            .split_once("..")
+            .ok_or_else(|| AamlError::InvalidValue(format!("Expected a range 'a..b', got '{s}'")))?;
+        let (inclusive_end, end_raw) = match rest.strip_prefix('=') {
+            Some(end) => (true, end),
+            None => (false, rest),
+        };
+
+        let bound = |raw: &str| -> Result<Option<f64>, AamlError> {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                return Ok(None);
+            }
+            parse_number(raw).map(Some).ok_or_else(|| {
+                AamlError::InvalidValue(format!("Invalid range bound '{raw}' in '{s}'"))
+            })
+        };
+
+        let range = Self {
+            start: bound(start_raw)?,
+            end: bound(end_raw)?,
+            inclusive_end,
+        };
+        if inclusive_end && range.end.is_none() {
+            return Err(AamlError::InvalidValue(format!(
+                "Inclusive range '{s}' needs an end bound"
+            )));
+        }
+        if let (Some(a), Some(b)) = (range.start, range.end)
+            && a > b
+        {
+            return Err(AamlError::InvalidValue(format!(
+                "Range start {a} is greater than end {b}"
+            )));
+        }
+        Ok(range)
+    }
+
+    /// Returns `true` when `n` lies inside the range.
+    pub fn contains(&self, n: f64) -> bool {
+        let above = self.start.is_none_or(|a| n >= a);
+        let below = match self.end {
+            Some(b) if self.inclusive_end => n <= b,
+            Some(b) => n < b,
+            None => true,
+        };
+        above && below
+    }
+
+    /// Parses `value` as a number and checks that it lies inside the range.
+    pub fn check(&self, value: &str) -> Result<(), AamlError> {
+        let n = parse_number(value.trim()).ok_or_else(|| {
+            AamlError::InvalidValue(format!("Expected a number for range {self}, got '{value}'"))
+        })?;
+        if !self.contains(n) {
+            return Err(AamlError::InvalidValue(format!(
+                "Value {value} is out of range {self}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Renders the bounds as JSON Schema keywords (without surrounding braces).
+    fn json_keywords(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(a) = self.start {
+            parts.push(format!(r#""minimum":{a}"#));
+        }
+        if let Some(b) = self.end {
+            let key = if self.inclusive_end { "maximum" } else { "exclusiveMaximum" };
+            parts.push(format!(r#""{key}":{b}"#));
+        }
+        parts.join(",")
+    }
+}
+
+impl fmt::Display for NumericRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(a) = self.start {
+            write!(f, "{a}")?;
+        }
+        f.write_str(if self.inclusive_end { "..=" } else { ".." })?;
+        if let Some(b) = self.end {
+            write!(f, "{b}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A restriction applied on top of a base type.
+pub enum Constraint {
+    /// `a..b` — the value must be a number inside the range.
+    Range(NumericRange),
+    /// `/pattern/` — the value must match the regular expression (`regex` feature).
+    #[cfg(feature = "regex")]
+    Pattern(regex::Regex),
+}
+
+impl Constraint {
+    /// Parses constraint arguments: `/pattern/` or a numeric range.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] for malformed ranges, a pattern with an
+    /// unescaped `/` or without its closing `/`, invalid regular expressions,
+    /// or a pattern used without the `regex` feature.
+    pub fn parse(args: &str) -> Result<Self, AamlError> {
+        let args = args.trim();
+        if let Some(body) = args.strip_prefix('/') {
+            return Self::parse_pattern(pattern_body(body)?);
+        }
+        NumericRange::parse(args).map(Constraint::Range)
+    }
+
+    #[cfg(feature = "regex")]
+    fn parse_pattern(pattern: &str) -> Result<Self, AamlError> {
+        compile_pattern(pattern)
+            .map(Constraint::Pattern)
+            .map_err(|e| AamlError::InvalidValue(format!("Invalid pattern /{pattern}/: {e}")))
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn parse_pattern(pattern: &str) -> Result<Self, AamlError> {
+        Err(AamlError::InvalidValue(format!(
+            "Pattern constraint /{pattern}/ requires the `regex` feature"
+        )))
+    }
+
+    /// Checks `value` (already accepted by the base type) against the constraint.
+    pub fn check(&self, value: &str) -> Result<(), AamlError> {
+        match self {
+            Constraint::Range(range) => range.check(value),
+            #[cfg(feature = "regex")]
+            Constraint::Pattern(re) => {
+                if re.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(AamlError::InvalidValue(format!(
+                        "Value '{value}' does not match pattern /{}/",
+                        re.as_str()
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Renders the constraint as JSON Schema keywords (without surrounding braces).
+    fn json_keywords(&self) -> String {
+        match self {
+            Constraint::Range(range) => range.json_keywords(),
+            #[cfg(feature = "regex")]
+            Constraint::Pattern(re) => {
+                format!(r#""pattern":{}"#, crate::aaml::json_schema::json_string(re.as_str()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Range(range) => write!(f, "{range}"),
+            #[cfg(feature = "regex")]
+            Constraint::Pattern(re) => write!(f, "/{}/", re.as_str()),
+        }
+    }
+}
+
+/// Returns the pattern of `/pattern/` given the text after the opening
+/// slash. A `\/` inside the pattern is kept as written; any other `/` must be
+/// the closing delimiter and the last character.
+fn pattern_body(body: &str) -> Result<&str, AamlError> {
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '/' if i + 1 == body.len() => return Ok(&body[..i]),
+            '/' => {
+                return Err(AamlError::InvalidValue(format!(
+                    "Unescaped '/' in pattern /{body}; write it as '\\/'"
+                )));
+            }
+            _ => {}
+        }
+    }
+    Err(AamlError::InvalidValue(format!("Pattern /{body} is missing its closing '/'")))
+}
+
+/// Compiles `pattern`, reusing the [`regex::Regex`] compiled for an earlier
+/// constraint with the same pattern: type strings are re-resolved for every
+/// validated value, so each pattern would otherwise be recompiled every time.
+#[cfg(feature = "regex")]
+fn compile_pattern(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock, PoisonError};
+
+    static COMPILED: OnceLock<Mutex<HashMap<String, regex::Regex>>> = OnceLock::new();
+    let mut compiled = COMPILED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(re) = compiled.get(pattern) {
+        return Ok(re.clone());
+    }
+    let re = regex::Regex::new(pattern)?;
+    compiled.insert(pattern.to_string(), re.clone());
+    Ok(re)
+}
+
+/// A base type restricted by a constraint written in parentheses.
+pub struct ConstrainedType {
+    base: Box<dyn Type>,
+    constraint: Constraint,
+}
+
+impl ConstrainedType {
+    /// Splits `base(args)` or `base[args]` into `("base", "args")`.
+    ///
+    /// Returns `None` when the string does not end with a parenthesised or
+    /// bracketed parameter list or the base name is empty.
+    pub fn split(type_str: &str) -> Option<(&str, &str)> {
+        let t = type_str.trim();
+        let close = t.chars().last()?;
+        let open_ch = match close {
+            ')' => '(',
+            ']' => '[',
+            _ => return None,
+        };
+        let open = t.find(open_ch)?;
+        let args = &t[open + 1..t.len() - 1];
+        let base = t[..open].trim();
+        (!base.is_empty()).then_some((base, args))
+    }
+
+    /// Builds a constrained type around an already-resolved base type.
+    ///
+    /// Range constraints require a numeric base; patterns accept any base.
+    pub fn new(base: Box<dyn Type>, args: &str) -> Result<Self, AamlError> {
+        let constraint = Constraint::parse(args)?;
+        if let Constraint::Range(range) = &constraint
+            && !base.base_type().is_numeric()
+        {
+            return Err(AamlError::InvalidValue(format!(
+                "Range constraint '{range}' requires a numeric type, got '{}'",
+                base.base_type()
+            )));
+        }
+        Ok(Self { base, constraint })
+    }
+
+    /// Parses `base(args)` resolving `base` as a built-in type.
+    pub fn parse(type_str: &str) -> Result<Self, AamlError> {
+        let (base, args) = Self::split(type_str)
+            .ok_or_else(|| AamlError::NotFound(type_str.to_string()))?;
+        Self::new(resolve_builtin(base)?, args)
+    }
+}
+
+impl Type for ConstrainedType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "ConstrainedType::from_name — use ConstrainedType::parse instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        self.base.base_type()
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        self.base.validate(value)?;
+        self.constraint.check(value)
+    }
+
+    fn normalize(&self, value: &str) -> String {
+        self.base.normalize(value)
+    }
+
+    fn dimension(&self) -> Option<Dimension> {
+        self.base.dimension()
+    }
+
+    fn json_schema(&self) -> String {
+        format!(
+            r#"{{"allOf":[{},{{{}}}]}}"#,
+            self.base.json_schema(),
+            self.constraint.json_keywords()
+        )
+    }
+}
@@ -0,0 +1,102 @@
+//! Data-size types (`data::bytes`).
+//!
+//! A byte size is a non-negative number with an optional unit suffix: `B`,
+//! SI multiples of 1000 (`KB`, `MB`, `GB`, `TB`, `PB`, `EB`) or IEC multiples
+//! of 1024 (`KiB`, `MiB`, `GiB`, `TiB`, `PiB`, `EiB`). Suffixes are
+//! case-insensitive and may be separated from the number by spaces. A
+//! fractional number is accepted when it comes out to a whole byte count.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let cfg = AAML::parse("@schema Cache { limit: data::bytes }\nlimit = 512KiB").unwrap();
+//! assert_eq!(cfg.find_obj("limit").unwrap().as_byte_size(), Some(512 * 1024));
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::primitive_type::PrimitiveType;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataTypes {
+    Bytes,
+}
+
+/// Unit suffixes and their size in bytes, matched case-insensitively.
+const BYTE_UNITS: &[(&str, u64)] = &[
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("eb", 1_000_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+    ("eib", 1 << 60),
+];
+
+/// Parses a byte size such as `10MB`, `512 KiB`, `1.5GB` or `4096` into a
+/// number of bytes. Returns `None` for malformed values, fractional byte
+/// counts and sizes that overflow a `u64`.
+pub(crate) fn parse_bytes(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let unit = unit.trim_start().to_ascii_lowercase();
+    let multiplier = if unit.is_empty() {
+        1
+    } else {
+        BYTE_UNITS.iter().find(|(name, _)| *name == unit)?.1
+    };
+
+    let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+    if int.is_empty() || frac.contains('.') || (number.contains('.') && frac.is_empty()) {
+        return None;
+    }
+    let scale = 10u128.checked_pow(frac.len() as u32)?;
+    let mantissa: u128 = format!("{int}{frac}").parse().ok()?;
+    let total = mantissa.checked_mul(multiplier as u128)?;
+    if total % scale != 0 {
+        return None;
+    }
+    (total / scale).try_into().ok()
+}
+
+impl Type for DataTypes {
+    fn from_name(name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        match name {
+            "bytes" => Ok(DataTypes::Bytes),
+            _ => Err(AamlError::NotFound(name.to_string())),
+        }
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::U64
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        match self {
+            DataTypes::Bytes => parse_bytes(value).map(|_| ()).ok_or_else(|| {
+                AamlError::InvalidValue(format!(
+                    "Invalid byte size '{value}': expected a whole number of bytes with an optional unit (B, KB, MB, GB, KiB, MiB, GiB, ...)"
+                ))
+            }),
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        match self {
+            DataTypes::Bytes => r#"{"anyOf":[{"type":"integer","minimum":0},{"type":"string","pattern":"^\\d+(\\.\\d+)?(\\s*([KkMmGgTtPpEe][Ii]?)?[Bb])?$"}]}"#.to_string(),
+        }
+    }
+}
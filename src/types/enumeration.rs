@@ -0,0 +1,99 @@
+//! `enum(a, b, c)` — a closed vocabulary of string values.
+//!
+//! ## Schema usage
+//! ```text
+//! @schema Logger { level: enum(debug, info, warn, error) }
+//! @type level = enum(debug, info, warn, error)
+//! ```
+//!
+//! A value is accepted only when it matches one of the variants exactly
+//! (case-sensitive). Variants may be quoted to include spaces or commas.
+
+use crate::aaml::parsing::unwrap_quotes;
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::list::split_top_level;
+use crate::types::primitive_type::PrimitiveType;
+
+/// A type that accepts only one of a fixed set of variants.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumType {
+    /// Allowed values, in declaration order.
+    pub(crate) variants: Vec<String>,
+}
+
+impl EnumType {
+    /// Returns `true` when `type_str` has the `enum(...)` form.
+    pub fn is_enum(type_str: &str) -> bool {
+        let t = type_str.trim();
+        t.starts_with("enum(") && t.ends_with(')')
+    }
+
+    /// Parses an `enum(a, b, c)` type string.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidType`] if the string is not in `enum(...)` form, lists
+    /// no variants, or repeats a variant.
+    pub fn parse(type_str: &str) -> Result<Self, AamlError> {
+        let make_err = |details: String| AamlError::InvalidType {
+            type_name: type_str.to_string(),
+            details,
+            location: None,
+        };
+
+        let inner = type_str
+            .trim()
+            .strip_prefix("enum(")
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| make_err("Expected 'enum(variant, ...)'".to_string()))?;
+
+        let mut variants: Vec<String> = Vec::new();
+        for item in split_top_level(inner) {
+            let variant = unwrap_quotes(&item).to_string();
+            if variants.contains(&variant) {
+                return Err(make_err(format!("Duplicate variant '{variant}'")));
+            }
+            variants.push(variant);
+        }
+
+        if variants.is_empty() {
+            return Err(make_err("Enum must declare at least one variant".to_string()));
+        }
+        Ok(Self { variants })
+    }
+}
+
+impl Type for EnumType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "EnumType::from_name — use EnumType::parse instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        if self.variants.iter().any(|v| v == value) {
+            return Ok(());
+        }
+        Err(AamlError::InvalidValue(format!(
+            "Expected one of [{}], got '{}'",
+            self.variants.join(", "),
+            value
+        )))
+    }
+
+    fn json_schema(&self) -> String {
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|v| crate::aaml::json_schema::json_string(v))
+            .collect();
+        format!(r#"{{"enum":[{}]}}"#, variants.join(","))
+    }
+}
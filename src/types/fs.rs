@@ -0,0 +1,70 @@
+//! Filesystem path types (`fs::path`, `fs::existing_file`, `fs::existing_dir`).
+//!
+//! `fs::path` only checks that the value is usable as a path: non-empty and
+//! free of NUL bytes. `fs::existing_file` and `fs::existing_dir` additionally
+//! require the path to name an existing file or directory when the value is
+//! validated. Relative paths are resolved against the process's current
+//! working directory, not the directory of the config file.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! assert!(AAML::parse("@schema Tls { cert: fs::path }\ncert = certs/server.pem").is_ok());
+//! assert!(AAML::parse("@schema Tls { cert: fs::existing_file }\ncert = /no/such/cert.pem").is_err());
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::primitive_type::PrimitiveType;
+use std::path::Path;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FsTypes {
+    Path,
+    ExistingFile,
+    ExistingDir,
+}
+
+impl Type for FsTypes {
+    fn from_name(name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        match name {
+            "path" => Ok(FsTypes::Path),
+            "existing_file" => Ok(FsTypes::ExistingFile),
+            "existing_dir" => Ok(FsTypes::ExistingDir),
+            _ => Err(AamlError::NotFound(name.to_string())),
+        }
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        if value.is_empty() || value.contains('\0') {
+            return Err(AamlError::InvalidValue(format!(
+                "Invalid path '{}': expected a non-empty path without NUL bytes",
+                value.escape_debug()
+            )));
+        }
+        let path = Path::new(value);
+        match self {
+            FsTypes::Path => Ok(()),
+            FsTypes::ExistingFile if path.is_file() => Ok(()),
+            FsTypes::ExistingFile => Err(AamlError::InvalidValue(format!(
+                "File '{value}' does not exist or is not a regular file"
+            ))),
+            FsTypes::ExistingDir if path.is_dir() => Ok(()),
+            FsTypes::ExistingDir => Err(AamlError::InvalidValue(format!(
+                "Directory '{value}' does not exist or is not a directory"
+            ))),
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        r#"{"type":"string","minLength":1}"#.to_string()
+    }
+}
@@ -0,0 +1,83 @@
+//! Geographic coordinate types (`geo::latitude`, `geo::longitude`,
+//! `geo::coordinate`), in decimal degrees.
+//!
+//! Latitudes lie in `-90..=90` and longitudes in `-180..=180`. A coordinate is
+//! a `lat, lon` pair.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let schema = "@schema Station { position: geo::coordinate }\nposition = ";
+//! assert!(AAML::parse(&format!("{schema}52.52, 13.405")).is_ok());
+//! assert!(AAML::parse(&format!("{schema}13.405, 252.52")).is_err());
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::primitive_type::{PrimitiveType, parse_float};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeoTypes {
+    Latitude,
+    Longitude,
+    Coordinate,
+}
+
+/// Parses `value` as a number of degrees within `-limit..=limit`.
+fn check_degrees(value: &str, label: &str, limit: f64) -> Result<f64, AamlError> {
+    let value = value.trim();
+    let degrees = parse_float(value)
+        .filter(|d| d.is_finite())
+        .ok_or_else(|| {
+            AamlError::InvalidValue(format!("Invalid {label} '{value}': expected decimal degrees"))
+        })?;
+    if degrees.abs() > limit {
+        return Err(AamlError::InvalidValue(format!(
+            "Invalid {label} '{value}': must be between -{limit} and {limit}"
+        )));
+    }
+    Ok(degrees)
+}
+
+impl Type for GeoTypes {
+    fn from_name(name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        match name {
+            "latitude" => Ok(GeoTypes::Latitude),
+            "longitude" => Ok(GeoTypes::Longitude),
+            "coordinate" => Ok(GeoTypes::Coordinate),
+            _ => Err(AamlError::NotFound(name.to_string())),
+        }
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::F64
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        match self {
+            GeoTypes::Latitude => check_degrees(value, "latitude", 90.0).map(|_| ()),
+            GeoTypes::Longitude => check_degrees(value, "longitude", 180.0).map(|_| ()),
+            GeoTypes::Coordinate => {
+                let (lat, lon) = value.split_once(',').ok_or_else(|| {
+                    AamlError::InvalidValue(format!(
+                        "Invalid coordinate '{value}': expected 'latitude, longitude'"
+                    ))
+                })?;
+                check_degrees(lat, "latitude", 90.0)?;
+                check_degrees(lon, "longitude", 180.0).map(|_| ())
+            }
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        match self {
+            GeoTypes::Latitude => r#"{"type":"number","minimum":-90,"maximum":90}"#.to_string(),
+            GeoTypes::Longitude => r#"{"type":"number","minimum":-180,"maximum":180}"#.to_string(),
+            GeoTypes::Coordinate => r#"{"type":"array","prefixItems":[{"type":"number","minimum":-90,"maximum":90},{"type":"number","minimum":-180,"maximum":180}],"minItems":2,"maxItems":2}"#.to_string(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Identifier types (`id::uuid`).
+//!
+//! A UUID must be written in the canonical hyphenated form
+//! `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` (hex digits, either case). With the
+//! `uuid` feature, [`FoundValue::as_uuid`](crate::found_value::FoundValue::as_uuid)
+//! converts a stored value to a `uuid::Uuid`.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let schema = "@schema Asset { id: id::uuid }\nid = ";
+//! assert!(AAML::parse(&format!("{schema}67e55044-10b1-426f-9247-bb680e5fe0c8")).is_ok());
+//! assert!(AAML::parse(&format!("{schema}67e5504410b1426f9247bb680e5fe0c8")).is_err());
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::primitive_type::PrimitiveType;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdTypes {
+    Uuid,
+}
+
+/// Lengths of the hyphen-separated groups of a canonical UUID.
+const UUID_GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+/// Returns `true` when `value` is a canonical hyphenated UUID.
+pub(crate) fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == UUID_GROUPS.len()
+        && groups
+            .iter()
+            .zip(UUID_GROUPS)
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+impl Type for IdTypes {
+    fn from_name(name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        match name {
+            "uuid" => Ok(IdTypes::Uuid),
+            _ => Err(AamlError::NotFound(name.to_string())),
+        }
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        match self {
+            IdTypes::Uuid if is_uuid(value) => Ok(()),
+            IdTypes::Uuid => Err(AamlError::InvalidValue(format!(
+                "Invalid UUID '{value}': expected xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx"
+            ))),
+        }
+    }
+
+    fn normalize(&self, value: &str) -> String {
+        value.trim().to_ascii_lowercase()
+    }
+
+    fn json_schema(&self) -> String {
+        match self {
+            IdTypes::Uuid => r#"{"type":"string","format":"uuid"}"#.to_string(),
+        }
+    }
+}
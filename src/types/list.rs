@@ -14,34 +14,181 @@
 //! ```text
 //! @schema Post { tags: list<string>, scores: list<f64> }
 //! ```
+//!
+//! ## Cardinality
+//! ```text
+//! @schema Firewall { allowed_ips: list<string, 1..>, ports: list<i32, 1..=10> }
+//! ```
+//!
+//! A range after the element type restricts how many items the list may hold,
+//! using the same `a..b` / `a..=b` syntax as numeric constraints. Bounds must
+//! be non-negative integers.
+//!
+//! ## Nesting
+//! ```text
+//! @schema Mesh { rows: list<list<f64>>, groups: list<map<string, list<i32>>> }
+//! ```
+//!
+//! The element type may itself be any type, including another generic. Errors
+//! in nested elements report the position at every level.
+//!
+//! ## Sets
+//! ```text
+//! @schema Firewall { allowed_ips: set<net::ipv4>, ports: set<i32, 1..> }
+//! ```
+//!
+//! `set<T>` is written and validated like `list<T>` but rejects duplicate
+//! elements. Elements are compared in their normalized form, so `set<i32>`
+//! treats `[1, 01]` as a duplicate.
 
 use crate::error::AamlError;
+use crate::types::constraint::NumericRange;
 use crate::types::primitive_type::PrimitiveType;
 use crate::types::{Type, resolve_builtin};
 
-/// A list type that validates every element against an inner type.
+/// A list type that validates every element against an inner type, and for
+/// `set<T>` rejects duplicates.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListType {
     /// Name of the inner element type (e.g. `"i32"`, `"math::vector3"`).
     pub(crate) inner_type: String,
+    /// Allowed number of elements (`list<T, 1..10>`), if restricted.
+    pub(crate) len: Option<NumericRange>,
+    /// Whether duplicate elements are rejected (`set<T>`).
+    pub(crate) unique: bool,
 }
 
 impl ListType {
     /// Creates a `ListType` wrapping the given inner type name.
     pub fn new(inner_type: String) -> Self {
-        Self { inner_type }
+        Self {
+            inner_type,
+            len: None,
+            unique: false,
+        }
+    }
+
+    /// Creates a `set<T>`: a list that rejects duplicate elements.
+    pub fn set(inner_type: String) -> Self {
+        Self {
+            unique: true,
+            ..Self::new(inner_type)
+        }
+    }
+
+    /// Restricts the number of elements the list may contain.
+    pub fn with_len(mut self, len: NumericRange) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Splits `list<T>` / `list<T, a..b>` (or the `set<...>` forms) into the
+    /// element type and the raw cardinality range, if one is given. Whatever
+    /// follows the last top-level comma is taken as the range, so a stray
+    /// segment is reported by [`parse`](Self::parse) rather than folded into
+    /// the element type.
+    ///
+    /// Returns `None` when the string is not a `list<...>` or `set<...>`, or
+    /// the element type is empty.
+    pub fn split(type_str: &str) -> Option<(&str, Option<&str>)> {
+        let t = type_str.trim();
+        let body = t
+            .strip_prefix("list<")
+            .or_else(|| t.strip_prefix("set<"))?
+            .strip_suffix('>')?;
+        let (inner, len) = match top_level_commas(body).last().copied() {
+            Some(i) => (&body[..i], Some(body[i + 1..].trim())),
+            None => (body, None),
+        };
+        let inner = inner.trim();
+        (!inner.is_empty()).then_some((inner, len))
     }
 
     /// Parses a `list<T>` type string and returns the inner type name.
     ///
-    /// Accepts both `list<T>` and `list<T>` with surrounding whitespace.
+    /// Accepts both `list<T>` and `list<T, a..b>` with surrounding whitespace.
     pub fn parse_inner(type_str: &str) -> Option<String> {
-        type_str
-            .trim()
-            .strip_prefix("list<")?
-            .strip_suffix('>')?
-            .trim()
-            .pipe_non_empty()
+        Self::split(type_str).map(|(inner, _)| inner.to_string())
+    }
+
+    /// Parses `list<T>`, `set<T>` or either with a `, a..b` cardinality,
+    /// without resolving `T`.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if the string is not a list type, or
+    /// [`AamlError::InvalidValue`] if the element type's `<` and `>` do not
+    /// balance or the cardinality range is malformed.
+    pub fn parse(type_str: &str) -> Result<Self, AamlError> {
+        let (inner, len) = Self::split(type_str)
+            .ok_or_else(|| AamlError::NotFound(type_str.to_string()))?;
+        if !angles_balanced(inner) {
+            return Err(AamlError::InvalidValue(format!(
+                "Unbalanced '<' and '>' in list element type '{inner}'"
+            )));
+        }
+        let list = if type_str.trim().starts_with("set<") {
+            Self::set(inner.to_string())
+        } else {
+            Self::new(inner.to_string())
+        };
+        match len {
+            Some(raw) => Ok(list.with_len(parse_len(raw)?)),
+            None => Ok(list),
+        }
+    }
+
+    /// Checks that a list of `count` items satisfies the cardinality range.
+    pub fn check_len(&self, count: usize) -> Result<(), AamlError> {
+        match &self.len {
+            Some(len) if !len.contains(count as f64) => Err(AamlError::InvalidValue(format!(
+                "List has {count} item(s), expected {len}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that no two items share the same normalized form when the list
+    /// is a set. `items` pairs each item's byte offset with its text.
+    pub(crate) fn check_unique(
+        &self,
+        items: &[(usize, String)],
+        normalize: impl Fn(&str) -> String,
+    ) -> Result<(), AamlError> {
+        if !self.unique {
+            return Ok(());
+        }
+        let mut seen: Vec<String> = Vec::with_capacity(items.len());
+        for (index, (offset, item)) in items.iter().enumerate() {
+            let key = normalize(item);
+            if let Some(first) = seen.iter().position(|k| *k == key) {
+                return Err(AamlError::InvalidValue(format!(
+                    "Set {} duplicates element [{first}]",
+                    describe_element(index, *offset, item)
+                )));
+            }
+            seen.push(key);
+        }
+        Ok(())
+    }
+
+    /// Renders the cardinality range as `minItems` / `maxItems` JSON Schema
+    /// keywords, and `uniqueItems` for sets, each preceded by a comma.
+    pub(crate) fn len_keywords(&self) -> String {
+        let mut out = String::new();
+        if self.unique {
+            out.push_str(r#","uniqueItems":true"#);
+        }
+        let Some(len) = &self.len else {
+            return out;
+        };
+        if let Some(a) = len.start {
+            out.push_str(&format!(r#","minItems":{a}"#));
+        }
+        if let Some(b) = len.end {
+            let max = if len.inclusive_end { b } else { b - 1.0 };
+            out.push_str(&format!(r#","maxItems":{max}"#));
+        }
+        out
     }
 
     /// Splits a `[…]` literal into top-level items, respecting nested `{}` and `[]`.
@@ -51,54 +198,108 @@ impl ListType {
         let inner = value.trim().strip_prefix('[')?.strip_suffix(']')?;
         Some(split_top_level(inner))
     }
+
+    /// Like [`parse_items`](Self::parse_items), but pairs each item with its
+    /// byte offset inside `value`, so errors can point at the failing element.
+    ///
+    /// `[1, 22]` → `[(1, "1"), (4, "22")]`
+    pub fn parse_items_with_offsets(value: &str) -> Option<Vec<(usize, String)>> {
+        let lead = value.len() - value.trim_start().len();
+        let inner = value.trim().strip_prefix('[')?.strip_suffix(']')?;
+        Some(
+            split_top_level_offsets(inner)
+                .into_iter()
+                .map(|(offset, item)| (lead + 1 + offset, item))
+                .collect(),
+        )
+    }
 }
 
-/// Splits `s` on commas that are not inside `{}` or `[]` nesting.
-pub(crate) fn split_top_level(s: &str) -> Vec<String> {
-    let mut items = Vec::new();
-    let mut depth: i32 = 0;
-    let mut cur = String::new();
+/// Parses a cardinality range, requiring non-negative integer bounds.
+fn parse_len(raw: &str) -> Result<NumericRange, AamlError> {
+    let len = NumericRange::parse(raw)?;
+    let valid = |b: Option<f64>| b.is_none_or(|n| n >= 0.0 && n.fract() == 0.0);
+    if !valid(len.start) || !valid(len.end) {
+        return Err(AamlError::InvalidValue(format!(
+            "List length '{raw}' must use non-negative integer bounds"
+        )));
+    }
+    Ok(len)
+}
 
+/// Returns `true` when every `<` in `s` outside `()` is closed by a later `>`.
+fn angles_balanced(s: &str) -> bool {
+    let (mut angles, mut parens) = (0i32, 0i32);
     for ch in s.chars() {
         match ch {
-            '{' | '[' => {
-                depth += 1;
-                cur.push(ch);
-            }
-            '}' | ']' => {
-                depth -= 1;
-                cur.push(ch);
-            }
-            ',' if depth == 0 => {
-                let t = cur.trim().to_string();
-                if !t.is_empty() {
-                    items.push(t);
+            '(' => parens += 1,
+            ')' => parens -= 1,
+            '<' if parens == 0 => angles += 1,
+            '>' if parens == 0 => {
+                angles -= 1;
+                if angles < 0 {
+                    return false;
                 }
-                cur.clear();
-            }
-            _ => {
-                cur.push(ch);
             }
+            _ => {}
         }
     }
-    let t = cur.trim().to_string();
-    if !t.is_empty() {
-        items.push(t);
+    angles == 0
+}
+
+/// Returns the byte indices of the commas in a type parameter list that lie
+/// outside `<>`, `()`, `[]`, `{}` nesting.
+pub(crate) fn top_level_commas(s: &str) -> Vec<usize> {
+    let mut depth: i32 = 0;
+    let mut commas = Vec::new();
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => commas.push(i),
+            _ => {}
+        }
     }
-    items
+    commas
 }
 
-trait PipeStr {
-    fn pipe_non_empty(self) -> Option<String>;
+/// Splits `s` on commas that are not inside `{}`, `[]` or `()` nesting.
+pub(crate) fn split_top_level(s: &str) -> Vec<String> {
+    split_top_level_offsets(s).into_iter().map(|(_, item)| item).collect()
 }
-impl PipeStr for &str {
-    fn pipe_non_empty(self) -> Option<String> {
-        if self.is_empty() {
-            None
-        } else {
-            Some(self.to_string())
+
+/// Like [`split_top_level`], but pairs each trimmed item with its byte offset in `s`.
+fn split_top_level_offsets(s: &str) -> Vec<(usize, String)> {
+    let mut items = Vec::new();
+    let mut depth: i32 = 0;
+    let mut start = 0;
+
+    let mut push = |segment: &str, start: usize| {
+        let t = segment.trim();
+        if !t.is_empty() {
+            let lead = segment.len() - segment.trim_start().len();
+            items.push((start + lead, t.to_string()));
+        }
+    };
+
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                push(&s[start..i], start);
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+    push(&s[start..], start);
+    items
+}
+
+/// Formats the position of a failing list element for error messages.
+pub(crate) fn describe_element(index: usize, offset: usize, item: &str) -> String {
+    format!("list element [{index}] '{item}' at offset {offset}")
 }
 
 impl Type for ListType {
@@ -116,28 +317,50 @@ impl Type for ListType {
     }
 
     /// Validates the list literal `[item, item, ...]` where each item must
-    /// satisfy the inner type.
+    /// satisfy the inner type and the item count must satisfy the cardinality.
+    /// Element errors name the index and byte offset of the failing item.
     fn validate(&self, value: &str) -> Result<(), AamlError> {
-        let items = ListType::parse_items(value).ok_or_else(|| {
+        let items = ListType::parse_items_with_offsets(value).ok_or_else(|| {
             AamlError::InvalidValue(format!(
                 "Expected a list literal in the form [item, item, ...], got '{}'",
                 value
             ))
         })?;
+        self.check_len(items.len())?;
 
         let inner = resolve_builtin(&self.inner_type).map_err(|_| {
             AamlError::NotFound(format!("Unknown list element type '{}'", self.inner_type))
         })?;
 
-        for item in &items {
+        for (index, (offset, item)) in items.iter().enumerate() {
             inner.validate(item).map_err(|e| {
                 AamlError::InvalidValue(format!(
-                    "List item '{}' failed validation for type '{}': {}",
-                    item, self.inner_type, e
+                    "{} failed validation for type '{}': {}",
+                    describe_element(index, *offset, item),
+                    self.inner_type,
+                    e
                 ))
             })?;
         }
 
-        Ok(())
+        self.check_unique(&items, |item| inner.normalize(item))
+    }
+
+    /// Normalizes every element and re-joins them as `[a, b, c]`.
+    fn normalize(&self, value: &str) -> String {
+        match (ListType::parse_items(value), resolve_builtin(&self.inner_type)) {
+            (Some(items), Ok(inner)) => {
+                let items: Vec<String> = items.iter().map(|i| inner.normalize(i)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            _ => value.trim().to_string(),
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        let items = resolve_builtin(&self.inner_type)
+            .map(|t| t.json_schema())
+            .unwrap_or_else(|_| "{}".to_string());
+        format!(r#"{{"type":"array","items":{items}{}}}"#, self.len_keywords())
     }
 }
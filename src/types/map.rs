@@ -0,0 +1,129 @@
+//! `map<K, V>` — a homogeneous dictionary type.
+//!
+//! ## Syntax in .aam files
+//! ```text
+//! limits = { cpu = 4, memory = 512 }
+//! ```
+//!
+//! The value must be an inline object. Keys are arbitrary; each key is
+//! validated against `K` and each value against `V`. Keys may not repeat.
+//!
+//! ## Schema usage
+//! ```text
+//! @schema Service { limits: map<string, i32>, labels*: map<string, string> }
+//! ```
+
+use crate::aaml::parsing;
+use crate::error::AamlError;
+use crate::types::list::top_level_commas;
+use crate::types::primitive_type::PrimitiveType;
+use crate::types::{Type, resolve_builtin};
+use std::collections::HashSet;
+
+/// A dictionary type whose keys and values all share one type each.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapType {
+    /// Name of the key type (usually `"string"`).
+    pub(crate) key_type: String,
+    /// Name of the value type (e.g. `"i32"`, `"math::vector3"`).
+    pub(crate) value_type: String,
+}
+
+impl MapType {
+    /// Creates a `MapType` from key and value type names.
+    pub fn new(key_type: String, value_type: String) -> Self {
+        Self {
+            key_type,
+            value_type,
+        }
+    }
+
+    /// Splits `map<K, V>` into `("K", "V")`.
+    ///
+    /// Returns `None` when the string is not a `map<...>` with exactly two
+    /// non-empty type parameters.
+    pub fn split(type_str: &str) -> Option<(&str, &str)> {
+        let body = type_str.trim().strip_prefix("map<")?.strip_suffix('>')?;
+        let [comma] = top_level_commas(body)[..] else {
+            return None;
+        };
+        let key = body[..comma].trim();
+        let value = body[comma + 1..].trim();
+        (!key.is_empty() && !value.is_empty()).then_some((key, value))
+    }
+
+    /// Parses a `map<K, V>` type string without resolving `K` or `V`.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if the string is not a valid map type.
+    pub fn parse(type_str: &str) -> Result<Self, AamlError> {
+        let (key, value) =
+            Self::split(type_str).ok_or_else(|| AamlError::NotFound(type_str.to_string()))?;
+        Ok(Self::new(key.to_string(), value.to_string()))
+    }
+
+    /// Parses an inline object literal into its entries, rejecting duplicate keys.
+    pub fn parse_entries(value: &str) -> Result<Vec<(String, String)>, AamlError> {
+        if !parsing::is_inline_object(value) {
+            return Err(AamlError::InvalidValue(format!(
+                "Expected an inline object '{{ key = value, ... }}', got '{value}'"
+            )));
+        }
+        let entries = parsing::parse_inline_object(value).map_err(AamlError::InvalidValue)?;
+        let mut seen = HashSet::new();
+        if let Some((dup, _)) = entries.iter().find(|(k, _)| !seen.insert(k.as_str())) {
+            return Err(AamlError::InvalidValue(format!("Duplicate map key '{dup}'")));
+        }
+        Ok(entries)
+    }
+}
+
+impl Type for MapType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "MapType::from_name — use MapType::new instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    /// Validates the inline object `{ key = value, ... }` where every key must
+    /// satisfy the key type and every value the value type.
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        let entries = Self::parse_entries(value)?;
+        let key_type = resolve_builtin(&self.key_type).map_err(|_| {
+            AamlError::NotFound(format!("Unknown map key type '{}'", self.key_type))
+        })?;
+        let value_type = resolve_builtin(&self.value_type).map_err(|_| {
+            AamlError::NotFound(format!("Unknown map value type '{}'", self.value_type))
+        })?;
+
+        for (k, v) in &entries {
+            key_type.validate(k).map_err(|e| {
+                AamlError::InvalidValue(format!(
+                    "Map key '{k}' failed validation for type '{}': {e}",
+                    self.key_type
+                ))
+            })?;
+            value_type.validate(v).map_err(|e| {
+                AamlError::InvalidValue(format!(
+                    "Map value for key '{k}' failed validation for type '{}': {e}",
+                    self.value_type
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn json_schema(&self) -> String {
+        let values = resolve_builtin(&self.value_type)
+            .map(|t| t.json_schema())
+            .unwrap_or_else(|_| "{}".to_string());
+        format!(r#"{{"type":"object","additionalProperties":{values}}}"#)
+    }
+}
@@ -1,12 +1,28 @@
+//! Vector, quaternion and matrix types (`math::vector3`, `math::quaternion`,
+//! `math::matrix4x4`, ...), written as comma-separated floats.
+//!
+//! Matrices (`matrix2x2`, `matrix3x3`, `matrix4x4`) list their elements in
+//! row-major order; errors name the row and column of a bad element.
+//!
+//! Quaternions are written `x, y, z, w`. `math::unit_quaternion` additionally
+//! requires the quaternion to be normalized, as rotations must be; its length
+//! may differ from 1 by at most [`UNIT_TOLERANCE`] to allow for rounded
+//! hand-written values.
+
 use crate::error::AamlError;
 use crate::types::Type;
 
+/// How far the length of a `math::unit_quaternion` may be from 1.
+pub const UNIT_TOLERANCE: f64 = 1e-3;
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MathTypes {
     Vector2,
     Vector3,
     Vector4,
     Quaternion,
+    UnitQuaternion,
+    Matrix2x2,
     Matrix3x3,
     Matrix4x4,
 }
@@ -21,6 +37,8 @@ impl Type for MathTypes {
             "vector3" => Ok(MathTypes::Vector3),
             "vector4" => Ok(MathTypes::Vector4),
             "quaternion" => Ok(MathTypes::Quaternion),
+            "unit_quaternion" => Ok(MathTypes::UnitQuaternion),
+            "matrix2x2" => Ok(MathTypes::Matrix2x2),
             "matrix3x3" => Ok(MathTypes::Matrix3x3),
             "matrix4x4" => Ok(MathTypes::Matrix4x4),
             _ => Err(AamlError::NotFound(name.to_string())),
@@ -33,28 +51,94 @@ impl Type for MathTypes {
 
     fn validate(&self, value: &str) -> Result<(), AamlError> {
         let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
-        let expected_len = match self {
-            MathTypes::Vector2 => 2,
-            MathTypes::Vector3 => 3,
-            MathTypes::Vector4 | MathTypes::Quaternion => 4,
-            MathTypes::Matrix3x3 => 9,
-            MathTypes::Matrix4x4 => 16,
-        };
+        let expected_len = self.component_count();
 
         if parts.len() != expected_len {
             return Err(AamlError::InvalidValue(format!(
-                "Expected {} components, got {}",
+                "Expected {} components for {}, got {}",
                 expected_len,
+                self.name(),
                 parts.len()
             )));
         }
 
-        for part in parts {
-            if part.parse::<f64>().is_err() {
-                return Err(AamlError::InvalidValue(format!("Invalid number: {}", part)));
+        let mut components = Vec::with_capacity(expected_len);
+        for (index, part) in parts.into_iter().enumerate() {
+            match part.parse::<f64>() {
+                Ok(n) => components.push(n),
+                Err(_) => {
+                    return Err(AamlError::InvalidValue(format!(
+                        "Invalid number '{}' at {} of {}",
+                        part,
+                        self.position(index),
+                        self.name()
+                    )));
+                }
+            }
+        }
+
+        if let MathTypes::UnitQuaternion = self {
+            let length = components.iter().map(|c| c * c).sum::<f64>().sqrt();
+            if components.iter().any(|c| !c.is_finite()) || (length - 1.0).abs() > UNIT_TOLERANCE {
+                return Err(AamlError::InvalidValue(format!(
+                    "Expected a unit quaternion, got length {length}"
+                )));
             }
         }
 
         Ok(())
     }
+
+    /// Vectors and matrices are described as fixed-length arrays of numbers.
+    fn json_schema(&self) -> String {
+        let n = self.component_count();
+        format!(r#"{{"type":"array","items":{{"type":"number"}},"minItems":{n},"maxItems":{n}}}"#)
+    }
+}
+
+impl MathTypes {
+    /// Number of comma-separated float components a value of this type must have.
+    fn component_count(&self) -> usize {
+        match self {
+            MathTypes::Vector2 => 2,
+            MathTypes::Vector3 => 3,
+            MathTypes::Vector4 | MathTypes::Quaternion | MathTypes::UnitQuaternion => 4,
+            MathTypes::Matrix2x2 => 4,
+            MathTypes::Matrix3x3 => 9,
+            MathTypes::Matrix4x4 => 16,
+        }
+    }
+
+    /// Number of rows (and columns) for square matrix types.
+    fn matrix_size(&self) -> Option<usize> {
+        match self {
+            MathTypes::Matrix2x2 => Some(2),
+            MathTypes::Matrix3x3 => Some(3),
+            MathTypes::Matrix4x4 => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Describes where the component at `index` sits: `row 2, column 3` in a
+    /// matrix (row-major, 1-based), `component 2` otherwise.
+    fn position(&self, index: usize) -> String {
+        match self.matrix_size() {
+            Some(n) => format!("row {}, column {}", index / n + 1, index % n + 1),
+            None => format!("component {}", index + 1),
+        }
+    }
+
+    /// The type's name as written after `math::`.
+    fn name(&self) -> &'static str {
+        match self {
+            MathTypes::Vector2 => "vector2",
+            MathTypes::Vector3 => "vector3",
+            MathTypes::Vector4 => "vector4",
+            MathTypes::Quaternion => "quaternion",
+            MathTypes::UnitQuaternion => "unit_quaternion",
+            MathTypes::Matrix2x2 => "matrix2x2",
+            MathTypes::Matrix3x3 => "matrix3x3",
+            MathTypes::Matrix4x4 => "matrix4x4",
+        }
+    }
 }
@@ -7,22 +7,56 @@
 //! ## Built-in type paths
 //! | Path | Description |
 //! |------|-------------|
-//! | `i32` / `f64` / `string` / `bool` / `color` | Primitive types |
-//! | `math::vector2` … `math::matrix4x4` | N-component float vectors/matrices |
-//! | `physics::kilogram` | Non-negative floating-point mass |
+//! | `i32` / `f64` / `string` / `bool` | Primitive types |
+//! | `bool_relaxed` | `bool` that also accepts `yes`/`no`, `on`/`off` and `y`/`n` |
+//! | `color` | `#rrggbb[aa]`, `rgb()` / `rgba()`, `hsl()` / `hsla()` or a CSS color name |
+//! | `enum(a, b, ...)` | One of a fixed set of string variants |
+//! | `map<K, V>` | Inline object with arbitrary keys of type `K` and values of type `V` |
+//! | `optional<T>` / `nullable<T>` | `none` / `null`, or a value of type `T` |
+//! | `set<T>` | A `[...]` list of `T` without duplicate elements |
+//! | `range<T>` | `start..end` / `start..=end` literal with endpoints of type `T` |
+//! | `A \| B` / `union<A, B>` | A value accepted by any member type |
+//! | `i32(1..65536)` / `f64(0.0..=1.0)` | Numeric type restricted to a range |
+//! | `string(/^[a-z]+$/)` | Value must match a regular expression (`regex` feature) |
+//! | `math::vector2` … `math::vector4`, `math::matrix2x2` … `math::matrix4x4` | N-component float vectors / row-major square matrices |
+//! | `math::quaternion` / `math::unit_quaternion` | Rotation `x, y, z, w`, optionally required to be normalized |
+//! | `physics::kilogram`, `physics::meter`, … | Physical quantity, bare or with a unit suffix (`75kg`, `3.5 km`) |
 //! | `time::datetime` | ISO 8601 date or datetime string |
+//! | `time::human_duration` | Duration such as `5s`, `2h30m` or `250ms` |
+//! | `net::ipv4` / `net::ipv6` / `net::socket_addr` / `net::cidr` | Network addresses and ranges |
+//! | `net::url` / `net::url(https, ...)` | Absolute URL, optionally limited to some schemes |
+//! | `id::uuid` | Canonical hyphenated UUID |
+//! | `fs::path` / `fs::existing_file` / `fs::existing_dir` | Filesystem path, optionally required to exist |
+//! | `geo::latitude` / `geo::longitude` / `geo::coordinate` | Decimal degrees; a coordinate is `lat, lon` |
+//! | `data::bytes` | Byte size with an optional SI/IEC unit (`10MB`, `512KiB`) |
 
 use crate::error::AamlError;
 use crate::types::primitive_type::PrimitiveType;
+use crate::units::Dimension;
 
 pub(crate) mod physics;
 pub(crate) mod primitive_type;
+pub(crate) mod color;
 pub(crate) mod list;
-mod math;
-mod time;
+pub(crate) mod map;
+pub(crate) mod optional;
+pub(crate) mod range;
+pub(crate) mod union;
+pub(crate) mod enumeration;
+pub(crate) mod constraint;
+pub(crate) mod math;
+pub(crate) mod time;
+pub(crate) mod data;
+mod net;
+pub(crate) mod id;
+mod fs;
+mod geo;
 
 /// Core trait that every AAML type must implement.
-pub trait Type {
+///
+/// Types are `Send + Sync` so a parsed [`AAML`](crate::aaml::AAML) can be
+/// shared across threads (see [`AAML::apply_schema_batch_parallel`](crate::aaml::AAML::apply_schema_batch_parallel)).
+pub trait Type: Send + Sync {
     /// Constructs the type from a name string.
     ///
     /// Used internally by [`resolve_builtin`] to create type instances from
@@ -39,6 +73,40 @@ pub trait Type {
     /// Returns `Ok(())` if the value is acceptable, or an
     /// [`AamlError`] with a human-readable message otherwise.
     fn validate(&self, value: &str) -> Result<(), AamlError>;
+
+    /// Returns a JSON Schema fragment describing the values this type accepts.
+    ///
+    /// The default implementation maps [`base_type`](Type::base_type) onto the
+    /// closest JSON Schema primitive. Types with a richer structure (vectors,
+    /// lists, formatted strings) override it.
+    fn json_schema(&self) -> String {
+        primitive_type::json_schema_for(self.base_type())
+    }
+
+    /// Returns the canonical form of a value that already passed
+    /// [`validate`](Type::validate).
+    ///
+    /// Used when coercion is enabled (see [`AAML::with_coercion`](crate::aaml::AAML::with_coercion)).
+    /// The default implementation only trims surrounding whitespace.
+    fn normalize(&self, value: &str) -> String {
+        value.trim().to_string()
+    }
+
+    /// Returns the name of the registered type or schema this type stands for,
+    /// when it is a pure alias (`@type b = a`).
+    ///
+    /// The [`AAML`](crate::aaml::AAML) registry follows alias targets before
+    /// validating, so an alias behaves exactly like the type it names.
+    fn alias_target(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the physical dimension of the quantity this type measures.
+    ///
+    /// `physics::` unit types report theirs; every other type returns `None`.
+    fn dimension(&self) -> Option<Dimension> {
+        None
+    }
 }
 
 /// Resolves a type from a module-qualified path or a plain primitive name.
@@ -47,15 +115,59 @@ pub trait Type {
 /// - `math::<name>` — see [`math::MathTypes`]
 /// - `time::<name>` — see [`time::TimeTypes`]
 /// - `physics::<name>` — see [`physics::PhysicsTypes`]
+/// - `data::<name>` — see [`data::DataTypes`]
+/// - `net::<name>` — see [`net::NetTypes`]
+/// - `id::<name>` — see [`id::IdTypes`]
+/// - `fs::<name>` — see [`fs::FsTypes`]
+/// - `geo::<name>` — see [`geo::GeoTypes`]
+/// - `net::url(a, b)` — an absolute URL whose scheme is `a` or `b`
+/// - `A | B` / `union<A, B>` — a value accepted by any member type
 /// - `list<T>` — a homogeneous list of elements with type `T`
+/// - `list<T, a..b>` — a list whose item count lies inside a range
+/// - `set<T>` / `set<T, a..b>` — a list without duplicate elements
+/// - `map<K, V>` — an inline object with keys of type `K` and values of type `V`
+/// - `optional<T>` / `nullable<T>` — `none` / `null`, or a value of type `T`
+/// - `range<T>` — a `start..end` / `start..=end` literal with endpoints of type `T`
+/// - `enum(a, b, ...)` — one of a fixed set of string variants
+/// - `<type>(a..b)` / `<type>[a..b]` — a numeric type restricted to a range
+/// - `<type>(/pattern/)` — a value matching a regular expression (`regex` feature)
 /// - `<name>` (no `::`) — a [`PrimitiveType`] name
 ///
 /// # Errors
 /// [`AamlError::NotFound`] if the path is not recognised.
 pub fn resolve_builtin(path: &str) -> Result<Box<dyn Type>, AamlError> {
+    // A | B — binds loosest, so it is split before any other form
+    if union::UnionType::split(path).is_some() {
+        return Ok(Box::new(union::UnionType::parse(path)?));
+    }
+
     // list<T> — must be checked before splitn to avoid confusion
-    if let Some(inner) = list::ListType::parse_inner(path) {
-        return Ok(Box::new(list::ListType::new(inner)));
+    if list::ListType::split(path).is_some() {
+        return Ok(Box::new(list::ListType::parse(path)?));
+    }
+
+    if map::MapType::split(path).is_some() {
+        return Ok(Box::new(map::MapType::parse(path)?));
+    }
+
+    if let Some(inner) = optional::OptionalType::parse_inner(path) {
+        return Ok(Box::new(optional::OptionalType::new(inner.to_string())));
+    }
+
+    if let Some(inner) = range::RangeType::parse_inner(path) {
+        return Ok(Box::new(range::RangeType::new(inner.to_string())));
+    }
+
+    if enumeration::EnumType::is_enum(path) {
+        return Ok(Box::new(enumeration::EnumType::parse(path)?));
+    }
+
+    if let Some(schemes) = net::NetTypes::url_schemes(path) {
+        return Ok(Box::new(net::NetTypes::url(schemes)?));
+    }
+
+    if constraint::ConstrainedType::split(path).is_some() {
+        return Ok(Box::new(constraint::ConstrainedType::parse(path)?));
     }
 
     let parts: Vec<&str> = path.splitn(2, "::").collect();
@@ -64,7 +176,89 @@ pub fn resolve_builtin(path: &str) -> Result<Box<dyn Type>, AamlError> {
         ["math", name] => Ok(Box::new(math::MathTypes::from_name(name)?)),
         ["time", name] => Ok(Box::new(time::TimeTypes::from_name(name)?)),
         ["physics", name] => Ok(Box::new(physics::PhysicsTypes::from_name(name)?)),
+        ["data", name] => Ok(Box::new(data::DataTypes::from_name(name)?)),
+        ["net", name] => Ok(Box::new(net::NetTypes::from_name(name)?)),
+        ["id", name] => Ok(Box::new(id::IdTypes::from_name(name)?)),
+        ["fs", name] => Ok(Box::new(fs::FsTypes::from_name(name)?)),
+        ["geo", name] => Ok(Box::new(geo::GeoTypes::from_name(name)?)),
         [name] => Ok(Box::new(primitive_type::PrimitiveType::from_name(name)?)),
         _ => Err(AamlError::NotFound(path.to_string())),
     }
-}
\ No newline at end of file
+}
+
+/// Checks the syntax of a parameterised type string (`enum(...)`, `i32(a..b)`, `net::url(...)`,
+/// `string(/re/)`, `list<T, a..b>`, `map<K, V>`, `optional<T>`, `A | B`)
+/// without resolving its base name, so malformed declarations are reported
+/// when a `@schema` or `@type` directive runs rather than on first use.
+///
+/// Generic parameters are checked recursively, so `list<map<string, list<i32>>>`
+/// is accepted while `list<list<i32>` or `list<map<string>>` is rejected.
+pub(crate) fn check_declaration(type_str: &str) -> Result<(), AamlError> {
+    if union::UnionType::split(type_str).is_some() {
+        let union = union::UnionType::parse(type_str)?;
+        return union.members.iter().try_for_each(|m| check_declaration(m));
+    }
+    if list::ListType::split(type_str).is_some() {
+        let list = list::ListType::parse(type_str)?;
+        return check_declaration(&list.inner_type);
+    }
+    if let Some((key, value)) = map::MapType::split(type_str) {
+        check_declaration(key)?;
+        return check_declaration(value);
+    }
+    if let Some(inner) = optional::OptionalType::parse_inner(type_str) {
+        return check_declaration(inner);
+    }
+    if let Some(inner) = range::RangeType::parse_inner(type_str) {
+        return check_declaration(inner);
+    }
+    if enumeration::EnumType::is_enum(type_str) {
+        return enumeration::EnumType::parse(type_str).map(|_| ());
+    }
+    if let Some(schemes) = net::NetTypes::url_schemes(type_str) {
+        return net::NetTypes::url(schemes).map(|_| ());
+    }
+    if let Some((_, args)) = constraint::ConstrainedType::split(type_str) {
+        return constraint::Constraint::parse(args).map(|_| ());
+    }
+    if let Some(head) = generic_head(type_str) {
+        return Err(AamlError::InvalidType {
+            type_name: type_str.trim().to_string(),
+            details: format!("Malformed {head}<...> type: unbalanced '<>' or missing parameters"),
+            location: None,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the name of the generic constructor `type_str` starts with
+/// (`list`, `set`, `map`, `optional`, `nullable`, `range`, `union`), if any.
+fn generic_head(type_str: &str) -> Option<&'static str> {
+    let t = type_str.trim();
+    ["list", "set", "map", "optional", "nullable", "range", "union"]
+        .into_iter()
+        .find(|head| t.strip_prefix(head).is_some_and(|rest| rest.starts_with('<')))
+}
+
+/// Returns `true` when `path` resolves without consulting any registry — i.e.
+/// it is a built-in type, and so is every element type of a `list<T>`, both
+/// parameters of a `map<K, V>`, the inner type of an `optional<T>` and every
+/// member of a union.
+pub(crate) fn is_builtin(path: &str) -> bool {
+    if let Some(members) = union::UnionType::split(path) {
+        return members.iter().all(|m| is_builtin(m));
+    }
+    if let Some(inner) = list::ListType::parse_inner(path) {
+        return is_builtin(&inner);
+    }
+    if let Some((key, value)) = map::MapType::split(path) {
+        return is_builtin(key) && is_builtin(value);
+    }
+    if let Some(inner) = optional::OptionalType::parse_inner(path) {
+        return is_builtin(inner);
+    }
+    if let Some(inner) = range::RangeType::parse_inner(path) {
+        return is_builtin(inner);
+    }
+    resolve_builtin(path).is_ok()
+}
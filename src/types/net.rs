@@ -0,0 +1,185 @@
+//! Network address types (`net::ipv4`, `net::ipv6`, `net::socket_addr`,
+//! `net::cidr`), validated with the [`std::net`] parsers, and absolute URLs
+//! (`net::url`).
+//!
+//! A URL type may list the schemes it allows: `net::url(https)` or
+//! `net::url(postgres, postgresql)`. Scheme names compare case-insensitively.
+//! With the `url` feature URLs are checked by the `url` crate's WHATWG parser;
+//! without it a lighter structural check is used (a valid scheme followed by a
+//! non-empty remainder without whitespace).
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//!
+//! let content = "@schema Server { bind: net::socket_addr, allow: net::cidr }\n\
+//!                bind = 0.0.0.0:8080\n\
+//!                allow = 10.0.0.0/8";
+//! assert!(AAML::parse(content).is_ok());
+//! assert!(AAML::parse("@schema Server { bind: net::socket_addr }\nbind = localhost").is_err());
+//!
+//! let hook = "@schema Hook { target: net::url(https) }\ntarget = ";
+//! assert!(AAML::parse(&format!("{hook}https://example.com/hook")).is_ok());
+//! assert!(AAML::parse(&format!("{hook}http://example.com/hook")).is_err());
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::primitive_type::PrimitiveType;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetTypes {
+    Ipv4,
+    Ipv6,
+    SocketAddr,
+    Cidr,
+    /// An absolute URL; a non-empty list restricts the allowed schemes.
+    Url(Vec<String>),
+}
+
+/// Returns `true` when `value` is an IPv4 or IPv6 network in CIDR notation
+/// (`10.0.0.0/8`, `fd00::/64`) with a prefix length that fits the address.
+fn is_cidr(value: &str) -> bool {
+    let Some((addr, prefix)) = value.split_once('/') else {
+        return false;
+    };
+    let Ok(prefix) = prefix.parse::<u8>() else {
+        return false;
+    };
+    match addr.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => prefix <= 32,
+        Ok(IpAddr::V6(_)) => prefix <= 128,
+        Err(_) => false,
+    }
+}
+
+impl NetTypes {
+    /// Returns the scheme list of a `net::url(...)` declaration.
+    pub(crate) fn url_schemes(type_str: &str) -> Option<&str> {
+        type_str.trim().strip_prefix("net::url(")?.strip_suffix(')')
+    }
+
+    /// Builds a `net::url` type from a comma-separated scheme list.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] if the list is empty or a name is not a
+    /// valid URL scheme.
+    pub(crate) fn url(schemes: &str) -> Result<Self, AamlError> {
+        let schemes = schemes
+            .split(',')
+            .map(|scheme| {
+                let scheme = scheme.trim();
+                if is_scheme(scheme) {
+                    Ok(scheme.to_ascii_lowercase())
+                } else {
+                    Err(AamlError::InvalidValue(format!(
+                        "Invalid URL scheme '{scheme}' in net::url({schemes})"
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(NetTypes::Url(schemes))
+    }
+}
+
+/// Returns `true` for an RFC 3986 scheme: a letter followed by letters,
+/// digits, `+`, `-` or `.`.
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Returns the lowercase scheme of `value` if it is an absolute URL.
+#[cfg(feature = "url")]
+fn url_scheme(value: &str) -> Option<String> {
+    url::Url::parse(value).ok().map(|url| url.scheme().to_string())
+}
+
+/// Returns the lowercase scheme of `value` if it is an absolute URL.
+#[cfg(not(feature = "url"))]
+fn url_scheme(value: &str) -> Option<String> {
+    let (scheme, rest) = value.split_once(':')?;
+    let rest_ok = !rest.is_empty()
+        && rest != "//"
+        && !rest.chars().any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | '"'));
+    (is_scheme(scheme) && rest_ok).then(|| scheme.to_ascii_lowercase())
+}
+
+/// Checks that `value` is an absolute URL whose scheme is in `schemes` (any
+/// scheme when the list is empty).
+fn check_url(value: &str, schemes: &[String]) -> Result<(), AamlError> {
+    let scheme = url_scheme(value).ok_or_else(|| {
+        AamlError::InvalidValue(format!(
+            "Invalid URL '{value}': expected an absolute URL like https://example.com"
+        ))
+    })?;
+    if schemes.is_empty() || schemes.contains(&scheme) {
+        Ok(())
+    } else {
+        Err(AamlError::InvalidValue(format!(
+            "URL scheme '{scheme}' in '{value}' is not allowed, expected one of: {}",
+            schemes.join(", ")
+        )))
+    }
+}
+
+impl Type for NetTypes {
+    fn from_name(name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        match name {
+            "ipv4" => Ok(NetTypes::Ipv4),
+            "ipv6" => Ok(NetTypes::Ipv6),
+            "socket_addr" => Ok(NetTypes::SocketAddr),
+            "cidr" => Ok(NetTypes::Cidr),
+            "url" => Ok(NetTypes::Url(Vec::new())),
+            _ => Err(AamlError::NotFound(name.to_string())),
+        }
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        let (valid, expected) = match self {
+            NetTypes::Url(schemes) => return check_url(value, schemes),
+            NetTypes::Ipv4 => (value.parse::<Ipv4Addr>().is_ok(), "an IPv4 address like 192.168.0.1"),
+            NetTypes::Ipv6 => (value.parse::<Ipv6Addr>().is_ok(), "an IPv6 address like ::1"),
+            NetTypes::SocketAddr => (
+                value.parse::<SocketAddr>().is_ok(),
+                "a socket address like 127.0.0.1:8080 or [::1]:8080",
+            ),
+            NetTypes::Cidr => (is_cidr(value), "a CIDR range like 10.0.0.0/8 or fd00::/64"),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(AamlError::InvalidValue(format!(
+                "Invalid network address '{value}': expected {expected}"
+            )))
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        match self {
+            NetTypes::Ipv4 => r#"{"type":"string","format":"ipv4"}"#.to_string(),
+            NetTypes::Ipv6 => r#"{"type":"string","format":"ipv6"}"#.to_string(),
+            NetTypes::SocketAddr | NetTypes::Cidr => r#"{"type":"string"}"#.to_string(),
+            NetTypes::Url(schemes) if schemes.is_empty() => {
+                r#"{"type":"string","format":"uri"}"#.to_string()
+            }
+            NetTypes::Url(schemes) => {
+                let escaped: Vec<String> =
+                    schemes.iter().map(|s| s.replace('.', r"\\.").replace('+', r"\\+")).collect();
+                format!(
+                    r#"{{"type":"string","format":"uri","pattern":"^({}):"}}"#,
+                    escaped.join("|")
+                )
+            }
+        }
+    }
+}
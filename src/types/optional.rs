@@ -0,0 +1,108 @@
+//! `optional<T>` (also written `nullable<T>`) — a value of type `T` or an
+//! explicit "unset" marker.
+//!
+//! ## Syntax in .aam files
+//! ```text
+//! proxy = none
+//! proxy = null
+//! proxy = "http://proxy.local:3128"
+//! ```
+//!
+//! Unlike the `*` field marker, which lets a key be absent, `optional<T>`
+//! applies to keys that are present but intentionally unset: the literals
+//! `none` and `null` are accepted, and any other value must satisfy `T`.
+//!
+//! The unset literals are distinct from the empty string: `proxy = ""` is
+//! checked against `T` like any other value, while a schema field assigned
+//! nothing at all (`proxy =`) is rejected.
+//!
+//! ## Schema usage
+//! ```text
+//! @schema Net { proxy: optional<string>, timeout*: nullable<i32>, retries?: i32 }
+//! ```
+//!
+//! A `?` after a field name is shorthand for wrapping its type in `optional<T>`.
+
+use crate::error::AamlError;
+use crate::types::primitive_type::PrimitiveType;
+use crate::types::{Type, resolve_builtin};
+
+/// Literals that mark an `optional<T>` value as unset.
+const UNSET: [&str; 2] = ["none", "null"];
+
+/// A wrapper type that accepts `none`/`null` or a value of the inner type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionalType {
+    /// Name of the wrapped type (e.g. `"i32"`, `"math::vector3"`).
+    pub(crate) inner_type: String,
+}
+
+impl OptionalType {
+    /// Creates an `OptionalType` wrapping the given inner type name.
+    pub fn new(inner_type: String) -> Self {
+        Self { inner_type }
+    }
+
+    /// Parses an `optional<T>` or `nullable<T>` type string and returns the
+    /// inner type name.
+    pub fn parse_inner(type_str: &str) -> Option<&str> {
+        let t = type_str.trim();
+        let inner = t
+            .strip_prefix("optional<")
+            .or_else(|| t.strip_prefix("nullable<"))?
+            .strip_suffix('>')?
+            .trim();
+        (!inner.is_empty()).then_some(inner)
+    }
+
+    /// Returns `true` when `value` is one of the unset literals (`none`, `null`).
+    pub fn is_unset(value: &str) -> bool {
+        UNSET.contains(&value.trim())
+    }
+}
+
+impl Type for OptionalType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "OptionalType::from_name — use OptionalType::new instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        resolve_builtin(&self.inner_type)
+            .map(|t| t.base_type())
+            .unwrap_or(PrimitiveType::String)
+    }
+
+    /// Accepts `none`/`null`; any other value must satisfy the inner type.
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        if Self::is_unset(value) {
+            return Ok(());
+        }
+        resolve_builtin(&self.inner_type)
+            .map_err(|_| {
+                AamlError::NotFound(format!("Unknown optional type '{}'", self.inner_type))
+            })?
+            .validate(value)
+    }
+
+    /// Unset markers are trimmed and kept as written; other values use the
+    /// inner type's form, or are trimmed when the inner type is not built in.
+    fn normalize(&self, value: &str) -> String {
+        if Self::is_unset(value) {
+            return value.trim().to_string();
+        }
+        resolve_builtin(&self.inner_type)
+            .map_or_else(|_| value.trim().to_string(), |t| t.normalize(value))
+    }
+
+    fn json_schema(&self) -> String {
+        let inner = resolve_builtin(&self.inner_type)
+            .map(|t| t.json_schema())
+            .unwrap_or_else(|_| "{}".to_string());
+        format!(r#"{{"anyOf":[{inner},{{"type":"null"}}]}}"#)
+    }
+}
@@ -2,6 +2,8 @@ use std::fmt;
 use crate::error::AamlError;
 use crate::types::primitive_type::PrimitiveType;
 use crate::types::Type;
+use crate::units::{self, Dimension, UnitSymbol};
+use crate::types::primitive_type::parse_float;
 
 pub(crate) enum PhysicsTypes {
     Meter,
@@ -136,6 +138,194 @@ pub(crate) enum PhysicsTypes {
     Horsepower
 }
 
+/// Shorthand for [`Dimension::new`]: exponents of m, kg, s, A, K, mol, cd.
+const fn dim(m: i8, kg: i8, s: i8, a: i8, k: i8, mol: i8, cd: i8) -> Dimension {
+    Dimension::new(m, kg, s, a, k, mol, cd)
+}
+
+const NONE: Dimension = Dimension::DIMENSIONLESS;
+const LENGTH: Dimension = dim(1, 0, 0, 0, 0, 0, 0);
+const FREQUENCY: Dimension = dim(0, 0, -1, 0, 0, 0, 0);
+const ENERGY: Dimension = dim(2, 1, -2, 0, 0, 0, 0);
+const POWER: Dimension = dim(2, 1, -3, 0, 0, 0, 0);
+const PRESSURE: Dimension = dim(-1, 1, -2, 0, 0, 0, 0);
+const TEMPERATURE: Dimension = dim(0, 0, 0, 0, 1, 0, 0);
+const CHARGE: Dimension = dim(0, 0, 1, 1, 0, 0, 0);
+const MAGNETIC_FLUX: Dimension = dim(2, 1, -2, -1, 0, 0, 0);
+const MAGNETIC_FIELD: Dimension = dim(0, 1, -2, -1, 0, 0, 0);
+const LUMINANCE: Dimension = dim(-2, 0, 0, 0, 0, 0, 1);
+const DOSE: Dimension = dim(2, 0, -2, 0, 0, 0, 0);
+
+impl PhysicsTypes {
+    /// Returns the symbol of this unit, if values may be written with a unit
+    /// suffix (see [`units::SYMBOLS`]).
+    pub(crate) fn symbol(&self) -> Option<&'static UnitSymbol> {
+        use PhysicsTypes::*;
+        let symbol = match self {
+            Meter => "m",
+            Kilogram => "kg",
+            Second => "s",
+            Ampere => "A",
+            Kelvin => "K",
+            Mole => "mol",
+            Candela => "cd",
+            SquareMeter => "m2",
+            CubicMeter => "m3",
+            MeterPerSecond => "m/s",
+            MeterPerSecondSquared => "m/s2",
+            Hertz => "Hz",
+            Newton => "N",
+            Joule => "J",
+            Watt => "W",
+            Pascal => "Pa",
+            Coulomb => "C",
+            Volt => "V",
+            Ohm => "ohm",
+            Farad => "F",
+            Siemens => "S",
+            Tesla => "T",
+            Weber => "Wb",
+            Henry => "H",
+            Radian => "rad",
+            ArcDegree => "deg",
+            ArcMinute => "arcmin",
+            ArcSecond => "arcsec",
+            Celsius => "°C",
+            Fahrenheit => "°F",
+            Rankine => "°R",
+            ElectronVolt => "eV",
+            Calorie => "cal",
+            BritishThermalUnit => "BTU",
+            Horsepower => "hp",
+            Bar => "bar",
+            Atmosphere => "atm",
+            MillimeterOfMercury => "mmHg",
+            Torr => "Torr",
+            Knots => "kn",
+            NauticalMile => "nmi",
+            LightYear => "ly",
+            Parsec => "pc",
+            AstronomicalUnit => "au",
+            Dalton => "Da",
+            _ => return None,
+        };
+        units::symbol(symbol)
+    }
+
+    /// Parses `value` as a quantity of this unit: a bare number, or a number
+    /// followed by a symbol of the same dimension (`3.5km` for a `meter`),
+    /// converted into this unit.
+    ///
+    /// # Errors
+    /// [`AamlError::InvalidValue`] if the value is not a number, the suffix is
+    /// not a known symbol, or it measures a different dimension.
+    pub(crate) fn quantity(&self, value: &str) -> Result<f64, AamlError> {
+        let value = value.trim();
+        if let Some(n) = parse_float(value) {
+            return Ok(n);
+        }
+        let Some((n, unit)) = units::split_quantity(value) else {
+            return Err(AamlError::InvalidValue(format!(
+                "Expected number for unit {self}, got '{value}'"
+            )));
+        };
+        let Some(target) = self.symbol() else {
+            return Err(AamlError::InvalidValue(format!(
+                "Unit {self} does not accept a unit suffix, got '{value}'"
+            )));
+        };
+        unit.convert(n, target).ok_or_else(|| {
+            AamlError::InvalidValue(format!(
+                "Unit '{}' ({}) in '{value}' is not compatible with {self} ({})",
+                unit.symbol, unit.dimension, target.dimension
+            ))
+        })
+    }
+
+    /// Returns the physical dimension of the quantity this unit measures.
+    pub(crate) fn dimension(&self) -> Dimension {
+        use PhysicsTypes::*;
+        match self {
+            Meter | LightYear | Parsec | AstronomicalUnit | Angstrom | Fermi | NauticalMile => LENGTH,
+            Kilogram | Dalton => dim(0, 1, 0, 0, 0, 0, 0),
+            Second => dim(0, 0, 1, 0, 0, 0, 0),
+            Ampere | Gilbert => dim(0, 0, 0, 1, 0, 0, 0),
+            Kelvin | Celsius | Fahrenheit | Rankine => TEMPERATURE,
+            Mole => dim(0, 0, 0, 0, 0, 1, 0),
+            Candela | Lumen => dim(0, 0, 0, 0, 0, 0, 1),
+
+            SquareMeter | Barn => dim(2, 0, 0, 0, 0, 0, 0),
+            CubicMeter => dim(3, 0, 0, 0, 0, 0, 0),
+            MeterPerSecond | Knots => dim(1, 0, -1, 0, 0, 0, 0),
+            MeterPerSecondSquared | Gal => dim(1, 0, -2, 0, 0, 0, 0),
+            MeterPerCubicSecond => dim(1, 0, -3, 0, 0, 0, 0),
+            RadianPerSecond | Hertz | Becquerel | HubbleConstant | Curie | Rutherford | Baud => {
+                FREQUENCY
+            }
+            RadianPerSecondSquared => dim(0, 0, -2, 0, 0, 0, 0),
+            KilogramPerCubicMeter => dim(-3, 1, 0, 0, 0, 0, 0),
+            KilogramMeterPerSecond | NewtonSecond => dim(1, 1, -1, 0, 0, 0, 0),
+            Newton => dim(1, 1, -2, 0, 0, 0, 0),
+            NewtonMeter | Joule | ElectronVolt | Calorie | BritishThermalUnit => ENERGY,
+            Pascal | NewtonPerMeterSquared | Bar | MillimeterOfMercury | Atmosphere | Torr => {
+                PRESSURE
+            }
+            Watt | WattPerSteradian | Horsepower => POWER,
+            NewtonPerMeter | JoulePerSquareMeter | Langley | Jansky => dim(0, 1, -2, 0, 0, 0, 0),
+            KilogramSquareMeter => dim(2, 1, 0, 0, 0, 0, 0),
+            JoulePerKilogramKelvin => dim(2, 0, -2, 0, -1, 0, 0),
+            JoulePerKilogram | Gray | Sievert => DOSE,
+            JoulePerKelvin => dim(2, 1, -2, 0, -1, 0, 0),
+            PascalSecond | Poise => dim(-1, 1, -1, 0, 0, 0, 0),
+            SquareMeterPerSecond | Stokes => dim(2, 0, -1, 0, 0, 0, 0),
+            NewtonPerCubicMeter => dim(-2, 1, -2, 0, 0, 0, 0),
+            JouleSecond => dim(2, 1, -1, 0, 0, 0, 0),
+            KilogramPerMole => dim(0, 1, 0, 0, 0, -1, 0),
+            CubicMeterPerKilogram => dim(3, -1, 0, 0, 0, 0, 0),
+            KilogramPerSecond => dim(0, 1, -1, 0, 0, 0, 0),
+            CubicMeterPerSecond | Sverdrup => dim(3, 0, -1, 0, 0, 0, 0),
+            InverseMeter | Dioptre | Kayser => dim(-1, 0, 0, 0, 0, 0, 0),
+            Rayl => dim(-2, 1, -1, 0, 0, 0, 0),
+            MetabolicEquivalent => dim(2, 0, -3, 0, 0, 0, 0),
+
+            Coulomb | Franklin => CHARGE,
+            Volt => dim(2, 1, -3, -1, 0, 0, 0),
+            Ohm => dim(2, 1, -3, -2, 0, 0, 0),
+            OhmMeter => dim(3, 1, -3, -2, 0, 0, 0),
+            Farad => dim(-2, -1, 4, 2, 0, 0, 0),
+            Siemens => dim(-2, -1, 3, 2, 0, 0, 0),
+            VoltPerMeter | NewtonPerCoulomb => dim(1, 1, -3, -1, 0, 0, 0),
+            Tesla | Gauss => MAGNETIC_FIELD,
+            Weber | TeslaSquareMeter | Maxwell => MAGNETIC_FLUX,
+            WeberPerMeter => dim(1, 1, -2, -1, 0, 0, 0),
+            Henry => dim(2, 1, -2, -2, 0, 0, 0),
+            HenryPerMeter => dim(1, 1, -2, -2, 0, 0, 0),
+            FaradPerMeter => dim(-3, -1, 4, 2, 0, 0, 0),
+            CoulombPerCubicMeter => dim(-3, 0, 1, 1, 0, 0, 0),
+            CoulombPerSquareMeter => dim(-2, 0, 1, 1, 0, 0, 0),
+            AmperePerMeter | Oersted => dim(-1, 0, 0, 1, 0, 0, 0),
+            AmperePerSquareMeter => dim(-2, 0, 0, 1, 0, 0, 0),
+            VoltPerKelvin => dim(2, 1, -3, -1, -1, 0, 0),
+            Debye => dim(1, 0, 1, 1, 0, 0, 0),
+            Roentgen => dim(0, -1, 1, 1, 0, 0, 0),
+
+            WattPerSquareMeter => dim(0, 1, -3, 0, 0, 0, 0),
+            WattPerMeterKelvin => dim(1, 1, -3, 0, -1, 0, 0),
+            KelvinPerWatt => dim(-2, -1, 3, 0, 1, 0, 0),
+            JoulePerMole => dim(2, 1, -2, 0, 0, -1, 0),
+            JoulePerMoleKelvin => dim(2, 1, -2, 0, -1, -1, 0),
+            Katal => dim(0, 0, -1, 0, 0, 1, 0),
+            MolePerCubicMeter => dim(-3, 0, 0, 0, 0, 1, 0),
+
+            Lux | CandelaPerSquareMeter | Lambert | Phot | Stilb => LUMINANCE,
+            LumenSecond => dim(0, 0, 1, 0, 0, 0, 1),
+
+            Dimensionless | Percentage | Decibel | Radian | Steradian | ArcDegree | ArcMinute
+            | ArcSecond | Bit | Byte | Erlang | MachNumber => NONE,
+        }
+    }
+}
+
 impl Type for PhysicsTypes {
     fn from_name(name: &str) -> Result<Self, AamlError> {
         match name.to_lowercase().replace(['_', '-'], "").as_str() {
@@ -306,13 +496,26 @@ impl Type for PhysicsTypes {
         }
     }
 
+    fn dimension(&self) -> Option<Dimension> {
+        Some(PhysicsTypes::dimension(self))
+    }
+
+    /// Converts a value written with a unit suffix to a bare number in this
+    /// unit (`3.5km` becomes `3500` for a `meter`).
+    fn normalize(&self, value: &str) -> String {
+        match self.quantity(value) {
+            Ok(n) if parse_float(value.trim()).is_none() => n.to_string(),
+            _ => value.trim().to_string(),
+        }
+    }
+
     fn validate(&self, value: &str) -> Result<(), AamlError> {
         match self.base_type() {
             PrimitiveType::I32 => {
                 value.parse::<i32>().map_err(|_| AamlError::InvalidValue(format!("Expected integer for unit {self}, got '{value}'")))?;
             }
             PrimitiveType::F64 => {
-                value.parse::<f64>().map_err(|_| AamlError::InvalidValue(format!("Expected number for unit {}, got '{}'", self, value)))?;
+                self.quantity(value)?;
             }
             _ => return Err(AamlError::InvalidValue(format!("Unsupported base type for unit {}", self))),
         }
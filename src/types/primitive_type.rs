@@ -1,17 +1,102 @@
 use crate::error::AamlError;
 use crate::types::Type;
+use crate::types::color::parse_color;
+use std::borrow::Cow;
 use std::fmt;
 
+/// Returns the JSON Schema fragment for a bare primitive, without format checks.
+pub(crate) fn json_schema_for(primitive: PrimitiveType) -> String {
+    match primitive {
+        PrimitiveType::F64 => r#"{"type":"number"}"#.to_string(),
+        PrimitiveType::F32 => format!(
+            r#"{{"type":"number","minimum":{},"maximum":{}}}"#,
+            f32::MIN as f64,
+            f32::MAX as f64
+        ),
+        PrimitiveType::Decimal => r#"{"type":"string"}"#.to_string(),
+        PrimitiveType::Bool => r#"{"type":"boolean"}"#.to_string(),
+        PrimitiveType::BoolRelaxed => format!(
+            r#"{{"anyOf":[{{"type":"boolean"}},{{"type":"string","enum":[{}]}}]}}"#,
+            RELAXED_BOOLS.iter().map(|(word, _)| format!(r#""{word}""#)).collect::<Vec<_>>().join(",")
+        ),
+        PrimitiveType::String | PrimitiveType::Color => r#"{"type":"string"}"#.to_string(),
+        integer => {
+            let (min, max) = integer.integer_bounds().expect("remaining primitives are integers");
+            format!(r#"{{"type":"integer","minimum":{min},"maximum":{max}}}"#)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrimitiveType {
     I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    F32,
     F64,
+    /// A fixed-point number such as `-12.50`, validated as text so no digit
+    /// is lost to float rounding.
+    Decimal,
     String,
     Bool,
+    /// A boolean that also accepts `yes`/`no`, `on`/`off` and `y`/`n`, for
+    /// configs imported from other systems.
+    BoolRelaxed,
     Color,
 }
 
+/// Spellings accepted by `bool_relaxed`, compared case-insensitively.
+const RELAXED_BOOLS: [(&str, bool); 10] = [
+    ("true", true),
+    ("false", false),
+    ("1", true),
+    ("0", false),
+    ("yes", true),
+    ("no", false),
+    ("on", true),
+    ("off", false),
+    ("y", true),
+    ("n", false),
+];
+
+/// Parses any `bool_relaxed` spelling (`true`, `1`, `yes`, `on`, `y`, and
+/// their negations), ignoring case.
+pub(crate) fn parse_bool_relaxed(value: &str) -> Option<bool> {
+    let value = value.trim();
+    RELAXED_BOOLS
+        .iter()
+        .find(|(word, _)| word.eq_ignore_ascii_case(value))
+        .map(|&(_, b)| b)
+}
+
+impl PrimitiveType {
+    /// Returns `(min, max)` for the integer primitives, `None` otherwise.
+    pub fn integer_bounds(self) -> Option<(i128, i128)> {
+        let bounds = match self {
+            PrimitiveType::I32 => (i32::MIN as i128, i32::MAX as i128),
+            PrimitiveType::I64 => (i64::MIN as i128, i64::MAX as i128),
+            PrimitiveType::U8 => (0, u8::MAX as i128),
+            PrimitiveType::U16 => (0, u16::MAX as i128),
+            PrimitiveType::U32 => (0, u32::MAX as i128),
+            PrimitiveType::U64 => (0, u64::MAX as i128),
+            PrimitiveType::Usize => (0, usize::MAX as i128),
+            _ => return None,
+        };
+        Some(bounds)
+    }
+
+    /// Returns `true` for the integer primitives, `f32`, `f64` and `decimal`.
+    pub fn is_numeric(self) -> bool {
+        matches!(self, PrimitiveType::F32 | PrimitiveType::F64 | PrimitiveType::Decimal)
+            || self.integer_bounds().is_some()
+    }
+}
+
 impl Type for PrimitiveType {
     fn from_name(name: &str) -> Result<Self, AamlError>
     where
@@ -19,9 +104,18 @@ impl Type for PrimitiveType {
     {
         match name {
             "i32" => Ok(PrimitiveType::I32),
+            "i64" => Ok(PrimitiveType::I64),
+            "u8" => Ok(PrimitiveType::U8),
+            "u16" => Ok(PrimitiveType::U16),
+            "u32" => Ok(PrimitiveType::U32),
+            "u64" => Ok(PrimitiveType::U64),
+            "usize" => Ok(PrimitiveType::Usize),
+            "f32" => Ok(PrimitiveType::F32),
             "f64" => Ok(PrimitiveType::F64),
+            "decimal" => Ok(PrimitiveType::Decimal),
             "string" => Ok(PrimitiveType::String),
             "bool" => Ok(PrimitiveType::Bool),
+            "bool_relaxed" => Ok(PrimitiveType::BoolRelaxed),
             "color" => Ok(PrimitiveType::Color),
             _ => Err(AamlError::NotFound(name.to_string())),
         }
@@ -33,16 +127,19 @@ impl Type for PrimitiveType {
 
     fn validate(&self, value: &str) -> Result<(), AamlError> {
         match self {
-            PrimitiveType::I32 => {
-                value.parse::<i32>().map_err(|_| {
-                    AamlError::InvalidValue(format!("Expected i32, got '{}'", value))
-                })?;
-            }
             PrimitiveType::F64 => {
-                value.parse::<f64>().map_err(|_| {
+                parse_float(value).ok_or_else(|| {
                     AamlError::InvalidValue(format!("Expected f64, got '{}'", value))
                 })?;
             }
+            PrimitiveType::F32 => check_f32(value)?,
+            PrimitiveType::Decimal => {
+                if split_decimal(value).is_none() {
+                    return Err(AamlError::InvalidValue(format!(
+                        "Expected decimal (digits with an optional '.' fraction, e.g. 12.50), got '{value}'"
+                    )));
+                }
+            }
             PrimitiveType::String => {
                 // Any string is valid, so no validation needed.
             }
@@ -55,35 +152,229 @@ impl Type for PrimitiveType {
                     )));
                 }
             },
-            PrimitiveType::Color => {
-                // Waiting hex #RRGGBB or #RRGGBBAA
-                if !value.starts_with('#') || (value.len() != 7 && value.len() != 9) {
+            PrimitiveType::BoolRelaxed => {
+                if parse_bool_relaxed(value).is_none() {
                     return Err(AamlError::InvalidValue(format!(
-                        "Expected color in #RRGGBB or #RRGGBBAA format, got '{}'",
-                        value
+                        "Expected bool (true/false, yes/no, on/off, y/n, 1/0), got '{value}'"
                     )));
                 }
-                if u64::from_str_radix(&value[1..], 16).is_err() {
+            }
+            PrimitiveType::Color => {
+                parse_color(value)?;
+            }
+            integer => {
+                let (min, max) = integer.integer_bounds().expect("remaining primitives are integers");
+                let n = parse_integer(value).ok_or_else(|| {
+                    AamlError::InvalidValue(format!("Expected {integer}, got '{value}'"))
+                })?;
+                if !(min..=max).contains(&n) {
                     return Err(AamlError::InvalidValue(format!(
-                        "Invalid hex color '{}'",
-                        value
+                        "Expected {integer} in range {min}..={max}, got '{value}'"
                     )));
                 }
             }
         }
         Ok(())
     }
+
+    /// Trims the value, lowercases booleans (`bool_relaxed` spellings become
+    /// `true` / `false`), strips padding from numbers
+    /// (`007` → `7`, `1.50` → `1.5`), writes integers in decimal (`0xFF` → `255`)
+    /// and writes colors in any notation as lowercase `#rrggbbaa`.
+    /// Decimals only lose a `+` sign and leading zeros: their fraction digits
+    /// are significant (`007.50` → `7.50`).
+    fn normalize(&self, value: &str) -> String {
+        let value = value.trim();
+        match self {
+            PrimitiveType::F32 => {
+                parse_float(value).map_or_else(|| value.to_string(), |n| (n as f32).to_string())
+            }
+            PrimitiveType::Decimal => normalize_decimal(value),
+            PrimitiveType::F64 => parse_float(value).map_or_else(|| value.to_string(), |n| n.to_string()),
+            PrimitiveType::Bool => value.to_lowercase(),
+            PrimitiveType::BoolRelaxed => {
+                parse_bool_relaxed(value).map_or_else(|| value.to_string(), |b| b.to_string())
+            }
+            PrimitiveType::Color => parse_color(value).map_or_else(
+                |_| value.to_string(),
+                |[r, g, b, a]| format!("#{r:02x}{g:02x}{b:02x}{a:02x}"),
+            ),
+            PrimitiveType::String => value.to_string(),
+            _ => parse_integer(value).map_or_else(|| value.to_string(), |n| n.to_string()),
+        }
+    }
+
+    fn json_schema(&self) -> String {
+        match self {
+            PrimitiveType::Color => {
+                r#"{"type":"string","pattern":"^\\s*(#([0-9a-fA-F]{3,4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})|(rgba?|hsla?)\\(.*\\)|[a-zA-Z]+)\\s*$"}"#.to_string()
+            }
+            PrimitiveType::Decimal => {
+                r#"{"type":"string","pattern":"^[+-]?[0-9]+(\\.[0-9]+)?$"}"#.to_string()
+            }
+            other => json_schema_for(*other),
+        }
+    }
 }
 
 impl fmt::Display for PrimitiveType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             PrimitiveType::I32 => "i32",
+            PrimitiveType::I64 => "i64",
+            PrimitiveType::U8 => "u8",
+            PrimitiveType::U16 => "u16",
+            PrimitiveType::U32 => "u32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::Usize => "usize",
+            PrimitiveType::F32 => "f32",
             PrimitiveType::F64 => "f64",
+            PrimitiveType::Decimal => "decimal",
             PrimitiveType::String => "string",
             PrimitiveType::Bool => "bool",
+            PrimitiveType::BoolRelaxed => "bool_relaxed",
             PrimitiveType::Color => "color",
         };
         write!(f, "{}", s)
     }
 }
+
+/// Accepts numbers that fit in an `f32` without overflowing or, for tiny
+/// magnitudes, losing precision to subnormal rounding.
+fn check_f32(value: &str) -> Result<(), AamlError> {
+    let wide = parse_float(value)
+        .ok_or_else(|| AamlError::InvalidValue(format!("Expected f32, got '{value}'")))?;
+    if wide.abs() > f32::MAX as f64 {
+        return Err(AamlError::InvalidValue(format!(
+            "Value '{value}' is out of range for f32 (max magnitude {})",
+            f32::MAX
+        )));
+    }
+    let narrow = wide as f32;
+    if wide != 0.0 && ((narrow as f64 - wide) / wide).abs() > f32::EPSILON as f64 {
+        return Err(AamlError::InvalidValue(format!(
+            "Value '{value}' cannot be represented as f32 without losing precision"
+        )));
+    }
+    Ok(())
+}
+
+/// Splits a decimal literal `[+-]digits[.digits]` into its sign, integer and
+/// fraction digits. Returns `None` when `value` is not such a literal.
+fn split_decimal(value: &str) -> Option<(bool, &str, &str)> {
+    let (negative, unsigned) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let frac_ok = frac.is_empty() != unsigned.contains('.');
+    (!int.is_empty() && digits(int) && digits(frac) && frac_ok).then_some((negative, int, frac))
+}
+
+/// Canonical form of a decimal literal: no `+`, no leading zeros and no sign
+/// on zero. Values that are not decimals are returned unchanged.
+fn normalize_decimal(value: &str) -> String {
+    let Some((negative, int, frac)) = split_decimal(value) else {
+        return value.to_string();
+    };
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+    let is_zero = int == "0" && frac.bytes().all(|b| b == b'0');
+    let sign = if negative && !is_zero { "-" } else { "" };
+    if frac.is_empty() {
+        format!("{sign}{int}")
+    } else {
+        format!("{sign}{int}.{frac}")
+    }
+}
+
+/// Removes `_` digit separators (`1_000_000`, `0xFF_FF`). Each `_` must sit
+/// between two digits; returns `None` otherwise.
+fn strip_separators(value: &str) -> Option<Cow<'_, str>> {
+    if !value.contains('_') {
+        return Some(Cow::Borrowed(value));
+    }
+    let unsigned = value.trim_start_matches(['+', '-']);
+    let hex = unsigned.starts_with("0x") || unsigned.starts_with("0X");
+    let is_digit = |b: &u8| if hex { b.is_ascii_hexdigit() } else { b.is_ascii_digit() };
+    let bytes = value.as_bytes();
+    let between_digits =
+        |i: usize| i > 0 && is_digit(&bytes[i - 1]) && bytes.get(i + 1).is_some_and(is_digit);
+    let valid = bytes
+        .iter()
+        .enumerate()
+        .all(|(i, &b)| b != b'_' || between_digits(i));
+    valid.then(|| Cow::Owned(value.replace('_', "")))
+}
+
+/// Parses a float literal, allowing scientific notation (`6.02e23`) and `_`
+/// digit separators.
+pub(crate) fn parse_float(value: &str) -> Option<f64> {
+    strip_separators(value)?.parse().ok()
+}
+
+/// Parses an integer literal: decimal, or hexadecimal (`0xFF`), octal
+/// (`0o755`) or binary (`0b1010`) with an optional sign before the prefix.
+/// `_` digit separators are allowed, and decimal literals may use scientific
+/// notation as long as the value is a whole number (`1e6`, `1.5e3`).
+pub(crate) fn parse_integer(value: &str) -> Option<i128> {
+    let value = strip_separators(value)?;
+    let value = value.as_ref();
+    let (negative, unsigned) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let prefix = unsigned.get(..2).map(str::to_ascii_lowercase);
+    let (radix, digits) = match prefix.as_deref() {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ if unsigned.contains(['e', 'E']) => {
+            let magnitude = parse_scientific_integer(unsigned)?;
+            return Some(if negative { -magnitude } else { magnitude });
+        }
+        _ => (10, unsigned),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| (b as char).is_digit(radix)) {
+        return None;
+    }
+    let magnitude = i128::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Parses an unsigned `mantissa e exponent` literal exactly, by moving the
+/// decimal point. Returns `None` unless the value is a whole number.
+fn parse_scientific_integer(literal: &str) -> Option<i128> {
+    let (mantissa, exponent) = literal.split_once(['e', 'E'])?;
+    let exponent: i64 = exponent.strip_prefix('+').unwrap_or(exponent).parse().ok()?;
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int.is_empty() || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = format!("{int}{frac}");
+    let point = (int.len() as i64).checked_add(exponent).filter(|p| (0..=40).contains(p));
+    let Some(point) = point else {
+        // Below 1 or far beyond i128: only zero survives.
+        return digits.bytes().all(|b| b == b'0').then_some(0);
+    };
+    let point = point as usize;
+    if point >= digits.len() {
+        let zeros = "0".repeat(point - digits.len());
+        return format!("{digits}{zeros}").parse().ok();
+    }
+    let (whole, rest) = digits.split_at(point);
+    if !rest.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    if whole.is_empty() { Some(0) } else { whole.parse().ok() }
+}
+
+/// Parses any numeric literal accepted by the numeric primitives as an `f64`.
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    parse_float(value).or_else(|| parse_integer(value).map(|n| n as f64))
+}
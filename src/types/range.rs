@@ -0,0 +1,110 @@
+//! `range<T>` — a `start..end` or `start..=end` literal with typed endpoints.
+//!
+//! ## Syntax in .aam files
+//! ```text
+//! levels = 1..10
+//! spawn_delay = 0.5..=2.5
+//! ```
+//!
+//! Both endpoints are required and each must satisfy `T`. When both are
+//! numbers the start may not be greater than the end. Read the endpoints back
+//! with [`FoundValue::as_range`](crate::found_value::FoundValue::as_range).
+//!
+//! ## Schema usage
+//! ```text
+//! @schema Spawner { levels: range<u32>, delay: range<f64> }
+//! ```
+
+use crate::error::AamlError;
+use crate::types::primitive_type::{PrimitiveType, parse_number};
+use crate::types::{Type, resolve_builtin};
+
+/// A range literal type whose endpoints are validated against an inner type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeType {
+    /// Name of the endpoint type (e.g. `"i32"`, `"f64"`).
+    pub(crate) inner_type: String,
+}
+
+impl RangeType {
+    /// Creates a `RangeType` whose endpoints have the given type.
+    pub fn new(inner_type: String) -> Self {
+        Self { inner_type }
+    }
+
+    /// Parses a `range<T>` type string and returns the endpoint type name.
+    pub fn parse_inner(type_str: &str) -> Option<&str> {
+        let inner = type_str
+            .trim()
+            .strip_prefix("range<")?
+            .strip_suffix('>')?
+            .trim();
+        (!inner.is_empty()).then_some(inner)
+    }
+
+    /// Splits `a..b` / `a..=b` into `(start, end, inclusive)` with trimmed
+    /// endpoints. Returns `None` if there is no `..` or an endpoint is empty.
+    pub fn split_literal(value: &str) -> Option<(&str, &str, bool)> {
+        let (start, rest) = value.trim().split_once("..")?;
+        let (inclusive, end) = match rest.strip_prefix('=') {
+            Some(end) => (true, end),
+            None => (false, rest),
+        };
+        let (start, end) = (start.trim(), end.trim());
+        (!start.is_empty() && !end.is_empty()).then_some((start, end, inclusive))
+    }
+
+    /// Splits `value` and checks each endpoint with `check`, then that the
+    /// start does not exceed the end when both are numbers.
+    pub(crate) fn check_literal(
+        value: &str,
+        mut check: impl FnMut(&str) -> Result<(), AamlError>,
+    ) -> Result<(), AamlError> {
+        let (start, end, _) = Self::split_literal(value).ok_or_else(|| {
+            AamlError::InvalidValue(format!(
+                "Expected a range literal 'start..end' or 'start..=end', got '{value}'"
+            ))
+        })?;
+        check(start).map_err(|e| endpoint_error("start", start, e))?;
+        check(end).map_err(|e| endpoint_error("end", end, e))?;
+        if let (Some(a), Some(b)) = (parse_number(start), parse_number(end))
+            && a > b
+        {
+            return Err(AamlError::InvalidValue(format!(
+                "Range start {start} is greater than end {end}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Wraps an endpoint's validation error with which endpoint failed.
+fn endpoint_error(which: &str, endpoint: &str, err: AamlError) -> AamlError {
+    AamlError::InvalidValue(format!("Invalid range {which} '{endpoint}': {err}"))
+}
+
+impl Type for RangeType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "RangeType::from_name — use RangeType::new instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        let inner = resolve_builtin(&self.inner_type).map_err(|_| {
+            AamlError::NotFound(format!("Unknown range type '{}'", self.inner_type))
+        })?;
+        Self::check_literal(value, |endpoint| inner.validate(endpoint))
+    }
+
+    fn json_schema(&self) -> String {
+        r#"{"type":"string","pattern":"^\\s*\\S+\\s*\\.\\.=?\\s*\\S+\\s*$"}"#.to_string()
+    }
+}
@@ -1,10 +1,12 @@
 use crate::error::AamlError;
 use crate::types::Type;
+use std::time::Duration;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeTypes {
     DateTime,
     Duration,
+    HumanDuration,
     Year,
     Day,
     Hour,
@@ -34,6 +36,61 @@ fn validate_datetime(value: &str) -> Result<(), AamlError> {
     Ok(())
 }
 
+/// Duration units in descending order with their length in nanoseconds.
+const DURATION_UNITS: &[(&str, u128)] = &[
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// Parses a human-readable duration such as `5s`, `2h30m` or `1.5d`.
+///
+/// The value is one or more `<number><unit>` components, optionally separated
+/// by spaces, with units `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) and `ns`.
+/// Units must appear from largest to smallest, each at most once. Fractions
+/// below a nanosecond are truncated. Returns `None` for malformed values and
+/// durations that overflow [`Duration`].
+pub(crate) fn parse_human_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total: u128 = 0;
+    let mut last_rank = None;
+    while !rest.is_empty() {
+        let split = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (number, tail) = rest.split_at(split);
+        let unit_len = tail
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let rank = DURATION_UNITS.iter().position(|(name, _)| *name == unit)?;
+        // `us` and `µs` share a rank.
+        let rank = if unit == "µs" { rank - 1 } else { rank };
+        if last_rank.is_some_and(|last| rank <= last) {
+            return None;
+        }
+        last_rank = Some(rank);
+
+        let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+        if int.is_empty() || (number.contains('.') && frac.is_empty()) {
+            return None;
+        }
+        let scale = 10u128.checked_pow(frac.len() as u32)?;
+        let mantissa: u128 = format!("{int}{frac}").parse().ok()?;
+        let nanos = mantissa.checked_mul(DURATION_UNITS[rank].1)? / scale;
+        total = total.checked_add(nanos)?;
+        rest = tail.trim_start();
+    }
+    let secs = u64::try_from(total / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (total % 1_000_000_000) as u32))
+}
+
 /// Validates that `value` parses as a finite `f64` number.
 fn validate_numeric(value: &str, label: &str) -> Result<(), AamlError> {
     value.parse::<f64>().map(|_| ()).map_err(|_| {
@@ -49,6 +106,7 @@ impl Type for TimeTypes {
         match name {
             "datetime" => Ok(TimeTypes::DateTime),
             "duration" => Ok(TimeTypes::Duration),
+            "human_duration" => Ok(TimeTypes::HumanDuration),
             "year" => Ok(TimeTypes::Year),
             "day" => Ok(TimeTypes::Day),
             "hour" => Ok(TimeTypes::Hour),
@@ -72,10 +130,30 @@ impl Type for TimeTypes {
                     validate_numeric(value, "Duration")
                 }
             }
+            TimeTypes::HumanDuration => parse_human_duration(value).map(|_| ()).ok_or_else(|| {
+                AamlError::InvalidValue(format!(
+                    "Invalid duration '{value}': expected components like 2h30m or 500ms (units d, h, m, s, ms, us, ns, largest first)"
+                ))
+            }),
             TimeTypes::Year => validate_numeric(value, "Year"),
             TimeTypes::Day => validate_numeric(value, "Day"),
             TimeTypes::Hour => validate_numeric(value, "Hour"),
             TimeTypes::Minute => validate_numeric(value, "Minute"),
         }
     }
+
+    fn json_schema(&self) -> String {
+        match self {
+            TimeTypes::DateTime => {
+                r#"{"type":"string","pattern":"^\\d{4}-\\d{2}-\\d{2}"}"#.to_string()
+            }
+            TimeTypes::Duration => {
+                r#"{"anyOf":[{"type":"number"},{"type":"string","pattern":"^P"}]}"#.to_string()
+            }
+            TimeTypes::HumanDuration => {
+                r#"{"type":"string","pattern":"^(\\d+(\\.\\d+)?(d|h|m|s|ms|us|µs|ns)\\s*)+$"}"#.to_string()
+            }
+            _ => r#"{"type":"number"}"#.to_string(),
+        }
+    }
 }
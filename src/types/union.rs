@@ -0,0 +1,150 @@
+//! `A | B` / `union<A, B>` — a value accepted by any of several types.
+//!
+//! ## Schema usage
+//! ```text
+//! @schema Server { port: i32 | string, timeout: union<f64, time::duration> }
+//! @type id = i32 | string(/^[a-z]+-\d+$/)
+//! ```
+//!
+//! Members are tried in declaration order and the first one that accepts the
+//! value wins. When none does, the error lists why each member rejected it.
+//! `|` binds loosest, so `list<i32> | string` is a list of integers or a string.
+
+use crate::error::AamlError;
+use crate::types::list::top_level_commas;
+use crate::types::primitive_type::PrimitiveType;
+use crate::types::{Type, resolve_builtin};
+
+/// A type that accepts a value when at least one member type does.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnionType {
+    /// Member type names, in declaration order.
+    pub(crate) members: Vec<String>,
+}
+
+impl UnionType {
+    /// Creates a `UnionType` from member type names.
+    pub fn new(members: Vec<String>) -> Self {
+        Self { members }
+    }
+
+    /// Splits `A | B | ...` or `union<A, B, ...>` into its member type names.
+    ///
+    /// Returns `None` unless the string is a union with at least two members.
+    /// Empty members (`i32 | | string`) are kept so the declaration can be
+    /// rejected by [`parse`](Self::parse).
+    pub fn split(type_str: &str) -> Option<Vec<&str>> {
+        let t = type_str.trim();
+        let members = match t.strip_prefix("union<").and_then(|s| s.strip_suffix('>')) {
+            Some(body) => split_at(body, &top_level_commas(body)),
+            None => split_at(t, &top_level_pipes(t)),
+        };
+        (members.len() >= 2).then_some(members)
+    }
+
+    /// Parses a union type string without resolving its members.
+    ///
+    /// # Errors
+    /// [`AamlError::NotFound`] if the string is not a union, or
+    /// [`AamlError::InvalidType`] if a member is empty.
+    pub fn parse(type_str: &str) -> Result<Self, AamlError> {
+        let members =
+            Self::split(type_str).ok_or_else(|| AamlError::NotFound(type_str.to_string()))?;
+        if members.iter().any(|m| m.is_empty()) {
+            return Err(AamlError::InvalidType {
+                type_name: type_str.to_string(),
+                details: "Union has an empty member".to_string(),
+                location: None,
+            });
+        }
+        Ok(Self::new(members.into_iter().map(str::to_string).collect()))
+    }
+
+    /// Checks `value` against each member with `check`, succeeding on the first
+    /// member that accepts it and otherwise reporting every member's failure.
+    pub(crate) fn check_members(
+        &self,
+        value: &str,
+        mut check: impl FnMut(&str) -> Result<(), AamlError>,
+    ) -> Result<(), AamlError> {
+        let mut failures = Vec::with_capacity(self.members.len());
+        for member in &self.members {
+            match check(member) {
+                Ok(()) => return Ok(()),
+                Err(e) => failures.push(format!("{member}: {e}")),
+            }
+        }
+        Err(AamlError::InvalidValue(format!(
+            "Value '{value}' matches no member of the union ({})",
+            failures.join("; ")
+        )))
+    }
+}
+
+/// Splits `s` at the given byte indices of single-byte separators, trimming each part.
+fn split_at<'a>(s: &'a str, separators: &[usize]) -> Vec<&'a str> {
+    let mut parts = Vec::with_capacity(separators.len() + 1);
+    let mut start = 0;
+    for &i in separators {
+        parts.push(s[start..i].trim());
+        start = i + 1;
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+/// Returns the byte indices of `|` characters outside `<>`, `()`, `[]`, `{}`.
+fn top_level_pipes(s: &str) -> Vec<usize> {
+    let mut depth: i32 = 0;
+    let mut pipes = Vec::new();
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            '|' if depth == 0 => pipes.push(i),
+            _ => {}
+        }
+    }
+    pipes
+}
+
+impl Type for UnionType {
+    fn from_name(_name: &str) -> Result<Self, AamlError>
+    where
+        Self: Sized,
+    {
+        Err(AamlError::NotFound(
+            "UnionType::from_name — use UnionType::parse instead".to_string(),
+        ))
+    }
+
+    fn base_type(&self) -> PrimitiveType {
+        PrimitiveType::String
+    }
+
+    fn validate(&self, value: &str) -> Result<(), AamlError> {
+        self.check_members(value, |member| resolve_builtin(member)?.validate(value))
+    }
+
+    /// Uses the canonical form of the first member that accepts the value.
+    fn normalize(&self, value: &str) -> String {
+        self.members
+            .iter()
+            .filter_map(|m| resolve_builtin(m).ok())
+            .find(|t| t.validate(value).is_ok())
+            .map_or_else(|| value.trim().to_string(), |t| t.normalize(value))
+    }
+
+    fn json_schema(&self) -> String {
+        let members: Vec<String> = self
+            .members
+            .iter()
+            .map(|m| {
+                resolve_builtin(m)
+                    .map(|t| t.json_schema())
+                    .unwrap_or_else(|_| "{}".to_string())
+            })
+            .collect();
+        format!(r#"{{"anyOf":[{}]}}"#, members.join(","))
+    }
+}
@@ -0,0 +1,274 @@
+//! Physical dimensions and unit symbols of the `physics::` unit types.
+//!
+//! Every `physics::` type reports the [`Dimension`] of the quantity it
+//! measures as exponents of the seven SI base quantities, so tools can check
+//! that two fields hold compatible quantities without knowing every unit name.
+//!
+//! Values of `physics::` fields may carry a unit symbol (`75kg`, `3.5 km`,
+//! `20°C`). The symbol must measure the same dimension as the field's unit;
+//! [`FoundValue::as_quantity_in`](crate::found_value::FoundValue::as_quantity_in)
+//! converts such a value into any compatible unit. See [`SYMBOLS`] for the
+//! recognised symbols.
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::units::Dimension;
+//!
+//! let cfg = AAML::parse("@type force = physics::newton").unwrap();
+//! let force = cfg.type_dimension("force").unwrap();
+//! assert_eq!(force, Dimension::new(1, 1, -2, 0, 0, 0, 0));
+//! assert_eq!(force.to_string(), "m·kg·s^-2");
+//! assert_eq!(cfg.type_dimension("physics::celsius"), cfg.type_dimension("physics::kelvin"));
+//! assert_eq!(cfg.type_dimension("i32"), None);
+//! ```
+
+use crate::types::primitive_type::parse_float;
+use std::fmt;
+
+/// Exponents of the SI base quantities making up a physical dimension.
+///
+/// Angles, ratios and counts are dimensionless; temperatures in any scale
+/// have the dimension of the kelvin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dimension {
+    /// Length (metre).
+    pub length: i8,
+    /// Mass (kilogram).
+    pub mass: i8,
+    /// Time (second).
+    pub time: i8,
+    /// Electric current (ampere).
+    pub current: i8,
+    /// Thermodynamic temperature (kelvin).
+    pub temperature: i8,
+    /// Amount of substance (mole).
+    pub amount: i8,
+    /// Luminous intensity (candela).
+    pub luminosity: i8,
+}
+
+impl Dimension {
+    /// The dimension of pure numbers.
+    pub const DIMENSIONLESS: Dimension = Dimension::new(0, 0, 0, 0, 0, 0, 0);
+
+    /// Creates a dimension from the exponents of metre, kilogram, second,
+    /// ampere, kelvin, mole and candela, in that order.
+    pub const fn new(
+        length: i8,
+        mass: i8,
+        time: i8,
+        current: i8,
+        temperature: i8,
+        amount: i8,
+        luminosity: i8,
+    ) -> Self {
+        Self { length, mass, time, current, temperature, amount, luminosity }
+    }
+
+    /// Returns `true` for the dimension of pure numbers.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::DIMENSIONLESS
+    }
+}
+
+/// Renders the dimension as base-unit symbols, e.g. `m·kg·s^-2`, or `1` when
+/// dimensionless.
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = [
+            ("m", self.length),
+            ("kg", self.mass),
+            ("s", self.time),
+            ("A", self.current),
+            ("K", self.temperature),
+            ("mol", self.amount),
+            ("cd", self.luminosity),
+        ]
+        .into_iter()
+        .filter(|(_, exp)| *exp != 0)
+        .map(|(symbol, exp)| match exp {
+            1 => symbol.to_string(),
+            _ => format!("{symbol}^{exp}"),
+        })
+        .collect();
+        if parts.is_empty() {
+            f.write_str("1")
+        } else {
+            f.write_str(&parts.join("·"))
+        }
+    }
+}
+
+/// A unit symbol usable as a value suffix.
+///
+/// A value `v` in this unit is `v * factor + offset` in the coherent SI unit
+/// of its dimension (the offset is only non-zero for temperature scales).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitSymbol {
+    /// The symbol as written after the number.
+    pub symbol: &'static str,
+    /// The dimension the unit measures.
+    pub dimension: Dimension,
+    /// Size of the unit in coherent SI units.
+    pub factor: f64,
+    /// Zero point of the unit in coherent SI units.
+    pub offset: f64,
+}
+
+impl UnitSymbol {
+    const fn new(symbol: &'static str, dimension: Dimension, factor: f64) -> Self {
+        Self { symbol, dimension, factor, offset: 0.0 }
+    }
+
+    const fn offset(symbol: &'static str, dimension: Dimension, factor: f64, offset: f64) -> Self {
+        Self { symbol, dimension, factor, offset }
+    }
+
+    /// Converts `value` in this unit to `target`, or `None` if the two units
+    /// measure different dimensions.
+    pub fn convert(&self, value: f64, target: &UnitSymbol) -> Option<f64> {
+        (self.dimension == target.dimension)
+            .then(|| (value * self.factor + self.offset - target.offset) / target.factor)
+    }
+}
+
+const LENGTH: Dimension = Dimension::new(1, 0, 0, 0, 0, 0, 0);
+const MASS: Dimension = Dimension::new(0, 1, 0, 0, 0, 0, 0);
+const TIME: Dimension = Dimension::new(0, 0, 1, 0, 0, 0, 0);
+const CURRENT: Dimension = Dimension::new(0, 0, 0, 1, 0, 0, 0);
+const TEMPERATURE: Dimension = Dimension::new(0, 0, 0, 0, 1, 0, 0);
+const AREA: Dimension = Dimension::new(2, 0, 0, 0, 0, 0, 0);
+const VOLUME: Dimension = Dimension::new(3, 0, 0, 0, 0, 0, 0);
+const SPEED: Dimension = Dimension::new(1, 0, -1, 0, 0, 0, 0);
+const ACCELERATION: Dimension = Dimension::new(1, 0, -2, 0, 0, 0, 0);
+const FREQUENCY: Dimension = Dimension::new(0, 0, -1, 0, 0, 0, 0);
+const FORCE: Dimension = Dimension::new(1, 1, -2, 0, 0, 0, 0);
+const ENERGY: Dimension = Dimension::new(2, 1, -2, 0, 0, 0, 0);
+const POWER: Dimension = Dimension::new(2, 1, -3, 0, 0, 0, 0);
+const PRESSURE: Dimension = Dimension::new(-1, 1, -2, 0, 0, 0, 0);
+const CHARGE: Dimension = Dimension::new(0, 0, 1, 1, 0, 0, 0);
+const VOLTAGE: Dimension = Dimension::new(2, 1, -3, -1, 0, 0, 0);
+const RESISTANCE: Dimension = Dimension::new(2, 1, -3, -2, 0, 0, 0);
+const CAPACITANCE: Dimension = Dimension::new(-2, -1, 4, 2, 0, 0, 0);
+const CONDUCTANCE: Dimension = Dimension::new(-2, -1, 3, 2, 0, 0, 0);
+const MAGNETIC_FIELD: Dimension = Dimension::new(0, 1, -2, -1, 0, 0, 0);
+const MAGNETIC_FLUX: Dimension = Dimension::new(2, 1, -2, -1, 0, 0, 0);
+const INDUCTANCE: Dimension = Dimension::new(2, 1, -2, -2, 0, 0, 0);
+const ANGLE: Dimension = Dimension::DIMENSIONLESS;
+
+/// The unit symbols accepted after a number in `physics::` values.
+pub const SYMBOLS: &[UnitSymbol] = &[
+    UnitSymbol::new("m", LENGTH, 1.0),
+    UnitSymbol::new("km", LENGTH, 1e3),
+    UnitSymbol::new("cm", LENGTH, 1e-2),
+    UnitSymbol::new("mm", LENGTH, 1e-3),
+    UnitSymbol::new("um", LENGTH, 1e-6),
+    UnitSymbol::new("µm", LENGTH, 1e-6),
+    UnitSymbol::new("nm", LENGTH, 1e-9),
+    UnitSymbol::new("in", LENGTH, 0.0254),
+    UnitSymbol::new("ft", LENGTH, 0.3048),
+    UnitSymbol::new("mi", LENGTH, 1609.344),
+    UnitSymbol::new("nmi", LENGTH, 1852.0),
+    UnitSymbol::new("au", LENGTH, 1.495_978_707e11),
+    UnitSymbol::new("ly", LENGTH, 9.460_730_472_580_8e15),
+    UnitSymbol::new("pc", LENGTH, 3.085_677_581_491_367e16),
+    UnitSymbol::new("kg", MASS, 1.0),
+    UnitSymbol::new("g", MASS, 1e-3),
+    UnitSymbol::new("mg", MASS, 1e-6),
+    UnitSymbol::new("t", MASS, 1e3),
+    UnitSymbol::new("lb", MASS, 0.453_592_37),
+    UnitSymbol::new("Da", MASS, 1.660_539_066_60e-27),
+    UnitSymbol::new("s", TIME, 1.0),
+    UnitSymbol::new("ms", TIME, 1e-3),
+    UnitSymbol::new("us", TIME, 1e-6),
+    UnitSymbol::new("µs", TIME, 1e-6),
+    UnitSymbol::new("ns", TIME, 1e-9),
+    UnitSymbol::new("min", TIME, 60.0),
+    UnitSymbol::new("h", TIME, 3600.0),
+    UnitSymbol::new("A", CURRENT, 1.0),
+    UnitSymbol::new("mA", CURRENT, 1e-3),
+    UnitSymbol::new("K", TEMPERATURE, 1.0),
+    UnitSymbol::offset("°C", TEMPERATURE, 1.0, 273.15),
+    UnitSymbol::offset("°F", TEMPERATURE, 5.0 / 9.0, 459.67 * 5.0 / 9.0),
+    UnitSymbol::new("°R", TEMPERATURE, 5.0 / 9.0),
+    UnitSymbol::new("mol", Dimension::new(0, 0, 0, 0, 0, 1, 0), 1.0),
+    UnitSymbol::new("cd", Dimension::new(0, 0, 0, 0, 0, 0, 1), 1.0),
+    UnitSymbol::new("m2", AREA, 1.0),
+    UnitSymbol::new("m²", AREA, 1.0),
+    UnitSymbol::new("m3", VOLUME, 1.0),
+    UnitSymbol::new("m³", VOLUME, 1.0),
+    UnitSymbol::new("L", VOLUME, 1e-3),
+    UnitSymbol::new("mL", VOLUME, 1e-6),
+    UnitSymbol::new("m/s", SPEED, 1.0),
+    UnitSymbol::new("km/h", SPEED, 1.0 / 3.6),
+    UnitSymbol::new("mph", SPEED, 0.447_04),
+    UnitSymbol::new("kn", SPEED, 1852.0 / 3600.0),
+    UnitSymbol::new("m/s2", ACCELERATION, 1.0),
+    UnitSymbol::new("m/s²", ACCELERATION, 1.0),
+    UnitSymbol::new("Hz", FREQUENCY, 1.0),
+    UnitSymbol::new("kHz", FREQUENCY, 1e3),
+    UnitSymbol::new("MHz", FREQUENCY, 1e6),
+    UnitSymbol::new("GHz", FREQUENCY, 1e9),
+    UnitSymbol::new("N", FORCE, 1.0),
+    UnitSymbol::new("kN", FORCE, 1e3),
+    UnitSymbol::new("J", ENERGY, 1.0),
+    UnitSymbol::new("kJ", ENERGY, 1e3),
+    UnitSymbol::new("MJ", ENERGY, 1e6),
+    UnitSymbol::new("eV", ENERGY, 1.602_176_634e-19),
+    UnitSymbol::new("cal", ENERGY, 4.184),
+    UnitSymbol::new("kcal", ENERGY, 4184.0),
+    UnitSymbol::new("Wh", ENERGY, 3600.0),
+    UnitSymbol::new("kWh", ENERGY, 3.6e6),
+    UnitSymbol::new("BTU", ENERGY, 1_055.055_852_62),
+    UnitSymbol::new("W", POWER, 1.0),
+    UnitSymbol::new("kW", POWER, 1e3),
+    UnitSymbol::new("MW", POWER, 1e6),
+    UnitSymbol::new("hp", POWER, 745.699_871_582_270_2),
+    UnitSymbol::new("Pa", PRESSURE, 1.0),
+    UnitSymbol::new("kPa", PRESSURE, 1e3),
+    UnitSymbol::new("MPa", PRESSURE, 1e6),
+    UnitSymbol::new("bar", PRESSURE, 1e5),
+    UnitSymbol::new("atm", PRESSURE, 101_325.0),
+    UnitSymbol::new("mmHg", PRESSURE, 133.322_387_415),
+    UnitSymbol::new("Torr", PRESSURE, 101_325.0 / 760.0),
+    UnitSymbol::new("psi", PRESSURE, 6_894.757_293_168),
+    UnitSymbol::new("C", CHARGE, 1.0),
+    UnitSymbol::new("V", VOLTAGE, 1.0),
+    UnitSymbol::new("mV", VOLTAGE, 1e-3),
+    UnitSymbol::new("kV", VOLTAGE, 1e3),
+    UnitSymbol::new("ohm", RESISTANCE, 1.0),
+    UnitSymbol::new("Ω", RESISTANCE, 1.0),
+    UnitSymbol::new("F", CAPACITANCE, 1.0),
+    UnitSymbol::new("S", CONDUCTANCE, 1.0),
+    UnitSymbol::new("T", MAGNETIC_FIELD, 1.0),
+    UnitSymbol::new("Wb", MAGNETIC_FLUX, 1.0),
+    UnitSymbol::new("H", INDUCTANCE, 1.0),
+    UnitSymbol::new("rad", ANGLE, 1.0),
+    UnitSymbol::new("deg", ANGLE, std::f64::consts::PI / 180.0),
+    UnitSymbol::new("°", ANGLE, std::f64::consts::PI / 180.0),
+    UnitSymbol::new("arcmin", ANGLE, std::f64::consts::PI / 10_800.0),
+    UnitSymbol::new("arcsec", ANGLE, std::f64::consts::PI / 648_000.0),
+];
+
+/// Looks up a unit symbol (case-sensitive: `mm` and `Mm` differ).
+pub fn symbol(symbol: &str) -> Option<&'static UnitSymbol> {
+    SYMBOLS.iter().find(|unit| unit.symbol == symbol)
+}
+
+/// Splits a value such as `3.5 km` into its number and unit symbol.
+///
+/// Returns `None` when the value has no recognised symbol suffix or the rest
+/// is not a number. The longest matching symbol wins, so `5eV` is five
+/// electronvolts rather than `5e` volts.
+pub(crate) fn split_quantity(value: &str) -> Option<(f64, &'static UnitSymbol)> {
+    let value = value.trim();
+    SYMBOLS
+        .iter()
+        .filter_map(|unit| {
+            let number = value.strip_suffix(unit.symbol)?.trim_end();
+            parse_float(number).map(|n| (n, unit))
+        })
+        .max_by_key(|(_, unit)| unit.symbol.len())
+}
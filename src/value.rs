@@ -0,0 +1,74 @@
+//! Structured view of AAML values.
+//!
+//! AAML stores every value as a string; [`AamlValue`] is the nested form
+//! produced by [`AAML::to_tree`](crate::aaml::AAML::to_tree), where dotted keys
+//! and inline objects become objects and list literals become lists.
+
+use crate::aaml::parsing;
+use crate::types::list::ListType;
+use std::collections::BTreeMap;
+
+/// A value in the hierarchical representation of an AAML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum AamlValue {
+    /// A scalar, with surrounding quotes removed.
+    String(String),
+    /// A `[a, b, c]` list literal.
+    List(Vec<AamlValue>),
+    /// An inline object or a group of dotted keys, ordered by key.
+    Object(BTreeMap<String, AamlValue>),
+}
+
+impl AamlValue {
+    /// Parses a raw stored value: `[...]` becomes a [`List`](Self::List),
+    /// `{ ... }` an [`Object`](Self::Object), anything else a
+    /// [`String`](Self::String). Malformed objects are kept as strings.
+    pub fn parse(raw: &str) -> AamlValue {
+        if let Some(items) = ListType::parse_items(raw) {
+            return AamlValue::List(items.iter().map(|i| AamlValue::parse(i)).collect());
+        }
+        if parsing::is_inline_object(raw)
+            && let Ok(pairs) = parsing::parse_inline_object(raw)
+        {
+            return AamlValue::Object(
+                pairs
+                    .into_iter()
+                    .map(|(k, v)| (k, AamlValue::parse(&v)))
+                    .collect(),
+            );
+        }
+        AamlValue::String(parsing::unwrap_quotes(raw).to_string())
+    }
+
+    /// Returns the scalar value, or `None` for lists and objects.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AamlValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the list items, or `None` for scalars and objects.
+    pub fn as_list(&self) -> Option<&[AamlValue]> {
+        match self {
+            AamlValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the object fields, or `None` for scalars and lists.
+    pub fn as_object(&self) -> Option<&BTreeMap<String, AamlValue>> {
+        match self {
+            AamlValue::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Follows a dotted `path` (`"server.tls.cert"`) through nested objects.
+    pub fn get(&self, path: &str) -> Option<&AamlValue> {
+        path.split('.')
+            .try_fold(self, |node, segment| node.as_object()?.get(segment))
+    }
+}
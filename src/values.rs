@@ -0,0 +1,286 @@
+//! Typed values for the structured built-in types.
+//!
+//! Validation proves that a stored string is a well-formed `color`,
+//! `math::vector3` or `physics::kilogram`; the types here carry the parsed
+//! result so callers do not have to split and parse the string again.
+//!
+//! Every type implements [`FromStr`] (using the same rules as the matching
+//! validator) and [`Display`](fmt::Display) (producing a string that parses
+//! back to the same value).
+//!
+//! # Example
+//! ```
+//! use aam_rs::aaml::AAML;
+//! use aam_rs::values::{Rgba, Vec3};
+//!
+//! let cfg = AAML::parse("tint = #FF8000\norigin = 1.0, 2.5, -3").unwrap();
+//! assert_eq!(cfg.find_obj("tint").unwrap().as_color(), Some(Rgba::new(255, 128, 0, 255)));
+//! assert_eq!(cfg.find_obj("origin").unwrap().as_vec3(), Some(Vec3::new(1.0, 2.5, -3.0)));
+//! ```
+
+use crate::error::AamlError;
+use crate::types::Type;
+use crate::types::color::parse_color;
+use crate::types::math::MathTypes;
+use crate::types::physics::PhysicsTypes;
+use crate::types::range::RangeType;
+use crate::units::{self, UnitSymbol};
+use std::fmt;
+use std::str::FromStr;
+
+/// An 8-bit RGBA color, parsed from any `color` notation: hex (`#RGB`,
+/// `#RRGGBB`, `#RRGGBBAA`, ...), `rgb()` / `rgba()`, `hsl()` / `hsla()` or a
+/// CSS color name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Creates a color from its four channels.
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = AamlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [r, g, b, a] = parse_color(s)?;
+        Ok(Self::new(r, g, b, a))
+    }
+}
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+}
+
+/// Validates `s` as `kind` and returns its comma-separated components.
+fn components<const N: usize>(kind: MathTypes, s: &str) -> Result<[f64; N], AamlError> {
+    kind.validate(s)?;
+    let mut out = [0.0; N];
+    for (slot, part) in out.iter_mut().zip(s.split(',')) {
+        *slot = part.trim().parse().unwrap_or_default();
+    }
+    Ok(out)
+}
+
+macro_rules! vector {
+    ($(#[$doc:meta])* $name:ident, $kind:expr, $($field:ident),+) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name {
+            $(pub $field: f64,)+
+        }
+
+        impl $name {
+            /// Creates a vector from its components.
+            pub fn new($($field: f64),+) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = AamlError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let [$($field),+] = components($kind, s)?;
+                Ok(Self { $($field),+ })
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let parts = [$(self.$field.to_string()),+];
+                f.write_str(&parts.join(", "))
+            }
+        }
+    };
+}
+
+vector!(
+    /// A `math::vector2` value, written `x, y`.
+    Vec2, MathTypes::Vector2, x, y
+);
+vector!(
+    /// A `math::vector3` value, written `x, y, z`.
+    Vec3, MathTypes::Vector3, x, y, z
+);
+vector!(
+    /// A `math::vector4` value, written `x, y, z, w`.
+    Vec4, MathTypes::Vector4, x, y, z, w
+);
+
+vector!(
+    /// A `math::quaternion` rotation, written `x, y, z, w`.
+    Quat, MathTypes::Quaternion, x, y, z, w
+);
+
+impl Quat {
+    /// The rotation that does nothing.
+    pub const IDENTITY: Quat = Quat { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+    /// Returns the length of the quaternion; 1 for a rotation.
+    pub fn length(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    /// Returns the quaternion scaled to unit length, or `None` for the zero
+    /// quaternion.
+    pub fn normalized(&self) -> Option<Quat> {
+        let len = self.length();
+        (len > 0.0).then(|| Quat::new(self.x / len, self.y / len, self.z / len, self.w / len))
+    }
+}
+
+/// A `physics::kilogram` mass, written as a bare number of kilograms or with
+/// a mass unit (`75kg`, `500 g`, `2 lb`).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kilogram(pub f64);
+
+impl FromStr for Kilogram {
+    type Err = AamlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        PhysicsTypes::Kilogram.quantity(s).map(Kilogram)
+    }
+}
+
+impl fmt::Display for Kilogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A number with a unit symbol, such as `3.5 km` or `20°C`.
+///
+/// Conversions go through [`units::SYMBOLS`] so every consumer of a config
+/// uses the same factors.
+///
+/// # Example
+/// ```
+/// use aam_rs::values::Quantity;
+///
+/// let q: Quantity = "750 g".parse().unwrap();
+/// assert_eq!(q.unit(), "g");
+/// assert_eq!(q.to("kg").unwrap().value, 0.75);
+/// assert!(q.to("m").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    /// The number, in [`unit`](Self::unit)s.
+    pub value: f64,
+    symbol: &'static UnitSymbol,
+}
+
+impl Quantity {
+    /// Creates a quantity from a number and a unit symbol, or `None` if the
+    /// symbol is not one of [`units::SYMBOLS`].
+    pub fn new(value: f64, symbol: &str) -> Option<Self> {
+        units::symbol(symbol).map(|symbol| Self { value, symbol })
+    }
+
+    /// Returns the unit symbol.
+    pub fn unit(&self) -> &'static str {
+        self.symbol.symbol
+    }
+
+    /// Returns the unit's definition: its dimension and SI conversion factors.
+    pub fn unit_symbol(&self) -> &'static UnitSymbol {
+        self.symbol
+    }
+
+    /// Converts the quantity into the unit `symbol`, or `None` if the symbol
+    /// is unknown or measures a different dimension.
+    pub fn to(&self, symbol: &str) -> Option<Quantity> {
+        let target = units::symbol(symbol)?;
+        let value = self.symbol.convert(self.value, target)?;
+        Some(Self { value, symbol: target })
+    }
+
+    /// Returns the number of `symbol` units in this quantity; shorthand for
+    /// [`to`](Self::to) followed by reading [`value`](Self::value).
+    pub fn value_in(&self, symbol: &str) -> Option<f64> {
+        self.to(symbol).map(|q| q.value)
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = AamlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        units::split_quantity(s)
+            .map(|(value, symbol)| Self { value, symbol })
+            .ok_or_else(|| {
+                AamlError::InvalidValue(format!(
+                    "Expected a number with a unit symbol like 3.5km, got '{}'",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.symbol.symbol)
+    }
+}
+
+/// The endpoints of a `range<T>` literal (`1..10`, `0.5..=2.5`).
+///
+/// # Example
+/// ```
+/// use aam_rs::found_value::FoundValue;
+///
+/// let levels = FoundValue::new("1..=10").as_range::<u32>().unwrap();
+/// assert_eq!((levels.start, levels.end, levels.inclusive), (1, 10, true));
+/// assert!(levels.contains(&10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span<T> {
+    /// The start of the range, always included.
+    pub start: T,
+    /// The end of the range.
+    pub end: T,
+    /// Whether `end` is part of the range (`..=`).
+    pub inclusive: bool,
+}
+
+impl<T: PartialOrd> Span<T> {
+    /// Returns `true` when `value` lies inside the range.
+    pub fn contains(&self, value: &T) -> bool {
+        *value >= self.start && if self.inclusive { *value <= self.end } else { *value < self.end }
+    }
+}
+
+impl<T: FromStr> FromStr for Span<T> {
+    type Err = AamlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AamlError::InvalidValue(format!("Expected a range 'start..end', got '{s}'"));
+        let (start, end, inclusive) = RangeType::split_literal(s).ok_or_else(invalid)?;
+        Ok(Self {
+            start: start.parse().map_err(|_| invalid())?,
+            end: end.parse().map_err(|_| invalid())?,
+            inclusive,
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Span<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.inclusive { "..=" } else { ".." };
+        write!(f, "{}{op}{}", self.start, self.end)
+    }
+}
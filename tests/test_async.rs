@@ -0,0 +1,41 @@
+#![cfg(feature = "tokio")]
+
+use aam_rs::aaml::AAML;
+use aam_rs::aaml::loader::MemoryLoader;
+use std::fs;
+
+#[tokio::test]
+async fn test_load_async_with_imports() {
+    let dir = "test_load_async";
+    fs::create_dir_all(format!("{dir}/nested")).unwrap();
+    fs::write(
+        format!("{dir}/app.aam"),
+        "@import nested/db.aam\n@import_optional local.aam\n@derive base.aam\nname = app\n",
+    )
+    .unwrap();
+    fs::write(format!("{dir}/nested/db.aam"), "@import pool.aam\nhost = db.local\n").unwrap();
+    fs::write(format!("{dir}/nested/pool.aam"), "pool = 8\n").unwrap();
+    fs::write(format!("{dir}/base.aam"), "timeout = 30\n").unwrap();
+
+    let res = AAML::load_async(format!("{dir}/app.aam")).await;
+    let _ = fs::remove_dir_all(dir);
+    let aaml = res.expect("Should load config and its imports");
+
+    assert_eq!(aaml.find_obj("name").unwrap().as_str(), "app");
+    assert_eq!(aaml.find_obj("host").unwrap().as_str(), "db.local");
+    assert_eq!(aaml.find_obj("pool").unwrap().as_str(), "8");
+    assert_eq!(aaml.find_obj("timeout").unwrap().as_str(), "30");
+}
+
+#[tokio::test]
+async fn test_merge_content_async_uses_loader_and_limits() {
+    let loader = MemoryLoader::new()
+        .with_file("cfg/a.aam", "a = 1")
+        .with_file("cfg/loop.aam", "@import loop.aam");
+    let mut aaml = AAML::new().with_loader(loader).with_source_dir("cfg");
+
+    aaml.merge_content_async("@import a.aam").await.unwrap();
+    assert_eq!(aaml.find_obj("a").unwrap().as_str(), "1");
+    assert!(aaml.merge_content_async("@import loop.aam").await.is_err());
+    assert!(aaml.merge_content_async("@import missing.aam").await.is_err());
+}
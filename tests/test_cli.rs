@@ -0,0 +1,73 @@
+#![cfg(feature = "cli")]
+
+use aam_rs::aaml::AAML;
+
+const SOURCE: &str = "@schema Server { host: string, port: i32, debug*: bool }";
+
+#[test]
+fn test_schema_to_command_flags() {
+    let aaml = AAML::parse(SOURCE).unwrap();
+    let cmd = aaml.schema_to_command("Server").expect("Schema should exist");
+
+    let longs: Vec<&str> = cmd.get_arguments().filter_map(|a| a.get_long()).collect();
+    assert_eq!(longs, vec!["debug", "host", "port"]);
+
+    let port = cmd.get_arguments().find(|a| a.get_id() == "port").unwrap();
+    assert!(port.is_required_set());
+    let debug = cmd.get_arguments().find(|a| a.get_id() == "debug").unwrap();
+    assert!(!debug.is_required_set());
+}
+
+#[test]
+fn test_schema_to_command_rejects_bad_value() {
+    let aaml = AAML::parse(SOURCE).unwrap();
+    let cmd = aaml.schema_to_command("Server").unwrap();
+    let res = cmd.try_get_matches_from(["app", "--host", "h", "--port", "eighty"]);
+    assert!(res.is_err(), "'eighty' is not an i32");
+}
+
+#[test]
+fn test_merge_arg_matches_writes_values() {
+    let mut aaml = AAML::parse(SOURCE).unwrap();
+    let cmd = aaml.schema_to_command("Server").unwrap();
+    let matches = cmd
+        .try_get_matches_from(["app", "--host", "example.org", "--port", "8080", "--debug"])
+        .unwrap();
+
+    aaml.merge_arg_matches("Server", &matches).unwrap();
+    assert_eq!(aaml.find_obj("host").unwrap().as_str(), "example.org");
+    assert_eq!(aaml.find_obj("port").unwrap().as_str(), "8080");
+    assert_eq!(aaml.find_obj("debug").unwrap().as_str(), "true");
+}
+
+#[test]
+fn test_merge_arg_matches_skips_foreign_args() {
+    use clap::{Arg, ArgAction};
+    use std::path::PathBuf;
+
+    let mut aaml = AAML::parse(SOURCE).unwrap();
+    let cmd = aaml
+        .schema_to_command("Server")
+        .unwrap()
+        .arg(Arg::new("verbose").long("verbose").action(ArgAction::SetTrue))
+        .arg(Arg::new("config").long("config").value_parser(clap::value_parser!(PathBuf)))
+        .arg(Arg::new("label").long("label"));
+    let matches = cmd
+        .try_get_matches_from([
+            "app", "--host", "h", "--port", "1", "--verbose", "--config", "a.aam", "--label", "x",
+        ])
+        .unwrap();
+
+    aaml.merge_arg_matches("Server", &matches).unwrap();
+    assert_eq!(aaml.find_obj("host").unwrap().as_str(), "h");
+    assert!(aaml.find_obj("verbose").is_none());
+    assert!(aaml.find_obj("config").is_none());
+    assert!(aaml.find_obj("label").is_none());
+    assert!(aaml.merge_arg_matches("Nope", &matches).is_err());
+}
+
+#[test]
+fn test_schema_to_command_unknown_schema() {
+    let aaml = AAML::new();
+    assert!(aaml.schema_to_command("Nope").is_err());
+}
@@ -0,0 +1,50 @@
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+
+use aam_rs::aaml::AAML;
+use std::fs;
+
+const SOURCE: &str = "@schema Server {\n    host: string\n    port: i32\n}\nhost = localhost\nport = 8080\n";
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_load_gz() {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let path = "test_load_gz.aam.gz";
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(SOURCE.as_bytes()).unwrap();
+    fs::write(path, encoder.finish().unwrap()).unwrap();
+
+    let res = AAML::load_gz(path);
+    let _ = fs::remove_file(path);
+    let aaml = res.expect("Should load gzip config");
+
+    assert_eq!(aaml.find_obj("host").unwrap().as_str(), "localhost");
+    assert!(aaml.get_schema("Server").is_some());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_load_zstd() {
+    let path = "test_load_zstd.aam.zst";
+    fs::write(path, zstd::encode_all(SOURCE.as_bytes(), 0).unwrap()).unwrap();
+
+    let res = AAML::load_zstd(path);
+    let _ = fs::remove_file(path);
+    let aaml = res.expect("Should load zstd config");
+
+    assert_eq!(aaml.find_obj("port").unwrap().as_str(), "8080");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_load_gz_rejects_plain_text() {
+    let path = "test_load_gz_plain.aam";
+    fs::write(path, SOURCE).unwrap();
+
+    let res = AAML::load_gz(path);
+    let _ = fs::remove_file(path);
+    assert!(res.is_err(), "Plain text is not gzip");
+}
@@ -36,7 +36,7 @@ mod tests {
 
     #[test]
     fn test_display_trait() {
-        let res = FoundValue::new(&*"hello".to_string());
+        let res = FoundValue::new("hello");
         let formatted = format!("{}", res);
         assert_eq!(formatted, "hello");
     }
@@ -222,4 +222,831 @@ mod tests {
         assert!(res.is_some());
         assert_eq!(res.unwrap().as_str(), "c");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_to_tree_groups_dotted_keys_and_inline_objects() {
+        use aam_rs::value::AamlValue;
+
+        let parser = AAML::parse(
+            "name = \"app\"\n\
+             server.host = localhost\n\
+             server.ports = [80, 443]\n\
+             server.tls = { enabled = true, cert = { path = /etc/cert } }\n\
+             server.tls.mode = strict",
+        )
+        .unwrap();
+        let tree = parser.to_tree().unwrap();
+
+        assert_eq!(tree.get("name").unwrap().as_str(), Some("app"));
+        assert_eq!(tree.get("server.host").unwrap().as_str(), Some("localhost"));
+        assert_eq!(
+            tree.get("server.ports").unwrap().as_list().unwrap(),
+            &[AamlValue::String("80".into()), AamlValue::String("443".into())]
+        );
+        assert_eq!(tree.get("server.tls.cert.path").unwrap().as_str(), Some("/etc/cert"));
+        assert_eq!(tree.get("server.tls.mode").unwrap().as_str(), Some("strict"));
+        assert_eq!(tree.get("server").unwrap().as_object().unwrap().len(), 3);
+        assert!(tree.get("server.missing").is_none());
+    }
+
+    #[test]
+    fn test_to_tree_rejects_scalar_prefix_conflict() {
+        let parser = AAML::parse("a = 1\na.b = 2").unwrap();
+        assert!(parser.to_tree().is_err());
+    }
+
+    #[test]
+    fn test_require_directive_checked_at_end_of_parse() {
+        let cfg = AAML::parse("@require api_key, region\napi_key = abc\nregion = eu").unwrap();
+        assert_eq!(cfg.required_keys(), ["api_key", "region"]);
+
+        let err = AAML::parse("@require api_key, region, zone\nregion = eu").unwrap_err().to_string();
+        assert!(err.contains("api_key, zone"), "got: {err}");
+    }
+
+    #[test]
+    fn test_require_accepts_schema_defaults_and_rejects_bad_lists() {
+        assert!(AAML::parse("@schema S { port: i32 = 80 }\n@require port").is_ok());
+        assert!(AAML::parse("@require a,,b").is_err());
+        assert!(AAML::parse("@require").is_err());
+
+        let mut cfg = AAML::new();
+        cfg.merge_content("@require token").unwrap();
+        assert!(cfg.validate_required_keys().is_err());
+        cfg.merge_content("token = t").unwrap();
+        assert!(cfg.validate_required_keys().is_ok());
+    }
+
+    #[test]
+    fn test_rule_directive_compares_fields() {
+        let rules = "@rule pool_min <= pool_max\n@rule tls == true requires port == 443\n";
+        let cfg = AAML::parse(&format!("{rules}pool_min = 2\npool_max = 10\ntls = true\nport = 443")).unwrap();
+        assert_eq!(cfg.rules().len(), 2);
+        assert_eq!(cfg.rules()[1].to_string(), "tls == true requires port == 443");
+
+        let err = AAML::parse(&format!("{rules}pool_min = 20\npool_max = 10")).unwrap_err().to_string();
+        assert!(err.contains("pool_min (20) <= pool_max (10)"), "got: {err}");
+
+        let err = AAML::parse(&format!("{rules}tls = true\nport = 80")).unwrap_err().to_string();
+        assert!(err.contains("port (80) == 443") && err.contains("since tls (true)"), "got: {err}");
+
+        // Condition false, or a key without a value: the rule does not apply.
+        assert!(AAML::parse(&format!("{rules}tls = false\nport = 80")).is_ok());
+        assert!(AAML::parse(&format!("{rules}pool_min = 20")).is_ok());
+    }
+
+    #[test]
+    fn test_rule_directive_literals_and_errors() {
+        assert!(AAML::parse("@rule env != \"prod\" requires debug == false\nenv = prod\ndebug = true").is_ok());
+        assert!(AAML::parse("@rule env != \"prod\" requires debug == false\nenv = dev\ndebug = true").is_err());
+        assert!(AAML::parse("@schema S { retries: i32 = 3 }\n@rule retries < 5").is_ok());
+        assert!(AAML::parse("@rule mode < other\nmode = a\nother = b").is_err());
+        assert!(AAML::parse("@rule pool_min pool_max").is_err());
+        assert!(AAML::parse("@rule <= 5").is_err());
+        assert!(AAML::parse("@rule a == b requires").is_err());
+    }
+
+    #[test]
+    fn test_assert_os_directive() {
+        let here = std::env::consts::OS;
+        assert!(AAML::parse(&format!("@assert_os plan9 | {here}\nk = v")).is_ok());
+
+        let err = AAML::parse("@assert_os plan9\nk = v").unwrap_err().to_string();
+        assert!(err.contains("plan9") && err.contains(here), "got: {err}");
+        assert!(AAML::parse("@assert_os linux |").is_err());
+    }
+
+    #[test]
+    fn test_assert_min_version_directive() {
+        assert!(AAML::parse("@assert_min_version crate=0.1").is_ok());
+        assert!(AAML::parse(&format!("@assert_min_version crate={}", env!("CARGO_PKG_VERSION"))).is_ok());
+
+        let err = AAML::parse("@assert_min_version crate=999.0.0").unwrap_err().to_string();
+        assert!(err.contains("999.0.0"), "got: {err}");
+        assert!(AAML::parse("@assert_min_version rustc=1.0").is_err());
+        assert!(AAML::parse("@assert_min_version crate=1.x").is_err());
+        assert!(AAML::parse("@assert_min_version 1.2").is_err());
+    }
+
+    #[test]
+    fn test_env_interpolation() {
+        // SAFETY: the variable names are unique to this test.
+        unsafe {
+            std::env::set_var("AAM_TEST_INTERP_URL", "postgres://db/prod");
+            std::env::set_var("AAM_TEST_INTERP_EMPTY", "");
+        }
+        let cfg = AAML::parse(
+            "db_url = ${AAM_TEST_INTERP_URL}\n\
+             cache = ${AAM_TEST_INTERP_UNSET:-redis://localhost}\n\
+             empty = ${AAM_TEST_INTERP_EMPTY:-fallback}\n\
+             label = \"db at ${AAM_TEST_INTERP_URL}, $${literal}\"",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("db_url").unwrap().as_str(), "postgres://db/prod");
+        assert_eq!(cfg.find_obj("cache").unwrap().as_str(), "redis://localhost");
+        assert_eq!(cfg.find_obj("empty").unwrap().as_str(), "fallback");
+        assert_eq!(cfg.find_obj("label").unwrap().as_str(), "db at postgres://db/prod, ${literal}");
+
+        let err = AAML::parse("x = ${AAM_TEST_INTERP_UNSET}").unwrap_err().to_string();
+        assert!(err.contains("AAM_TEST_INTERP_UNSET"), "got: {err}");
+        assert!(AAML::parse("x = ${AAM_TEST_INTERP_URL").is_err());
+        assert!(AAML::parse("x = ${BAD NAME}").is_err());
+        assert!(AAML::parse("@schema S { port: i32 }\nport = ${AAM_TEST_INTERP_URL}").is_err());
+    }
+
+    #[test]
+    fn test_env_directive() {
+        // SAFETY: the variable names are unique to this test.
+        unsafe {
+            std::env::set_var("AAM_TEST_DIRECTIVE_PORT", "8080");
+            std::env::set_var("AAM_TEST_DIRECTIVE_DB_URL", "postgres://db");
+        }
+        let cfg = AAML::parse("port = 1\n@env AAM_TEST_DIRECTIVE_\nhost = local").unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "8080");
+        assert_eq!(cfg.find_obj("db_url").unwrap().as_str(), "postgres://db");
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "local");
+
+        let cfg = AAML::parse("@env AAM_TEST_DIRECTIVE_\nport = 9090").unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "9090");
+
+        assert!(AAML::parse("@schema S { port: u8 }\n@env AAM_TEST_DIRECTIVE_").is_err());
+        assert!(AAML::parse("@env").is_err());
+        assert!(AAML::parse("@env A B").is_err());
+    }
+
+    #[test]
+    fn test_key_references() {
+        let cfg = AAML::parse(
+            "log_dir = ${base_dir}/logs\n\
+             base_dir = ${root}/app\n\
+             root = /srv\n\
+             alias = root\n\
+             via_alias = ${alias}\n\
+             missing = ${no_such_key:-none}",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("log_dir").unwrap().as_str(), "/srv/app/logs");
+        assert_eq!(cfg.find_obj("base_dir").unwrap().as_str(), "/srv/app");
+        assert_eq!(cfg.find_obj("via_alias").unwrap().as_str(), "/srv", "follows alias chains");
+        assert_eq!(cfg.find_obj("missing").unwrap().as_str(), "none");
+
+        let cfg = AAML::parse("@schema S { port: i32, url: string }\nurl = http://host:${port}\nport = 8080").unwrap();
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://host:8080");
+        let cfg = AAML::parse("@schema S { port: i32 = 80 }\nurl = :${port}").unwrap();
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), ":80", "schema defaults count as values");
+
+        assert!(AAML::parse("@schema S { port: i32 }\nbase = 80\nport = ${base}0").is_ok());
+        assert!(AAML::parse("@schema S { port: i32 }\nbase = x\nport = ${base}").is_err());
+
+        let err = AAML::parse("a = ${b}\nb = ${c}\nc = ${a}").unwrap_err().to_string();
+        assert!(err.contains("Circular reference: a -> b -> c -> a"), "got: {err}");
+        assert!(AAML::parse("a = ${a}").is_err());
+
+        let cfg = AAML::parse("a = ${b}\na = plain").unwrap();
+        assert_eq!(cfg.find_obj("a").unwrap().as_str(), "plain", "reassignment drops the reference");
+    }
+
+    const CONDITIONAL: &str = "
+        @if env == prod
+        replicas = 3
+        @elif env == staging || debug
+        replicas = 2
+        @else
+        replicas = 1
+        @endif
+    ";
+
+    fn context(pairs: &[(&str, &str)]) -> std::collections::HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_conditional_blocks_choose_one_branch() {
+        let replicas = |pairs: &[(&str, &str)]| {
+            let cfg = AAML::parse_with_context(CONDITIONAL, context(pairs)).unwrap();
+            cfg.find_obj("replicas").unwrap().as_str().to_string()
+        };
+        assert_eq!(replicas(&[("env", "prod")]), "3");
+        assert_eq!(replicas(&[("env", "staging")]), "2");
+        assert_eq!(replicas(&[("env", "dev"), ("debug", "yes")]), "2");
+        assert_eq!(replicas(&[("env", "dev"), ("debug", "off")]), "1");
+        assert_eq!(replicas(&[]), "1");
+    }
+
+    #[test]
+    fn test_conditional_blocks_nest_and_read_keys() {
+        let content = "platform = linux\n\
+                       @if platform == \"linux\" && !legacy\n\
+                       @if gpu\nrenderer = vulkan\n@else\nrenderer = software\n@endif\n\
+                       @schema Skipped { a: i32 }\n\
+                       @elif platform != linux\nrenderer = metal\n\
+                       @endif";
+        let cfg = AAML::parse_with_context(content, context(&[("gpu", "true")])).unwrap();
+        assert_eq!(cfg.find_obj("renderer").unwrap().as_str(), "vulkan");
+        assert!(cfg.get_schema("Skipped").is_some());
+
+        let cfg = AAML::parse_with_context(content, context(&[("legacy", "1")])).unwrap();
+        assert!(cfg.find_obj("renderer").is_none(), "no branch matched");
+        assert!(cfg.get_schema("Skipped").is_none(), "directives in skipped branches do not run");
+    }
+
+    #[test]
+    fn test_conditional_blocks_reject_malformed_structure() {
+        for bad in ["@if a\nk = v", "@endif", "@else", "@elif a", "@if a\n@else\n@elif b\n@endif",
+                    "@if a\n@else\n@else\n@endif", "@if\n@endif", "@if a\n@endif b"] {
+            assert!(AAML::parse(bad).is_err(), "{bad:?} should be rejected");
+        }
+        let err = AAML::parse("k = v\n@if a\nx = 1").unwrap_err().to_string();
+        assert!(err.contains("Unclosed @if"), "got: {err}");
+    }
+
+    #[test]
+    fn test_define_constants() {
+        let cfg = AAML::parse(
+            "@define MAX_PLAYERS 64\n\
+             @define TEAM_SIZE MAX_PLAYERS\n\
+             @define GREETING \"Hello, world\"\n\
+             lobby = MAX_PLAYERS\n\
+             teams = [MAX_PLAYERS, TEAM_SIZE, MAX_PLAYERS_X]\n\
+             label = \"MAX_PLAYERS players\"\n\
+             motd = GREETING\n\
+             env = ${MAX_PLAYERS:-x}",
+        )
+        .unwrap();
+        assert_eq!(cfg.constant("MAX_PLAYERS"), Some("64"));
+        assert_eq!(cfg.find_obj("lobby").unwrap().as_str(), "64");
+        assert_eq!(cfg.find_obj("teams").unwrap().as_str(), "[64, 64, MAX_PLAYERS_X]");
+        assert_eq!(cfg.find_obj("label").unwrap().as_str(), "MAX_PLAYERS players", "quoted values are literal");
+        assert_eq!(cfg.find_obj("motd").unwrap().as_str(), "Hello, world");
+        assert_eq!(cfg.find_obj("env").unwrap().as_str(), "x", "references are not constants");
+
+        let err = AAML::parse("@define MAX_PLAYERS lots\n@schema S { lobby: i32 }\nlobby = MAX_PLAYERS")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("MAX_PLAYERS = lots"), "got: {err}");
+
+        assert!(AAML::parse("@define A 1\n@define A 2").is_err());
+        assert!(AAML::parse("@define 1A 1").is_err());
+        assert!(AAML::parse("@define A").is_err());
+    }
+
+    #[test]
+    fn test_namespace_prefixes_keys() {
+        let cfg = AAML::parse(
+            "name = app\n\
+             @namespace database\n\
+             host = db.local\n\
+             port = 5432\n\
+             @namespace net.proxy\n\
+             url = ${database.host}:3128\n\
+             @endnamespace\n\
+             debug = true",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("database.host").unwrap().as_str(), "db.local");
+        assert_eq!(cfg.find_obj("database.port").unwrap().as_str(), "5432");
+        assert_eq!(cfg.find_obj("net.proxy.url").unwrap().as_str(), "db.local:3128");
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "app");
+        assert_eq!(cfg.find_obj("debug").unwrap().as_str(), "true");
+        assert!(cfg.find_obj("host").is_none());
+
+        let mut cfg = AAML::new();
+        cfg.merge_content("@namespace a\nx = 1").unwrap();
+        cfg.merge_content("y = 2").unwrap();
+        assert!(cfg.find_obj("y").is_some(), "a namespace ends with its content");
+
+        assert!(AAML::parse("@endnamespace").is_err());
+        assert!(AAML::parse("@namespace").is_err());
+        assert!(AAML::parse("@namespace a..b").is_err());
+    }
+
+    #[test]
+    fn test_require_with_type() {
+        let cfg = AAML::parse("@require api_key: string, retries: i32
+api_key = abc
+retries = 3").unwrap();
+        assert_eq!(cfg.required_type("retries"), Some("i32"));
+        assert_eq!(cfg.required_type("api_key"), Some("string"));
+
+        let err = AAML::parse("retries = many
+@require retries: i32").unwrap_err().to_string();
+        assert!(err.contains("retries") && err.contains("i32"), "{err}");
+
+        let err = AAML::parse("@require api_key: string").unwrap_err().to_string();
+        assert!(err.contains("Missing required key(s): api_key"), "{err}");
+
+        assert!(AAML::parse("@require limits: map<string, i32>
+limits = { a = 1, b = 2 }").is_ok());
+        assert!(AAML::parse("@schema S { port*: i32 = 80 }
+@require port: i32").is_ok());
+        assert!(AAML::parse("@require x: no_such_type").is_err());
+    }
+
+    #[test]
+    fn test_deprecated_key_warns() {
+        let cfg = AAML::parse(
+            "@deprecated old_timeout \"use timeout_ms instead\"\n\
+             @deprecated legacy\n\
+             old_timeout = 30\n\
+             timeout_ms = 500",
+        )
+        .unwrap();
+        assert!(cfg.is_deprecated("old_timeout"));
+        assert_eq!(cfg.find_obj("old_timeout").unwrap().as_str(), "30");
+        assert_eq!(cfg.warnings().len(), 1);
+        assert_eq!(cfg.warnings()[0].line, Some(3));
+        assert_eq!(
+            cfg.warnings()[0].to_string(),
+            "line 3: Key 'old_timeout' is deprecated: use timeout_ms instead"
+        );
+
+        let cfg = AAML::parse("legacy = on\n@deprecated legacy").unwrap();
+        assert_eq!(cfg.warnings()[0].message, "Key 'legacy' is deprecated");
+
+        let mut cfg = AAML::new().with_strict_deprecations(true);
+        let err = cfg.merge_content("@deprecated old_timeout\nold_timeout = 30").unwrap_err();
+        assert!(err.to_string().contains("deprecated"), "{err}");
+        assert!(AAML::parse("@deprecated").is_err());
+    }
+
+    #[test]
+    fn test_alias_maps_old_key_to_new() {
+        let cfg = AAML::parse(
+            "@schema Server { host: string, port: i32 }\n\
+             @alias hostname = host\n\
+             hostname = example.com\n\
+             port = 80\n\
+             url = http://${hostname}:${port}",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "example.com");
+        assert_eq!(cfg.find_obj("hostname").unwrap().as_str(), "example.com");
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://example.com:80");
+
+        let record = std::collections::HashMap::from([
+            ("hostname".to_string(), "example.com".to_string()),
+            ("port".to_string(), "80".to_string()),
+        ]);
+        assert!(cfg.apply_schema("Server", &record).is_ok());
+
+        assert!(AAML::parse("@schema S { port: i32 }\n@alias p = port\np = many").is_err());
+
+        let cfg = AAML::parse("old = 1\n@alias old = mid\n@alias mid = new").unwrap();
+        assert_eq!(cfg.canonical_key("old"), "new");
+        assert_eq!(cfg.find_obj("new").unwrap().as_str(), "1");
+
+        assert!(AAML::parse("@alias a = b\n@alias b = a").is_err());
+        assert!(AAML::parse("@alias a").is_err());
+    }
+
+    #[test]
+    fn test_profile_blocks() {
+        let content = "log_level = info\n\
+                       replicas = 1\n\
+                       @profile dev {\n\
+                       log_level = debug\n\
+                       @if verbose\n\
+                       trace = on\n\
+                       @endif\n\
+                       }\n\
+                       @profile staging, prod { replicas = 3 }";
+
+        let mut dev = AAML::new().with_profile("dev");
+        dev.merge_content(content).unwrap();
+        assert_eq!(dev.find_obj("log_level").unwrap().as_str(), "debug");
+        assert_eq!(dev.find_obj("replicas").unwrap().as_str(), "1");
+        assert!(dev.find_obj("trace").is_none());
+
+        let mut prod = AAML::new().with_profile("prod");
+        prod.merge_content(content).unwrap();
+        assert_eq!(prod.find_obj("log_level").unwrap().as_str(), "info");
+        assert_eq!(prod.find_obj("replicas").unwrap().as_str(), "3");
+
+        let none = AAML::parse(content).unwrap();
+        assert_eq!(none.find_obj("log_level").unwrap().as_str(), "info");
+        assert_eq!(none.find_obj("replicas").unwrap().as_str(), "1");
+
+        let path = "test_profile_blocks.aam";
+        std::fs::write(path, content).unwrap();
+        let loaded = AAML::load_with_profile(path, "staging");
+        let _ = std::fs::remove_file(path);
+        assert_eq!(loaded.unwrap().find_obj("replicas").unwrap().as_str(), "3");
+
+        assert!(AAML::parse("@profile dev {\nx = 1").is_err(), "unclosed block");
+        assert!(AAML::parse("@profile dev {\n@endif\n}").is_err());
+        assert!(AAML::parse("@profile dev { x = 1\n}").is_err());
+        assert!(AAML::parse("@profile { x = 1 }").is_err());
+    }
+
+    #[test]
+    fn test_command_receives_execution_context() {
+        use aam_rs::commands::{Command, ExecutionContext};
+        use aam_rs::error::AamlError;
+
+        struct WhereCommand;
+        impl Command for WhereCommand {
+            fn name(&self) -> &str {
+                "where"
+            }
+            fn execute(&self, aaml: &mut AAML, args: &str, ctx: &ExecutionContext) -> Result<(), AamlError> {
+                aaml.merge_content(&format!("{} = {}", args.trim(), ctx.line()))
+            }
+        }
+
+        let mut cfg = AAML::new();
+        cfg.register_command(WhereCommand);
+        cfg.merge_content("a = 1\n\n@where here").unwrap();
+        assert_eq!(cfg.find_obj("here").unwrap().as_str(), "3");
+
+        match AAML::parse("a = 1\n@type broken") {
+            Err(AamlError::ParseError { line, .. }) => assert_eq!(line, 2),
+            other => panic!("unexpected: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_directive_handler() {
+        use aam_rs::error::AamlError;
+
+        assert!(AAML::parse("@unity_guid 6f1c2a").is_err());
+
+        let mut cfg = AAML::new();
+        cfg.set_unknown_directive_handler(|aaml, name, args, _| match name {
+            "unity_guid" => aaml.merge_content(&format!("guid = {}", args.trim())),
+            _ => Err(AamlError::DirectiveError(name.into(), "not supported".into(), None)),
+        });
+        cfg.merge_content("@unity_guid 6f1c2a\n@schema S { port: i32 }").unwrap();
+        assert_eq!(cfg.find_obj("guid").unwrap().as_str(), "6f1c2a");
+        assert!(cfg.get_schema("S").is_some(), "registered commands still run");
+
+        let err = cfg.merge_content("a = 1\n@vendor_x on").unwrap_err();
+        assert_eq!(err.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn test_escape_sequences_in_quoted_values() {
+        let cfg = AAML::parse(
+            r#"greeting = "say \"hi\" # not a comment" # comment
+lines = "one\ntwo\tthree"
+smile = "\u{1F600}\\"
+raw = 'C:\new\table'
+path = "C:\Games"
+bare = a\nb"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("greeting").unwrap().as_str(), r#"say "hi" # not a comment"#);
+        assert_eq!(cfg.find_obj("lines").unwrap().as_str(), "one\ntwo\tthree");
+        assert_eq!(cfg.find_obj("smile").unwrap().as_str(), "\u{1F600}\\");
+        assert_eq!(cfg.find_obj("raw").unwrap().as_str(), r"C:\new\table");
+        assert_eq!(cfg.find_obj("path").unwrap().as_str(), r"C:\Games");
+        assert_eq!(cfg.find_obj("bare").unwrap().as_str(), r"a\nb");
+
+        assert!(AAML::parse(r#"bad = "\u{zz}""#).is_err());
+        assert!(AAML::parse(r#"bad = "\u{110000}""#).is_err());
+    }
+
+    #[test]
+    fn test_backslash_line_continuation() {
+        let cfg = AAML::parse(
+            "tags = [a, b, \\\n\
+             \x20   c, d]\n\
+             motd = \"hello \\\n\
+             world\"\n\
+             dir = C:\\Games\\\n\
+             @schema S { \\\n\
+             port: i32 }\n\
+             port = 80",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("tags").unwrap().as_str(), "[a, b, c, d]");
+        assert_eq!(cfg.find_obj("motd").unwrap().as_str(), "hello world");
+        assert_eq!(cfg.find_obj("dir").unwrap().as_str(), "C:\\Games\\");
+        assert!(cfg.get_schema("S").is_some());
+
+        let err = AAML::parse("a = 1\nlist = [x, \\\ny, z]\nbroken \\\nvalue").unwrap_err();
+        assert!(err.to_string().contains("line 4"), "errors name the first line of the statement: {err}");
+    }
+
+    #[test]
+    fn test_multi_line_literals_in_assignments() {
+        let cfg = AAML::parse(
+            "@schema Net { allowed_ips: list<net::ipv4>, limits: map<string, i32> }\n\
+             allowed_ips = [\n\
+             \x20   10.0.0.1,   # office\n\
+             \x20   10.0.0.2\n\
+             ]\n\
+             limits = {\n\
+             \x20   cpu = 2,\n\
+             \x20   mem = 512\n\
+             }\n\
+             label = \"[not a list\"\n\
+             after = ok",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("allowed_ips").unwrap().as_str(), "[ 10.0.0.1, 10.0.0.2 ]");
+        assert_eq!(cfg.find_obj("limits").unwrap().as_str(), "{ cpu = 2, mem = 512 }");
+        assert_eq!(cfg.find_obj("label").unwrap().as_str(), "[not a list");
+        assert_eq!(cfg.find_obj("after").unwrap().as_str(), "ok");
+
+        let err = AAML::parse("@schema Net { ips: list<net::ipv4> }\nips = [\n10.0.0.1,\nnope\n]").unwrap_err();
+        assert!(err.to_string().contains("nope"), "{err}");
+    }
+
+    #[test]
+    fn test_unclosed_list_at_end_of_input() {
+        use aam_rs::error::AamlError;
+        use std::io::Cursor;
+
+        let err = AAML::parse("name = app\na = [1,\n2").unwrap_err();
+        assert!(matches!(err, AamlError::ParseError { line: 2, .. }), "{err}");
+        assert!(err.to_string().contains("Unclosed '[' opened on line 2"), "{err}");
+        let err = AAML::parse_reader(Cursor::new("a = [\n  1\n  2")).unwrap_err();
+        assert!(err.to_string().contains("Unclosed '[' opened on line 1"), "{err}");
+        assert!(AAML::parse("a = [1,\n2]").is_ok());
+    }
+
+    #[test]
+    fn test_multi_line_inline_objects() {
+        let cfg = AAML::parse(
+            "@schema Tls { cert: string, verify: bool }\n\
+             @schema Server { host: string, tls: Tls, ports: list<i32> }\n\
+             server = {\n\
+             \x20   host = db.local\n\
+             \x20   tls = {\n\
+             \x20       cert = \"a b.pem\"\n\
+             \x20       verify = true\n\
+             \x20   }\n\
+             \x20   ports = [\n\
+             \x20       80\n\
+             \x20       443\n\
+             \x20   ]\n\
+             }\n\
+             @bind server Server",
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.find_obj("server").unwrap().as_str(),
+            "{ host = db.local, tls = { cert = \"a b.pem\", verify = true }, ports = [ 80, 443 ] }"
+        );
+        assert_eq!(cfg.bound_schema("server"), Some("Server"));
+
+        let mut builder = aam_rs::builder::AAMBuilder::new();
+        builder.add_object_multiline("point", [("x", "1"), ("y", "2")]);
+        let cfg = AAML::parse(&builder.build()).unwrap();
+        assert_eq!(cfg.find_obj("point").unwrap().as_str(), "{ x = 1, y = 2 }");
+    }
+
+    #[test]
+    fn test_unclosed_object_at_end_of_input() {
+        use aam_rs::error::AamlError;
+        use std::io::Cursor;
+
+        let err = AAML::parse("a = {\n x = 1").unwrap_err();
+        assert!(matches!(err, AamlError::ParseError { line: 1, .. }), "{err}");
+        assert!(err.to_string().contains("Unclosed '{' opened on line 1"), "{err}");
+        let err = AAML::parse("name = app\nserver {\n  host = db\n  tls = { verify = true }").unwrap_err();
+        assert!(err.to_string().contains("Unclosed '{' opened on line 2"), "{err}");
+        assert!(AAML::parse_reader(Cursor::new("a = {\n x = [1, 2]")).is_err());
+    }
+
+    #[test]
+    fn test_section_headers() {
+        let cfg = AAML::parse(
+            "name = app\n\
+             [database]\n\
+             host = db.local\n\
+             port = 5432\n\
+             [net.proxy]\n\
+             url = http://proxy\n\
+             @endnamespace\n\
+             tags = [a, b]\n\
+             [ cache ]\n\
+             ttl = 60",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "app");
+        assert_eq!(cfg.find_obj("database.host").unwrap().as_str(), "db.local");
+        assert_eq!(cfg.find_obj("database.port").unwrap().as_str(), "5432");
+        assert_eq!(cfg.find_obj("net.proxy.url").unwrap().as_str(), "http://proxy");
+        assert_eq!(cfg.find_obj("tags").unwrap().as_str(), "[a, b]");
+        assert_eq!(cfg.find_obj("cache.ttl").unwrap().as_str(), "60");
+        assert!(cfg.find_obj("host").is_none());
+
+        let err = AAML::parse("a = 1\n[bad name]").unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+    }
+
+    #[test]
+    fn test_nested_block_syntax() {
+        let cfg = AAML::parse(
+            "server { host = localhost  port = 8080 }\n\
+             owner { name = Jane Doe, email = \"j@x\" }\n\
+             db {\n\
+             \x20   host = db.local\n\
+             \x20   tls {\n\
+             \x20       cert = a.pem\n\
+             \x20       verify = true\n\
+             \x20   }\n\
+             \x20   ports = [80, 443]\n\
+             }\n\
+             empty {}",
+        )
+        .unwrap();
+        assert_eq!(cfg.find_obj("server").unwrap().as_str(), "{ host = localhost, port = 8080 }");
+        assert_eq!(cfg.find_obj("owner").unwrap().as_str(), "{ name = Jane Doe, email = \"j@x\" }");
+        assert_eq!(
+            cfg.find_obj("db").unwrap().as_str(),
+            "{ host = db.local, tls = { cert = a.pem, verify = true }, ports = [80, 443] }"
+        );
+        assert_eq!(cfg.find_obj("empty").unwrap().as_str(), "{}");
+        let server = aam_rs::aaml::parsing::parse_inline_object(cfg.find_obj("server").unwrap().as_str()).unwrap();
+        assert_eq!(server[1], ("port".to_string(), "8080".to_string()));
+
+        let err = AAML::parse("a = 1\nserver { host }").unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+    }
+
+    #[test]
+    fn test_case_insensitive_keys() {
+        let content = "Host = example.com\n\
+                       [Database]\n\
+                       Port = 5432\n\
+                       @endnamespace\n\
+                       @alias HostName = host\n\
+                       url = http://${HOST}";
+        let mut cfg = AAML::new().with_case_insensitive_keys(true);
+        cfg.merge_content(content).unwrap();
+        assert!(cfg.case_insensitive_keys_enabled());
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "example.com");
+        assert_eq!(cfg.find_obj("HOST").unwrap().as_str(), "example.com");
+        assert_eq!(cfg.find_obj("hostname").unwrap().as_str(), "example.com");
+        assert_eq!(cfg.find_obj("database.port").unwrap().as_str(), "5432");
+        assert_eq!(cfg.find_deep("Database.Port").unwrap().as_str(), "5432");
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://example.com");
+
+        cfg.merge_content("HOST = other").unwrap();
+        assert_eq!(cfg.find_obj("Host").unwrap().as_str(), "other");
+        assert!(AAML::parse("Host = a").unwrap().find_obj("host").is_none());
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        use std::io::{BufReader, Cursor};
+
+        let content = "@schema Server { host: string, port: i32 }\r\n\
+                       host = localhost\r\n\
+                       server = {\r\n\
+                       \x20   host = ${host}\r\n\
+                       \x20   port = 8080\r\n\
+                       }\r\n\
+                       @bind server Server\r\n";
+        let cfg = AAML::parse_reader(BufReader::with_capacity(8, Cursor::new(content))).unwrap();
+        assert_eq!(cfg.find_obj("server").unwrap().as_str(), "{ host = localhost, port = 8080 }");
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "localhost");
+
+        let err = AAML::parse_reader(Cursor::new("a = 1\nbroken")).unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+        assert!(matches!(
+            AAML::parse_reader(Cursor::new(b"a = \xff\n".to_vec())),
+            Err(aam_rs::error::AamlError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_event_reader() {
+        use aam_rs::aaml::events::{Event, EventReader};
+
+        let content = "# generated\n\
+                       @schema Server {\n\
+                       \x20   # Host name\n\
+                       \x20   host: string\n\
+                       \x20   port*: i32 = 80\n\
+                       }\n\
+                       [db]\n\
+                       url = \"postgres://x\\ty\" # inline comment\n\
+                       tags = [\n\
+                       \x20   a\n\
+                       \x20   b\n\
+                       ]\n\
+                       server { host = localhost }\n\
+                       @import other.aam\n\
+                       name = ${user}";
+        let events: Vec<Event> = EventReader::new(content.as_bytes()).collect::<Result<_, _>>().unwrap();
+        let field = |name: &str, type_name: &str, optional, default: Option<&str>| Event::SchemaField {
+            line: 2,
+            schema: "Server".into(),
+            name: name.into(),
+            type_name: type_name.into(),
+            optional,
+            default: default.map(Into::into),
+        };
+        let assignment = |line, key: &str, value: &str| Event::Assignment { line, key: key.into(), value: value.into() };
+        assert_eq!(events[0], Event::Comment { line: 1, text: "generated".into() });
+        assert!(matches!(&events[1], Event::DirectiveStart { line: 2, name, .. } if name == "schema"));
+        assert_eq!(events[2..4], [field("host", "string", false, None), field("port", "i32", true, Some("80"))]);
+        assert_eq!(events[4], Event::DirectiveEnd { line: 2, name: "schema".into() });
+        assert_eq!(events[5], Event::Section { line: 7, name: "db".into() });
+        assert_eq!(events[6], assignment(8, "url", "postgres://x\ty"));
+        assert_eq!(events[7], assignment(9, "tags", "[ a, b ]"));
+        assert_eq!(events[8], assignment(13, "server", "{ host = localhost }"));
+        assert_eq!(events[9], Event::DirectiveStart { line: 14, name: "import".into(), args: "other.aam".into() });
+        assert_eq!(events[11], assignment(15, "name", "${user}"));
+        assert_eq!(events.len(), 12);
+
+        let mut reader = EventReader::new("a = 1\nbroken\nb = 2".as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap().line(), 1);
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_tokenize() {
+        use aam_rs::aaml::parsing::{TokenKind, tokenize};
+        use TokenKind::*;
+
+        let source = "@schema Server {\n\
+                      \x20   host: string # name\n\
+                      }\n\
+                      [db]\n\
+                      url = \"a # not comment\" # comment\n\
+                      tags = [\n\
+                      \x20   a, b\n\
+                      ]\n\
+                      server {\n\
+                      \x20   port = 80\n\
+                      }\n\
+                      @derive \"base file.aam\"\n\
+                      broken";
+        let tokens = tokenize(source);
+        let lexed: Vec<(TokenKind, &str, usize)> = tokens.iter().map(|t| (t.kind, t.text(source), t.line)).collect();
+        assert_eq!(lexed, [
+            (Directive, "@schema", 1), (Argument, "Server", 1), (OpenBrace, "{", 1),
+            (Argument, "host:", 2), (Argument, "string", 2), (Comment, "# name", 2),
+            (CloseBrace, "}", 3),
+            (Section, "[db]", 4),
+            (Key, "url", 5), (Assign, "=", 5), (Value, "\"a # not comment\"", 5), (Comment, "# comment", 5),
+            (Key, "tags", 6), (Assign, "=", 6), (Value, "[", 6), (Value, "a, b", 7), (Value, "]", 8),
+            (Key, "server", 9), (OpenBrace, "{", 9), (Key, "port", 10), (Assign, "=", 10), (Value, "80", 10),
+            (CloseBrace, "}", 11),
+            (Directive, "@derive", 12), (Argument, "\"base file.aam\"", 12),
+            (Key, "broken", 13),
+        ]);
+
+        let one_line = "srv { host = a  port = 1 }";
+        let kinds: Vec<TokenKind> = tokenize(one_line).iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, [Key, OpenBrace, Value, CloseBrace]);
+    }
+
+    #[test]
+    fn test_parse_to_ast() {
+        use aam_rs::aaml::ast::{Node, Position};
+
+        let source = "# header\n\
+                      @schema Server {\n\
+                      \x20   host: string\n\
+                      }\n\
+                      [db]\n\
+                      name = \"héllo\" # greeting\n\
+                      tags = [\n\
+                      \x20   a\n\
+                      ]\n\
+                      server {\n\
+                      \x20   port = 80\n\
+                      \x20   tls { verify = true }\n\
+                      }\n\
+                      empty =";
+        let doc = AAML::parse_to_ast(source).unwrap();
+        assert_eq!(doc.nodes.len(), 6);
+        assert_eq!(doc.comments.iter().map(|c| c.text.as_str()).collect::<Vec<_>>(), ["# header", "# greeting"]);
+
+        let Node::Directive { name, args, span } = &doc.nodes[0] else { panic!("{:?}", doc.nodes[0]) };
+        assert_eq!(name.text, "@schema");
+        assert_eq!(args.iter().map(|a| a.text.as_str()).collect::<Vec<_>>(), ["Server", "{", "host:", "string", "}"]);
+        assert_eq!((span.start, span.end), (Position { line: 2, column: 1 }, Position { line: 4, column: 2 }));
+
+        let Node::Section { name, .. } = &doc.nodes[1] else { panic!() };
+        assert_eq!(name.text, "db");
+
+        let Node::Assignment { key, value, span } = &doc.nodes[2] else { panic!() };
+        assert_eq!((key.text.as_str(), value.text.as_str()), ("name", "\"héllo\""));
+        assert_eq!(&source[value.span.range.clone()], "\"héllo\"");
+        assert_eq!(span.end, Position { line: 6, column: 15 });
+
+        let Node::Assignment { value, .. } = &doc.nodes[3] else { panic!() };
+        assert_eq!(value.text, "[\n    a\n]");
+
+        let Node::Block { key, nodes, span } = &doc.nodes[4] else { panic!() };
+        assert_eq!(key.text, "server");
+        assert_eq!(span.end.line, 13);
+        let Node::Assignment { key, value, .. } = &nodes[1] else { panic!() };
+        assert_eq!((key.text.as_str(), value.text.as_str()), ("tls", "{ verify = true }"));
+
+        let Node::Assignment { value, .. } = &doc.nodes[5] else { panic!() };
+        assert!(value.text.is_empty());
+
+        let err = AAML::parse_to_ast("a = 1\nbroken").unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+        assert!(AAML::parse_to_ast("server {\n    a = 1").is_err());
+    }
+}
@@ -644,4 +644,90 @@ mod tests {
         assert!(result.is_err(), "missing required field must cause error");
         assert!(matches!(result.unwrap_err(), AamlError::SchemaValidationError { .. }));
     }
+
+    #[test]
+    fn test_derive_multiple_bases() {
+        let mut parser = AAML::new();
+        parser.register_source(
+            "common",
+            "@schema Server { host: string, port: i32 }\nhost = common.local\nport = 80\nlog = info",
+        );
+        parser.register_source(
+            "staging",
+            "@schema Server { host: string, port: i32, tls: bool }\n@schema Extra { x: i32 }\nhost = staging.local\ntls = true\nx = 1",
+        );
+        parser
+            .merge_content("port = 8080\n@derive mem://common, mem://staging::Server")
+            .expect("bases layer left to right");
+
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "staging.local", "later base wins");
+        assert_eq!(parser.find_obj("port").unwrap().as_str(), "8080", "child wins");
+        assert_eq!(parser.find_obj("log").unwrap().as_str(), "info");
+        assert!(parser.get_schema("Server").unwrap().fields.contains_key("tls"));
+        assert!(parser.get_schema("Extra").is_none(), "selectors apply per base");
+
+        assert!(parser.merge_content("@derive mem://common,").is_err());
+        assert!(parser.merge_content("@derive mem://common, mem://missing").is_err());
+    }
+
+    #[test]
+    fn test_derive_strict_overrides() {
+        let strict = || {
+            let mut parser = AAML::new().with_strict_overrides(true);
+            parser.register_source("base", "host = localhost\nport = 80\nlog = info");
+            parser
+        };
+
+        // Lenient by default: the child silently wins.
+        let mut parser = AAML::new();
+        parser.register_source("base", "port = 80");
+        parser.merge_content("port = 8080\n@derive mem://base").unwrap();
+        assert_eq!(parser.find_obj("port").unwrap().as_str(), "8080");
+
+        // Child assigned before @derive.
+        let err = strict().merge_content("port = 8080\n@derive mem://base").unwrap_err();
+        assert!(err.to_string().contains("@override port"), "got: {err}");
+        let mut parser = strict();
+        parser.merge_content("port = 8080\n@override port\n@derive mem://base").unwrap();
+        assert_eq!(parser.find_obj("port").unwrap().as_str(), "8080");
+        assert!(parser.is_override("port"));
+
+        // Child assigned after @derive.
+        assert!(strict().merge_content("@derive mem://base\nhost = example.com").is_err());
+        let mut parser = strict();
+        parser
+            .merge_content("@derive mem://base\n@override host = example.com\n@override port, log\nport = 1\nlog = debug")
+            .unwrap();
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "example.com");
+        assert_eq!(parser.find_obj("log").unwrap().as_str(), "debug");
+
+        // Keys the base does not define need no marker.
+        assert!(strict().merge_content("@derive mem://base\nname = app").is_ok());
+        assert!(strict().merge_content("@override").is_err());
+    }
+
+    #[test]
+    fn test_derive_maps_base_keys_through_aliases() {
+        let mut parser = AAML::new();
+        parser.register_source("old", "hostname = legacy
+port = 80");
+        parser.merge_content("@alias hostname = host
+@derive mem://old").unwrap();
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "legacy");
+        assert_eq!(parser.find_obj("hostname").unwrap().as_str(), "legacy");
+
+        let mut parser = AAML::new();
+        parser.register_source("old", "hostname = legacy");
+        parser.merge_content("@alias hostname = host
+host = current
+@derive mem://old").unwrap();
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "current");
+
+        let mut parser = AAML::new().with_strict_overrides(true);
+        parser.register_source("old", "hostname = legacy");
+        let err = parser.merge_content("@alias hostname = host
+host = current
+@derive mem://old").unwrap_err();
+        assert!(err.to_string().contains("@override host"), "got: {err}");
+    }
 }
@@ -2,6 +2,9 @@
 mod tests {
     use aam_rs::aaml::AAML;
     use aam_rs::builder::AAMBuilder;
+    use aam_rs::aaml::limits::ImportLimits;
+    use aam_rs::aaml::loader::MemoryLoader;
+    use aam_rs::error::AamlError;
     use std::fs;
 
 
@@ -82,4 +85,346 @@ mod tests {
         let parser = parser.expect("Should parse quoted import path");
         assert_eq!(parser.find_obj("q_key").unwrap().as_str(), "q_val");
     }
-}
\ No newline at end of file
+
+    const DEFAULTS: &str = "@schema App { name: string, workers: i32 }\nname = demo\nworkers = 4\n";
+
+    #[test]
+    fn test_embedded_defaults_overridden_by_user_file() {
+        let user_file = "test_embedded_user.aam";
+        let mut b = AAMBuilder::new();
+        b.add_line("workers", "16");
+        b.to_file(user_file).unwrap();
+
+        let mut cfg = AAML::with_embedded_defaults(DEFAULTS).unwrap();
+        let found = cfg.merge_file_if_exists(user_file);
+        let _ = fs::remove_file(user_file);
+
+        assert!(found.expect("User file should merge"));
+        assert_eq!(cfg.find_obj("workers").unwrap().as_str(), "16");
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "demo");
+    }
+
+    #[test]
+    fn test_embedded_defaults_missing_user_file() {
+        let cfg = AAML::load_with_defaults(DEFAULTS, Some("does_not_exist.aam"))
+            .expect("A missing user file is not an error");
+        assert_eq!(cfg.find_obj("workers").unwrap().as_str(), "4");
+    }
+
+    #[test]
+    fn test_embedded_defaults_user_value_is_validated() {
+        let user_file = "test_embedded_invalid.aam";
+        let mut b = AAMBuilder::new();
+        b.add_line("workers", "many");
+        b.to_file(user_file).unwrap();
+
+        let res = AAML::load_with_defaults(DEFAULTS, Some(user_file));
+        let _ = fs::remove_file(user_file);
+        assert!(res.is_err(), "Schema from defaults must validate user values");
+    }
+
+    #[test]
+    fn test_embedded_defaults_completeness_checked_after_merge() {
+        let defaults = "@schema App { name: string, token: string }\nname = demo\n";
+        let res = AAML::load_with_defaults(defaults, None::<&str>);
+        assert!(res.is_err(), "token is required but supplied by neither source");
+    }
+
+    #[test]
+    fn test_embedded_defaults_required_key_from_user_file() {
+        let defaults = "@require token\nname = demo\n";
+        assert!(AAML::with_embedded_defaults(defaults).is_ok());
+        assert!(AAML::load_with_defaults(defaults, None::<&str>).is_err());
+
+        let user_file = "test_embedded_required.aam";
+        let mut b = AAMBuilder::new();
+        b.add_line("token", "secret");
+        b.to_file(user_file).unwrap();
+        let res = AAML::load_with_defaults(defaults, Some(user_file));
+        let _ = fs::remove_file(user_file);
+        assert!(res.is_ok(), "token is supplied by the user file");
+    }
+
+    #[test]
+    fn test_imported_references_see_importing_document() {
+        let sub_file = "test_ref_import.aam";
+        fs::write(sub_file, "log_dir = ${base_dir}/logs\n").unwrap();
+
+        let parser = AAML::parse(&format!("@import {sub_file}\nbase_dir = /srv/app"));
+        let _ = fs::remove_file(sub_file);
+
+        let parser = parser.expect("references resolve after the whole document");
+        assert_eq!(parser.find_obj("log_dir").unwrap().as_str(), "/srv/app/logs");
+    }
+
+    #[test]
+    fn test_import_optional() {
+        let parser = AAML::parse("@import_optional test_missing_overrides.aam\nname = base")
+            .expect("a missing optional import is skipped");
+        assert_eq!(parser.find_obj("name").unwrap().as_str(), "base");
+
+        let overrides = "test_optional_overrides.aam";
+        fs::write(overrides, "name = local\n").unwrap();
+        let parser = AAML::parse(&format!("name = base\n@import_optional {overrides}"));
+        let _ = fs::remove_file(overrides);
+        assert_eq!(parser.unwrap().find_obj("name").unwrap().as_str(), "local");
+
+        assert!(AAML::parse("@import test_missing_overrides.aam").is_err());
+    }
+
+    #[test]
+    fn test_relative_paths_resolve_against_importing_file() {
+        let dir = "test_relative_imports";
+        fs::create_dir_all(format!("{dir}/nested")).unwrap();
+        fs::write(format!("{dir}/main.aam"), "@import nested/db.aam\n@derive base.aam\n").unwrap();
+        fs::write(format!("{dir}/nested/db.aam"), "@import pool.aam\nhost = db.local\n").unwrap();
+        fs::write(format!("{dir}/nested/pool.aam"), "pool = 8\n").unwrap();
+        fs::write(format!("{dir}/base.aam"), "name = app\n").unwrap();
+
+        let loaded = AAML::load(format!("{dir}/main.aam"));
+        let from_string = AAML::new()
+            .with_source_dir(dir)
+            .merge_content("@import nested/db.aam")
+            .map(|_| ());
+        let _ = fs::remove_dir_all(dir);
+
+        let parser = loaded.expect("paths resolve against the directory of each file");
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "db.local");
+        assert_eq!(parser.find_obj("pool").unwrap().as_str(), "8");
+        assert_eq!(parser.find_obj("name").unwrap().as_str(), "app");
+        assert!(from_string.is_ok(), "with_source_dir applies to string content");
+    }
+
+    #[test]
+    fn test_import_limits() {
+        let dir = "test_import_limits";
+        fs::create_dir_all(dir).unwrap();
+        fs::write(format!("{dir}/loop.aam"), "@import loop.aam\n").unwrap();
+        fs::write(format!("{dir}/derive_loop.aam"), "@derive derive_loop.aam\n").unwrap();
+        fs::write(format!("{dir}/a.aam"), "a = 1\n").unwrap();
+        fs::write(format!("{dir}/b.aam"), "b = 2\n").unwrap();
+
+        let self_import = AAML::load(format!("{dir}/loop.aam"));
+        let self_derive = AAML::load(format!("{dir}/derive_loop.aam"));
+        let two_files = "@import a.aam\n@import b.aam";
+        let count = AAML::new()
+            .with_source_dir(dir)
+            .with_import_limits(ImportLimits { max_files: 1, ..ImportLimits::default() })
+            .merge_content(two_files)
+            .map(|_| ());
+        let size = AAML::new()
+            .with_source_dir(dir)
+            .with_import_limits(ImportLimits { max_bytes: 8, ..ImportLimits::default() })
+            .merge_content(two_files)
+            .map(|_| ());
+        let unlimited = AAML::new()
+            .with_source_dir(dir)
+            .with_import_limits(ImportLimits::UNLIMITED)
+            .merge_content(two_files)
+            .map(|_| ());
+        let _ = fs::remove_dir_all(dir);
+
+        for result in [self_import.map(|_| ()), self_derive.map(|_| ()), count, size] {
+            assert!(
+                matches!(result, Err(AamlError::DirectiveError(..))),
+                "limit must stop the import: {result:?}"
+            );
+        }
+        assert!(unlimited.is_ok());
+    }
+
+    #[test]
+    fn test_slash_comments_apply_to_imports_and_readers() {
+        let loader = MemoryLoader::new()
+            .with_file("app.aam", "/* app */\n@derive base.aam // shared\nname = app // label")
+            .with_file("base.aam", "timeout = 30 // seconds\n/*\nretired = 1\n*/");
+
+        let mut parser = AAML::new().with_loader(loader).with_slash_comments(true);
+        parser.merge_content("@import app.aam").unwrap();
+        assert_eq!(parser.find_obj("name").unwrap().as_str(), "app");
+        assert_eq!(parser.find_obj("timeout").unwrap().as_str(), "30");
+        assert!(parser.find_obj("retired").is_none());
+
+        let mut parser = AAML::new().with_slash_comments(true);
+        parser.merge_reader(std::io::Cursor::new("a = 1 // one\n/* b = 2\n*/ c = 3")).unwrap();
+        assert_eq!(parser.find_obj("a").unwrap().as_str(), "1");
+        assert_eq!(parser.find_obj("c").unwrap().as_str(), "3");
+        let err = parser.merge_reader(std::io::Cursor::new("d = 4\n/* open")).unwrap_err();
+        assert!(matches!(err, AamlError::ParseError { line: 2, .. }), "{err}");
+    }
+
+    #[test]
+    fn test_import_limits_checked_before_reading() {
+        use aam_rs::aaml::loader::SourceLoader;
+        use std::path::Path;
+        use std::sync::{Arc, Mutex};
+
+        /// Records the byte budget of each read.
+        struct Budgets(Arc<Mutex<Vec<u64>>>);
+        impl SourceLoader for Budgets {
+            fn load(&self, _: &Path) -> std::io::Result<String> {
+                unreachable!("directives read through load_limited")
+            }
+            fn load_limited(&self, _: &Path, max_bytes: u64) -> std::io::Result<String> {
+                self.0.lock().unwrap().push(max_bytes);
+                Ok("a = 1\n".to_string())
+            }
+        }
+
+        let budgets = Arc::new(Mutex::new(Vec::new()));
+        let limits = ImportLimits { max_files: 2, max_bytes: 100, ..ImportLimits::default() };
+        let mut parser = AAML::new().with_loader(Budgets(budgets.clone())).with_import_limits(limits);
+        let err = parser.merge_content("@import a.aam\n@import b.aam\n@import c.aam").unwrap_err();
+        assert!(err.to_string().contains("more than 2 files"), "{err}");
+        assert_eq!(*budgets.lock().unwrap(), [100, 94]);
+
+        let dir = "test_import_budget";
+        fs::create_dir_all(dir).unwrap();
+        fs::write(format!("{dir}/big.aam"), "x = 1\n".repeat(100)).unwrap();
+        let result = AAML::new()
+            .with_source_dir(dir)
+            .with_import_limits(ImportLimits { max_bytes: 64, ..ImportLimits::default() })
+            .merge_content("@import big.aam");
+        let _ = fs::remove_dir_all(dir);
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("exceed 64 bytes"), "{err}");
+    }
+
+    #[test]
+    fn test_memory_loader_serves_directives() {
+        let loader = MemoryLoader::new()
+            .with_file("assets/app.aam", "@import net/db.aam\n@import_optional local.aam\nname = app")
+            .with_file("assets/net/db.aam", "@derive ../base.aam\nhost = db.local")
+            .with_file("assets/base.aam", "timeout = 30");
+
+        let mut parser = AAML::new().with_loader(loader);
+        parser.merge_content("@import assets/app.aam").expect("nothing is read from disk");
+        assert_eq!(parser.find_obj("name").unwrap().as_str(), "app");
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "db.local");
+        assert_eq!(parser.find_obj("timeout").unwrap().as_str(), "30");
+
+        let err = AAML::new().with_loader(MemoryLoader::new()).merge_content("@import missing.aam");
+        assert!(matches!(err, Err(AamlError::IoError(_))));
+    }
+
+    #[test]
+    fn test_import_with_prefix() {
+        let mut parser = AAML::new().with_loader(
+            MemoryLoader::new()
+                .with_file("db.aam", "host = db.local\nport = 5432\nurl = ${host}:${port}\n@import pool.aam as pool")
+                .with_file("pool.aam", "size = 8")
+                .with_file("cache.aam", "host = cache.local\nport = 6379"),
+        );
+        parser
+            .merge_content("port = 80\n@import db.aam as database\n@import \"cache.aam\" as cache")
+            .unwrap();
+        assert_eq!(parser.find_obj("database.host").unwrap().as_str(), "db.local");
+        assert_eq!(parser.find_obj("cache.port").unwrap().as_str(), "6379");
+        assert_eq!(parser.find_obj("port").unwrap().as_str(), "80");
+        assert_eq!(parser.find_obj("database.url").unwrap().as_str(), "db.local:5432");
+        assert_eq!(parser.find_obj("database.pool.size").unwrap().as_str(), "8");
+        assert!(parser.find_obj("host").is_none());
+
+        assert!(parser.merge_content("@import db.aam as").is_err());
+        assert!(parser.merge_content("@import db.aam as bad..name").is_err());
+    }
+
+    #[test]
+    fn test_derive_from_registered_source() {
+        let mut parser = AAML::new();
+        parser.register_source(
+            "defaults/base",
+            "@schema Server { host: string, port: i32 }\n@import net.aam\nhost = localhost\nport = 8080",
+        );
+        parser.register_source("defaults/net.aam", "timeout = 30");
+        parser
+            .merge_content("@derive mem://defaults/base::Server\nport = 9000")
+            .expect("sources are read without touching disk");
+
+        assert!(parser.get_schema("Server").is_some());
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "localhost");
+        assert_eq!(parser.find_obj("port").unwrap().as_str(), "9000");
+        assert_eq!(parser.find_obj("timeout").unwrap().as_str(), "30");
+
+        let err = parser.merge_content("@derive mem://missing");
+        assert!(matches!(err, Err(AamlError::IoError(_))));
+        assert!(parser.merge_content("@import_optional mem://missing").is_ok());
+    }
+
+    #[test]
+    fn test_directive_errors_carry_location() {
+        let dir = "test_directive_locations";
+        fs::create_dir_all(dir).unwrap();
+        fs::write(format!("{dir}/main.aam"), "name = app\n@import db.aam\n").unwrap();
+        fs::write(format!("{dir}/db.aam"), "host = db.local\n@bind host Missing\n").unwrap();
+        let result = AAML::load(format!("{dir}/main.aam"));
+        let _ = fs::remove_dir_all(dir);
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, AamlError::DirectiveError(..)));
+        let location = err.location().expect("directive errors are located");
+        assert_eq!((location.line, location.column), (2, 1));
+        assert!(location.file.as_ref().unwrap().ends_with("db.aam"));
+        assert_eq!(location.import_stack.len(), 2);
+        assert!(err.to_string().contains("db.aam:2:1 (imported from"), "{err}");
+
+        let err = AAML::parse("x = 1\n@bind x Missing").unwrap_err();
+        assert!(err.to_string().starts_with("line 2, column 1: "), "{err}");
+    }
+
+    #[test]
+    fn test_assignment_errors_carry_location() {
+        let dir = "test_assignment_locations";
+        fs::create_dir_all(dir).unwrap();
+        fs::write(format!("{dir}/main.aam"), "@schema S { port: i32, debug: bool }\n@import net.aam\n").unwrap();
+        fs::write(format!("{dir}/net.aam"), "debug = true\n  port =  http\n").unwrap();
+        let result = AAML::load(format!("{dir}/main.aam"));
+        let _ = fs::remove_dir_all(dir);
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, AamlError::SchemaValidationError { .. }), "{err}");
+        let location = err.location().expect("assignment errors are located");
+        assert_eq!((location.line, location.column), (2, 11));
+        assert!(location.file.as_ref().unwrap().ends_with("net.aam"));
+        assert!(err.to_string().contains("net.aam:2:11 (imported from"), "{err}");
+
+        let err = AAML::parse("@type port = i32\nlimit = ${max}\nmax = 10\n@schema L { limit: port }\nlimit = x").unwrap_err();
+        assert_eq!(err.location().map(|l| l.line), Some(5), "{err}");
+        let err = AAML::parse("@schema L { limit: i32 }\nmax = ten\nlimit = ${max}").unwrap_err();
+        assert_eq!(err.location().map(|l| (l.line, l.column)), Some((3, 0)), "{err}");
+    }
+
+    #[cfg(feature = "http-import")]
+    #[test]
+    fn test_http_import() {
+        use aam_rs::aaml::loader::HttpLoader;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        let fetched = Arc::new(Mutex::new(Vec::new()));
+        let log = fetched.clone();
+        let loader = HttpLoader::new(move |request| {
+            log.lock().unwrap().push((request.url.to_string(), request.timeout));
+            match request.url {
+                "https://cfg.example.com/team/base.aam" => Ok("@import db.aam\nregion = eu".into()),
+                "https://cfg.example.com/team/db.aam" => Ok("host = db.eu".into()),
+                "https://cfg.example.com/huge.aam" => Ok("x = 1\n".repeat(100)),
+                _ => Err(std::io::Error::new(std::io::ErrorKind::NotFound, request.url)),
+            }
+        })
+        .with_timeout(Duration::from_secs(3))
+        .with_max_bytes(64)
+        .with_fallback(MemoryLoader::new().with_file("local.aam", "debug = true"));
+
+        let mut parser = AAML::new().with_loader(loader);
+        parser
+            .merge_content("@import https://cfg.example.com/team/base.aam\n@import local.aam")
+            .unwrap();
+        assert_eq!(parser.find_obj("host").unwrap().as_str(), "db.eu");
+        assert_eq!(parser.find_obj("debug").unwrap().as_str(), "true");
+        assert_eq!(fetched.lock().unwrap()[1], ("https://cfg.example.com/team/db.aam".into(), Duration::from_secs(3)));
+
+        assert!(parser.merge_content("@import https://cfg.example.com/huge.aam").is_err());
+        assert!(parser.merge_content("@import_optional https://cfg.example.com/none.aam").is_ok());
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use aam_rs::aaml::AAML;
+    use aam_rs::aaml::merge::MergeConflict;
+
+    fn merge(base: &str, ours: &str, theirs: &str) -> aam_rs::aaml::merge::MergeResult {
+        AAML::merge3(
+            &AAML::parse(base).unwrap(),
+            &AAML::parse(ours).unwrap(),
+            &AAML::parse(theirs).unwrap(),
+        )
+    }
+
+    #[test]
+    fn one_sided_changes_are_taken() {
+        let result = merge("a = 1\nb = 2\nc = 3", "a = 10\nb = 2\nc = 3", "a = 1\nc = 3\nd = 4");
+        assert!(result.is_clean());
+        let m = &result.merged;
+        assert_eq!(m.find_obj("a").unwrap().as_str(), "10");
+        assert!(m.find_obj("b").is_none(), "removed on their side");
+        assert_eq!(m.find_obj("c").unwrap().as_str(), "3");
+        assert_eq!(m.find_obj("d").unwrap().as_str(), "4");
+    }
+
+    #[test]
+    fn identical_changes_do_not_conflict() {
+        let result = merge("a = 1", "a = 2\nnew = x", "a = 2\nnew = x");
+        assert!(result.is_clean());
+        assert_eq!(result.merged.find_obj("a").unwrap().as_str(), "2");
+    }
+
+    #[test]
+    fn divergent_changes_conflict_and_keep_ours() {
+        let result = merge("a = 1\nb = 1", "a = 2\nb = 5", "a = 3");
+        assert_eq!(
+            result.conflicts,
+            [
+                MergeConflict { path: "a".into(), base: Some("1".into()), ours: Some("2".into()), theirs: Some("3".into()) },
+                MergeConflict { path: "b".into(), base: Some("1".into()), ours: Some("5".into()), theirs: None },
+            ]
+        );
+        assert_eq!(result.merged.find_obj("a").unwrap().as_str(), "2");
+        assert_eq!(result.merged.find_obj("b").unwrap().as_str(), "5");
+    }
+
+    #[test]
+    fn inline_objects_merge_field_by_field() {
+        let result = merge(
+            "srv = { host = a, port = 80, tls = { on = false, v = 1 } }",
+            "srv = { host = b, port = 80, tls = { on = true, v = 1 } }",
+            "srv = { host = a, port = 81, tls = { on = false, v = 2 }, extra = y }",
+        );
+        assert!(result.is_clean(), "{:?}", result.conflicts);
+        assert_eq!(
+            result.merged.find_obj("srv").unwrap().as_str(),
+            "{ host = b, port = 81, tls = { on = true, v = 2 }, extra = y }"
+        );
+    }
+
+    #[test]
+    fn inline_object_field_conflicts_use_dotted_paths() {
+        let result = merge("srv = { port = 80 }", "srv = { port = 81 }", "srv = { port = 82 }");
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].path, "srv.port");
+        assert_eq!(result.merged.find_obj("srv").unwrap().as_str(), "{ port = 81 }");
+
+        let result = merge("srv = 1", "srv = { port = 81 }", "srv = { port = 82 }");
+        assert_eq!(result.conflicts[0].path, "srv", "base is not an object");
+    }
+
+    #[test]
+    fn schemas_are_carried_over() {
+        let result = merge("", "@schema A { x: i32 }", "@schema B { y: i32 }");
+        assert!(result.merged.get_schema("A").is_some());
+        assert!(result.merged.get_schema("B").is_some());
+    }
+}
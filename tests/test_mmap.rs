@@ -0,0 +1,24 @@
+#![cfg(feature = "mmap")]
+
+use aam_rs::aaml::AAML;
+use std::fs;
+
+#[test]
+fn test_load_mmap() {
+    let dir = std::env::temp_dir().join(format!("aam_mmap_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("base.aam"), "region = eu\n").unwrap();
+    fs::write(dir.join("app.aam"), "@import base.aam\nhost = localhost\nport = 8080\n").unwrap();
+    fs::write(dir.join("bad.aam"), b"host = \xff\n").unwrap();
+
+    let res = AAML::load_mmap(dir.join("app.aam"));
+    let bad = AAML::load_mmap(dir.join("bad.aam"));
+    let missing = AAML::load_mmap(dir.join("missing.aam"));
+    let _ = fs::remove_dir_all(&dir);
+    let aaml = res.expect("Should load mapped config");
+
+    assert_eq!(aaml.find_obj("port").unwrap().as_str(), "8080");
+    assert_eq!(aaml.find_obj("region").unwrap().as_str(), "eu");
+    assert!(bad.is_err(), "Invalid UTF-8 is rejected");
+    assert!(missing.is_err());
+}
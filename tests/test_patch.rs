@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use aam_rs::aaml::AAML;
+    use aam_rs::aaml::patch::{Patch, PatchOp};
+    use aam_rs::error::AamlError;
+
+    fn apply(doc: &str, patch: &str) -> Result<AAML, AamlError> {
+        let mut cfg = AAML::parse(doc)?;
+        cfg.apply_patch(&Patch::parse(patch)?)?;
+        Ok(cfg)
+    }
+
+    #[test]
+    fn parse_reads_operations_and_skips_comments() {
+        let patch = Patch::parse("# audit: ticket 42\nadd a = 1\n\nreplace b = \"x y\"  # note\nremove c").unwrap();
+        assert_eq!(
+            patch.ops(),
+            [
+                PatchOp::Add { path: "a".into(), value: "1".into() },
+                PatchOp::Replace { path: "b".into(), value: "x y".into() },
+                PatchOp::Remove { path: "c".into() },
+            ]
+        );
+        assert_eq!(patch.to_string().parse::<Patch>().unwrap(), patch);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines_with_line_numbers() {
+        let err = Patch::parse("add a = 1\nmove a b").unwrap_err();
+        assert!(matches!(err, AamlError::ParseError { line: 2, .. }), "got: {err}");
+        assert!(Patch::parse("add a").is_err());
+        assert!(Patch::parse("remove a b").is_err());
+        assert!(Patch::parse("remove").is_err());
+    }
+
+    #[test]
+    fn top_level_add_replace_remove() {
+        let cfg = apply("host = a\ndebug = true", "add port = 80\nreplace host = b\nremove debug").unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "80");
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "b");
+        assert!(cfg.find_obj("debug").is_none());
+    }
+
+    #[test]
+    fn operations_enforce_existence() {
+        assert!(matches!(apply("host = a", "add host = b"), Err(AamlError::InvalidValue(_))));
+        assert!(matches!(apply("host = a", "replace port = 1"), Err(AamlError::NotFound(_))));
+        assert!(matches!(apply("host = a", "remove port"), Err(AamlError::NotFound(_))));
+    }
+
+    #[test]
+    fn edits_inline_object_fields() {
+        let doc = "server = { host = a, tls = { port = 443 } }";
+        let cfg = apply(doc, "replace server.host = b\nadd server.timeout = 30\nreplace server.tls.port = 8443").unwrap();
+        assert_eq!(
+            cfg.find_obj("server").unwrap().as_str(),
+            "{ host = b, tls = { port = 8443 }, timeout = 30 }"
+        );
+
+        let cfg = apply(doc, "remove server.host\nadd server.note = \"a b\"").unwrap();
+        let server = cfg.find_obj("server").unwrap().as_object().unwrap();
+        assert!(!server.contains_key("host"));
+        assert_eq!(server["note"], "a b");
+        assert!(apply(doc, "add server.note = \"a, b\"").is_err());
+
+        assert!(apply(doc, "remove server.missing").is_err());
+        assert!(apply(doc, "add server.host.x = 1").is_err());
+    }
+
+    #[test]
+    fn new_values_are_validated_and_failures_roll_back() {
+        let doc = "@schema Server { port: i32 }\nport = 80\nhost = a";
+        let mut cfg = AAML::parse(doc).unwrap();
+        let patch = Patch::parse("replace host = b\nreplace port = http").unwrap();
+        let err = cfg.apply_patch(&patch).unwrap_err();
+        assert!(matches!(err, AamlError::SchemaValidationError { .. }), "got: {err}");
+        assert_eq!(cfg.find_obj("host").unwrap().as_str(), "a");
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "80");
+    }
+}
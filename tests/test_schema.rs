@@ -0,0 +1,908 @@
+#[cfg(test)]
+mod tests {
+    use aam_rs::aaml::AAML;
+    use aam_rs::commands::schema::SchemaDef;
+    use std::collections::{HashMap, HashSet};
+    use serde_json::Value;
+
+    // ─────────────────────────────────────────────────────────────
+    //  JSON Schema export
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn json_schema_maps_primitives_and_optionality() {
+        let cfg = AAML::parse("@schema Server { host: string, port: i32, debug*: bool }").unwrap();
+        let json = cfg.get_schema("Server").unwrap().to_json_schema();
+        let doc: Value = serde_json::from_str(&json).expect("Export must be valid JSON");
+
+        assert_eq!(doc["type"], "object");
+        assert_eq!(doc["properties"]["host"]["type"], "string");
+        assert_eq!(doc["properties"]["port"]["type"], "integer");
+        assert_eq!(doc["properties"]["debug"]["type"], "boolean");
+        assert_eq!(doc["required"], serde_json::json!(["host", "port"]));
+    }
+
+    #[test]
+    fn json_schema_maps_lists_and_vectors() {
+        let cfg = AAML::parse("@schema Mesh { tags: list<string>, origin: math::vector3 }").unwrap();
+        let doc: Value =
+            serde_json::from_str(&cfg.get_schema("Mesh").unwrap().to_json_schema()).unwrap();
+
+        assert_eq!(doc["properties"]["tags"]["type"], "array");
+        assert_eq!(doc["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(doc["properties"]["origin"]["minItems"], 3);
+        assert_eq!(doc["properties"]["origin"]["maxItems"], 3);
+    }
+
+    #[test]
+    fn json_schema_maps_list_cardinality() {
+        let cfg = AAML::parse("@schema Fw { ips: list<string, 1..>, ports: list<i32, 1..10> }").unwrap();
+        let doc: Value =
+            serde_json::from_str(&cfg.get_schema("Fw").unwrap().to_json_schema()).unwrap();
+
+        assert_eq!(doc["properties"]["ips"]["minItems"], 1);
+        assert!(doc["properties"]["ips"].get("maxItems").is_none());
+        assert_eq!(doc["properties"]["ports"]["maxItems"], 9);
+    }
+
+    #[test]
+    fn json_schema_maps_map_values() {
+        let cfg = AAML::parse("@schema Svc { limits: map<string, i32> }").unwrap();
+        let doc: Value =
+            serde_json::from_str(&cfg.get_schema("Svc").unwrap().to_json_schema()).unwrap();
+
+        assert_eq!(doc["properties"]["limits"]["type"], "object");
+        assert_eq!(doc["properties"]["limits"]["additionalProperties"]["type"], "integer");
+    }
+
+    #[test]
+    fn json_schema_maps_optional_to_nullable() {
+        let cfg = AAML::parse("@schema Net { proxy: optional<string> }").unwrap();
+        let doc: Value =
+            serde_json::from_str(&cfg.get_schema("Net").unwrap().to_json_schema()).unwrap();
+
+        assert_eq!(doc["properties"]["proxy"]["anyOf"][0]["type"], "string");
+        assert_eq!(doc["properties"]["proxy"]["anyOf"][1]["type"], "null");
+    }
+
+    #[test]
+    fn json_schema_maps_union_to_any_of() {
+        let cfg = AAML::parse("@schema S { port: i32 | string }").unwrap();
+        let doc: Value =
+            serde_json::from_str(&cfg.get_schema("S").unwrap().to_json_schema()).unwrap();
+
+        assert_eq!(doc["properties"]["port"]["anyOf"][0]["type"], "integer");
+        assert_eq!(doc["properties"]["port"]["anyOf"][1]["type"], "string");
+    }
+
+    #[test]
+    fn json_schema_document_includes_nested_defs_and_aliases() {
+        let cfg = AAML::parse(
+            "@type port_t = i32\n\
+             @schema Point { x: f64, y: f64 }\n\
+             @schema Shape { name: string, port: port_t, points: list<Point> }",
+        )
+        .unwrap();
+        let doc: Value = serde_json::from_str(&cfg.schema_to_json_schema("Shape").unwrap()).unwrap();
+
+        assert_eq!(doc["title"], "Shape");
+        assert_eq!(doc["properties"]["port"]["type"], "integer");
+        assert_eq!(doc["properties"]["points"]["items"]["$ref"], "#/$defs/Point");
+        assert_eq!(doc["$defs"]["Point"]["properties"]["x"]["type"], "number");
+    }
+
+    #[test]
+    fn json_schema_deny_extra_objects_disallows_additional_properties() {
+        let cfg = AAML::parse("@schema P deny_extra_objects { x: f64 }\n@schema Q { p: P }").unwrap();
+        let doc: Value = serde_json::from_str(&cfg.schema_to_json_schema("Q").unwrap()).unwrap();
+
+        assert_eq!(doc["$defs"]["P"]["additionalProperties"], false);
+        assert!(doc.get("additionalProperties").is_none());
+    }
+
+    #[test]
+    fn json_schema_standalone_declares_referenced_defs() {
+        let fields = HashMap::from([
+            ("origin".to_string(), "Point".to_string()),
+            ("odd".to_string(), r#"We"ird\/Name"#.to_string()),
+        ]);
+        let json = SchemaDef::new(fields, HashSet::new()).to_json_schema();
+        let doc: Value = serde_json::from_str(&json).expect("Export must be valid JSON");
+
+        assert_eq!(doc["properties"]["origin"]["$ref"], "#/$defs/Point");
+        assert_eq!(doc["$defs"]["Point"]["type"], "object");
+        assert_eq!(doc["properties"]["odd"]["$ref"], r#"#/$defs/We"ird\~1Name"#);
+        assert!(doc["$defs"].get(r#"We"ird\/Name"#).is_some());
+    }
+
+    #[test]
+    fn json_schema_unknown_schema_is_not_found() {
+        let cfg = AAML::new();
+        assert!(cfg.schema_to_json_schema("Missing").is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Default values
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn defaults_are_stored_and_exposed() {
+        let cfg = AAML::parse(r#"@schema Config { retries: i32 = 3, host: string = "localhost", debug*: bool }"#)
+            .unwrap();
+        let schema = cfg.get_schema("Config").unwrap();
+
+        assert_eq!(schema.defaults().len(), 2);
+        assert_eq!(schema.default_for("retries"), Some("3"));
+        assert_eq!(schema.default_for("host"), Some("localhost"));
+        assert!(!schema.is_required("retries"));
+        assert!(schema.default_for("debug").is_none());
+    }
+
+    #[test]
+    fn defaults_apply_to_lookups_when_key_absent() {
+        let cfg = AAML::parse("@schema Config { retries: i32 = 3, timeout: i32 = 30 }
+timeout = 5").unwrap();
+
+        assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "3");
+        assert_eq!(cfg.find_obj("timeout").unwrap().as_str(), "5");
+        assert_eq!(cfg.find_deep("retries").unwrap().as_str(), "3");
+    }
+
+    #[test]
+    fn defaulted_fields_satisfy_completeness() {
+        let cfg = AAML::parse("@schema Config { retries: i32 = 3, name: string }
+name = app").unwrap();
+        cfg.validate_schemas_completeness().expect("retries has a default");
+
+        let data = std::collections::HashMap::from([("name".to_string(), "x".to_string())]);
+        assert!(cfg.apply_schema("Config", &data).is_ok());
+    }
+
+    #[test]
+    fn defaults_without_spaces_and_multiline() {
+        let cfg = AAML::parse("@schema Config {\n    retries:i32=3\n    tags: list<string> = [a, b]\n}").unwrap();
+        let schema = cfg.get_schema("Config").unwrap();
+        assert_eq!(schema.default_for("retries"), Some("3"));
+        assert_eq!(schema.default_for("tags"), Some("[a, b]"));
+    }
+
+    #[test]
+    fn invalid_default_is_rejected_at_declaration() {
+        let res = AAML::parse("@schema Config { retries: i32 = many }");
+        assert!(res.is_err(), "'many' is not a valid i32 default");
+    }
+
+    #[test]
+    fn invalid_default_leaves_no_schema_behind() {
+        let mut cfg = AAML::new();
+        assert!(cfg.merge_content("@schema Config { retries: i32 = many }").is_err());
+        assert!(cfg.get_schema("Config").is_none());
+
+        cfg.merge_content("@schema Config { retries: i32 = 3 }").unwrap();
+        assert!(cfg.merge_content("@schema Config { retries: i32 = many }").is_err());
+        assert_eq!(cfg.get_schema("Config").unwrap().default_for("retries"), Some("3"));
+    }
+
+    #[test]
+    fn conflicting_defaults_resolve_through_bound_schema() {
+        let src = "@schema A { retries: i32 = 3 }\n@schema B { retries: i32 = 5 }\n";
+        let cfg = AAML::parse(src).unwrap();
+        assert!(cfg.find_obj("retries").is_none(), "ambiguous defaults are not guessed");
+
+        let cfg = AAML::parse(&format!("{src}@conform B\n")).unwrap();
+        assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "5");
+
+        let cfg = AAML::parse(&format!("{src}@bind job A\n")).unwrap();
+        assert_eq!(cfg.find_obj("job.retries").unwrap().as_str(), "3");
+
+        let cfg = AAML::parse("@schema A { retries: i32 = 3 }\n@schema B { retries: i32 = 3 }").unwrap();
+        assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "3");
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Nullable fields
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn question_mark_field_accepts_null() {
+        let content = "@schema Net { proxy?: string, timeout*?: nullable<i32> }\n";
+        let cfg = AAML::parse(&format!("{content}proxy = null\ntimeout = none")).unwrap();
+        let schema = cfg.get_schema("Net").unwrap();
+        assert_eq!(schema.fields["proxy"], "optional<string>");
+        assert!(schema.is_required("proxy") && !schema.is_required("timeout"));
+        assert!(cfg.find_obj("proxy").unwrap().is_null());
+
+        assert!(AAML::parse(&format!("{content}proxy = null\ntimeout = x")).is_err());
+    }
+
+    #[test]
+    fn bare_assignment_to_schema_field_is_rejected() {
+        let err = AAML::parse("@schema S { name: string }\nname =").unwrap_err().to_string();
+        assert!(err.contains("Missing value for schema field 'name'"), "got: {err}");
+
+        let cfg = AAML::parse("@schema S { name: string }\nname = \"\"\nother =").unwrap();
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "");
+        assert!(!cfg.find_obj("name").unwrap().is_null());
+        assert_eq!(cfg.find_obj("other").unwrap().as_str(), "", "undeclared keys keep empty values");
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Custom field messages
+    // ─────────────────────────────────────────────────────────────
+
+    const PORT_MSG: &str = "port must be between 1 and 65535";
+
+    #[test]
+    fn field_message_replaces_type_error_details() {
+        let doc = format!("@schema Server {{ port: i32(1..65535) \"{PORT_MSG}\", host: string }}\nport = 70000");
+        match AAML::parse(&doc).unwrap_err() {
+            aam_rs::error::AamlError::SchemaValidationError { field, details, .. } => {
+                assert_eq!(field, "port");
+                assert_eq!(details, PORT_MSG);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        let cfg = AAML::parse(&format!("@schema Server {{ port: i32(1..65535) \"{PORT_MSG}\" }}")).unwrap();
+        assert_eq!(cfg.get_schema("Server").unwrap().message_for("port"), Some(PORT_MSG));
+    }
+
+    #[test]
+    fn field_message_with_default_and_in_inline_objects() {
+        let schema = "@schema Pool { size: i32 = 4 'size must be a whole number' }\n\
+                      @schema App { pool: Pool }\n";
+        let cfg = AAML::parse(schema).unwrap();
+        assert_eq!(cfg.get_schema("Pool").unwrap().default_for("size"), Some("4"));
+
+        let err = AAML::parse(&format!("{schema}size = many")).unwrap_err().to_string();
+        assert!(err.contains("size must be a whole number"), "got: {err}");
+        let err = AAML::parse(&format!("{schema}pool = {{ size = 1.5 }}")).unwrap_err().to_string();
+        assert!(err.contains("size must be a whole number"), "got: {err}");
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Field doc comments
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn comment_lines_document_the_following_field() {
+        let doc = "@schema Server {\n\
+                   \x20   # Port the HTTP listener binds to.\n\
+                   \x20   # Use 0 for any free port.\n\
+                   \x20   port: i32,\n\
+                   \x20   host: string  # trailing comments are not docs\n\
+                   \x20   # Shown in \"status\" {output}\n\
+                   \x20   name*: string\n\
+                   }";
+        let cfg = AAML::parse(doc).unwrap();
+        let schema = cfg.get_schema("Server").unwrap();
+        assert_eq!(
+            schema.field_doc("port"),
+            Some("Port the HTTP listener binds to.\nUse 0 for any free port.")
+        );
+        assert_eq!(schema.field_doc("host"), None);
+        assert_eq!(schema.field_doc("name"), Some("Shown in \"status\" {output}"));
+        assert!(schema.is_optional("name"));
+
+        let json: Value = serde_json::from_str(&schema.to_json_schema()).unwrap();
+        assert_eq!(json["properties"]["name"]["description"], "Shown in \"status\" {output}");
+    }
+
+    #[test]
+    fn mixin_field_docs_are_spliced() {
+        let doc = "@mixin Audit {\n# Who made the change.\nauthor: string\n}\n@schema Post { ..Audit, title: string }";
+        let cfg = AAML::parse(doc).unwrap();
+        assert_eq!(cfg.get_schema("Post").unwrap().field_doc("author"), Some("Who made the change."));
+    }
+
+    #[test]
+    fn markdown_lists_every_schema_field() {
+        let doc = "@schema Server! {\n# Bind address | host name\nhost: string\nport*: i32\nretries: i32 = 3\n}\n\
+                   @schema App { name: string }";
+        let md = AAML::parse(doc).unwrap().schemas_to_markdown();
+        let expected = "## App\n\n\
+                        | Field | Type | Required | Default | Description |\n\
+                        |-------|------|----------|---------|-------------|\n\
+                        | `name` | `string` | yes |  |  |\n\
+                        \n## Server\n\n\
+                        Strict: keys it does not declare are rejected.\n\n\
+                        | Field | Type | Required | Default | Description |\n\
+                        |-------|------|----------|---------|-------------|\n\
+                        | `host` | `string` | yes |  | Bind address \\| host name |\n\
+                        | `port` | `i32` | no |  |  |\n\
+                        | `retries` | `i32` | no | `3` |  |\n";
+        assert_eq!(md, expected);
+        assert_eq!(AAML::new().schemas_to_markdown(), "");
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Versioning and migrations
+    // ─────────────────────────────────────────────────────────────
+
+    fn migrating_config() -> AAML {
+        let mut cfg = AAML::new();
+        cfg.register_migration("Config", 1, 2, |map| {
+            let secs = map.remove("timeout").ok_or("timeout is missing")?;
+            map.insert("timeout_ms".into(), format!("{secs}000"));
+            Ok(())
+        })
+        .unwrap();
+        cfg.register_migration("Config", 2, 3, |map| {
+            map.entry("retries".into()).or_insert_with(|| "3".into());
+            Ok(())
+        })
+        .unwrap();
+        cfg
+    }
+
+    #[test]
+    fn schema_version_flag_is_parsed() {
+        let cfg = AAML::parse("@schema Config v2 { a: i32 }\n@schema Plain { a: i32 }").unwrap();
+        assert_eq!(cfg.get_schema("Config").unwrap().version(), Some(2));
+        assert_eq!(cfg.get_schema("Plain").unwrap().version(), None);
+        assert!(cfg.schemas_to_markdown().contains("## Config (v2)\n"));
+        assert!(AAML::parse("@schema Config vx { a: i32 }").is_err());
+        assert!(AAML::parse("@mixin M v1 { a: i32 }").is_err());
+    }
+
+    #[test]
+    fn migrations_chain_from_declared_version() {
+        let mut cfg = migrating_config();
+        cfg.merge_content("@schema Config v1 { timeout: i32 }\ntimeout = 5").unwrap();
+        assert_eq!(cfg.get_schema("Config").unwrap().version(), Some(3));
+        assert_eq!(cfg.find_obj("timeout_ms").unwrap().as_str(), "5000");
+        assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "3");
+        assert!(cfg.find_obj("timeout").is_none());
+
+        let mut cfg = migrating_config();
+        cfg.merge_content("@schema Config v2 { timeout_ms: i32 }\ntimeout_ms = 10\nretries = 1").unwrap();
+        assert_eq!(cfg.find_obj("retries").unwrap().as_str(), "1");
+        assert_eq!(cfg.get_schema("Config").unwrap().version(), Some(3));
+    }
+
+    #[test]
+    fn failed_migration_leaves_document_unchanged() {
+        let mut cfg = migrating_config();
+        let err = cfg.merge_content("@schema Config v1 { timeout*: i32 }\nname = x").unwrap_err().to_string();
+        assert!(err.contains("v1 to v2") && err.contains("timeout is missing"), "got: {err}");
+        assert_eq!(cfg.get_schema("Config").unwrap().version(), Some(1));
+        assert!(cfg.find_obj("retries").is_none());
+
+        assert!(cfg.register_migration("Config", 3, 3, |_| Ok(())).is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Batch validation
+    // ─────────────────────────────────────────────────────────────
+
+    fn user_records(n: usize) -> Vec<std::collections::HashMap<String, String>> {
+        (0..n)
+            .map(|i| {
+                let age = if i % 7 == 3 { "old".to_string() } else { i.to_string() };
+                std::collections::HashMap::from([
+                    ("name".to_string(), format!("user{i}")),
+                    ("age".to_string(), age),
+                ])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batch_collects_failures_by_record_index() {
+        let cfg = AAML::parse("@schema User { name: string, age: i32, nick*: string }").unwrap();
+        let records = user_records(20);
+
+        let report = cfg.apply_schema_batch("User", &records).unwrap();
+        assert_eq!(report.total, 20);
+        assert_eq!(report.failed(), 3);
+        assert_eq!(report.passed(), 17);
+        assert!(!report.is_ok());
+        let indices: Vec<usize> = report.failures.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![3, 10, 17]);
+    }
+
+    #[test]
+    fn batch_parallel_matches_sequential() {
+        let cfg = AAML::parse("@schema User { name: string, age: i32 }").unwrap();
+        let records = user_records(1000);
+
+        let seq = cfg.apply_schema_batch("User", &records).unwrap();
+        let par = cfg.apply_schema_batch_parallel("User", &records).unwrap();
+        let seq_idx: Vec<usize> = seq.failures.iter().map(|(i, _)| *i).collect();
+        let par_idx: Vec<usize> = par.failures.iter().map(|(i, _)| *i).collect();
+        assert_eq!(par.total, 1000);
+        assert_eq!(seq_idx, par_idx);
+    }
+
+    #[test]
+    fn batch_unknown_schema_is_not_found() {
+        let cfg = AAML::new();
+        assert!(cfg.apply_schema_batch("Missing", &[]).is_err());
+        assert!(cfg.apply_schema_batch_parallel("Missing", &user_records(4)).is_err());
+        assert!(cfg.apply_schema_batch_parallel("Missing", &[]).is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  @conform
+    // ─────────────────────────────────────────────────────────────
+
+    const TWO_SCHEMAS: &str = "@schema Server { port: i32 }\n@schema Client { name: string, timeout: f64 }\n";
+
+    #[test]
+    fn conform_limits_validation_to_bound_schema() {
+        assert!(AAML::parse(&format!("{TWO_SCHEMAS}timeout = soon")).is_err());
+
+        let cfg = AAML::parse(&format!("{TWO_SCHEMAS}@conform Server\ntimeout = soon\nport = 80")).unwrap();
+        assert_eq!(cfg.conformed_schema(), Some("Server"));
+        assert_eq!(cfg.find_obj("timeout").unwrap().as_str(), "soon");
+
+        assert!(AAML::parse(&format!("{TWO_SCHEMAS}@conform Server\nport = http")).is_err());
+    }
+
+    #[test]
+    fn conform_requires_registered_schema() {
+        assert!(AAML::parse("@conform Missing").is_err());
+        assert!(AAML::parse("@conform").is_err());
+        assert!(AAML::parse("@conform Late\n@schema Late { a: i32 }").is_err());
+        assert_eq!(AAML::new().conformed_schema(), None);
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  @mixin
+    // ─────────────────────────────────────────────────────────────
+
+    const TIMESTAMPS: &str = "@mixin Timestamps {\n  created_at: time::datetime\n  updated_at*: time::datetime\n  version: i32 = 1\n}\n";
+
+    #[test]
+    fn mixin_fields_are_spliced_into_schema() {
+        let cfg = AAML::parse(&format!("{TIMESTAMPS}@schema Asset {{ ..Timestamps, name: string }}")).unwrap();
+        let asset = cfg.get_schema("Asset").unwrap();
+
+        assert_eq!(asset.fields["created_at"], "time::datetime");
+        assert!(asset.is_optional("updated_at"));
+        assert_eq!(asset.default_for("version"), Some("1"));
+        assert!(cfg.get_schema("Timestamps").is_none(), "a mixin is not a schema");
+        assert!(cfg.get_mixin("Timestamps").is_some());
+
+        let doc = format!("{TIMESTAMPS}@schema Asset {{ ..Timestamps, name: string }}\ncreated_at = yesterday");
+        assert!(AAML::parse(&doc).is_err(), "spliced fields are validated");
+    }
+
+    #[test]
+    fn schema_fields_override_mixin_fields() {
+        let cfg = AAML::parse(&format!(
+            "{TIMESTAMPS}@mixin Audited {{ ..Timestamps, author: string }}\n@schema Doc {{ version: string, ..Audited }}"
+        ))
+        .unwrap();
+        let doc = cfg.get_schema("Doc").unwrap();
+
+        assert_eq!(doc.fields["version"], "string");
+        assert!(doc.is_required("version"));
+        assert_eq!(doc.fields["author"], "string");
+        assert_eq!(doc.fields["created_at"], "time::datetime");
+    }
+
+    #[test]
+    fn unknown_or_invalid_mixin_is_rejected() {
+        assert!(AAML::parse("@schema S { ..Missing, a: i32 }").is_err());
+        assert!(AAML::parse("@mixin M { n: i32 = many }").is_err());
+        assert!(AAML::parse("@mixin M deny_extra_objects { n: i32 }").is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Strict schemas
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn strict_schema_rejects_undeclared_keys() {
+        let cfg = AAML::parse("@schema Config! { retries: i32 }\n@conform Config\nretries = 3").unwrap();
+        assert!(cfg.get_schema("Config").unwrap().is_strict());
+
+        let err = AAML::parse("@schema Config! { retries: i32 }\n@conform Config\nretires = 3").unwrap_err();
+        assert!(err.to_string().contains("retires"), "got: {err}");
+
+        assert!(AAML::parse("@schema Config strict { retries: i32 }\n@conform Config\nretires = 3").is_err());
+    }
+
+    #[test]
+    fn strict_schema_checks_keys_assigned_before_conform() {
+        assert!(AAML::parse("@schema Config! { retries: i32 }\nretires = 3\n@conform Config").is_err());
+    }
+
+    #[test]
+    fn strict_schema_without_conform_or_non_strict_allows_extra_keys() {
+        assert!(AAML::parse("@schema Config! { retries: i32 }\nother = 1").is_ok());
+        assert!(AAML::parse("@schema Config { retries: i32 }\n@conform Config\nother = 1").is_ok());
+        assert!(AAML::parse("@schema ! { a: i32 }").is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  @bind
+    // ─────────────────────────────────────────────────────────────
+
+    const PLAYER_ENEMY: &str = "@schema Player { name: string, hp: i32 }\n\
+                                @schema Enemy { name: string, hp: f64 }\n";
+
+    #[test]
+    fn bind_validates_key_as_schema_instance() {
+        let doc = format!("{PLAYER_ENEMY}@bind player1 Player\nplayer1 = {{ name = Ann, hp = 100 }}");
+        let cfg = AAML::parse(&doc).unwrap();
+        assert_eq!(cfg.bound_schema("player1"), Some("Player"));
+
+        let doc = format!("{PLAYER_ENEMY}@bind player1 Player\nplayer1 = {{ name = Ann, hp = 1.5 }}");
+        assert!(AAML::parse(&doc).is_err());
+        let doc = format!("{PLAYER_ENEMY}@bind player1 Player\nplayer1 = {{ name = Ann }}");
+        assert!(AAML::parse(&doc).is_err());
+    }
+
+    #[test]
+    fn bind_validates_dotted_sub_keys_against_their_own_schema() {
+        let doc = format!("{PLAYER_ENEMY}@bind player1 Player\n@bind boss Enemy\nplayer1.hp = 100\nboss.hp = 12.5");
+        assert!(AAML::parse(&doc).is_ok());
+
+        let doc = format!("{PLAYER_ENEMY}@bind player1 Player\nplayer1.hp = 12.5");
+        let err = AAML::parse(&doc).unwrap_err().to_string();
+        assert!(err.contains("Player"), "got: {err}");
+    }
+
+    #[test]
+    fn bind_checks_values_assigned_before_the_directive() {
+        let doc = format!("{PLAYER_ENEMY}player1.hp = 12.5\n@bind player1 Player");
+        assert!(AAML::parse(&doc).is_err());
+    }
+
+    #[test]
+    fn bind_strict_schema_rejects_undeclared_sub_keys() {
+        let doc = "@schema Player! { hp: i32 }\n@bind p Player\np.hpp = 3";
+        assert!(AAML::parse(doc).is_err());
+        assert!(AAML::parse("@schema Player { hp: i32 }\n@bind p Player\np.hpp = 3").is_ok());
+    }
+
+    #[test]
+    fn bind_rejects_malformed_directives() {
+        assert!(AAML::parse("@bind player1 Missing").is_err());
+        assert!(AAML::parse("@schema Player { hp: i32 }\n@bind player1").is_err());
+        assert!(AAML::parse("@schema Player { hp: i32 }\n@bind a b Player").is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  register_schema
+    // ─────────────────────────────────────────────────────────────
+
+    fn server_fields() -> HashMap<String, String> {
+        HashMap::from([
+            ("host".to_string(), "string".to_string()),
+            ("port".to_string(), "i32(1..65536)".to_string()),
+        ])
+    }
+
+    #[test]
+    fn register_schema_validates_later_assignments() {
+        let mut cfg = AAML::new();
+        cfg.register_schema("Server", SchemaDef::new(server_fields(), HashSet::new())).unwrap();
+        assert!(cfg.get_schema("Server").unwrap().is_required("port"));
+        assert!(cfg.merge_content("port = 8080").is_ok());
+        assert!(cfg.merge_content("port = 0").is_err());
+    }
+
+    #[test]
+    fn register_schema_rejects_malformed_definitions() {
+        let mut cfg = AAML::new();
+        let bad_type = HashMap::from([("port".to_string(), "i32(9..1)".to_string())]);
+        assert!(cfg.register_schema("S", SchemaDef::new(bad_type, HashSet::new())).is_err());
+
+        let stray = HashSet::from(["missing".to_string()]);
+        assert!(cfg.register_schema("S", SchemaDef::new(server_fields(), stray)).is_err());
+        assert!(cfg.register_schema(" ", SchemaDef::default()).is_err());
+        assert!(cfg.get_schema("S").is_none());
+    }
+
+    #[test]
+    fn register_schema_keeps_previous_schema_when_default_is_invalid() {
+        let mut cfg = AAML::parse("@schema Server { port: i32 }").unwrap();
+        let schema = aam_rs::builder::SchemaDefBuilder::new("Server")
+            .required("host", "string")
+            .field(aam_rs::builder::SchemaField::required("port", "i32(1..65536)").with_default("http"))
+            .build();
+        assert!(cfg.register_schema("Server", schema).is_err());
+        assert_eq!(cfg.get_schema("Server").unwrap().fields["port"], "i32");
+    }
+
+    #[test]
+    fn register_schema_refuses_to_replace_sealed_schema() {
+        let mut cfg = AAML::parse("@schema Server { port: i32 }\n@sealed Server").unwrap();
+        let err = cfg.register_schema("Server", SchemaDef::new(server_fields(), HashSet::new())).unwrap_err();
+        assert!(err.to_string().contains("sealed"), "{err}");
+        assert!(cfg.get_schema("Server").unwrap().is_sealed());
+        assert_eq!(cfg.get_schema("Server").unwrap().fields["port"], "i32");
+    }
+
+    #[test]
+    fn schema_def_builder_produces_equivalent_schema() {
+        use aam_rs::builder::{SchemaDefBuilder, SchemaField};
+
+        let built = SchemaDefBuilder::new("Server")
+            .required("host", "string")
+            .optional("debug", "bool")
+            .field(SchemaField::required("retries", "i32").with_default("3"))
+            .strict()
+            .build();
+        let parsed = AAML::parse("@schema Server! { host: string, debug*: bool, retries: i32 = 3 }").unwrap();
+        let parsed = parsed.get_schema("Server").unwrap();
+
+        assert_eq!(built.fields, parsed.fields);
+        assert_eq!(built.optional_fields, parsed.optional_fields);
+        assert_eq!(built.defaults(), parsed.defaults());
+        assert_eq!(built.is_strict(), parsed.is_strict());
+        assert!(!built.denies_extra_objects());
+    }
+
+    #[test]
+    fn schema_def_builder_registers_and_redeclares_fields() {
+        use aam_rs::builder::SchemaDefBuilder;
+
+        let mut cfg = AAML::new();
+        let builder = SchemaDefBuilder::new("S").optional("port", "string").required("port", "i32");
+        assert_eq!(builder.name(), "S");
+        builder.register(&mut cfg).unwrap();
+        assert!(cfg.get_schema("S").unwrap().is_required("port"));
+        assert!(cfg.merge_content("port = http").is_err());
+
+        assert!(SchemaDefBuilder::new("Bad").required("n", "i32(5..1)").register(&mut cfg).is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Validation reports
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn apply_schema_report_lists_every_failing_field() {
+        use aam_rs::aaml::report::ValidationReport;
+
+        let cfg = AAML::parse("@schema User { name: string, age: i32, admin: bool, note*: string }").unwrap();
+        let data = HashMap::from([
+            ("age".to_string(), "old".to_string()),
+            ("admin".to_string(), "maybe".to_string()),
+        ]);
+        let report: ValidationReport = cfg.apply_schema_report("User", &data).unwrap();
+        let fields: Vec<_> = report.violations.iter().map(|v| v.field().unwrap()).collect();
+        assert_eq!(fields, ["admin", "age", "name"]);
+        assert_eq!(report.violations[1].type_name(), Some("i32"));
+        assert!(report.violations[2].reason().contains("Missing required field"));
+
+        let ok = HashMap::from([("name".to_string(), "ann".to_string()), ("age".to_string(), "3".to_string()), ("admin".to_string(), "true".to_string())]);
+        assert!(cfg.apply_schema_report("User", &ok).unwrap().is_empty());
+        assert!(cfg.apply_schema_report("Missing", &ok).is_err());
+    }
+
+    #[test]
+    fn parse_with_options_collects_errors_across_lines() {
+        use aam_rs::aaml::ParseOptions;
+
+        let content = "@schema S { port: i32, debug: bool }\n@require token\nport = x\ndebug = maybe\nname = ok";
+        let collect = ParseOptions { collect_errors: true, ..ParseOptions::default() };
+        let (cfg, report) = AAML::parse_with_options(content, &collect).unwrap();
+        let lines: Vec<_> = report.violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(3), Some(4), None]);
+        assert_eq!(report.violations[1].field(), Some("debug"));
+        assert!(report.to_string().contains("line 3:"));
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "ok");
+        assert!(cfg.find_obj("port").is_none(), "invalid assignments are skipped");
+        assert!(AAML::parse_with_options("port = x\nbad line", &collect).is_err(), "syntax errors still stop");
+
+        assert!(AAML::parse_with_options(content, &ParseOptions::default()).is_err());
+        let (_, report) = AAML::parse_with_options("a = 1", &ParseOptions::default()).unwrap();
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn merge_content_report_collects_every_error() {
+        let mut cfg = AAML::parse("@schema S { port: i32, debug: bool }\nname = base").unwrap();
+        let content = "port = x\nok = 1\ndebug = maybe\nbad line\nref = ${missing}\n@nope";
+        let report = cfg.merge_content_report(content);
+        let lines: Vec<_> = report.violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(1), Some(3), Some(4), Some(6), Some(5)], "{report}");
+        assert_eq!(cfg.find_obj("ok").unwrap().as_str(), "1");
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "base");
+        assert!(cfg.find_obj("port").is_none());
+
+        let errors = report.into_errors();
+        assert_eq!(errors.len(), 5);
+        assert!(cfg.merge_content_report("fine = yes").is_empty());
+    }
+
+    #[test]
+    fn parse_with_options_accepts_slash_comments() {
+        use aam_rs::aaml::ParseOptions;
+
+        let options = ParseOptions { slash_comments: true, ..ParseOptions::default() };
+        let content = "// migrated from json\n\
+                       url = http://example.com // primary\n\
+                       /* retired:\n\
+                       port = 1\n\
+                       */ port = 8080\n\
+                       note = \"a // b /* c */\"\n\
+                       # hash comments still work\n\
+                       debug = /* inline */ true";
+        let (cfg, _) = AAML::parse_with_options(content, &options).unwrap();
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://example.com");
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "8080");
+        assert_eq!(cfg.find_obj("note").unwrap().as_str(), "a // b /* c */");
+        assert_eq!(cfg.find_obj("debug").unwrap().as_str(), "true");
+
+        let err = AAML::parse_with_options("a = 1\n/* open\nb = 2", &options).unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 2, .. }), "{err}");
+        assert!(AAML::parse("// not a comment by default").is_err());
+    }
+
+    #[test]
+    fn parse_with_options_accepts_colon_assignments() {
+        use aam_rs::aaml::ParseOptions;
+
+        let options = ParseOptions { colon_assignments: true, ..ParseOptions::default() };
+        let content = "@schema Server { host: string, port: i32 }\n\
+                       name: app\n\
+                       start: 12:30\n\
+                       url = http://example.com\n\
+                       server: {\n\
+                       \x20   host: db\n\
+                       \x20   port = 5432\n\
+                       }\n\
+                       @bind server Server";
+        let (cfg, _) = AAML::parse_with_options(content, &options).unwrap();
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "app");
+        assert_eq!(cfg.find_obj("start").unwrap().as_str(), "12:30");
+        assert_eq!(cfg.find_obj("url").unwrap().as_str(), "http://example.com");
+        assert_eq!(cfg.find_obj("server").unwrap().as_str(), "{ host: db, port = 5432 }");
+
+        assert!(AAML::parse_with_options("name: app", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn parse_with_options_duplicate_key_policy() {
+        use aam_rs::aaml::{DuplicateKeys, ParseOptions};
+
+        let content = "port = 80\nhost = a\nport = 8080";
+        let parse = |duplicate_keys| {
+            AAML::parse_with_options(content, &ParseOptions { duplicate_keys, ..ParseOptions::default() })
+        };
+
+        let (cfg, _) = parse(DuplicateKeys::LastWins).unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "8080");
+        assert!(cfg.warnings().is_empty());
+
+        let (cfg, _) = parse(DuplicateKeys::FirstWins).unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "80");
+
+        let (cfg, _) = parse(DuplicateKeys::Warn).unwrap();
+        assert_eq!(cfg.find_obj("port").unwrap().as_str(), "8080");
+        assert_eq!(cfg.warnings()[0].line, Some(3));
+        assert!(cfg.warnings()[0].message.contains("already assigned on line 1"));
+
+        let err = parse(DuplicateKeys::Error).unwrap_err();
+        assert!(matches!(err, aam_rs::error::AamlError::ParseError { line: 3, .. }), "{err}");
+    }
+
+    #[test]
+    fn load_with_options_applies_every_switch() {
+        use aam_rs::aaml::limits::ImportLimits;
+        use aam_rs::aaml::{DuplicateKeys, ParseOptions};
+
+        let dir = std::env::temp_dir().join(format!("aam_options_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.aam");
+        std::fs::write(&path, "/* legacy */\nDebug: true\nName = a\nname = b\n").unwrap();
+
+        let options = ParseOptions::new()
+            .with_slash_comments(true)
+            .with_colon_assignments(true)
+            .with_case_insensitive_keys(true)
+            .with_duplicate_keys(DuplicateKeys::Warn)
+            .with_import_limits(ImportLimits::default());
+        let (cfg, report) = AAML::load_with_options(&path, &options).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(cfg.find_obj("name").unwrap().as_str(), "b");
+        assert_eq!(cfg.find_obj("DEBUG").unwrap().as_str(), "true");
+        assert_eq!(cfg.warnings().len(), 1);
+        assert!(cfg.case_insensitive_keys_enabled() && cfg.colon_assignments_enabled());
+        assert!(cfg.parse_options().slash_comments);
+        let built = AAML::new().with_coercion(true).with_duplicate_keys(DuplicateKeys::Warn);
+        assert!(built.parse_options().coercion);
+        assert_eq!(built.parse_options().duplicate_keys, DuplicateKeys::Warn);
+        let limits = ImportLimits { max_files: 3, ..ImportLimits::default() };
+        assert_eq!(AAML::new().with_import_limits(limits).parse_options().import_limits, limits);
+        assert_eq!(AAML::new().with_parse_options(&options).import_limits(), &ImportLimits::default());
+
+        let err = AAML::load_with_options(&path, &options.with_duplicate_keys(DuplicateKeys::Error)).unwrap_err();
+        assert!(err.to_string().contains("already assigned on line 3"), "{err}");
+        let err = AAML::load_with_options(&path, &options.with_collect_errors(true).with_duplicate_keys(DuplicateKeys::Error)).unwrap_err();
+        assert!(err.to_string().contains("already assigned on line 3"), "duplicates are not value violations: {err}");
+        assert!(AAML::load_with_options(dir.join("missing.aam"), &options).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Field validators
+    // ─────────────────────────────────────────────────────────────
+
+    fn internal_only(host: &str) -> Result<(), String> {
+        if host.ends_with(".internal") {
+            Ok(())
+        } else {
+            Err(format!("'{host}' is not an internal host"))
+        }
+    }
+
+    #[test]
+    fn field_validator_runs_after_type_check_on_assignments() {
+        let mut cfg = AAML::new();
+        cfg.register_field_validator("Server", "host", internal_only);
+        cfg.register_field_validator("Server", "port", |_| Err("never reached".into()));
+        cfg.merge_content("@schema Server { host: string, port*: i32 }").unwrap();
+
+        assert!(cfg.merge_content("host = db.internal").is_ok());
+        let err = cfg.merge_content("host = example.com").unwrap_err().to_string();
+        assert!(err.contains("'example.com' is not an internal host"), "got: {err}");
+
+        // A type error is reported before the validator runs.
+        let err = cfg.merge_content("port = http").unwrap_err().to_string();
+        assert!(!err.contains("never reached"), "got: {err}");
+    }
+
+    #[test]
+    fn field_validator_applies_to_inline_objects_bound_keys_and_apply_schema() {
+        let mut cfg = AAML::new();
+        cfg.register_field_validator("Server", "host", internal_only);
+        cfg.merge_content("@schema Server { host: string }\n@schema App { server: Server }").unwrap();
+
+        assert!(cfg.merge_content("server = { host = example.com }").is_err());
+        cfg.merge_content("@bind primary Server").unwrap();
+        assert!(cfg.merge_content("primary.host = example.com").is_err());
+        assert!(cfg.merge_content("primary.host = db.internal").is_ok());
+
+        let data = HashMap::from([("host".to_string(), "example.com".to_string())]);
+        assert!(cfg.apply_schema("Server", &data).is_err());
+    }
+
+    #[test]
+    fn field_validators_run_in_registration_order() {
+        let mut cfg = AAML::new();
+        cfg.register_field_validator("S", "n", |_| Ok(()));
+        cfg.register_field_validator("S", "n", |v| if v == "7" { Err("seven".into()) } else { Ok(()) });
+        cfg.merge_content("@schema S { n: i32 }").unwrap();
+        assert!(cfg.merge_content("n = 6").is_ok());
+        assert!(cfg.merge_content("n = 7").unwrap_err().to_string().contains("seven"));
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    //  Sealed schemas
+    // ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_sealed_schema_cannot_be_redefined() {
+        let base = "@schema Config { port: i32(1..65535) }\n@sealed Config\nport = 80";
+
+        let err = AAML::parse(&format!("{base}\n@schema Config {{ port: string }}")).unwrap_err();
+        assert!(err.to_string().contains("sealed"), "got: {err}");
+
+        let mut cfg = AAML::new();
+        cfg.register_source("base", base);
+        assert!(cfg.merge_content("@derive mem://base\n@schema Config { port: string }").is_err());
+        assert!(cfg.get_schema("Config").unwrap().is_sealed(), "the seal travels with @derive");
+
+        let mut cfg = AAML::new();
+        cfg.register_source("base", base);
+        let err = cfg.merge_content("@schema Config { port: string }\n@derive mem://base").unwrap_err();
+        assert!(err.to_string().contains("sealed in 'mem://base'"), "got: {err}");
+
+        let mut cfg = AAML::new();
+        cfg.register_source("base", "@schema Config { port: i32 }\nport = 80");
+        assert!(
+            cfg.merge_content("@schema Config { port: string }\n@derive mem://base").is_ok(),
+            "unsealed base schemas are still shadowed by the child"
+        );
+
+        assert!(AAML::parse("@sealed Missing").is_err());
+        assert!(AAML::parse("@sealed").is_err());
+    }
+}
@@ -1,5 +1,9 @@
 use aam_rs::aaml::AAML;
+use aam_rs::found_value::FoundValue;
+use aam_rs::units::Dimension;
+use aam_rs::values::{Quantity, Quat, Span};
 use std::collections::HashMap;
+use std::time::Duration;
 
 mod test_imports;
 mod test_derive;
@@ -18,6 +22,527 @@ fn test_builtin_types() {
     assert!(aaml.validate_value("f64", "3.14").is_ok());
 }
 
+#[test]
+fn test_integer_family_bounds() {
+    let aaml = AAML::new();
+
+    assert!(aaml.validate_value("i64", "-9223372036854775808").is_ok());
+    assert!(aaml.validate_value("i64", "9223372036854775808").is_err());
+    assert!(aaml.validate_value("u64", "18446744073709551615").is_ok());
+    assert!(aaml.validate_value("u64", "-1").is_err());
+    assert!(aaml.validate_value("u32", "4294967296").is_err());
+    assert!(aaml.validate_value("u16", "65535").is_ok());
+    assert!(aaml.validate_value("u8", "256").is_err());
+    assert!(aaml.validate_value("usize", "3000000000").is_ok());
+    assert!(aaml.validate_value("u8", "1.5").is_err());
+
+    let err = aaml.validate_value("u8", "300").unwrap_err().to_string();
+    assert!(err.contains("0..=255"), "got: {err}");
+}
+
+#[test]
+fn test_radix_integer_literals() {
+    let aaml = AAML::new();
+    for ok in ["0xFF", "0XfF", "0o755", "0b1010", "-0x10", "+0b1"] {
+        assert!(aaml.validate_value("i32", ok).is_ok(), "{ok}");
+    }
+    for bad in ["0x", "0xG1", "0o8", "0b102", "0x-1", "0x1.5"] {
+        assert!(aaml.validate_value("i32", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("u8", "0xFF").is_ok());
+    assert!(aaml.validate_value("u8", "0x100").is_err());
+    assert!(aaml.validate_value("u16(0..=0o777)", "0o755").is_ok());
+    assert!(aaml.validate_value("u16(0..=0o777)", "0o1000").is_err());
+    assert!(aaml.validate_value("f64", "0xFF").is_err(), "floats stay decimal");
+
+    let mut aaml = AAML::new().with_coercion(true);
+    aaml.merge_content("@schema File { mode: u32, flags: u8 }\nmode = 0o755\nflags = 0b1010").unwrap();
+    assert_eq!(aaml.find_obj("mode").unwrap().as_str(), "493");
+    let cfg = AAML::parse("mode = 0o755\nbig = 0xFFFFFFFFFFFFFFFF").unwrap();
+    assert_eq!(cfg.find_obj("mode").unwrap().as_u64(), Some(0o755));
+    assert_eq!(cfg.find_obj("big").unwrap().as_u64(), Some(u64::MAX));
+    assert_eq!(cfg.find_obj("big").unwrap().as_i64(), None);
+}
+
+#[test]
+fn test_scientific_notation_and_separators() {
+    let aaml = AAML::new();
+    for ok in ["1e-3", "6.02e23", "1_000_000", "1_000.000_1", "-2.5E+4"] {
+        assert!(aaml.validate_value("f64", ok).is_ok(), "{ok}");
+    }
+    for ok in ["1_000_000", "1e6", "1.5e3", "-2E2", "0xFF_FF", "0b1010_1010"] {
+        assert!(aaml.validate_value("i32", ok).is_ok(), "{ok}");
+    }
+    for bad in ["1e-3", "1.25e1", "_1", "1_", "1__0", "1_e5", "1e", "3e9"] {
+        assert!(aaml.validate_value("i32", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("f64", "1._5").is_err());
+    assert!(aaml.validate_value("i32(0..=1e3)", "1_000").is_ok());
+    assert!(aaml.validate_value("i32(0..=1e3)", "1_001").is_err());
+
+    let cfg = AAML::parse("rate = 1e-3
+big = 1_000_000
+exp = 2.5e3").unwrap();
+    assert_eq!(cfg.find_obj("rate").unwrap().as_f64(), Some(0.001));
+    assert_eq!(cfg.find_obj("big").unwrap().as_i32(), Some(1_000_000));
+    assert_eq!(cfg.find_obj("big").unwrap().as_f64(), Some(1_000_000.0));
+    assert_eq!(cfg.find_obj("exp").unwrap().as_i64(), Some(2500));
+    assert_eq!(cfg.find_obj("rate").unwrap().as_i64(), None);
+}
+
+#[test]
+fn test_scientific_notation_extreme_exponents() {
+    let aaml = AAML::new();
+    for huge in ["1e9223372036854775807", "9.9e9223372036854775807", "1e-9223372036854775808"] {
+        assert!(aaml.validate_value("i32", huge).is_err(), "{huge}");
+    }
+    assert!(aaml.validate_value("i64", "0e9223372036854775807").is_ok());
+    assert!(AAML::parse("@schema S { a: i32 }\na = 1e9223372036854775807").is_err());
+    let mut coerced = AAML::new().with_coercion(true);
+    assert!(coerced.merge_content("@schema S { a: i64 }\na = 0e9223372036854775807").is_ok());
+
+    let value = FoundValue::new("1e9223372036854775807");
+    assert_eq!(value.as_i32(), None);
+    assert_eq!(value.as_i64(), None);
+    assert_eq!(value.as_u64(), None);
+}
+
+#[test]
+fn test_data_bytes() {
+    let aaml = AAML::new();
+    for ok in ["0", "4096", "10MB", "512KiB", "1.5GB", "2 gib", "100b", "15EiB"] {
+        assert!(aaml.validate_value("data::bytes", ok).is_ok(), "{ok}");
+    }
+    for bad in ["", "MB", "-1KB", "1.5B", "10 XB", "1.KB", "1..5MB", "16EiB", "10K"] {
+        assert!(aaml.validate_value("data::bytes", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("data::ghz", "1").is_err());
+
+    let cfg = AAML::parse(
+        "@schema Cache { limit: data::bytes, page: data::bytes }
+limit = 10MB
+page = 4 KiB",
+    )
+    .unwrap();
+    assert_eq!(cfg.find_obj("limit").unwrap().as_byte_size(), Some(10_000_000));
+    assert_eq!(cfg.find_obj("page").unwrap().as_byte_size(), Some(4096));
+    assert!(AAML::parse("@schema Cache { limit: data::bytes }\nlimit = lots").is_err());
+}
+
+#[test]
+fn test_time_human_duration() {
+    let aaml = AAML::new();
+    for ok in ["5s", "2h30m", "1d 12h", "250ms", "1.5h", "10us", "3µs", "1m30s500ms", "0s"] {
+        assert!(aaml.validate_value("time::human_duration", ok).is_ok(), "{ok}");
+    }
+    for bad in ["", "5", "s", "5x", "30m2h", "5s5s", "1.h", "-5s", "PT5S", "5 s"] {
+        assert!(aaml.validate_value("time::human_duration", bad).is_err(), "{bad}");
+    }
+
+    let cfg = AAML::parse(
+        "@schema Http { timeout: time::human_duration }\ntimeout = 2h30m\nretry = 1.5s",
+    )
+    .unwrap();
+    assert_eq!(cfg.find_obj("timeout").unwrap().as_duration(), Some(Duration::from_secs(9000)));
+    assert_eq!(cfg.find_obj("retry").unwrap().as_duration(), Some(Duration::from_millis(1500)));
+    assert!(AAML::parse("@schema Http { timeout: time::human_duration }\ntimeout = 5").is_err());
+}
+
+#[test]
+fn test_net_types() {
+    let aaml = AAML::new();
+    let cases = [
+        ("net::ipv4", vec!["127.0.0.1", "0.0.0.0"], vec!["256.0.0.1", "::1", "localhost", "1.2.3"]),
+        ("net::ipv6", vec!["::1", "fe80::1", "2001:db8::8a2e:370:7334"], vec!["127.0.0.1", "::g"]),
+        (
+            "net::socket_addr",
+            vec!["0.0.0.0:8080", "[::1]:443"],
+            vec!["0.0.0.0", "localhost:80", "::1:443", "1.2.3.4:70000"],
+        ),
+        (
+            "net::cidr",
+            vec!["10.0.0.0/8", "192.168.1.0/24", "fd00::/64", "::/0"],
+            vec!["10.0.0.0", "10.0.0.0/33", "fd00::/129", "10.0.0.0/x", "host/8"],
+        ),
+    ];
+    for (ty, good, bad) in cases {
+        for v in good {
+            assert!(aaml.validate_value(ty, v).is_ok(), "{ty} should accept {v}");
+        }
+        for v in bad {
+            assert!(aaml.validate_value(ty, v).is_err(), "{ty} should reject {v}");
+        }
+    }
+    assert!(aaml.validate_value("net::mac", "00:00:00:00:00:00").is_err());
+}
+
+#[test]
+fn test_net_url() {
+    let aaml = AAML::new();
+    for ok in ["https://example.com", "postgres://user:pw@db:5432/app", "file:///etc/hosts"] {
+        assert!(aaml.validate_value("net::url", ok).is_ok(), "{ok}");
+    }
+    for bad in ["", "example.com", "/relative/path", "https://exa mple.com", "1http://x"] {
+        assert!(aaml.validate_value("net::url", bad).is_err(), "{bad}");
+    }
+
+    assert!(aaml.validate_value("net::url(https)", "https://example.com/hook").is_ok());
+    assert!(aaml.validate_value("net::url(https)", "HTTPS://example.com/hook").is_ok());
+    let err = aaml.validate_value("net::url(https)", "http://example.com").unwrap_err().to_string();
+    assert!(err.contains("not allowed"), "got: {err}");
+    assert!(aaml.validate_value("net::url(postgres, postgresql)", "postgresql://db/app").is_ok());
+    assert!(aaml.validate_value("net::url(postgres, postgresql)", "mysql://db/app").is_err());
+
+    assert!(AAML::parse("@schema Db { url: net::url(1bad) }").is_err());
+    assert!(AAML::parse("@schema Db { url: net::url() }").is_err());
+    let cfg = AAML::parse("@schema Db { url: net::url(postgres) }\nurl = postgres://localhost/app");
+    assert!(cfg.is_ok());
+}
+
+#[test]
+fn test_id_uuid() {
+    let aaml = AAML::new();
+    for ok in ["67e55044-10b1-426f-9247-bb680e5fe0c8", "00000000-0000-0000-0000-000000000000", "67E55044-10B1-426F-9247-BB680E5FE0C8"] {
+        assert!(aaml.validate_value("id::uuid", ok).is_ok(), "{ok}");
+    }
+    for bad in [
+        "",
+        "67e5504410b1426f9247bb680e5fe0c8",
+        "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+        "67e55044-10b1-426f-9247-bb680e5fe0c",
+        "67e55044-10b1-426f-9247-bb680e5fe0cz",
+        "67e5504-410b1-426f-9247-bb680e5fe0c8",
+    ] {
+        assert!(aaml.validate_value("id::uuid", bad).is_err(), "{bad}");
+    }
+
+    let mut aaml = AAML::new().with_coercion(true);
+    aaml.merge_content("@schema Asset { id: id::uuid }\nid = 67E55044-10B1-426F-9247-BB680E5FE0C8").unwrap();
+    assert_eq!(aaml.find_obj("id").unwrap().as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_id_uuid_conversion() {
+    let cfg = AAML::parse("id = 67e55044-10b1-426f-9247-bb680e5fe0c8\nraw = 67e5504410b1426f9247bb680e5fe0c8").unwrap();
+    let id = cfg.find_obj("id").unwrap().as_uuid().unwrap();
+    assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(cfg.find_obj("raw").unwrap().as_uuid(), None, "only the canonical form converts");
+}
+
+#[test]
+fn test_fs_types() {
+    let aaml = AAML::new();
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    let dir = env!("CARGO_MANIFEST_DIR");
+
+    assert!(aaml.validate_value("fs::path", "assets/textures/grass.png").is_ok());
+    assert!(aaml.validate_value("fs::path", "/does/not/exist").is_ok(), "fs::path is structural only");
+    assert!(aaml.validate_value("fs::path", "").is_err());
+    assert!(aaml.validate_value("fs::path", "bad\0path").is_err());
+
+    assert!(aaml.validate_value("fs::existing_file", manifest).is_ok());
+    assert!(aaml.validate_value("fs::existing_file", dir).is_err());
+    assert!(aaml.validate_value("fs::existing_file", "/does/not/exist.pem").is_err());
+    assert!(aaml.validate_value("fs::existing_dir", dir).is_ok());
+    assert!(aaml.validate_value("fs::existing_dir", manifest).is_err());
+
+    let content = format!("@schema Tls {{ cert: fs::existing_file }}\ncert = {manifest}");
+    assert!(AAML::parse(&content).is_ok());
+}
+
+#[test]
+fn test_physics_dimensions() {
+    let aaml = AAML::parse("@type force = physics::newton\n@type thrust = force").unwrap();
+    let newton = Dimension::new(1, 1, -2, 0, 0, 0, 0);
+    assert_eq!(aaml.type_dimension("physics::newton"), Some(newton));
+    assert_eq!(aaml.type_dimension("thrust"), Some(newton), "aliases resolve");
+    assert_eq!(aaml.type_dimension("physics::newton(0..)"), Some(newton));
+
+    assert_eq!(aaml.type_dimension("physics::joule"), aaml.type_dimension("physics::electronvolt"));
+    assert_eq!(aaml.type_dimension("physics::watt"), Some(Dimension::new(2, 1, -3, 0, 0, 0, 0)));
+    assert_eq!(aaml.type_dimension("physics::celsius"), aaml.type_dimension("physics::kelvin"));
+    assert_eq!(aaml.type_dimension("physics::meter"), aaml.type_dimension("physics::light_year"));
+    assert!(aaml.type_dimension("physics::radian").unwrap().is_dimensionless());
+    assert_eq!(aaml.type_dimension("physics::farad").unwrap().to_string(), "m^-2·kg^-1·s^4·A^2");
+    assert_eq!(Dimension::DIMENSIONLESS.to_string(), "1");
+
+    assert_eq!(aaml.type_dimension("f64"), None);
+    assert_eq!(aaml.type_dimension("physics::unobtainium"), None);
+}
+
+#[test]
+fn test_physics_unit_suffixes() {
+    let aaml = AAML::new();
+    for (ty, ok) in [
+        ("physics::kilogram", "75kg"),
+        ("physics::kilogram", "500 g"),
+        ("physics::meter", "3.5m"),
+        ("physics::meter", "3.5km"),
+        ("physics::meter", "12 ft"),
+        ("physics::second", "250ms"),
+        ("physics::celsius", "300K"),
+        ("physics::electronvolt", "5eV"),
+        ("physics::joule", "1.2kWh"),
+        ("physics::meter", "42"),
+    ] {
+        assert!(aaml.validate_value(ty, ok).is_ok(), "{ty} should accept {ok}");
+    }
+    for (ty, bad) in [
+        ("physics::meter", "75kg"),
+        ("physics::kilogram", "3.5m"),
+        ("physics::meter", "3.5 parsecs"),
+        ("physics::meter", "km"),
+        ("physics::newton_meter", "5J"),
+    ] {
+        assert!(aaml.validate_value(ty, bad).is_err(), "{ty} should reject {bad}");
+    }
+    let err = aaml.validate_value("physics::meter", "75kg").unwrap_err().to_string();
+    assert!(err.contains("not compatible"), "got: {err}");
+
+    let cfg = AAML::parse(
+        "@schema Body { mass: physics::kilogram, length: physics::meter, temp: physics::kelvin }\n\
+         mass = 750 g\nlength = 3.5km\ntemp = 20°C",
+    )
+    .unwrap();
+    assert_eq!(cfg.find_obj("mass").unwrap().as_kilogram().unwrap().0, 0.75);
+    assert_eq!(cfg.find_obj("length").unwrap().as_quantity_in("m"), Some(3500.0));
+    assert_eq!(cfg.find_obj("length").unwrap().as_quantity_in("kg"), None);
+    let kelvin = cfg.find_obj("temp").unwrap().as_quantity_in("K").unwrap();
+    assert!((kelvin - 293.15).abs() < 1e-9);
+    assert!(AAML::parse("@schema Body { mass: physics::kilogram }\nmass = 2m").is_err());
+
+    let mut aaml = AAML::new().with_coercion(true);
+    aaml.merge_content("@schema Rod { length: physics::meter }\nlength = 3.5km").unwrap();
+    assert_eq!(aaml.find_obj("length").unwrap().as_str(), "3500");
+}
+
+#[test]
+fn test_quantity_conversion() {
+    let cfg = AAML::parse(
+        "@schema Part { mass: physics::kilogram, width: physics::meter }\nmass = 1.25kg\nwidth = 42",
+    )
+    .unwrap();
+    let mass = cfg.find_obj("mass").unwrap().as_quantity().unwrap();
+    assert_eq!((mass.value, mass.unit()), (1.25, "kg"));
+    assert_eq!(mass.value_in("g"), Some(1250.0));
+    assert_eq!(mass.to("g").unwrap().to("kg").unwrap(), mass);
+    assert_eq!(mass.to("g").unwrap().to_string(), "1250 g");
+    assert_eq!(mass.value_in("cm"), None, "incompatible dimension");
+    assert_eq!(mass.value_in("furlong"), None, "unknown symbol");
+    assert!(cfg.find_obj("width").unwrap().as_quantity().is_none(), "bare number has no unit");
+
+    let width = Quantity::new(2.5, "m").unwrap();
+    assert_eq!(width.value_in("cm"), Some(250.0));
+    assert!(Quantity::new(1.0, "parsec").is_none());
+    let boiling: Quantity = "212°F".parse().unwrap();
+    assert!((boiling.value_in("°C").unwrap() - 100.0).abs() < 1e-9);
+    assert!("fast".parse::<Quantity>().is_err());
+}
+
+#[test]
+fn test_math_quaternion() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("math::quaternion", "0, 0, 0, 2").is_ok());
+    assert!(aaml.validate_value("math::quaternion", "0, 0, 1").is_err());
+    assert!(aaml.validate_value("math::unit_quaternion", "0, 0, 0, 1").is_ok());
+    assert!(aaml.validate_value("math::unit_quaternion", "0, 0.7071, 0, 0.7071").is_ok(), "rounded values");
+    let err = aaml.validate_value("math::unit_quaternion", "0, 0, 0, 2").unwrap_err().to_string();
+    assert!(err.contains("unit quaternion"), "got: {err}");
+    assert!(aaml.validate_value("math::unit_quaternion", "0, 0, x, 1").is_err());
+    for non_finite in ["NaN, 0, 0, 1", "0, inf, 0, 0", "-inf, NaN, 0, 0"] {
+        assert!(aaml.validate_value("math::unit_quaternion", non_finite).is_err(), "{non_finite}");
+    }
+
+    let cfg = AAML::parse("@schema Node { rotation: math::unit_quaternion }\nrotation = 0, 0.6, 0, 0.8").unwrap();
+    let q = cfg.find_obj("rotation").unwrap().as_quat().unwrap();
+    assert_eq!(q, Quat::new(0.0, 0.6, 0.0, 0.8));
+    assert!((q.length() - 1.0).abs() < 1e-12);
+    assert_eq!(Quat::new(0.0, 0.0, 0.0, 2.0).normalized(), Some(Quat::IDENTITY));
+    assert_eq!(Quat::default().normalized(), None);
+}
+
+#[test]
+fn test_math_matrix_dimensions() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("math::matrix2x2", "1, 0, 0, 1").is_ok());
+    assert!(aaml.validate_value("math::matrix3x3", "1, 0, 0, 0, 1, 0, 0, 0, 1").is_ok());
+
+    let err = aaml.validate_value("math::matrix3x3", "1, 0, 0, 0, 1, 0, 0, 0").unwrap_err().to_string();
+    assert!(err.contains("Expected 9 components for matrix3x3, got 8"), "got: {err}");
+    let err = aaml.validate_value("math::matrix2x2", "1, 0, 0, 1, 0").unwrap_err().to_string();
+    assert!(err.contains("Expected 4 components for matrix2x2, got 5"), "got: {err}");
+
+    let err = aaml.validate_value("math::matrix3x3", "1, 0, 0, 0, 1, x, 0, 0, 1").unwrap_err().to_string();
+    assert!(err.contains("'x' at row 2, column 3 of matrix3x3"), "got: {err}");
+    let err = aaml.validate_value("math::vector3", "1, y, 3").unwrap_err().to_string();
+    assert!(err.contains("'y' at component 2 of vector3"), "got: {err}");
+}
+
+#[test]
+fn test_geo_types() {
+    let aaml = AAML::new();
+    for ok in ["0", "-90", "90", "52.52"] {
+        assert!(aaml.validate_value("geo::latitude", ok).is_ok(), "{ok}");
+    }
+    for bad in ["90.0001", "-91", "north", "NaN", ""] {
+        assert!(aaml.validate_value("geo::latitude", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("geo::longitude", "-180").is_ok());
+    assert!(aaml.validate_value("geo::longitude", "180.5").is_err());
+
+    assert!(aaml.validate_value("geo::coordinate", "52.52, 13.405").is_ok());
+    assert!(aaml.validate_value("geo::coordinate", "-33.87,151.21").is_ok());
+    for bad in ["52.52", "13.405, 252.52", "100, 0", "1, 2, 3", "a, b"] {
+        assert!(aaml.validate_value("geo::coordinate", bad).is_err(), "{bad}");
+    }
+    let err = aaml.validate_value("geo::coordinate", "100, 0").unwrap_err().to_string();
+    assert!(err.contains("latitude"), "got: {err}");
+}
+
+#[test]
+fn test_range_literal_type() {
+    let aaml = AAML::new();
+    for ok in ["1..10", "1..=10", " -5 .. 5 ", "3..3"] {
+        assert!(aaml.validate_value("range<i32>", ok).is_ok(), "{ok}");
+    }
+    for bad in ["1", "..10", "1..", "1..x", "10..1", "1...2"] {
+        assert!(aaml.validate_value("range<i32>", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("range<f64>", "0.5..=2.5").is_ok());
+    assert!(aaml.validate_value("range<u8>", "0..300").is_err());
+    let err = aaml.validate_value("range<u8>", "0..300").unwrap_err().to_string();
+    assert!(err.contains("range end '300'"), "got: {err}");
+
+    let cfg = AAML::parse(
+        "@type level = u32(1..=99)\n@schema Spawner { levels: range<level>, delay: range<f64> }\n\
+         levels = 1..=10\ndelay = 0.5..2.5",
+    )
+    .unwrap();
+    let levels = cfg.find_obj("levels").unwrap().as_range::<u32>().unwrap();
+    assert_eq!(levels, Span { start: 1, end: 10, inclusive: true });
+    assert_eq!(levels.to_string(), "1..=10");
+    let delay = cfg.find_obj("delay").unwrap().as_range::<f64>().unwrap();
+    assert!(delay.contains(&0.5) && !delay.contains(&2.5));
+    assert!(cfg.find_obj("delay").unwrap().as_range::<u32>().is_none());
+    assert!(AAML::parse("@type level = u32(1..=99)\n@schema S { l: range<level> }\nl = 0..5").is_err());
+    assert!(AAML::parse("@schema S { l: range<nope> }\nl = 1..2").is_err());
+}
+
+#[test]
+fn test_set_type_rejects_duplicates() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("set<string>", "[a, b, c]").is_ok());
+    assert!(aaml.validate_value("set<string>", "[]").is_ok());
+    let err = aaml.validate_value("set<string>", "[a, b, a]").unwrap_err().to_string();
+    assert!(err.contains("[2] 'a'") && err.contains("duplicates element [0]"), "got: {err}");
+    assert!(aaml.validate_value("set<i32>", "[1, 01]").is_err(), "compared after normalization");
+    assert!(aaml.validate_value("set<i32>", "[1, x]").is_err());
+    assert!(aaml.validate_value("set<i32, 2..>", "[1]").is_err());
+    assert!(aaml.validate_value("list<string>", "[a, a]").is_ok(), "lists keep duplicates");
+
+    let content = "@schema Firewall { allowed_ips: set<net::ipv4> }\n";
+    assert!(AAML::parse(&format!("{content}allowed_ips = [10.0.0.1, 10.0.0.2]")).is_ok());
+    assert!(AAML::parse(&format!("{content}allowed_ips = [10.0.0.1, 10.0.0.1]")).is_err());
+
+    let cfg = AAML::parse("@type host = string\n@schema S { hosts: set<host> }").unwrap();
+    assert!(cfg.validate_value("set<host>", "[a, b, a]").is_err(), "registered element types");
+    let schema = cfg.schema_to_json_schema("S").unwrap();
+    assert!(schema.contains(r#""uniqueItems":true"#), "got: {schema}");
+}
+
+#[test]
+fn test_nested_generic_types() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("list<list<i32>>", "[[1, 2], [3], []]").is_ok());
+    assert!(aaml.validate_value("list<map<string, i32>>", "[{a = 1}, {b = 2, c = 3}]").is_ok());
+    assert!(aaml.validate_value("map<string, list<i32, 1..>>", "{a = [1], b = [2, 3]}").is_ok());
+    assert!(aaml.validate_value("map<string, list<i32, 1..>>", "{a = []}").is_err());
+    assert!(aaml.validate_value("list<optional<list<i32>>>", "[none, [1]]").is_ok());
+    assert!(aaml.validate_value("list<list<list<i32>>, 1..>", "[[[1]], [[2, 3], []]]").is_ok());
+    assert!(aaml.validate_value("set<list<i32>>", "[[1, 2], [1, 02]]").is_err());
+
+    let err = aaml.validate_value("list<list<i32>>", "[[1], [2, x]]").unwrap_err().to_string();
+    assert!(err.contains("list element [1]") && err.contains("'x'"), "got: {err}");
+
+    let cfg = AAML::parse(
+        "@type grid = list<list<f64>>
+@schema Mesh { rows: grid, groups: list<map<string, list<i32>>> }
+         rows = [[0.0, 1.0], [2.5]]
+groups = [{a = [1]}, {b = [2, 3]}]",
+    )
+    .unwrap();
+    let schema = cfg.schema_to_json_schema("Mesh").unwrap();
+    assert!(schema.contains(r#""items":{"type":"array","items":{"type":"number""#), "got: {schema}");
+    assert!(AAML::parse("@schema S { g: list<list<i32>> }
+g = [[1], [x]]").is_err());
+
+    for malformed in ["list<list<i32>", "list<map<string>>", "map<string, list<>>", "optional<set<>>"] {
+        let content = format!("@schema S {{ f: {malformed} }}");
+        assert!(AAML::parse(&content).is_err(), "{malformed} should be rejected");
+    }
+}
+
+#[test]
+fn test_bool_relaxed_type() {
+    let aaml = AAML::new();
+    for ok in ["yes", "No", "ON", "off", "y", "N", "true", "0"] {
+        assert!(aaml.validate_value("bool_relaxed", ok).is_ok(), "{ok} should be accepted");
+    }
+    assert!(aaml.validate_value("bool_relaxed", "maybe").is_err());
+    assert!(aaml.validate_value("bool", "yes").is_err(), "strict bool is unchanged");
+
+    let mut cfg = AAML::new().with_coercion(true);
+    cfg.merge_content("@schema Legacy { enabled: bool_relaxed, verbose: bool_relaxed }\nenabled = Yes\nverbose = off")
+        .unwrap();
+    assert_eq!(cfg.find_obj("enabled").unwrap().as_str(), "true");
+    assert_eq!(cfg.find_obj("verbose").unwrap().as_bool(), Some(false));
+    let schema = cfg.schema_to_json_schema("Legacy").unwrap();
+    assert!(schema.contains(r#""enum":["true","false","1","0","yes","no","on","off","y","n"]"#), "got: {schema}");
+}
+
+#[test]
+fn test_f32_range_and_precision() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("f32", "3.14").is_ok());
+    assert!(aaml.validate_value("f32", "-3.4e38").is_ok());
+    assert!(aaml.validate_value("f32", "0").is_ok());
+    assert!(aaml.validate_value("f32", "abc").is_err());
+
+    let err = aaml.validate_value("f32", "1e39").unwrap_err().to_string();
+    assert!(err.contains("out of range"), "got: {err}");
+    let err = aaml.validate_value("f32", "1e-44").unwrap_err().to_string();
+    assert!(err.contains("losing precision"), "got: {err}");
+    assert!(aaml.validate_value("f32(0..=1)", "0.5").is_ok());
+    assert!(aaml.validate_value("f32(0..=1)", "1.5").is_err());
+}
+
+#[test]
+fn test_decimal_validates_fixed_point_text() {
+    let aaml = AAML::new();
+    for ok in ["12.50", "-0.001", "+7", "0", "123456789012345678901234567890.000000001"] {
+        assert!(aaml.validate_value("decimal", ok).is_ok(), "{ok}");
+    }
+    for bad in ["1.", ".5", "1e5", "1.2.3", "-", "", "12,5", "NaN"] {
+        assert!(aaml.validate_value("decimal", bad).is_err(), "{bad}");
+    }
+    assert!(aaml.validate_value("decimal(0..=100)", "99.99").is_ok());
+    assert!(aaml.validate_value("decimal(0..=100)", "100.01").is_err());
+
+    let mut aaml = AAML::new().with_coercion(true);
+    aaml.merge_content("@schema Price { amount: decimal, zero: decimal }\namount = +007.50\nzero = -0.00").unwrap();
+    assert_eq!(aaml.find_obj("amount").unwrap().as_str(), "7.50");
+    assert_eq!(aaml.find_obj("zero").unwrap().as_str(), "0.00");
+}
+
+#[test]
+fn test_integer_family_in_schemas_and_constraints() {
+    let schema = "@schema Disk { bytes: u64, id: u32(1..) }\n";
+    assert!(AAML::parse(&format!("{schema}bytes = 5000000000\nid = 7")).is_ok());
+    assert!(AAML::parse(&format!("{schema}id = 0")).is_err());
+    assert!(AAML::parse(&format!("{schema}bytes = -5")).is_err());
+}
+
 #[test]
 fn test_schema_field_validation() {
     let config = r#"
@@ -68,3 +593,434 @@ fn test_apply_schema_manual() {
     assert!(aaml.apply_schema("Point", &data).is_err());
 }
 
+
+#[test]
+fn test_enum_field_accepts_declared_variants() {
+    let config = "@schema Logger { level: enum(debug, info, warn, error) }\nlevel = warn";
+    let aaml = AAML::parse(config).expect("'warn' is a declared variant");
+    assert_eq!(aaml.find_obj("level").unwrap().as_str(), "warn");
+}
+
+#[test]
+fn test_enum_field_error_lists_variants() {
+    let config = "@schema Logger { level: enum(debug, info, warn, error) }\nlevel = verbose";
+    let err = AAML::parse(config).unwrap_err().to_string();
+    assert!(err.contains("debug, info, warn, error"), "got: {err}");
+    assert!(err.contains("verbose"), "got: {err}");
+}
+
+#[test]
+fn test_enum_type_alias_and_list() {
+    let config = "@type level = enum(low, high)\n@schema S { a: level, b: list<enum(x, y)> }\na = low\nb = [x, y, x]";
+    assert!(AAML::parse(config).is_ok());
+
+    let aaml = AAML::parse("@type level = enum(low, high)").unwrap();
+    assert!(aaml.validate_value("level", "medium").is_err());
+}
+
+#[test]
+fn test_empty_enum_rejected_at_declaration() {
+    assert!(AAML::parse("@schema S { mode: enum() }").is_err());
+    assert!(AAML::parse("@schema S { mode: enum(a, a) }").is_err());
+}
+
+#[test]
+fn test_range_constraint_in_schema() {
+    let schema = "@schema Server { port: i32(1..65536) }\n";
+    assert!(AAML::parse(&format!("{schema}port = 65535")).is_ok());
+    assert!(AAML::parse(&format!("{schema}port = 0")).is_err());
+    assert!(AAML::parse(&format!("{schema}port = 65536")).is_err(), "'..' excludes the end");
+    assert!(AAML::parse(&format!("{schema}port = 80.5")).is_err(), "base type still applies");
+}
+
+#[test]
+fn test_range_constraint_type_alias() {
+    let aaml = AAML::parse("@type percent = f64(0.0..=100.0)").unwrap();
+    assert!(aaml.validate_value("percent", "100.0").is_ok());
+    assert!(aaml.validate_value("percent", "0").is_ok());
+    assert!(aaml.validate_value("percent", "100.01").is_err());
+    assert!(aaml.validate_value("percent", "-1").is_err());
+}
+
+#[test]
+fn test_range_constraint_open_bounds() {
+    let aaml = AAML::new();
+    assert!(aaml.validate_value("i32(1..)", "999999").is_ok());
+    assert!(aaml.validate_value("i32(1..)", "0").is_err());
+    assert!(aaml.validate_value("f64(..=1.5)", "-20").is_ok());
+    assert!(aaml.validate_value("f64(..=1.5)", "1.6").is_err());
+}
+
+#[test]
+fn test_malformed_range_rejected_at_declaration() {
+    assert!(AAML::parse("@schema S { n: i32(ten..20) }").is_err());
+    assert!(AAML::parse("@schema S { n: i32(20..10) }").is_err());
+    assert!(AAML::parse("@type t = f64(1..=)").is_err());
+    assert!(AAML::new().validate_value("string(1..2)", "x").is_err(), "ranges need a numeric base");
+}
+
+#[test]
+fn test_type_alias_unknown_target_fails_at_declaration_line() {
+    let err = AAML::parse("a = 1\n@type speed = unicorn").unwrap_err();
+    match err {
+        aam_rs::error::AamlError::ParseError { line, details, .. } => {
+            assert_eq!(line, 2);
+            assert!(details.contains("unicorn"), "got: {details}");
+        }
+        other => panic!("Expected ParseError, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_type_alias_chain_resolves() {
+    let aaml = AAML::parse("@type age = i32\n@type years = age\n@schema P { y: years }\ny = 30").unwrap();
+    assert!(aaml.validate_value("years", "12").is_ok());
+    assert!(aaml.validate_value("years", "twelve").is_err());
+    assert!(aaml.check_type("years", "twelve").is_err());
+    assert!(AAML::parse("@type age = i32\n@type years = age\n@schema P { y: years }\ny = old").is_err());
+}
+
+#[test]
+fn test_type_alias_cycle_rejected() {
+    let res = AAML::parse("@type a = i32\n@type b = a\n@type a = b");
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("cycle"), "got: {err}");
+}
+
+#[test]
+fn test_type_alias_to_schema() {
+    let aaml = AAML::parse("@schema Point { x: f64, y: f64 }\n@type pos = Point").unwrap();
+    assert!(aaml.validate_value("pos", "{ x = 1, y = 2 }").is_ok());
+    assert!(aaml.validate_value("pos", "{ x = 1 }").is_err());
+}
+
+#[test]
+fn test_parameterized_alias_and_list_of_alias() {
+    let aaml = AAML::parse("@type port = i32[1..65536]\n@type ports = list<port>").unwrap();
+    assert!(aaml.validate_value("ports", "[80, 443, 8080]").is_ok());
+    assert!(aaml.validate_value("ports", "[80, 0]").is_err());
+    assert!(aaml.validate_value("ports", "[80, http]").is_err());
+
+    let doc = "@type port = i32[1..65536]\n@type ports = list<port>\n@schema S { listen: ports }\n";
+    assert!(AAML::parse(&format!("{doc}listen = [80, 443]")).is_ok());
+    assert!(AAML::parse(&format!("{doc}listen = [80, 70000]")).is_err());
+}
+
+#[test]
+fn test_constraint_on_registered_alias() {
+    let doc = "@type port_t = i32\n@type low_port = port_t(1..1024)\n@schema S { p: port_t[1..100] }\n";
+    let aaml = AAML::parse(doc).unwrap();
+    assert!(aaml.validate_value("low_port", "22").is_ok());
+    assert!(aaml.validate_value("low_port", "2048").is_err());
+    assert!(AAML::parse(&format!("{doc}p = 50")).is_ok());
+    assert!(AAML::parse(&format!("{doc}p = 500")).is_err());
+}
+
+#[test]
+fn test_alias_to_unknown_list_element_rejected() {
+    assert!(AAML::parse("@type ports = list<port>").is_err());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_pattern_constraint_in_schema() {
+    let schema = "@schema Order { id: string(/^[A-Z]{3}-\\d+$/), qty: i32 }\n";
+    assert!(AAML::parse(&format!("{schema}id = ABC-42\nqty = 1")).is_ok());
+    assert!(AAML::parse(&format!("{schema}id = abc-42\nqty = 1")).is_err());
+    assert!(AAML::parse(&format!("{schema}id = ABC-\nqty = 1")).is_err());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_pattern_constraint_type_alias() {
+    let aaml = AAML::parse("@type slug = string(/^[a-z0-9-]+$/)").unwrap();
+    assert!(aaml.validate_value("slug", "my-post-1").is_ok());
+    assert!(aaml.validate_value("slug", "My Post").is_err());
+    assert!(AAML::parse("@type bad = string(/[a-z/)").is_err(), "invalid regex");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_pattern_constraint_delimiters() {
+    let schema = "@schema Route { path: string(/^\\/api\\/v\\d+$/) }\n";
+    assert!(AAML::parse(&format!("{schema}path = /api/v2")).is_ok());
+    assert!(AAML::parse(&format!("{schema}path = api/v2")).is_err());
+
+    let err = AAML::parse("@schema S { id: string(/a/b/) }").unwrap_err().to_string();
+    assert!(err.contains("Directive '@schema' error") && err.contains("Unescaped '/'"), "got: {err}");
+    let err = AAML::parse("@schema S { id: string(/[a-z/) }").unwrap_err().to_string();
+    assert!(err.contains("Directive '@schema' error"), "invalid regex fails at @schema: {err}");
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn test_pattern_constraint_requires_feature() {
+    let err = AAML::parse("@schema S { id: string(/^a$/) }").unwrap_err().to_string();
+    assert!(err.contains("regex"), "got: {err}");
+}
+
+#[test]
+fn test_alias_definition_does_not_accept_everything() {
+    let aaml = AAML::parse("@type age = i32\n@type years = age").unwrap();
+    let years = aaml.get_type("years").unwrap();
+    assert!(years.validate("12").is_err(), "aliases resolve only through the registry");
+    assert!(aaml.check_type("years", "12").is_ok());
+    assert!(aaml.check_type("years", "old").is_err());
+}
+
+#[test]
+fn test_opaque_type_accepts_any_value() {
+    let doc = "@type token = opaque\n@schema Auth { key: token, retries: i32 }\n";
+    assert!(AAML::parse(&format!("{doc}key = ~!@anything {{ goes\nretries = 3")).is_ok());
+    assert!(AAML::parse(&format!("{doc}key = x\nretries = many")).is_err());
+
+    let aaml = AAML::parse("@type token = opaque\n@type secret = token").unwrap();
+    assert!(aaml.check_type("token", "").is_ok());
+    assert!(aaml.validate_value("secret", "whatever").is_ok());
+}
+
+#[test]
+fn test_list_cardinality_in_schema() {
+    let schema = "@schema Firewall { allowed_ips: list<string, 1..>, ports: list<i32, ..=2> }\n";
+    assert!(AAML::parse(&format!("{schema}allowed_ips = [10.0.0.1]\nports = [80, 443]")).is_ok());
+    assert!(AAML::parse(&format!("{schema}allowed_ips = []\nports = [80]")).is_err());
+    assert!(AAML::parse(&format!("{schema}allowed_ips = [a]\nports = [1, 2, 3]")).is_err());
+    assert!(AAML::parse(&format!("{schema}allowed_ips = [a]\nports = [x]")).is_err());
+}
+
+#[test]
+fn test_list_cardinality_with_registered_element_type() {
+    let aaml = AAML::parse("@schema P { x: i32 }\n@type points = list<P, 2..=3>").unwrap();
+    assert!(aaml.validate_value("points", "[{ x = 1 }, { x = 2 }]").is_ok());
+    assert!(aaml.validate_value("points", "[{ x = 1 }]").is_err());
+    assert!(aaml.validate_value("list<enum(a, b), 1..>", "[a, b]").is_ok());
+    assert!(aaml.validate_value("list<enum(a, b), 1..>", "[]").is_err());
+}
+
+#[test]
+fn test_malformed_list_cardinality_rejected_at_declaration() {
+    assert!(AAML::parse("@schema S { l: list<i32, 1.5..> }").is_err());
+    assert!(AAML::parse("@schema S { l: list<i32, 5..2> }").is_err());
+    assert!(AAML::parse("@type t = list<i32, -1..>").is_err());
+}
+
+#[test]
+fn test_malformed_list_generics_rejected_at_declaration() {
+    for bad in ["list<i32>>", "list<string, abc>", "set<map<string, i32>", "list<i32, 1..3, x>"] {
+        assert!(AAML::parse(&format!("@schema S {{ a: {bad} }}")).is_err(), "{bad}");
+        assert!(AAML::parse(&format!("@type t = {bad}")).is_err(), "{bad}");
+    }
+    assert!(AAML::parse("@schema S { a: list<i32(0..=5)>, b: list<map<string, list<i32>>, 1..> }").is_ok());
+}
+
+#[test]
+fn test_inline_object_extra_keys_ignored_by_default() {
+    let doc = "@schema Endpoint { host: string, port: i32 }\n@schema App { api: Endpoint }\n";
+    assert!(AAML::parse(&format!("{doc}api = {{ host = x, port = 1, hots = y }}")).is_ok());
+}
+
+#[test]
+fn test_inline_object_deny_extra_objects() {
+    let doc = "@schema Endpoint deny_extra_objects { host: string, port*: i32 }\n@schema App { api: Endpoint }\n";
+    assert!(AAML::parse(&format!("{doc}api = {{ host = x, port = 1 }}")).is_ok());
+    assert!(AAML::parse(&format!("{doc}api = {{ host = x }}")).is_ok());
+
+    let err = AAML::parse(&format!("{doc}api = {{ host = x, hots = y }}")).unwrap_err();
+    assert!(err.to_string().contains("hots"), "got: {err}");
+
+    let aaml = AAML::parse(doc).unwrap();
+    assert!(aaml.get_schema("Endpoint").unwrap().denies_extra_objects());
+    assert!(!aaml.get_schema("App").unwrap().denies_extra_objects());
+}
+
+#[test]
+fn test_unknown_schema_flag_rejected() {
+    assert!(AAML::parse("@schema S sealed { a: i32 }").is_err());
+}
+
+#[test]
+fn test_list_element_error_reports_index_and_offset() {
+    let schema = "@schema Ports { ports: list<i32> }\n";
+    let err = AAML::parse(&format!("{schema}ports = [80, 443, http, 8080]")).unwrap_err().to_string();
+    assert!(err.contains("'ports'"), "got: {err}");
+    assert!(err.contains("[2] 'http' at offset 10"), "got: {err}");
+}
+
+#[test]
+fn test_list_of_schema_element_error_reports_index() {
+    let doc = "@schema P { x: i32 }\n@schema Shape { points: list<P> }\n";
+    let err = AAML::parse(&format!("{doc}points = [{{ x = 1 }}, {{ x = 2 }}, {{ x = bad }}]"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("list element [2]"), "got: {err}");
+}
+
+#[test]
+fn test_map_type_in_schema() {
+    let schema = "@schema Service { limits: map<string, i32> }\n";
+    assert!(AAML::parse(&format!("{schema}limits = {{ cpu = 4, memory = 512 }}")).is_ok());
+    assert!(AAML::parse(&format!("{schema}limits = {{}}")).is_ok());
+    assert!(AAML::parse(&format!("{schema}limits = {{ cpu = four }}")).is_err());
+    assert!(AAML::parse(&format!("{schema}limits = {{ cpu = 1, cpu = 2 }}")).is_err(), "duplicate key");
+    assert!(AAML::parse(&format!("{schema}limits = [1, 2]")).is_err());
+}
+
+#[test]
+fn test_map_type_with_registered_types() {
+    let doc = "@schema P { x: i32 }\n@type port = i32[1..65536]\n";
+    let aaml = AAML::parse(doc).unwrap();
+    assert!(aaml.validate_value("map<string, P>", "{ a = { x = 1 }, b = { x = 2 } }").is_ok());
+    assert!(aaml.validate_value("map<string, P>", "{ a = { x = nope } }").is_err());
+    assert!(aaml.validate_value("map<i32, port>", "{ 1 = 80, 2 = 443 }").is_ok());
+    assert!(aaml.validate_value("map<i32, port>", "{ one = 80 }").is_err(), "keys follow K");
+    assert!(aaml.validate_value("map<string, list<port, 1..>>", "{ web = [80, 443] }").is_ok());
+    assert!(aaml.validate_value("map<string, list<port, 1..>>", "{ web = [] }").is_err());
+}
+
+#[test]
+fn test_map_type_unknown_parameter_rejected_at_declaration() {
+    assert!(AAML::parse("@type limits = map<string, unicorn>").is_err());
+    assert!(AAML::parse("@schema S { m: map<string, enum()> }").is_err());
+}
+
+#[test]
+fn test_optional_type_in_schema() {
+    let schema = "@schema Net { proxy: optional<string>, timeout: optional<i32> }\n";
+    assert!(AAML::parse(&format!("{schema}proxy = none\ntimeout = null")).is_ok());
+    assert!(AAML::parse(&format!("{schema}proxy = http://p:3128\ntimeout = 30")).is_ok());
+    assert!(AAML::parse(&format!("{schema}proxy = none\ntimeout = soon")).is_err());
+
+    let aaml = AAML::parse(schema).unwrap();
+    let data = std::collections::HashMap::from([("proxy".to_string(), "none".to_string())]);
+    assert!(aaml.apply_schema("Net", &data).is_err(), "field must still be present");
+}
+
+#[test]
+fn test_optional_type_with_registered_types() {
+    let aaml = AAML::parse("@schema P { x: i32 }\n@type port = i32[1..65536]\n@type maybe_port = optional<port>").unwrap();
+    assert!(aaml.validate_value("maybe_port", "none").is_ok());
+    assert!(aaml.validate_value("maybe_port", "8080").is_ok());
+    assert!(aaml.validate_value("maybe_port", "0").is_err());
+    assert!(aaml.validate_value("optional<P>", "null").is_ok());
+    assert!(aaml.validate_value("optional<P>", "{ x = 1 }").is_ok());
+    assert!(aaml.validate_value("list<optional<i32>>", "[1, none, 3]").is_ok());
+    assert!(AAML::parse("@type t = optional<unicorn>").is_err());
+}
+
+#[test]
+fn test_union_type_in_schema() {
+    let schema = "@schema Server { port: i32 | string, debug: bool }\n";
+    assert!(AAML::parse(&format!("{schema}port = 8080\ndebug = true")).is_ok());
+    assert!(AAML::parse(&format!("{schema}port = http\ndebug = true")).is_ok());
+
+    let schema = "@schema S { v: union<i32, bool> }\n";
+    assert!(AAML::parse(&format!("{schema}v = true")).is_ok());
+    let err = AAML::parse(&format!("{schema}v = maybe")).unwrap_err().to_string();
+    assert!(err.contains("i32:") && err.contains("bool:"), "got: {err}");
+}
+
+#[test]
+fn test_union_type_with_registered_and_nested_members() {
+    let aaml = AAML::parse("@schema P { x: i32 }\n@type port = i32[1..65536]\n@type target = port | P | list<port>").unwrap();
+    assert!(aaml.validate_value("target", "443").is_ok());
+    assert!(aaml.validate_value("target", "{ x = 1 }").is_ok());
+    assert!(aaml.validate_value("target", "[80, 443]").is_ok());
+    assert!(aaml.validate_value("target", "0").is_err());
+    assert!(aaml.validate_value("f64(0..1) | bool", "false").is_ok());
+    assert!(aaml.validate_value("i32 | enum(a, b)", "c").is_err());
+}
+
+#[test]
+fn test_malformed_union_rejected_at_declaration() {
+    assert!(AAML::parse("@schema S { v: i32 | }").is_err());
+    assert!(AAML::parse("@type t = i32 | unicorn").is_err());
+    assert!(AAML::parse("@type t = union<i32, >").is_err());
+}
+
+#[test]
+fn test_coercion_normalizes_validated_values() {
+    let mut aaml = AAML::new().with_coercion(true);
+    aaml.merge_content(
+        "@type pct = f64(0..=100)\n\
+         @schema S { debug: bool, n: i32, ratio: pct, tint: color, tags: list<bool>, v: i32 | bool }\n\
+         debug = FALSE\nn = 007\nratio = 050.50\ntint = #AABBCC\ntags = [True,FALSE]\nv = TRUE\nfree = 007",
+    )
+    .unwrap();
+    assert!(aaml.coercion_enabled());
+    assert_eq!(aaml.find_obj("debug").unwrap().as_str(), "false");
+    assert_eq!(aaml.find_obj("n").unwrap().as_str(), "7");
+    assert_eq!(aaml.find_obj("ratio").unwrap().as_str(), "50.5");
+    assert_eq!(aaml.find_obj("tint").unwrap().as_str(), "#aabbccff");
+    assert_eq!(aaml.find_obj("tags").unwrap().as_str(), "[true, false]");
+    assert_eq!(aaml.find_obj("v").unwrap().as_str(), "true");
+    assert_eq!(aaml.find_obj("free").unwrap().as_str(), "007", "undeclared keys are untouched");
+}
+
+#[test]
+fn test_coercion_is_off_by_default() {
+    let aaml = AAML::parse("@schema S { n: i32 }\nn = 007").unwrap();
+    assert!(!aaml.coercion_enabled());
+    assert_eq!(aaml.find_obj("n").unwrap().as_str(), "007");
+}
+
+#[test]
+fn test_typed_color_values() {
+    use aam_rs::values::Rgba;
+    assert_eq!("#FF8000".parse::<Rgba>().unwrap(), Rgba::new(255, 128, 0, 255));
+    assert_eq!("#00000080".parse::<Rgba>().unwrap().a, 128);
+    assert_eq!(Rgba::new(255, 128, 0, 255).to_string(), "#ff8000ff");
+    assert!("#GG0000".parse::<Rgba>().is_err());
+    assert!("reddish".parse::<Rgba>().is_err());
+}
+
+#[test]
+fn test_extended_color_formats() {
+    use aam_rs::values::Rgba;
+    let aaml = AAML::new();
+    for ok in ["#f80", "#f808", "rgb(255, 128, 0)", "rgba(255, 128, 0, 0.5)", "rgb(100% 50% 0% / 50%)",
+               "hsl(30, 100%, 50%)", "HSLA(30deg, 100%, 50%, 1)", "RebeccaPurple", "transparent"] {
+        assert!(aaml.validate_value("color", ok).is_ok(), "{ok} should be a color");
+    }
+    for bad in ["rgb(256, 0, 0)", "rgb(1, 2)", "rgba(0, 0, 0, 1.5)", "hsl(0, 120%, 50%)", "cmyk(0, 0, 0, 0)", "#12345"] {
+        assert!(aaml.validate_value("color", bad).is_err(), "{bad} should be rejected");
+    }
+
+    assert_eq!("#f80".parse::<Rgba>().unwrap(), Rgba::new(255, 136, 0, 255));
+    assert_eq!("rgba(255, 128, 0, 0.5)".parse::<Rgba>().unwrap(), Rgba::new(255, 128, 0, 128));
+    assert_eq!("rgb(100% 0% 0% / 0%)".parse::<Rgba>().unwrap(), Rgba::new(255, 0, 0, 0));
+    assert_eq!("hsl(120, 100%, 25%)".parse::<Rgba>().unwrap(), Rgba::new(0, 128, 0, 255));
+    assert_eq!("hsl(-120, 100%, 50%)".parse::<Rgba>().unwrap(), Rgba::new(0, 0, 255, 255));
+    assert_eq!("Orange".parse::<Rgba>().unwrap(), Rgba::new(255, 165, 0, 255));
+
+    let mut cfg = AAML::new().with_coercion(true);
+    cfg.merge_content("@schema Theme { fg: color, palette: list<color> }\nfg = hsl(0, 100%, 50%)\npalette = [rgb(0, 0, 255), teal]")
+        .unwrap();
+    assert_eq!(cfg.find_obj("fg").unwrap().as_str(), "#ff0000ff");
+    assert_eq!(cfg.find_obj("palette").unwrap().as_list().unwrap(), vec!["#0000ffff", "#008080ff"]);
+    assert_eq!(cfg.find_obj("fg").unwrap().as_color(), Some(Rgba::new(255, 0, 0, 255)));
+}
+
+#[test]
+fn test_typed_vector_and_mass_values() {
+    use aam_rs::values::{Kilogram, Vec2, Vec3, Vec4};
+    let v: Vec3 = "1.0, 2.5, -3".parse().unwrap();
+    assert_eq!(v, Vec3::new(1.0, 2.5, -3.0));
+    assert_eq!(v.to_string().parse::<Vec3>().unwrap(), v);
+    assert_eq!("0, 1".parse::<Vec2>().unwrap().y, 1.0);
+    assert_eq!("1, 2, 3, 4".parse::<Vec4>().unwrap().w, 4.0);
+    assert!("1, 2".parse::<Vec3>().is_err());
+    assert!("1, x, 3".parse::<Vec3>().is_err());
+    assert_eq!("72.5".parse::<Kilogram>().unwrap(), Kilogram(72.5));
+    assert!("heavy".parse::<Kilogram>().is_err());
+}
+
+#[test]
+fn test_found_value_typed_getters() {
+    let content = "@schema Body { tint: color, origin: math::vector3, mass: physics::kilogram }\n\
+                   tint = #102030\norigin = 0, 1, 2\nmass = 12.5";
+    let aaml = AAML::parse(content).unwrap();
+    assert_eq!(aaml.find_obj("tint").unwrap().as_color().unwrap().b, 0x30);
+    assert_eq!(aaml.find_obj("origin").unwrap().as_vec3().unwrap().z, 2.0);
+    assert_eq!(aaml.find_obj("mass").unwrap().as_kilogram().unwrap().0, 12.5);
+    assert!(aaml.find_obj("mass").unwrap().as_vec2().is_none());
+}